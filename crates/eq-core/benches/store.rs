@@ -0,0 +1,70 @@
+use chrono::Duration;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use eq_core::{clock, Quadrant, Task, TaskStatus, TaskStore};
+use std::env;
+
+/// About a year of steady use
+const TASKS: usize = 5_000;
+/// Days the tasks are spread over, all recent enough to stay out of the
+/// archive
+const DAYS: i64 = 60;
+
+fn store() -> TaskStore {
+    let today = clock::today();
+    let mut store = TaskStore::default();
+    for i in 0..TASKS {
+        let date = today - Duration::days(i as i64 % DAYS);
+        let level = |n: usize| (n % 3 + 1) as u8;
+        let mut task = Task::new(format!("Task {}", i), level(i), level(i / 3), date);
+        if i % 10 < 7 {
+            task.complete();
+        }
+        store.tasks.push(task);
+    }
+    store
+}
+
+fn bench_load_save(c: &mut Criterion) {
+    // Never touch the real data directory
+    let dir = env::temp_dir().join(format!("eq-bench-{}", std::process::id()));
+    env::set_var("EQ_DATA_DIR", &dir);
+    let store = store();
+    store.save().unwrap();
+
+    c.bench_function("store/save", |b| b.iter(|| store.save().unwrap()));
+    c.bench_function("store/load", |b| {
+        b.iter(|| black_box(TaskStore::load().unwrap()))
+    });
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+fn pending_in(tasks: Vec<&Task>, quadrant: Quadrant) -> usize {
+    tasks
+        .into_iter()
+        .filter(|t| t.status == TaskStatus::Pending && t.quadrant() == quadrant)
+        .count()
+}
+
+fn bench_quadrants(c: &mut Criterion) {
+    let store = store();
+    let today = clock::today();
+    // What the matrix does on every frame: each quadrant of one day
+    c.bench_function("quadrants/day_index", |b| {
+        b.iter(|| {
+            for quadrant in Quadrant::ALL {
+                black_box(pending_in(store.tasks_on(today), quadrant));
+            }
+        })
+    });
+    c.bench_function("quadrants/full_scan", |b| {
+        b.iter(|| {
+            for quadrant in Quadrant::ALL {
+                let day = store.tasks.iter().filter(|t| t.date == today).collect();
+                black_box(pending_in(day, quadrant));
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_load_save, bench_quadrants);
+criterion_main!(benches);
@@ -0,0 +1,290 @@
+use crate::config::Config;
+use chrono::{
+    DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+};
+use chrono_tz::Tz;
+use std::fmt::Debug;
+use std::sync::{Arc, OnceLock};
+
+/// Where the current time comes from
+pub trait Clock: Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// The current day, which rolls over at `day_starts_at`
+    fn today(&self) -> NaiveDate {
+        date_of(self.now())
+    }
+}
+
+/// The computer's clock
+#[derive(Debug)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock stopped at one moment, for `--now` and tests
+#[derive(Debug)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// The clock everything uses unless handed another; set by `--now`
+static CLOCK: OnceLock<Arc<dyn Clock>> = OnceLock::new();
+
+/// Zone chosen with `--tz`, else `timezone` from the config; `None` means
+/// the system's
+static TIME_ZONE: OnceLock<Option<Tz>> = OnceLock::new();
+
+/// `day_starts_at` from the config
+static DAY_START: OnceLock<NaiveTime> = OnceLock::new();
+
+/// Read `HH:MM`, e.g. `04:00`
+pub fn parse_day_start(input: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(input.trim(), "%H:%M").ok()
+}
+
+/// The day `moment` belongs to when days start at `day_start`: earlier
+/// moments still count toward the day before
+pub fn day_of(moment: NaiveDateTime, day_start: NaiveTime) -> NaiveDate {
+    (moment - Duration::seconds(day_start.num_seconds_from_midnight() as i64)).date()
+}
+
+/// Roll days over at `config`'s `day_starts_at` and reckon them in its
+/// `timezone`, unless `--tz` chose a zone already. Call once at startup,
+/// before anything asks for the date; until then days start at midnight in
+/// the system's zone. A setting that doesn't parse is left at its default
+/// and reported in the error
+pub fn configure(config: &Config) -> Result<(), String> {
+    let (day_start, zone, problems) = read_settings(config);
+    let _ = DAY_START.set(day_start);
+    let _ = TIME_ZONE.set(zone);
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// The day start and zone `config` asks for, with what couldn't be read
+fn read_settings(config: &Config) -> (NaiveTime, Option<Tz>, Vec<String>) {
+    let mut problems = Vec::new();
+    let day_start = match config.day_starts_at.as_deref() {
+        Some(text) => parse_day_start(text).unwrap_or_else(|| {
+            problems.push(format!(
+                "day_starts_at '{}' is not a time like 04:00, so days start at midnight",
+                text
+            ));
+            NaiveTime::default()
+        }),
+        None => NaiveTime::default(),
+    };
+    let zone = match config.timezone.as_deref() {
+        Some(name) => parse_time_zone(name).ok().or_else(|| {
+            problems.push(format!(
+                "timezone '{}' is not a zone like Asia/Tokyo, so the system's zone is used",
+                name
+            ));
+            None
+        }),
+        None => None,
+    };
+    (day_start, zone, problems)
+}
+
+/// When the day rolls over; midnight unless configured
+fn day_start() -> NaiveTime {
+    DAY_START.get().copied().unwrap_or_default()
+}
+
+/// Read an IANA zone name such as `Asia/Tokyo` or `UTC`
+pub fn parse_time_zone(name: &str) -> Result<Tz, String> {
+    name.trim()
+        .parse::<Tz>()
+        .map_err(|_| format!("Unknown time zone '{}' (try a name like Asia/Tokyo)", name))
+}
+
+/// Reckon dates in `name` instead of the configured zone. Call before
+/// anything asks for the date
+pub fn set_time_zone(name: &str) -> Result<(), String> {
+    let zone = parse_time_zone(name)?;
+    TIME_ZONE
+        .set(Some(zone))
+        .map_err(|_| String::from("Time zone already in use"))
+}
+
+/// The zone dates are reckoned in; the system's unless one was chosen
+fn time_zone() -> Option<Tz> {
+    TIME_ZONE.get().copied().flatten()
+}
+
+/// Wall-clock time of `timestamp` in `zone`, or the system's zone
+pub fn local_time(timestamp: DateTime<Utc>, zone: Option<Tz>) -> NaiveDateTime {
+    match zone {
+        Some(zone) => timestamp.with_timezone(&zone).naive_local(),
+        None => timestamp.with_timezone(&Local).naive_local(),
+    }
+}
+
+/// Read a moment such as `2024-06-13 09:00` in `zone` (the system's when
+/// `None`), or an RFC 3339 timestamp; a bare date means its midnight
+pub fn parse_moment(input: &str, zone: Option<Tz>) -> Option<DateTime<Utc>> {
+    let input = input.trim();
+    if let Ok(moment) = DateTime::parse_from_rfc3339(input) {
+        return Some(moment.with_timezone(&Utc));
+    }
+    let naive = [
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            .ok()
+            .map(|date| date.and_time(NaiveTime::default()))
+    })?;
+    match zone {
+        Some(zone) => zone
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|m| m.to_utc()),
+        None => Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|m| m.to_utc()),
+    }
+}
+
+/// Use `clock` instead of the system clock. Call before anything asks for
+/// the time
+pub fn set_clock(clock: Arc<dyn Clock>) -> Result<(), String> {
+    CLOCK
+        .set(clock)
+        .map_err(|_| String::from("Clock already in use"))
+}
+
+/// Pretend it is `input` (see `parse_moment`), reckoned in the chosen zone
+pub fn set_now(input: &str) -> Result<(), String> {
+    let moment = parse_moment(input, time_zone())
+        .ok_or_else(|| format!("Invalid time '{}' (try 2024-06-13 09:00)", input))?;
+    set_clock(Arc::new(FixedClock(moment)))
+}
+
+/// The clock in use
+pub fn current() -> Arc<dyn Clock> {
+    CLOCK.get_or_init(|| Arc::new(SystemClock)).clone()
+}
+
+/// The current moment
+pub fn now() -> DateTime<Utc> {
+    current().now()
+}
+
+/// The current wall-clock time in the chosen zone
+pub fn now_local() -> NaiveDateTime {
+    local_time(now(), time_zone())
+}
+
+/// The current day, which rolls over at `day_starts_at`
+pub fn today() -> NaiveDate {
+    current().today()
+}
+
+/// The day a recorded moment belongs to
+pub fn date_of(timestamp: DateTime<Utc>) -> NaiveDate {
+    day_of(local_time(timestamp, time_zone()), day_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_of_rolls_over_at_day_start() {
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        let four = parse_day_start("04:00").unwrap();
+        let june = |d| NaiveDate::from_ymd_opt(2024, 6, d).unwrap();
+
+        assert_eq!(day_of(at("2024-06-12 01:30"), four), june(11));
+        assert_eq!(day_of(at("2024-06-12 04:00"), four), june(12));
+        assert_eq!(day_of(at("2024-06-12 23:59"), four), june(12));
+        assert_eq!(
+            day_of(at("2024-06-12 01:30"), NaiveTime::default()),
+            june(12)
+        );
+        assert_eq!(parse_day_start("4am"), None);
+    }
+
+    #[test]
+    fn test_dates_follow_the_chosen_zone() {
+        let late_utc = "2024-06-12T16:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let tokyo = parse_time_zone("Asia/Tokyo").unwrap();
+        assert_eq!(
+            local_time(late_utc, Some(tokyo)).date(),
+            NaiveDate::from_ymd_opt(2024, 6, 13).unwrap()
+        );
+        assert_eq!(
+            local_time(late_utc, Some(parse_time_zone(" UTC ").unwrap())).date(),
+            NaiveDate::from_ymd_opt(2024, 6, 12).unwrap()
+        );
+        assert!(parse_time_zone("Mars/Olympus").is_err());
+    }
+
+    #[test]
+    fn test_parse_moment_in_zone() {
+        let tokyo = Some(parse_time_zone("Asia/Tokyo").unwrap());
+        let utc = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            parse_moment("2024-06-13 09:00", tokyo),
+            Some(utc("2024-06-13T00:00:00Z"))
+        );
+        assert_eq!(
+            parse_moment("2024-06-13", tokyo),
+            Some(utc("2024-06-12T15:00:00Z"))
+        );
+        assert_eq!(
+            parse_moment("2024-06-13T09:00:00+02:00", tokyo),
+            Some(utc("2024-06-13T07:00:00Z"))
+        );
+        assert_eq!(parse_moment("tomorrow", tokyo), None);
+    }
+
+    #[test]
+    fn test_bad_settings_are_reported() {
+        let mut config = Config::default();
+        config.day_starts_at = Some("04:30".into());
+        config.timezone = Some("Asia/Tokyo".into());
+        let (day_start, zone, problems) = read_settings(&config);
+        assert_eq!(day_start, NaiveTime::from_hms_opt(4, 30, 0).unwrap());
+        assert_eq!(zone, Some(chrono_tz::Asia::Tokyo));
+        assert!(problems.is_empty());
+
+        config.day_starts_at = Some("4am".into());
+        config.timezone = Some("Mars/Olympus".into());
+        let (day_start, zone, problems) = read_settings(&config);
+        assert_eq!((day_start, zone), (NaiveTime::default(), None));
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].contains("'4am'"));
+        assert!(problems[1].contains("Mars/Olympus"));
+    }
+
+    #[test]
+    fn test_fixed_clock_today() {
+        // Fixed moments fall on the same days whatever the machine's zone
+        let _ = set_time_zone("UTC");
+        let at = |s: &str| FixedClock(s.parse::<DateTime<Utc>>().unwrap());
+        let june = |d| NaiveDate::from_ymd_opt(2024, 6, d).unwrap();
+        assert_eq!(at("2024-06-12T23:59:59Z").today(), june(12));
+        assert_eq!(at("2024-06-13T00:00:00Z").today(), june(13));
+    }
+}
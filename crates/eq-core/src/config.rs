@@ -0,0 +1,319 @@
+use crate::models::log::EventAction;
+use crate::models::task::Quadrant;
+use crate::storage::paths::config_path;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::{ErrorKind, Write};
+
+/// Model and sampling for one kind of AI request; unset fields fall back
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ModelSettings {
+    pub model: Option<String>,
+    pub temperature: Option<f32>,
+    pub max_tokens: Option<u32>,
+}
+
+impl ModelSettings {
+    /// `self` with the fields set in `other` taking priority
+    pub fn overridden_by(&self, other: &ModelSettings) -> ModelSettings {
+        ModelSettings {
+            model: other.model.clone().or_else(|| self.model.clone()),
+            temperature: other.temperature.or(self.temperature),
+            max_tokens: other.max_tokens.or(self.max_tokens),
+        }
+    }
+}
+
+/// What is kept out of AI requests
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionRules {
+    /// Tasks tagged with any of these are never sent
+    pub exclude_tags: Vec<String>,
+    /// Regexes whose matches are replaced with `[redacted]` before sending
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionRules {
+    fn default() -> Self {
+        Self {
+            exclude_tags: vec!["#private".to_string()],
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Most pending tasks each quadrant should hold on one day; unlimited when
+/// unset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WipLimits {
+    pub do_first: Option<usize>,
+    pub schedule: Option<usize>,
+    pub delegate: Option<usize>,
+    pub drop: Option<usize>,
+}
+
+impl WipLimits {
+    pub fn limit(&self, quadrant: Quadrant) -> Option<usize> {
+        match quadrant {
+            Quadrant::DoFirst => self.do_first,
+            Quadrant::Schedule => self.schedule,
+            Quadrant::Delegate => self.delegate,
+            Quadrant::Drop => self.drop,
+        }
+    }
+}
+
+/// A shell command or webhook run when a task event is logged
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Hook {
+    /// Events that fire the hook, e.g. `["Completed"]`; every event when empty
+    pub on: Vec<EventAction>,
+    /// Shell command that gets the event JSON on stdin
+    pub command: Option<String>,
+    /// URL the event JSON is POSTed to
+    pub url: Option<String>,
+}
+
+impl Hook {
+    pub fn fires_on(&self, action: EventAction) -> bool {
+        self.on.is_empty() || self.on.contains(&action)
+    }
+}
+
+/// A CalDAV task list kept in step with `eq caldav`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CalDavAccount {
+    /// The task list's collection URL, e.g.
+    /// `https://cloud.example.com/remote.php/dav/calendars/me/tasks/`
+    pub url: String,
+    pub username: String,
+    /// An app password; `EQ_CALDAV_PASSWORD` is used when unset
+    pub password: Option<String>,
+}
+
+/// GitHub access for `eq gh`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GitHubSettings {
+    /// Token for private repositories and for updating issues; `GITHUB_TOKEN`
+    /// is used when unset
+    pub token: Option<String>,
+    /// What completing an imported issue's task does on GitHub: `close` or
+    /// `comment`; nothing when unset
+    pub on_done: Option<String>,
+    /// The comment posted when `on_done` is `comment`
+    pub done_comment: Option<String>,
+}
+
+/// A Jira site for `eq jira`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JiraSettings {
+    /// The site, e.g. `https://acme.atlassian.net`
+    pub url: String,
+    /// The account's email address
+    pub email: String,
+    /// An API token; `JIRA_API_TOKEN` is used when unset
+    pub token: Option<String>,
+    /// Priority names mapped to urgency and importance, e.g. `"P0": "u3i3"`,
+    /// ahead of Jira's stock Highest to Lowest
+    pub priorities: HashMap<String, String>,
+    /// Transition taken when a task is done, e.g. `"Resolve"`; the first one
+    /// into a done status when unset
+    pub done_transition: Option<String>,
+}
+
+/// A chat that gets the morning summary and completion milestones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Sink {
+    /// A Slack incoming webhook
+    Slack { webhook: String },
+    /// A Telegram chat, posted to by a bot
+    Telegram { bot_token: String, chat_id: String },
+}
+
+/// User preferences persisted in `config.json` next to the task data
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Name of the built-in color preset
+    pub theme: Option<String>,
+    /// Per-slot color overrides, e.g. `"do_first": "#ff5555"`
+    pub colors: HashMap<String, String>,
+    /// Mark quadrants with glyphs and border patterns in addition to color
+    pub accessible: bool,
+    /// Task order within quadrants: score, created, alpha, or manual
+    pub sort: Option<String>,
+    /// Zen mode animation: full, reduced, or still
+    pub motion: Option<String>,
+    /// AI backend: openai, anthropic, gemini, openrouter, azure, or compatible
+    pub ai_provider: Option<String>,
+    /// Endpoint for an OpenAI-compatible server, e.g. `http://localhost:11434/v1`
+    pub ai_base_url: Option<String>,
+    /// Model name passed to the provider
+    pub ai_model: Option<String>,
+    /// Azure OpenAI deployment to call; defaults to the model name
+    pub ai_azure_deployment: Option<String>,
+    /// Azure OpenAI `api-version`, e.g. `2024-10-21`
+    pub ai_azure_api_version: Option<String>,
+    /// Seconds to wait for each AI request before giving up (default 60)
+    pub ai_timeout_secs: Option<u64>,
+    /// Proxy for AI requests; `HTTPS_PROXY` or `ALL_PROXY` when unset
+    pub ai_proxy: Option<String>,
+    /// Extra root certificate (PEM) to trust, e.g. a corporate CA
+    pub ai_ca_cert: Option<String>,
+    /// Template file replacing the built-in system prompt; `{tasks}`,
+    /// `{quotes}`, and `{today}` are filled in
+    pub ai_system_prompt: Option<String>,
+    /// Seconds an identical AI request reuses the saved reply (default 3600;
+    /// 0 turns the cache off)
+    pub ai_cache_ttl_secs: Option<u64>,
+    /// whisper.cpp model file; set it to transcribe voice memos locally
+    pub ai_whisper_model: Option<String>,
+    /// whisper.cpp program to run (default `whisper-cli`)
+    pub ai_whisper_bin: Option<String>,
+    /// Tags and patterns kept out of AI requests
+    pub ai_redact: RedactionRules,
+    /// Words read as priorities, e.g. `"urgent": "u3i2"`; replaces the
+    /// built-in list, so `{}` turns priority words off
+    pub priority_words: Option<HashMap<String, String>>,
+    /// When one day ends and the next begins, e.g. `"04:00"`; midnight when unset
+    pub day_starts_at: Option<String>,
+    /// Time zone dates are reckoned in, e.g. `"Asia/Tokyo"`; the system's when unset
+    pub timezone: Option<String>,
+    /// Interface language, e.g. `"zh"`; the locale (`LANG`) decides when unset
+    pub language: Option<String>,
+    /// Model and sampling for regular chat and planning
+    pub ai_planning: ModelSettings,
+    /// Model and sampling for the `quote` command
+    pub ai_quote: ModelSettings,
+    /// Ask before dropping tasks, clearing chat, or acting on a multi-selection
+    pub confirm_destructive: bool,
+    /// Add tasks without checking for a similar pending one
+    pub ignore_duplicates: bool,
+    /// Also ask the AI whether a new task repeats a pending one worded
+    /// differently, when no pending task shares its words
+    pub ai_duplicate_check: bool,
+    /// Pending tasks allowed per quadrant per day, e.g. `"do_first": 5`;
+    /// adding more warns and turns the quadrant's header red
+    pub wip_limits: WipLimits,
+    /// Share of a day's pending tasks that may be DO FIRST before eq
+    /// suggests re-triaging, e.g. `0.5`; 0.6 when unset
+    pub do_first_share: Option<f64>,
+    /// Ask the AI for a priority when `eq add` is given none
+    pub ai_suggest_priority: bool,
+    /// Commands and webhooks run on task events
+    pub hooks: Vec<Hook>,
+    /// When `eq notify --daemon` reminds you of due and overdue tasks, e.g.
+    /// `"08:30"`; 09:00 when unset
+    pub morning_reminder_at: Option<String>,
+    /// CalDAV server synced by `eq caldav`
+    pub caldav: Option<CalDavAccount>,
+    /// API token for `eq import --from todoist`; `TODOIST_API_TOKEN` is used
+    /// when unset
+    pub todoist_token: Option<String>,
+    /// Token and issue updates for `eq gh`
+    pub github: GitHubSettings,
+    /// Jira site for `eq jira`; completing a pulled issue's task moves the
+    /// issue to done
+    pub jira: Option<JiraSettings>,
+    /// Obsidian folder that gets a note with each day's matrix, e.g.
+    /// `"/home/me/Vault/Daily"`; ticking a checkbox there completes the task
+    pub obsidian_dir: Option<String>,
+    /// Chats posted the morning summary and completion milestones
+    pub sinks: Vec<Sink>,
+    /// Integration token for `eq export --to notion`; `NOTION_TOKEN` is used
+    /// when unset
+    pub notion_token: Option<String>,
+    /// Why `config.json` couldn't be read, when these are the stand-in
+    /// defaults; `save` refuses so the file isn't overwritten
+    #[serde(skip)]
+    load_error: Option<String>,
+}
+
+impl Config {
+    /// Load the config; the defaults when there is no `config.json` yet,
+    /// and an error when it can't be read or doesn't parse
+    pub fn load() -> Result<Self, String> {
+        let path = config_path().map_err(|e| e.to_string())?;
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// The config in `content`, as written in `config.json`
+    pub fn parse(content: &str) -> Result<Self, String> {
+        serde_json::from_str(content).map_err(|e| format!("config.json is not valid: {}", e))
+    }
+
+    /// `load`, or the defaults when it fails; those can't be saved over the
+    /// broken file. For callers that go on without settings
+    pub fn load_or_default() -> Self {
+        Self::load().unwrap_or_else(Self::fallback)
+    }
+
+    /// Defaults standing in for a config that failed to load with `error`
+    pub fn fallback(error: String) -> Self {
+        tracing::warn!(%error, "using the default config");
+        Config {
+            load_error: Some(error),
+            ..Config::default()
+        }
+    }
+
+    /// Why the config couldn't be loaded, when these are stand-in defaults
+    pub fn load_error(&self) -> Option<&str> {
+        self.load_error.as_deref()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(error) = &self.load_error {
+            return Err(format!(
+                "not overwriting config.json, which failed to load ({})",
+                error
+            )
+            .into());
+        }
+        let path = config_path()?;
+        let content = serde_json::to_string_pretty(self)?;
+
+        // Atomic write: write to .tmp then rename
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_broken_config_is_reported_and_never_saved() {
+        let config = Config::parse("{\"theme\": \"nord\"}").unwrap();
+        assert_eq!(config.theme.as_deref(), Some("nord"));
+        let error = Config::parse("{\"theme\": \"nord\",}").unwrap_err();
+        assert!(error.contains("not valid"), "{}", error);
+
+        let fallback = Config::fallback(error);
+        assert!(fallback.load_error().is_some());
+        assert!(fallback
+            .save()
+            .unwrap_err()
+            .to_string()
+            .contains("not overwriting"));
+    }
+}
@@ -0,0 +1,33 @@
+//! The task store, task-line parsing, and stats behind `eq`, without the
+//! terminal interface, so other frontends can share the same data.
+//!
+//! ```
+//! use chrono::NaiveDate;
+//! use eq_core::{parse_input, Quadrant};
+//!
+//! let today = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+//! let task = parse_input("Email Bob u3i2 ~15m", today).into_task(today);
+//! assert_eq!(task.title, "Email Bob");
+//! assert_eq!(task.estimate_minutes, Some(15));
+//! assert_eq!(task.quadrant(), Quadrant::DoFirst);
+//! ```
+//!
+//! [`TaskStore::load`] and [`TaskStore::save`] read and write the same
+//! `tasks.json` the `eq` binary uses.
+
+pub mod clock;
+pub mod config;
+pub mod models;
+pub mod parser;
+pub mod similar;
+pub mod stats;
+pub mod storage;
+pub mod timings;
+
+pub use clock::{Clock, FixedClock, SystemClock};
+pub use models::focus::FocusSession;
+pub use models::log::{EventAction, LogEvent};
+pub use models::store::{TaskLookup, TaskStore};
+pub use models::task::{Quadrant, Task, TaskStatus};
+pub use parser::date::parse_date;
+pub use parser::input::{parse_input, TaskInput};
@@ -0,0 +1,56 @@
+use crate::clock;
+use crate::storage::paths::focus_sessions_path;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use uuid::Uuid;
+
+/// A finished pomodoro, optionally bound to the task it was spent on.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FocusSession {
+    pub id: Uuid,
+    pub task_id: Option<Uuid>,
+    pub date: NaiveDate,
+    pub ended_at: DateTime<Utc>,
+    pub duration_secs: u64,
+}
+
+impl FocusSession {
+    pub fn new(task_id: Option<Uuid>, date: NaiveDate, duration_secs: u64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            task_id,
+            date,
+            ended_at: clock::now(),
+            duration_secs,
+        }
+    }
+}
+
+pub fn append_session(session: &FocusSession) -> std::io::Result<()> {
+    let path = focus_sessions_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    let json = serde_json::to_string(session)?;
+    writeln!(file, "{}", json)?;
+    Ok(())
+}
+
+/// Read every recorded focus session, skipping lines that fail to parse.
+pub fn read_sessions() -> std::io::Result<Vec<FocusSession>> {
+    let path = focus_sessions_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
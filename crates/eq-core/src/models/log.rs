@@ -0,0 +1,144 @@
+use crate::clock;
+use crate::models::task::Task;
+use crate::storage::paths::history_log_path;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventAction {
+    Created,
+    Completed,
+    Dropped,
+    Updated,
+    Moved,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LogEvent {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub action: EventAction,
+    pub task_id: Uuid,
+    pub details: String,
+    /// Hash of the git commit whose message completed the task
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+}
+
+impl LogEvent {
+    pub fn new(action: EventAction, task_id: Uuid, details: String) -> Self {
+        Self::at(clock::now(), action, task_id, details)
+    }
+
+    /// An event that happened at `timestamp`
+    pub fn at(
+        timestamp: DateTime<Utc>,
+        action: EventAction,
+        task_id: Uuid,
+        details: String,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            timestamp,
+            action,
+            task_id,
+            details,
+            commit: None,
+        }
+    }
+
+    /// Parse the `(from, to)` dates out of a `Moved` event's details.
+    pub fn moved_dates(&self) -> Option<(NaiveDate, NaiveDate)> {
+        if !matches!(self.action, EventAction::Moved) {
+            return None;
+        }
+        let rest = self.details.strip_prefix("Moved: ")?;
+        let (from, to) = rest.split_once(" -> ")?;
+        Some((from.trim().parse().ok()?, to.trim().parse().ok()?))
+    }
+}
+
+/// Told about each event once it is in the log, with the store's tasks as
+/// they stand after it; empty for events not logged by the store
+type Listener = Box<dyn Fn(&LogEvent, &[Task]) + Send + Sync>;
+
+static LISTENER: OnceLock<Listener> = OnceLock::new();
+
+/// Call `listener` with every event appended to the log from now on; only
+/// the first listener set is kept
+pub fn set_listener(listener: impl Fn(&LogEvent, &[Task]) + Send + Sync + 'static) {
+    let _ = LISTENER.set(Box::new(listener));
+}
+
+pub fn append_log(event: &LogEvent) -> std::io::Result<()> {
+    append_log_with(event, &[])
+}
+
+/// Append `event`, handing the listener `tasks`, the store after the change
+pub fn append_log_with(event: &LogEvent, tasks: &[Task]) -> std::io::Result<()> {
+    if let Err(err) = write_event(event) {
+        // Most callers carry on without the entry, so keep a trace of it
+        tracing::warn!(%err, action = ?event.action, "could not append to the history log");
+        return Err(err);
+    }
+    tracing::trace!(action = ?event.action, task = %event.task_id, "logged event");
+    if let Some(listener) = LISTENER.get() {
+        listener(event, tasks);
+    }
+    Ok(())
+}
+
+fn write_event(event: &LogEvent) -> std::io::Result<()> {
+    let path = history_log_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    let json = serde_json::to_string(event)?;
+    writeln!(file, "{}", json)?;
+    Ok(())
+}
+
+/// Read every event from the history log, skipping lines that fail to parse.
+pub fn read_log() -> std::io::Result<Vec<LogEvent>> {
+    let path = history_log_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Events appended to the history log after byte `offset`, and the offset
+/// to read from next time. Only whole lines are read; when the log has
+/// shrunk, as after `eq sync` replaced it, reading starts over
+pub fn read_log_since(offset: u64) -> std::io::Result<(Vec<LogEvent>, u64)> {
+    let path = history_log_path()?;
+    let len = match std::fs::metadata(&path) {
+        Ok(metadata) => metadata.len(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(err) => return Err(err),
+    };
+    let offset = if len < offset { 0 } else { offset };
+    let mut appended = String::new();
+    let mut file = std::fs::File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    file.take(len - offset).read_to_string(&mut appended)?;
+
+    let consumed = appended.rfind('\n').map_or(0, |end| end + 1);
+    let events = appended[..consumed]
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    Ok((events, offset + consumed as u64))
+}
@@ -0,0 +1,6 @@
+pub mod focus;
+pub mod log;
+pub mod store;
+pub mod summary;
+pub mod task;
+pub mod undo;
@@ -0,0 +1,783 @@
+use crate::clock::{self, Clock};
+use crate::models::log::{append_log, append_log_with, EventAction, LogEvent};
+use crate::models::summary::Summary;
+use crate::models::task::{Task, TaskStatus};
+use crate::models::undo::UndoJournal;
+use crate::similar::{match_title, TitleMatch};
+use crate::storage::atomic::write_atomic;
+use crate::storage::paths::{archive_file_path, chat_history_path, tasks_file_path};
+use crate::timings;
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use uuid::Uuid;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskStore {
+    pub tasks: Vec<Task>,
+    /// Changes made this session, newest last
+    #[serde(skip)]
+    pub journal: UndoJournal,
+    /// Stamps completions and log events, and decides what day it is
+    #[serde(skip, default = "clock::current")]
+    pub clock: Arc<dyn Clock>,
+    /// Bumped by every change made through the store, so views built from
+    /// `tasks` know when they are out of date
+    #[serde(skip)]
+    revision: u64,
+    /// Positions in `tasks` by day, rebuilt on first use after a change
+    #[serde(skip)]
+    by_date: Mutex<DateIndex>,
+    /// Whether `archive.json` has been read into `tasks`
+    #[serde(skip)]
+    history_loaded: bool,
+    /// The archived tasks as last read or written, so saves leave
+    /// `archive.json` alone until something ages into it or changes there.
+    /// Shared with snapshots, which may be the ones saving
+    #[serde(skip)]
+    archived: Arc<Mutex<HashMap<Uuid, Task>>>,
+}
+
+/// Finished tasks planned more than this many days ago move to
+/// `archive.json`, which is only read when history is asked for
+pub const ARCHIVE_AFTER_DAYS: i64 = 90;
+
+/// Whether `task` belongs in the archive rather than `tasks.json`
+pub fn is_archived(task: &Task, today: NaiveDate) -> bool {
+    task.status != TaskStatus::Pending && task.date < today - Duration::days(ARCHIVE_AFTER_DAYS)
+}
+
+/// The tasks as written to `tasks.json` or `archive.json`
+#[derive(Serialize)]
+struct Saved<'a> {
+    tasks: Vec<&'a Task>,
+}
+
+#[derive(Deserialize)]
+struct Archive {
+    tasks: Vec<Task>,
+}
+
+#[derive(Debug, Default)]
+struct DateIndex {
+    /// The revision and task count the positions were built for
+    built_for: Option<(u64, usize)>,
+    positions: HashMap<NaiveDate, Vec<usize>>,
+}
+
+impl Default for TaskStore {
+    fn default() -> Self {
+        Self::with_clock(clock::current())
+    }
+}
+
+/// A copy of the tasks kept elsewhere, such as Markdown notes, that can be
+/// edited there too
+pub trait Mirror: Send + Sync {
+    /// Take in changes made to the copy; returns whether there were any
+    fn read_back(&self, store: &mut TaskStore) -> bool;
+    /// Bring the copy up to date with `store`
+    fn write(&self, store: &TaskStore);
+}
+
+static MIRROR: OnceLock<Box<dyn Mirror>> = OnceLock::new();
+
+/// Read `mirror` back in `catch_up` and write it on every save from now on;
+/// only the first mirror set is kept
+pub fn set_mirror(mirror: impl Mirror + 'static) {
+    let _ = MIRROR.set(Box::new(mirror));
+}
+
+/// Fewest hex digits an ID prefix needs, so short numbers and words are
+/// read as indexes and titles instead
+pub const MIN_ID_PREFIX: usize = 4;
+
+/// Tasks whose ID starts with `prefix`, in any case; none when it has fewer
+/// than `MIN_ID_PREFIX` hex digits or anything but hex digits and dashes
+pub fn tasks_with_id_prefix<'a>(tasks: &'a [Task], prefix: &str) -> Vec<&'a Task> {
+    let prefix = prefix.to_lowercase();
+    let digits = prefix.chars().filter(char::is_ascii_hexdigit).count();
+    if digits < MIN_ID_PREFIX || !prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        return Vec::new();
+    }
+    tasks
+        .iter()
+        .filter(|t| t.id.to_string().starts_with(&prefix))
+        .collect()
+}
+
+/// How a task identifier given on the command line resolved
+#[derive(Debug, PartialEq)]
+pub enum TaskLookup<'a> {
+    Found(Uuid),
+    /// Several tasks fit equally well; the user has to pick one
+    Ambiguous(Vec<&'a Task>),
+    NotFound,
+}
+
+/// Chat message for persistence
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+fn read_archive(path: &Path) -> Result<Vec<Task>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let archive: Archive = serde_json::from_str(&fs::read_to_string(path)?)?;
+    Ok(archive.tasks)
+}
+
+/// Write `path` atomically. Errors name the file, since a read-only or
+/// full data directory is something the user has to fix
+fn write_file(path: &Path, content: &str) -> Result<(), Box<dyn std::error::Error>> {
+    write_atomic(path, content)
+        .map_err(|err| format!("can't write {}: {}", path.display(), err).into())
+}
+
+impl TaskStore {
+    /// An empty store that takes the time from `clock`
+    pub fn with_clock(clock: Arc<dyn Clock>) -> Self {
+        Self {
+            tasks: Vec::new(),
+            journal: UndoJournal::default(),
+            clock,
+            revision: 0,
+            by_date: Mutex::default(),
+            history_loaded: false,
+            archived: Arc::default(),
+        }
+    }
+
+    /// A copy of the tasks to save elsewhere, such as on a background
+    /// thread; it has no undo journal
+    pub fn snapshot(&self) -> Self {
+        Self {
+            tasks: self.tasks.clone(),
+            journal: UndoJournal::default(),
+            clock: Arc::clone(&self.clock),
+            revision: self.revision,
+            by_date: Mutex::default(),
+            history_loaded: self.history_loaded,
+            archived: Arc::clone(&self.archived),
+        }
+    }
+
+    /// Changes so far; it moves on with every change made through the store
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Record a change made by editing `tasks` directly
+    pub fn touch(&mut self) {
+        self.revision += 1;
+    }
+
+    /// Tasks planned for `date`, in store order, found through the day index
+    /// rather than a scan of every task
+    pub fn tasks_on(&self, date: NaiveDate) -> Vec<&Task> {
+        let mut index = self.by_date.lock().unwrap_or_else(|e| e.into_inner());
+        let current = (self.revision, self.tasks.len());
+        if index.built_for != Some(current) {
+            index.positions.clear();
+            for (i, task) in self.tasks.iter().enumerate() {
+                index.positions.entry(task.date).or_default().push(i);
+            }
+            index.built_for = Some(current);
+        }
+        index
+            .positions
+            .get(&date)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.tasks[i])
+            .collect()
+    }
+
+    /// The current day by the store's clock
+    pub fn today(&self) -> NaiveDate {
+        self.clock.today()
+    }
+
+    /// The store as saved. Never writes or changes a task, so it is safe
+    /// from background threads and unlocked readers; see `catch_up`
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let started = Instant::now();
+        let path = Self::get_path()?;
+
+        if !path.exists() {
+            return Ok(TaskStore::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let store: TaskStore = serde_json::from_str(&content)?;
+        timings::record_load(started.elapsed(), store.tasks.len());
+        tracing::debug!(tasks = store.tasks.len(), elapsed = ?started.elapsed(), "loaded store");
+        Ok(store)
+    }
+
+    /// Read the archived tasks in with the recent ones, for stats and
+    /// anything else that looks further back than the last few months.
+    /// Does nothing after the first call
+    pub fn load_history(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.history_loaded {
+            return Ok(());
+        }
+        let archive = read_archive(&archive_file_path()?)?;
+        // `tasks.json` has the newer copy of a task reopened since archiving
+        let recent: HashSet<Uuid> = self.tasks.iter().map(|t| t.id).collect();
+        let mut archived = self.archived.lock().unwrap_or_else(|e| e.into_inner());
+        for task in archive {
+            archived.insert(task.id, task.clone());
+            if !recent.contains(&task.id) {
+                self.tasks.push(task);
+            }
+        }
+        drop(archived);
+        self.history_loaded = true;
+        self.touch();
+        tracing::debug!(tasks = self.tasks.len(), "read the archive in");
+        Ok(())
+    }
+
+    /// Whether the archived tasks have been read in
+    pub fn history_loaded(&self) -> bool {
+        self.history_loaded
+    }
+
+    /// Write pending and recent tasks to `tasks.json`, and old finished ones
+    /// to `archive.json` when that has anything new for it
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let started = Instant::now();
+        let today = self.today();
+        let (old, recent): (Vec<&Task>, Vec<&Task>) =
+            self.tasks.iter().partition(|t| is_archived(t, today));
+        self.save_archive(&old)?;
+        write_file(
+            &Self::get_path()?,
+            &serde_json::to_string_pretty(&Saved { tasks: recent })?,
+        )?;
+        let _ = Summary::of(&self.tasks, today).save();
+        if let Some(mirror) = MIRROR.get() {
+            mirror.write(self);
+        }
+        timings::SAVES.record(started.elapsed());
+        tracing::debug!(tasks = self.tasks.len(), elapsed = ?started.elapsed(), "saved store");
+        Ok(())
+    }
+
+    fn save_archive(&self, old: &[&Task]) -> Result<(), Box<dyn std::error::Error>> {
+        let mut archived = self.archived.lock().unwrap_or_else(|e| e.into_inner());
+        if old.iter().all(|t| archived.get(&t.id) == Some(*t)) {
+            return Ok(());
+        }
+        let path = archive_file_path()?;
+        // With history loaded, `tasks` holds the whole archive; otherwise the
+        // file keeps whatever this store never read
+        let kept = if self.history_loaded {
+            Vec::new()
+        } else {
+            let in_memory: HashSet<Uuid> = self.tasks.iter().map(|t| t.id).collect();
+            read_archive(&path)?
+                .into_iter()
+                .filter(|t| !in_memory.contains(&t.id))
+                .collect()
+        };
+        let mut tasks: Vec<&Task> = kept.iter().collect();
+        tasks.extend(old);
+        tracing::debug!(tasks = tasks.len(), "writing the archive");
+        write_file(&path, &serde_json::to_string_pretty(&Saved { tasks })?)?;
+        archived.extend(old.iter().map(|t| (t.id, (*t).clone())));
+        Ok(())
+    }
+
+    fn get_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
+        tasks_file_path().map_err(|e| e.into())
+    }
+
+    /// Load chat history from file
+    pub fn load_chat_history() -> Vec<ChatMessage> {
+        let path = match chat_history_path() {
+            Ok(p) => p,
+            Err(_) => return Vec::new(),
+        };
+
+        if !path.exists() {
+            return Vec::new();
+        }
+
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Save chat history to file
+    pub fn save_chat_history(history: &[ChatMessage]) -> Result<(), Box<dyn std::error::Error>> {
+        let path = chat_history_path()?;
+        write_atomic(&path, &serde_json::to_string_pretty(history)?)?;
+        Ok(())
+    }
+
+    pub fn add_task(&mut self, task: Task) {
+        self.touch();
+        let event = LogEvent::at(
+            self.clock.now(),
+            EventAction::Created,
+            task.id,
+            format!("Created task: {}", task.title),
+        );
+        let _ = append_log(&event);
+        self.journal
+            .record(format!("add \"{}\"", task.title), task.id, None);
+        self.tasks.push(task);
+    }
+
+    pub fn toggle_complete_task(&mut self, id: Uuid) -> bool {
+        self.touch();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+            let verb = if task.status == TaskStatus::Completed {
+                "reopen"
+            } else {
+                "complete"
+            };
+            self.journal.record(
+                format!("{} \"{}\"", verb, task.title),
+                id,
+                Some(task.clone()),
+            );
+            if task.status == TaskStatus::Completed {
+                task.undo_complete();
+                let event = LogEvent::at(
+                    self.clock.now(),
+                    EventAction::Updated,
+                    id,
+                    format!("Undone task: {}", task.title),
+                );
+                let _ = append_log(&event);
+            } else {
+                task.complete_at(self.clock.now());
+                let event = LogEvent::at(
+                    self.clock.now(),
+                    EventAction::Completed,
+                    id,
+                    format!("Completed task: {}", task.title),
+                );
+                // Hooks count the day's completions from the tasks
+                let _ = append_log_with(&event, &self.tasks);
+            }
+            return true;
+        }
+        false
+    }
+
+    pub fn complete_task(&mut self, id: Uuid) -> bool {
+        self.complete_task_in_commit(id, None)
+    }
+
+    /// Complete a task, recording the git commit that closed it in the log
+    pub fn complete_task_in_commit(&mut self, id: Uuid, commit: Option<&str>) -> bool {
+        self.touch();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+            if task.status != TaskStatus::Completed {
+                self.journal.record(
+                    format!("complete \"{}\"", task.title),
+                    id,
+                    Some(task.clone()),
+                );
+                task.complete_at(self.clock.now());
+                let mut event = LogEvent::at(
+                    self.clock.now(),
+                    EventAction::Completed,
+                    id,
+                    format!("Completed task: {}", task.title),
+                );
+                event.commit = commit.map(str::to_string);
+                let _ = append_log_with(&event, &self.tasks);
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn drop_task(&mut self, id: Uuid) -> bool {
+        self.drop_task_with_reason(id, None)
+    }
+
+    /// Drop a task, recording why it was dropped for the journal
+    pub fn drop_task_with_reason(&mut self, id: Uuid, reason: Option<String>) -> bool {
+        self.touch();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+            if task.status != TaskStatus::Dropped {
+                self.journal
+                    .record(format!("drop \"{}\"", task.title), id, Some(task.clone()));
+                task.drop_task();
+                let details = match &reason {
+                    Some(r) => format!("Dropped task: {} ({})", task.title, r),
+                    None => format!("Dropped task: {}", task.title),
+                };
+                task.drop_reason = reason;
+                let event = LogEvent::at(self.clock.now(), EventAction::Dropped, id, details);
+                let _ = append_log(&event);
+                return true;
+            }
+        }
+        false
+    }
+
+    pub fn update_task(&mut self, id: Uuid, title: String, urgency: u8, importance: u8) -> bool {
+        self.touch();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+            self.journal
+                .record(format!("edit \"{}\"", task.title), id, Some(task.clone()));
+            let old_details = format!("{} (u{}i{})", task.title, task.urgency, task.importance);
+            task.title = title;
+            // Urgency set by hand no longer follows the deadline
+            task.urgency_from_due &= urgency == task.urgency;
+            task.urgency = urgency;
+            task.importance = importance;
+            let new_details = format!("{} (u{}i{})", task.title, task.urgency, task.importance);
+
+            let event = LogEvent::at(
+                self.clock.now(),
+                EventAction::Updated,
+                id,
+                format!("Updated: {} -> {}", old_details, new_details),
+            );
+            let _ = append_log(&event);
+            return true;
+        }
+        false
+    }
+
+    /// Recompute the urgency of tasks that follow their deadline, logging
+    /// each change; returns how many changed
+    pub fn refresh_due_urgency(&mut self, today: NaiveDate) -> usize {
+        self.touch();
+        let now = self.clock.now();
+        let mut changed = 0;
+        for task in &mut self.tasks {
+            let old = task.urgency;
+            if task.refresh_due_urgency(today) {
+                changed += 1;
+                let event = LogEvent::at(
+                    now,
+                    EventAction::Updated,
+                    task.id,
+                    format!(
+                        "Urgency from deadline: {} (u{} -> u{})",
+                        task.title, old, task.urgency
+                    ),
+                );
+                let _ = append_log(&event);
+            }
+        }
+        changed
+    }
+
+    /// Bring a just-loaded store up to date: take in edits made to the
+    /// mirror and let deadlines raise urgency for today. Returns whether
+    /// anything changed; the caller saves, so the changes are taken in once
+    pub fn catch_up(&mut self) -> bool {
+        let mirrored = MIRROR.get().is_some_and(|mirror| mirror.read_back(self));
+        let today = self.today();
+        let reprioritized = self.refresh_due_urgency(today);
+        mirrored || reprioritized > 0
+    }
+
+    pub fn move_task_to_date(&mut self, id: Uuid, date: NaiveDate) -> bool {
+        self.touch();
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+            self.journal
+                .record(format!("move \"{}\"", task.title), id, Some(task.clone()));
+            let old_date = task.date;
+            task.date = date;
+            let event = LogEvent::at(
+                self.clock.now(),
+                EventAction::Moved,
+                id,
+                format!("Moved: {} -> {}", old_date, date),
+            );
+            let _ = append_log(&event);
+            return true;
+        }
+        false
+    }
+
+    /// Rank `ids` 0, 1, 2, … in the manual sort order, as one undo step
+    pub fn set_manual_order(&mut self, ids: &[Uuid]) {
+        self.touch();
+        self.journal.begin_batch();
+        for (rank, id) in ids.iter().enumerate() {
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *id) {
+                if task.position != Some(rank as u32) {
+                    self.journal.record(
+                        format!("reorder \"{}\"", task.title),
+                        *id,
+                        Some(task.clone()),
+                    );
+                    task.position = Some(rank as u32);
+                }
+            }
+        }
+        self.journal.end_batch("reorder");
+    }
+
+    /// Replace the `#tags` of each of `ids` with `tags`, as one undo step
+    pub fn retag(&mut self, ids: &[Uuid], tags: &[String]) {
+        self.touch();
+        self.journal.begin_batch();
+        for id in ids {
+            if let Some(task) = self.tasks.iter_mut().find(|t| t.id == *id) {
+                let old_title = task.title.clone();
+                task.set_tags(tags);
+                if task.title == old_title {
+                    continue;
+                }
+                let mut before = task.clone();
+                before.title = old_title;
+                self.journal
+                    .record(format!("retag \"{}\"", before.title), *id, Some(before));
+                let event = LogEvent::at(
+                    self.clock.now(),
+                    EventAction::Updated,
+                    *id,
+                    format!("Retagged: {}", task.title),
+                );
+                let _ = append_log(&event);
+            }
+        }
+        self.journal.end_batch("retag");
+    }
+
+    /// Revert the most recent change made this session, returning its label
+    pub fn undo(&mut self) -> Option<String> {
+        self.touch();
+        let entry = self.journal.undo(&mut self.tasks)?;
+        for id in entry.task_ids() {
+            let event = LogEvent::at(
+                self.clock.now(),
+                EventAction::Updated,
+                id,
+                format!("Undo: {}", entry.label),
+            );
+            let _ = append_log(&event);
+        }
+        Some(entry.label)
+    }
+
+    /// Find a task, pending or not, by the start of its ID; see
+    /// `tasks_with_id_prefix`
+    pub fn find_by_id_prefix(&self, prefix: &str) -> TaskLookup<'_> {
+        let found = tasks_with_id_prefix(&self.tasks, prefix);
+        match found.as_slice() {
+            [task] => TaskLookup::Found(task.id),
+            [] => TaskLookup::NotFound,
+            _ => TaskLookup::Ambiguous(found),
+        }
+    }
+
+    /// Find a task by index, ID prefix of at least `MIN_ID_PREFIX` digits,
+    /// or title. Indexes count the pending tasks on `filter_date` by score,
+    /// and titles are matched fuzzily among those same tasks
+    pub fn find_task_id(&self, identifier: &str, filter_date: Option<NaiveDate>) -> TaskLookup<'_> {
+        let mut pending: Vec<&Task> = self
+            .tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Pending && filter_date.is_none_or(|d| t.date == d))
+            .collect();
+        pending.sort_by_key(|t| std::cmp::Reverse(t.score()));
+
+        // Try to parse as 1-based index
+        if let Ok(idx) = identifier.parse::<usize>() {
+            if idx > 0 && idx <= pending.len() {
+                return TaskLookup::Found(pending[idx - 1].id);
+            }
+        }
+
+        let by_id = self.find_by_id_prefix(identifier);
+        if by_id != TaskLookup::NotFound {
+            return by_id;
+        }
+
+        match match_title(pending, identifier) {
+            TitleMatch::Found(task) => TaskLookup::Found(task.id),
+            TitleMatch::Ambiguous(tasks) => TaskLookup::Ambiguous(tasks),
+            TitleMatch::NotFound => TaskLookup::NotFound,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+
+    #[test]
+    fn test_find_task_id_by_index_id_and_title() {
+        let date: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut store = TaskStore::default();
+        for (title, urgency) in [
+            ("Email Bob", 3),
+            ("Mail rent check", 2),
+            ("Write report", 1),
+        ] {
+            store.tasks.push(Task::new(title.into(), urgency, 2, date));
+        }
+        let ids: Vec<Uuid> = store.tasks.iter().map(|t| t.id).collect();
+
+        assert_eq!(
+            store.find_task_id("2", Some(date)),
+            TaskLookup::Found(ids[1])
+        );
+        let prefix = &ids[2].to_string()[..8];
+        assert_eq!(
+            store.find_task_id(prefix, Some(date)),
+            TaskLookup::Found(ids[2])
+        );
+        assert_eq!(
+            store.find_task_id("report", Some(date)),
+            TaskLookup::Found(ids[2])
+        );
+        match store.find_task_id("mail", Some(date)) {
+            TaskLookup::Ambiguous(tasks) => assert_eq!(tasks.len(), 2),
+            other => panic!("expected ambiguity, got {:?}", other),
+        }
+        assert_eq!(
+            store.find_task_id("mail", None),
+            store.find_task_id("mail", Some(date))
+        );
+        // Too short for an ID, so "bad" is looked up as a title
+        store.tasks[0].id = Uuid::parse_str("bad00000-0000-4000-8000-000000000000").unwrap();
+        assert_eq!(store.find_task_id("bad", Some(date)), TaskLookup::NotFound);
+        assert_eq!(
+            store.find_task_id("BAD0", Some(date)),
+            TaskLookup::Found(store.tasks[0].id)
+        );
+        assert_eq!(
+            store.find_task_id("groceries", Some(date)),
+            TaskLookup::NotFound
+        );
+    }
+
+    #[test]
+    fn test_find_by_id_prefix_ignores_status() {
+        let day: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut store = TaskStore::default();
+        for (title, id) in [("Ship", "ab3f"), ("Plan", "ab3e")] {
+            let mut task = Task::new(title.into(), 3, 3, day);
+            task.id = Uuid::parse_str(&format!("{}0000-0000-4000-8000-000000000000", id)).unwrap();
+            store.tasks.push(task);
+        }
+        store.tasks[0].status = TaskStatus::Completed;
+        assert_eq!(
+            store.find_by_id_prefix("ab3f"),
+            TaskLookup::Found(store.tasks[0].id)
+        );
+        assert_eq!(
+            store.find_by_id_prefix("AB3E0"),
+            TaskLookup::Found(store.tasks[1].id)
+        );
+        assert_eq!(store.find_by_id_prefix("ab3"), TaskLookup::NotFound);
+        assert_eq!(store.find_by_id_prefix("ffff"), TaskLookup::NotFound);
+    }
+    #[test]
+    fn test_tasks_on_follows_changes() {
+        let monday: NaiveDate = "2024-06-10".parse().unwrap();
+        let tuesday = monday.succ_opt().unwrap();
+        let mut store = TaskStore::default();
+        store
+            .tasks
+            .push(Task::new("Email Bob".into(), 2, 2, monday));
+        let titles = |store: &TaskStore, date| -> Vec<String> {
+            store
+                .tasks_on(date)
+                .iter()
+                .map(|t| t.title.clone())
+                .collect()
+        };
+        assert_eq!(titles(&store, monday), vec!["Email Bob"]);
+
+        // Added behind the store's back: the count gives it away
+        store.tasks.push(Task::new("Plan Q3".into(), 1, 3, tuesday));
+        assert_eq!(titles(&store, tuesday), vec!["Plan Q3"]);
+
+        store.tasks[0].date = tuesday;
+        store.touch();
+        assert!(titles(&store, monday).is_empty());
+        assert_eq!(titles(&store, tuesday), vec!["Email Bob", "Plan Q3"]);
+    }
+
+    #[test]
+    fn test_only_old_finished_tasks_are_archived() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let old = today - Duration::days(ARCHIVE_AFTER_DAYS + 1);
+        let edge = today - Duration::days(ARCHIVE_AFTER_DAYS);
+        let mut done = Task::new("Ship release".into(), 3, 3, old);
+        done.complete();
+        let mut dropped = Task::new("Learn Go".into(), 1, 2, old);
+        dropped.drop_task();
+        let mut recent = Task::new("Plan Q3".into(), 1, 3, edge);
+        recent.complete();
+        assert!(is_archived(&done, today));
+        assert!(is_archived(&dropped, today));
+        assert!(!is_archived(&recent, today));
+        // Never done, so still shown wherever it is planned
+        assert!(!is_archived(
+            &Task::new("Old idea".into(), 1, 1, old),
+            today
+        ));
+    }
+
+    #[test]
+    fn test_store_takes_the_day_from_its_clock() {
+        let _ = clock::set_time_zone("UTC");
+        let mut task = Task::new("Ship release".into(), 1, 3, "2024-06-12".parse().unwrap());
+        task.due = Some("2024-06-19".parse().unwrap());
+        task.urgency_from_due = true;
+        let at = |moment: &str| {
+            let mut store = TaskStore::with_clock(Arc::new(FixedClock(moment.parse().unwrap())));
+            store.tasks.push(task.clone());
+            store
+        };
+
+        let mut before = at("2024-06-12T23:59:00Z");
+        assert_eq!(before.today().to_string(), "2024-06-12");
+        assert_eq!(before.refresh_due_urgency(before.today()), 0);
+
+        let mut after = at("2024-06-13T00:01:00Z");
+        assert_eq!(after.today().to_string(), "2024-06-13");
+        assert!(after.catch_up());
+        assert_eq!(after.tasks[0].urgency, 2);
+        assert!(!after.catch_up());
+    }
+
+    #[test]
+    fn test_retag_is_one_undo_step() {
+        let day: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut store = TaskStore::default();
+        for title in ["Email Bob #home", "Write report #work #q3", "Plan Q3"] {
+            store.tasks.push(Task::new(title.into(), 2, 2, day));
+        }
+        let ids: Vec<Uuid> = store.tasks.iter().map(|t| t.id).collect();
+
+        store.retag(&ids[..2], &["#work".to_string(), "#urgent".to_string()]);
+        assert_eq!(store.tasks[0].title, "Email Bob #work #urgent");
+        assert_eq!(store.tasks[1].tags(), ["#work", "#urgent"]);
+        assert_eq!(store.tasks[2].title, "Plan Q3");
+
+        assert_eq!(store.undo().as_deref(), Some("retag"));
+        let titles: Vec<&str> = store.tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["Email Bob #home", "Write report #work #q3", "Plan Q3"]
+        );
+    }
+}
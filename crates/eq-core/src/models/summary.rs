@@ -0,0 +1,100 @@
+use crate::models::task::{Task, TaskStatus};
+use crate::storage::paths::{summary_cache_path, tasks_file_path};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Pending tasks named in the summary, highest score first
+const TOP_TASKS: usize = 3;
+
+/// A day at a glance, saved with every save of the store so status bars can
+/// poll it without reading `tasks.json`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+    /// The day the counts are for; a summary from another day is stale
+    pub date: NaiveDate,
+    pub pending: usize,
+    pub done: usize,
+    /// Pending tasks past their deadline, whatever day they are planned for
+    pub overdue: usize,
+    /// The first few pending tasks for the day, as `Q1 Title`
+    pub top: Vec<String>,
+}
+
+impl Summary {
+    pub fn of(tasks: &[Task], today: NaiveDate) -> Self {
+        let mut pending: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.date == today && t.status == TaskStatus::Pending)
+            .collect();
+        pending.sort_by_key(|t| std::cmp::Reverse(t.score()));
+        Self {
+            date: today,
+            pending: pending.len(),
+            done: tasks
+                .iter()
+                .filter(|t| t.date == today && t.status == TaskStatus::Completed)
+                .count(),
+            overdue: tasks
+                .iter()
+                .filter(|t| t.status == TaskStatus::Pending && t.due.is_some_and(|d| d < today))
+                .count(),
+            top: pending
+                .iter()
+                .take(TOP_TASKS)
+                .map(|t| format!("{} {}", t.quadrant().badge(), t.title.replace('\n', " ")))
+                .collect(),
+        }
+    }
+
+    /// The saved summary, if there is one for `today` that is no older than
+    /// `tasks.json`, which `eq sync` can replace without a save
+    pub fn load(today: NaiveDate) -> Option<Self> {
+        let path = summary_cache_path().ok()?;
+        let modified = |path| fs::metadata(path).and_then(|m| m.modified()).ok();
+        if modified(&path)? < modified(&tasks_file_path().ok()?)? {
+            return None;
+        }
+        let summary: Self = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+        (summary.date == today).then_some(summary)
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = summary_cache_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string(self)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_counts_today_and_overdue() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut done = Task::new("Ship release".into(), 3, 3, today);
+        done.complete();
+        let mut late = Task::new("File taxes".into(), 1, 1, "2024-06-10".parse().unwrap());
+        late.due = Some("2024-06-11".parse().unwrap());
+        let tasks = vec![
+            done,
+            late,
+            Task::new("Plan Q3".into(), 1, 3, today),
+            Task::new("Fix outage".into(), 3, 3, today),
+            Task::new("Reply to Sam".into(), 3, 1, today),
+            Task::new("Water plants".into(), 1, 1, today),
+        ];
+        let summary = Summary::of(&tasks, today);
+        assert_eq!((summary.pending, summary.done, summary.overdue), (4, 1, 1));
+        assert_eq!(
+            summary.top,
+            vec!["Q1 Fix outage", "Q2 Plan Q3", "Q3 Reply to Sam"]
+        );
+    }
+}
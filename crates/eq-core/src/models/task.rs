@@ -0,0 +1,467 @@
+use crate::clock;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+/// Score points per level of importance
+pub const IMPORTANCE_WEIGHT: u8 = 3;
+/// Score points per level of urgency
+pub const URGENCY_WEIGHT: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Quadrant {
+    DoFirst,
+    Schedule,
+    Delegate,
+    Drop,
+}
+
+impl fmt::Display for Quadrant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quadrant::DoFirst => write!(f, "DO FIRST"),
+            Quadrant::Schedule => write!(f, "SCHEDULE"),
+            Quadrant::Delegate => write!(f, "DELEGATE"),
+            Quadrant::Drop => write!(f, "DROP"),
+        }
+    }
+}
+
+impl Quadrant {
+    /// Quadrant for a given urgency/importance pair
+    pub fn from_priority(urgency: u8, importance: u8) -> Self {
+        if importance >= 2 && urgency >= 2 {
+            Quadrant::DoFirst
+        } else if importance >= 2 && urgency == 1 {
+            Quadrant::Schedule
+        } else if importance == 1 && urgency >= 2 {
+            Quadrant::Delegate
+        } else {
+            Quadrant::Drop
+        }
+    }
+
+    /// Short badge used where the full name does not fit
+    pub fn badge(&self) -> &'static str {
+        match self {
+            Quadrant::DoFirst => "Q1",
+            Quadrant::Schedule => "Q2",
+            Quadrant::Delegate => "Q3",
+            Quadrant::Drop => "Q4",
+        }
+    }
+
+    /// All quadrants in reading order
+    pub const ALL: [Quadrant; 4] = [
+        Quadrant::DoFirst,
+        Quadrant::Schedule,
+        Quadrant::Delegate,
+        Quadrant::Drop,
+    ];
+
+    /// Next quadrant in reading order, wrapping around
+    pub fn next(self) -> Self {
+        match self {
+            Quadrant::DoFirst => Quadrant::Schedule,
+            Quadrant::Schedule => Quadrant::Delegate,
+            Quadrant::Delegate => Quadrant::Drop,
+            Quadrant::Drop => Quadrant::DoFirst,
+        }
+    }
+
+    /// Previous quadrant in reading order, wrapping around
+    pub fn prev(self) -> Self {
+        match self {
+            Quadrant::DoFirst => Quadrant::Drop,
+            Quadrant::Schedule => Quadrant::DoFirst,
+            Quadrant::Delegate => Quadrant::Schedule,
+            Quadrant::Drop => Quadrant::Delegate,
+        }
+    }
+}
+
+/// Direction across the matrix as laid out on screen: urgent on the left,
+/// important on top
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// Urgency and importance after nudging a task one quadrant in `direction`.
+///
+/// Moving toward urgent/important raises a value of 1 to 2; moving away
+/// lowers it to 1. Values already on the target side are left unchanged.
+pub fn nudge_priority(urgency: u8, importance: u8, direction: MatrixDirection) -> (u8, u8) {
+    match direction {
+        MatrixDirection::Left => (urgency.max(2), importance),
+        MatrixDirection::Right => (1, importance),
+        MatrixDirection::Up => (urgency, importance.max(2)),
+        MatrixDirection::Down => (urgency, 1),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskStatus {
+    Pending,
+    Completed,
+    Dropped,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    pub id: Uuid,
+    pub title: String,
+    pub urgency: u8,
+    pub importance: u8,
+    pub status: TaskStatus,
+    pub date: NaiveDate,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub estimate_minutes: Option<u32>,
+    #[serde(default)]
+    pub drop_reason: Option<String>,
+    /// Place in the manual sort order; unranked tasks sort last
+    #[serde(default)]
+    pub position: Option<u32>,
+    /// Deadline, set with a `due:` token
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+    /// Urgency follows `due` until it is set by hand
+    #[serde(default)]
+    pub urgency_from_due: bool,
+    /// Where the task came from, such as a GitHub issue's URL
+    #[serde(default)]
+    pub link: Option<String>,
+}
+
+impl Task {
+    pub fn new(title: String, urgency: u8, importance: u8, date: NaiveDate) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            title,
+            urgency: urgency.clamp(1, 3),
+            importance: importance.clamp(1, 3),
+            status: TaskStatus::Pending,
+            date,
+            created_at: clock::now(),
+            completed_at: None,
+            estimate_minutes: None,
+            drop_reason: None,
+            position: None,
+            due: None,
+            urgency_from_due: false,
+            link: None,
+        }
+    }
+
+    pub fn score(&self) -> u8 {
+        (self.importance * IMPORTANCE_WEIGHT) + (self.urgency * URGENCY_WEIGHT)
+    }
+
+    /// How the score and quadrant come out of the task's fields, with the
+    /// deadline reckoned from `today`
+    pub fn explain(&self, today: NaiveDate) -> ScoreBreakdown {
+        ScoreBreakdown {
+            urgency: self.urgency,
+            importance: self.importance,
+            due: self.due.map(|due| (due, (due - today).num_days())),
+            urgency_from_due: self.urgency_from_due,
+        }
+    }
+
+    pub fn quadrant(&self) -> Quadrant {
+        Quadrant::from_priority(self.urgency, self.importance)
+    }
+
+    pub fn complete(&mut self) {
+        self.complete_at(clock::now());
+    }
+
+    /// Mark the task done as of `moment`
+    pub fn complete_at(&mut self, moment: DateTime<Utc>) {
+        self.status = TaskStatus::Completed;
+        self.completed_at = Some(moment);
+    }
+
+    pub fn undo_complete(&mut self) {
+        self.status = TaskStatus::Pending;
+        self.completed_at = None;
+    }
+
+    pub fn drop_task(&mut self) {
+        self.status = TaskStatus::Dropped;
+    }
+
+    /// Set a pending task's urgency from how close its deadline is, if it
+    /// follows the deadline. Returns whether the urgency changed
+    pub fn refresh_due_urgency(&mut self, today: NaiveDate) -> bool {
+        let Some(due) = self.due.filter(|_| self.urgency_from_due) else {
+            return false;
+        };
+        let urgency = due_urgency(due, today);
+        if self.status != TaskStatus::Pending || urgency == self.urgency {
+            return false;
+        }
+        self.urgency = urgency;
+        true
+    }
+
+    /// The `#tags` in the title
+    pub fn tags(&self) -> Vec<&str> {
+        self.title
+            .split_whitespace()
+            .filter(|w| is_tag(w))
+            .collect()
+    }
+
+    /// Swap the title's `#tags` for `tags`, which go at the end
+    pub fn set_tags(&mut self, tags: &[String]) {
+        let mut words: Vec<&str> = self
+            .title
+            .split_whitespace()
+            .filter(|w| !is_tag(w))
+            .collect();
+        words.extend(tags.iter().map(String::as_str));
+        self.title = words.join(" ");
+    }
+}
+
+/// Whether a title word is a `#tag`
+fn is_tag(word: &str) -> bool {
+    word.len() > 1 && word.starts_with('#')
+}
+
+/// Urgency implied by a deadline: 3 when due today or overdue, 2 within the
+/// week, 1 after that
+pub fn due_urgency(due: NaiveDate, today: NaiveDate) -> u8 {
+    match (due - today).num_days() {
+        ..=0 => 3,
+        1..=6 => 2,
+        _ => 1,
+    }
+}
+
+/// The terms behind a task's score and quadrant, for `eq explain`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreBreakdown {
+    pub urgency: u8,
+    pub importance: u8,
+    /// Deadline and the days left until it, negative once overdue
+    pub due: Option<(NaiveDate, i64)>,
+    pub urgency_from_due: bool,
+}
+
+impl ScoreBreakdown {
+    pub fn score(&self) -> u8 {
+        self.importance * IMPORTANCE_WEIGHT + self.urgency * URGENCY_WEIGHT
+    }
+
+    pub fn quadrant(&self) -> Quadrant {
+        Quadrant::from_priority(self.urgency, self.importance)
+    }
+
+    /// One-line sum, e.g. "13 = importance 3×3 + urgency 2×2"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} = importance {}×{} + urgency {}×{}",
+            self.score(),
+            self.importance,
+            IMPORTANCE_WEIGHT,
+            self.urgency,
+            URGENCY_WEIGHT
+        )
+    }
+
+    /// Where the urgency comes from: the deadline or a hand-set value
+    pub fn urgency_source(&self) -> String {
+        let Some((due, days)) = self.due else {
+            return format!("urgency {} set by hand; no deadline", self.urgency);
+        };
+        let when = match days {
+            -1 => "overdue by a day".to_string(),
+            ..=-2 => format!("overdue by {} days", -days),
+            0 => "due today".to_string(),
+            1 => "due tomorrow".to_string(),
+            _ => format!("due in {} days", days),
+        };
+        if !self.urgency_from_due {
+            return format!(
+                "urgency {} set by hand; {} ({}) but it does not follow the deadline",
+                self.urgency, when, due
+            );
+        }
+        let rule = match days {
+            ..=0 => "3 when due today or overdue",
+            1..=6 => "2 within the week",
+            _ => "1 a week or more out",
+        };
+        format!(
+            "urgency {} follows the deadline: {} ({}), {}",
+            self.urgency, when, due, rule
+        )
+    }
+
+    /// Why waiting adds nothing to the score
+    pub fn aging(&self) -> &'static str {
+        "none; a task ranks by its priority alone, so one put off for weeks \
+         doesn't overtake more important work. Raise its urgency if it has \
+         become pressing"
+    }
+
+    /// The threshold that put the task in its quadrant
+    pub fn quadrant_rule(&self) -> String {
+        let rule = match self.quadrant() {
+            Quadrant::DoFirst => "urgency ≥ 2 and importance ≥ 2",
+            Quadrant::Schedule => "importance ≥ 2, urgency 1",
+            Quadrant::Delegate => "urgency ≥ 2, importance 1",
+            Quadrant::Drop => "urgency 1 and importance 1",
+        };
+        format!("{}: {}", self.quadrant(), rule)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::option;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_nudge_lands_in_adjacent_quadrant() {
+        // Schedule (u1i3) -> left -> DoFirst
+        let (u, i) = nudge_priority(1, 3, MatrixDirection::Left);
+        assert_eq!(Quadrant::from_priority(u, i), Quadrant::DoFirst);
+        assert_eq!((u, i), (2, 3));
+
+        // DoFirst (u3i3) -> down -> Delegate, urgency kept
+        assert_eq!(nudge_priority(3, 3, MatrixDirection::Down), (3, 1));
+
+        // Drop (u1i1) -> up -> Schedule
+        let (u, i) = nudge_priority(1, 1, MatrixDirection::Up);
+        assert_eq!(Quadrant::from_priority(u, i), Quadrant::Schedule);
+    }
+
+    #[test]
+    fn test_nudge_is_noop_at_edge() {
+        assert_eq!(nudge_priority(3, 2, MatrixDirection::Left), (3, 2));
+        assert_eq!(nudge_priority(1, 1, MatrixDirection::Right), (1, 1));
+        assert_eq!(nudge_priority(2, 1, MatrixDirection::Down), (2, 1));
+    }
+
+    #[test]
+    fn test_due_urgency_follows_the_deadline() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let days = |n| today + chrono::Duration::days(n);
+        assert_eq!(due_urgency(days(-2), today), 3);
+        assert_eq!(due_urgency(today, today), 3);
+        assert_eq!(due_urgency(days(1), today), 2);
+        assert_eq!(due_urgency(days(6), today), 2);
+        assert_eq!(due_urgency(days(7), today), 1);
+
+        let mut task = Task::new("File taxes".into(), 1, 3, today);
+        task.due = Some(days(3));
+        assert!(!task.refresh_due_urgency(today));
+        task.urgency_from_due = true;
+        assert!(task.refresh_due_urgency(today));
+        assert_eq!(task.quadrant(), Quadrant::DoFirst);
+        assert!(!task.refresh_due_urgency(today));
+        assert!(task.refresh_due_urgency(days(3)));
+        assert_eq!(task.urgency, 3);
+    }
+
+    #[test]
+    fn test_explain_adds_up_to_the_score() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut task = Task::new("File taxes".into(), 1, 3, today);
+        let breakdown = task.explain(today);
+        assert_eq!(breakdown.score(), task.score());
+        assert_eq!(breakdown.summary(), "11 = importance 3×3 + urgency 1×2");
+        assert_eq!(breakdown.quadrant(), task.quadrant());
+        assert!(breakdown.urgency_source().contains("no deadline"));
+
+        task.due = Some(today + chrono::Duration::days(3));
+        task.urgency_from_due = true;
+        task.refresh_due_urgency(today);
+        let breakdown = task.explain(today);
+        assert_eq!(breakdown.score(), task.score());
+        assert_eq!(
+            breakdown.urgency_source(),
+            "urgency 2 follows the deadline: due in 3 days (2024-06-15), 2 within the week"
+        );
+        assert_eq!(
+            breakdown.quadrant_rule(),
+            "DO FIRST: urgency ≥ 2 and importance ≥ 2"
+        );
+
+        task.urgency_from_due = false;
+        assert!(task
+            .explain(today + chrono::Duration::days(5))
+            .urgency_source()
+            .contains("overdue by 2 days"));
+    }
+
+    #[test]
+    fn test_quadrant_cycle_order() {
+        for q in Quadrant::ALL {
+            assert_eq!(q.next().prev(), q);
+        }
+        assert_eq!(Quadrant::Drop.next(), Quadrant::DoFirst);
+        assert_eq!(Quadrant::DoFirst.prev(), Quadrant::Drop);
+    }
+
+    fn any_task() -> impl Strategy<Value = Task> {
+        let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        let day = (0i64..50_000).prop_map(move |n| epoch + chrono::Duration::days(n));
+        let moment = (0i64..4_000_000_000, 0u32..1_000_000_000)
+            .prop_map(|(secs, nanos)| DateTime::from_timestamp(secs, nanos).unwrap());
+        let status = prop_oneof![
+            Just(TaskStatus::Pending),
+            Just(TaskStatus::Completed),
+            Just(TaskStatus::Dropped),
+        ];
+        (
+            (any::<u128>(), any::<String>(), 1u8..=3, 1u8..=3, status),
+            (day.clone(), moment.clone(), option::of(moment)),
+            (option::of(any::<u32>()), option::of(any::<String>())),
+            (option::of(any::<u32>()), option::of(day), any::<bool>()),
+            option::of(any::<String>()),
+        )
+            .prop_map(
+                |(
+                    (id, title, urgency, importance, status),
+                    (date, created_at, completed_at),
+                    (estimate_minutes, drop_reason),
+                    (position, due, urgency_from_due),
+                    link,
+                )| Task {
+                    id: Uuid::from_u128(id),
+                    title,
+                    urgency,
+                    importance,
+                    status,
+                    date,
+                    created_at,
+                    completed_at,
+                    estimate_minutes,
+                    drop_reason,
+                    position,
+                    due,
+                    urgency_from_due,
+                    link,
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_task_survives_json(task in any_task()) {
+            let json = serde_json::to_string(&task).unwrap();
+            prop_assert_eq!(serde_json::from_str::<Task>(&json).unwrap(), task);
+        }
+    }
+}
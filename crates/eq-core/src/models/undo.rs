@@ -0,0 +1,164 @@
+use crate::models::task::Task;
+use uuid::Uuid;
+
+/// Most changes kept for undo
+const MAX_ENTRIES: usize = 100;
+
+/// One reversible change: the touched tasks as they were before it
+#[derive(Debug, Clone)]
+pub struct UndoEntry {
+    pub label: String,
+    before: Vec<(Uuid, Option<Task>)>, // None: the task did not exist yet
+}
+
+impl UndoEntry {
+    pub fn task_ids(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.before.iter().map(|(id, _)| *id)
+    }
+}
+
+/// In-memory history of store mutations for the current session
+#[derive(Debug, Clone, Default)]
+pub struct UndoJournal {
+    entries: Vec<UndoEntry>,
+    batch_start: Option<usize>,
+}
+
+impl UndoJournal {
+    /// Remember a task as it was before a change; `None` for a new task
+    pub fn record(&mut self, label: impl Into<String>, id: Uuid, before: Option<Task>) {
+        self.entries.push(UndoEntry {
+            label: label.into(),
+            before: vec![(id, before)],
+        });
+        self.trim();
+    }
+
+    /// Group everything recorded until `end_batch` into a single undo step
+    pub fn begin_batch(&mut self) {
+        self.batch_start = Some(self.entries.len());
+    }
+
+    /// Close the batch; a lone change keeps its own label
+    pub fn end_batch(&mut self, label: impl Into<String>) {
+        let Some(start) = self.batch_start.take() else {
+            return;
+        };
+        if self.entries.len() > start + 1 {
+            let before = self
+                .entries
+                .split_off(start)
+                .into_iter()
+                .flat_map(|e| e.before)
+                .collect();
+            self.entries.push(UndoEntry {
+                label: label.into(),
+                before,
+            });
+        }
+        self.trim();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Revert the latest change in `tasks` and return it
+    pub fn undo(&mut self, tasks: &mut Vec<Task>) -> Option<UndoEntry> {
+        let entry = self.entries.pop()?;
+        // Newest snapshot last, so restore in reverse
+        for (id, before) in entry.before.iter().rev() {
+            let pos = tasks.iter().position(|t| t.id == *id);
+            match (before, pos) {
+                (Some(task), Some(i)) => tasks[i] = task.clone(),
+                (Some(task), None) => tasks.push(task.clone()),
+                (None, Some(i)) => {
+                    tasks.remove(i);
+                }
+                (None, None) => {}
+            }
+        }
+        Some(entry)
+    }
+
+    fn trim(&mut self) {
+        if self.batch_start.is_none() && self.entries.len() > MAX_ENTRIES {
+            let excess = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::task::TaskStatus;
+    use chrono::NaiveDate;
+
+    fn task(title: &str) -> Task {
+        Task::new(
+            title.to_string(),
+            2,
+            2,
+            NaiveDate::from_ymd_opt(2024, 6, 12).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_undo_restores_previous_state() {
+        let mut tasks = vec![task("write report")];
+        let mut journal = UndoJournal::default();
+
+        journal.record("complete", tasks[0].id, Some(tasks[0].clone()));
+        tasks[0].complete();
+
+        let added = task("call bank");
+        journal.record("add", added.id, None);
+        tasks.push(added);
+
+        assert_eq!(journal.undo(&mut tasks).unwrap().label, "add");
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(journal.undo(&mut tasks).unwrap().label, "complete");
+        assert_eq!(tasks[0].status, TaskStatus::Pending);
+        assert!(journal.undo(&mut tasks).is_none());
+    }
+
+    #[test]
+    fn test_batch_undoes_as_one_step() {
+        let mut tasks = vec![task("a"), task("b")];
+        let mut journal = UndoJournal::default();
+
+        journal.begin_batch();
+        for t in tasks.iter_mut() {
+            journal.record("drop", t.id, Some(t.clone()));
+            t.drop_task();
+        }
+        journal.end_batch("drop 2 tasks");
+
+        let entry = journal.undo(&mut tasks).unwrap();
+        assert_eq!(entry.label, "drop 2 tasks");
+        assert_eq!(entry.task_ids().count(), 2);
+        assert!(tasks.iter().all(|t| t.status == TaskStatus::Pending));
+        assert!(journal.is_empty());
+    }
+
+    #[test]
+    fn test_single_change_batch_keeps_label() {
+        let mut journal = UndoJournal::default();
+        let t = task("a");
+        journal.begin_batch();
+        journal.record("drop \"a\"", t.id, Some(t.clone()));
+        journal.end_batch("drop 1 tasks");
+        assert_eq!(journal.undo(&mut vec![t]).unwrap().label, "drop \"a\"");
+    }
+
+    #[test]
+    fn test_journal_is_capped() {
+        let mut journal = UndoJournal::default();
+        for _ in 0..MAX_ENTRIES + 5 {
+            let t = task("x");
+            journal.record("add", t.id, None);
+        }
+        assert_eq!(journal.entries.len(), MAX_ENTRIES);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::parser::input::parse_priority;
+use crate::clock;
+use crate::parser::input::{parse_input, parse_priority_token};
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum AICommand {
@@ -11,6 +12,11 @@ pub enum AICommand {
         new_urgency: Option<u8>,
         new_importance: Option<u8>,
     },
+    /// Reschedule; `date` is as written, e.g. `2024-06-14`, `tomorrow`, `friday`
+    Move {
+        target: TaskIdentifier,
+        date: String,
+    },
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -32,6 +38,7 @@ pub struct CommandResults {
     pub tasks_completed: Vec<String>,
     pub tasks_dropped: Vec<String>,
     pub tasks_edited: Vec<String>,
+    pub tasks_moved: Vec<String>,
     pub errors: Vec<String>,
 }
 
@@ -67,6 +74,13 @@ impl CommandResults {
             }
         }
         
+        if !self.tasks_moved.is_empty() {
+            msg.push_str("✓ Moved:\n");
+            for t in &self.tasks_moved {
+                msg.push_str(&format!("  • {}\n", t));
+            }
+        }
+
         if !self.errors.is_empty() {
             msg.push_str("⚠ Errors:\n");
             for e in &self.errors {
@@ -100,6 +114,10 @@ pub fn parse_commands(response: &str) -> Vec<AICommand> {
             if let Some(edit) = parse_edit_command(rest.trim()) {
                 commands.push(edit);
             }
+        } else if let Some(rest) = trimmed.strip_prefix("[MOVE]") {
+            if let Some(mv) = parse_move_command(rest) {
+                commands.push(mv);
+            }
         }
     }
 
@@ -112,33 +130,20 @@ fn parse_add_command(input: &str) -> Option<ParsedTask> {
         return None;
     }
 
-    let mut urgency = 1u8;
-    let mut importance = 1u8;
-    let mut title_parts = Vec::new();
-
-    for word in input.split_whitespace() {
-        if let Some((u, i)) = parse_priority(word) {
-            urgency = u;
-            importance = i;
-        } else {
-            title_parts.push(word);
-        }
-    }
-
-    let title = title_parts.join(" ");
-    if title.is_empty() {
+    let parsed = parse_input(input, clock::today());
+    if parsed.title.is_empty() {
         return None;
     }
 
     Some(ParsedTask {
-        title,
-        urgency,
-        importance,
+        urgency: parsed.urgency(),
+        importance: parsed.importance(),
+        title: parsed.title,
     })
 }
 
 /// Parse task identifier (title or #index)
-fn parse_task_identifier(input: &str) -> Option<TaskIdentifier> {
+pub fn parse_task_identifier(input: &str) -> Option<TaskIdentifier> {
     let trimmed = input.trim();
     if trimmed.is_empty() {
         return None;
@@ -177,8 +182,9 @@ fn parse_edit_command(input: &str) -> Option<AICommand> {
         let mut new_importance = None;
         let mut title_parts = Vec::new();
 
-        for word in right.trim().split_whitespace() {
-            if let Some((u, i)) = parse_priority(word) {
+        let words: Vec<&str> = right.split_whitespace().collect();
+        for (n, &word) in words.iter().enumerate() {
+            if let Some((u, i)) = parse_priority_token(word, n + 1 == words.len()) {
                 new_urgency = Some(u);
                 new_importance = Some(i);
             } else {
@@ -205,8 +211,9 @@ fn parse_edit_command(input: &str) -> Option<AICommand> {
     let mut importance = None;
     let mut title_parts = Vec::new();
 
-    for word in input.split_whitespace() {
-        if let Some((u, i)) = parse_priority(word) {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    for (n, &word) in words.iter().enumerate() {
+        if let Some((u, i)) = parse_priority_token(word, n + 1 == words.len()) {
             urgency = Some(u);
             importance = Some(i);
         } else {
@@ -227,6 +234,20 @@ fn parse_edit_command(input: &str) -> Option<AICommand> {
     })
 }
 
+/// Parse [MOVE] command
+/// Format: [MOVE] task title -> friday
+fn parse_move_command(input: &str) -> Option<AICommand> {
+    let (left, right) = input.rsplit_once("->")?;
+    let date = right.trim();
+    if date.is_empty() {
+        return None;
+    }
+    Some(AICommand::Move {
+        target: parse_task_identifier(left)?,
+        date: date.to_string(),
+    })
+}
+
 // ============================================================================
 // Legacy API for backward compatibility
 // ============================================================================
@@ -351,6 +372,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_move() {
+        let cmds = parse_commands("[MOVE] Write report -> friday\n[MOVE] #2 ->");
+        assert_eq!(
+            cmds,
+            vec![AICommand::Move {
+                target: TaskIdentifier::Title("Write report".into()),
+                date: "friday".into(),
+            }]
+        );
+    }
+
     #[test]
     fn test_parse_multiple_commands() {
         let response = r#"Here's what I'll do:
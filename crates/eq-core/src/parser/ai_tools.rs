@@ -0,0 +1,211 @@
+use crate::parser::ai_commands::{parse_task_identifier, AICommand, ParsedTask, TaskIdentifier};
+use serde_json::{json, Value};
+
+/// Appended to the system prompt when the provider can call tools
+pub const TOOL_INSTRUCTIONS: &str = "\n\n## TOOLS\nTo add, complete, drop, edit, or reschedule tasks, call the provided tools instead of writing [ADD], [DONE], [DROP], [EDIT], or [MOVE] lines. The user confirms every change before it is applied.";
+
+/// Task reference accepted by every tool that targets an existing task
+fn task_param() -> Value {
+    json!({
+        "type": "string",
+        "description": "Title (or a unique part of it) of an existing task, or #N for the Nth task"
+    })
+}
+
+fn level(description: &str) -> Value {
+    json!({ "type": "integer", "minimum": 1, "maximum": 3, "description": description })
+}
+
+/// OpenAI `tools` array describing the task commands
+pub fn tool_definitions() -> Value {
+    let function = |name: &str, description: &str, properties: Value, required: &[&str]| {
+        json!({
+            "type": "function",
+            "function": {
+                "name": name,
+                "description": description,
+                "parameters": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                    "additionalProperties": false,
+                },
+            },
+        })
+    };
+
+    json!([
+        function(
+            "add_task",
+            "Add a new task for today",
+            json!({
+                "title": { "type": "string", "description": "Short actionable title" },
+                "urgency": level("1 = no time pressure, 3 = due within 24h"),
+                "importance": level("1 = nice-to-have, 3 = advances key goals"),
+            }),
+            &["title", "urgency", "importance"],
+        ),
+        function(
+            "complete_task",
+            "Mark an existing task as done",
+            json!({ "task": task_param() }),
+            &["task"],
+        ),
+        function(
+            "drop_task",
+            "Drop an existing task that is not worth doing",
+            json!({ "task": task_param() }),
+            &["task"],
+        ),
+        function(
+            "edit_task",
+            "Rename or reprioritize an existing task; omit fields that stay the same",
+            json!({
+                "task": task_param(),
+                "title": { "type": "string", "description": "New title" },
+                "urgency": level("New urgency"),
+                "importance": level("New importance"),
+            }),
+            &["task"],
+        ),
+        function(
+            "move_task",
+            "Reschedule an existing task to another day",
+            json!({
+                "task": task_param(),
+                "date": {
+                    "type": "string",
+                    "description": "YYYY-MM-DD, today, tomorrow, +N days, or a weekday name for its next occurrence"
+                },
+            }),
+            &["task", "date"],
+        ),
+    ])
+}
+
+fn string_arg(args: &Value, key: &str) -> Result<String, String> {
+    args[key]
+        .as_str()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| format!("missing '{}'", key))
+}
+
+/// An optional 1-3 level; out-of-range values are clamped
+fn level_arg(args: &Value, key: &str) -> Option<u8> {
+    args[key].as_u64().map(|n| n.clamp(1, 3) as u8)
+}
+
+fn target_arg(args: &Value) -> Result<TaskIdentifier, String> {
+    parse_task_identifier(&string_arg(args, "task")?).ok_or_else(|| "missing 'task'".to_string())
+}
+
+/// Turn one tool call into a command; `arguments` is the JSON string the
+/// model produced
+pub fn command_from_tool_call(name: &str, arguments: &str) -> Result<AICommand, String> {
+    let args: Value =
+        serde_json::from_str(arguments).map_err(|e| format!("{}: bad arguments ({})", name, e))?;
+    let command = match name {
+        "add_task" => string_arg(&args, "title").map(|title| {
+            AICommand::Add(ParsedTask {
+                title,
+                urgency: level_arg(&args, "urgency").unwrap_or(1),
+                importance: level_arg(&args, "importance").unwrap_or(1),
+            })
+        }),
+        "complete_task" => target_arg(&args).map(AICommand::Done),
+        "drop_task" => target_arg(&args).map(AICommand::Drop),
+        "edit_task" => target_arg(&args).map(|target| AICommand::Edit {
+            target,
+            new_title: string_arg(&args, "title").ok(),
+            new_urgency: level_arg(&args, "urgency"),
+            new_importance: level_arg(&args, "importance"),
+        }),
+        "move_task" => target_arg(&args).and_then(|target| {
+            Ok(AICommand::Move {
+                target,
+                date: string_arg(&args, "date")?,
+            })
+        }),
+        other => Err(format!("unknown tool '{}'", other)),
+    };
+    command.map_err(|e| format!("{}: {}", name, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_definitions_name_every_tool() {
+        let definitions = tool_definitions();
+        let names: Vec<&str> = definitions
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|t| t["function"]["name"].as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "add_task",
+                "complete_task",
+                "drop_task",
+                "edit_task",
+                "move_task"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_add_task_call() {
+        let cmd = command_from_tool_call(
+            "add_task",
+            r#"{"title": "Draft agenda", "urgency": 2, "importance": 3}"#,
+        );
+        assert_eq!(
+            cmd,
+            Ok(AICommand::Add(ParsedTask {
+                title: "Draft agenda".into(),
+                urgency: 2,
+                importance: 3,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_task_targets() {
+        assert_eq!(
+            command_from_tool_call("complete_task", r##"{"task": "#2"}"##),
+            Ok(AICommand::Done(TaskIdentifier::Index(2)))
+        );
+        assert_eq!(
+            command_from_tool_call("edit_task", r#"{"task": "report", "urgency": 9}"#),
+            Ok(AICommand::Edit {
+                target: TaskIdentifier::Title("report".into()),
+                new_title: None,
+                new_urgency: Some(3),
+                new_importance: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_move_task_call() {
+        assert_eq!(
+            command_from_tool_call("move_task", r#"{"task": "report", "date": "friday"}"#),
+            Ok(AICommand::Move {
+                target: TaskIdentifier::Title("report".into()),
+                date: "friday".into(),
+            })
+        );
+        assert!(command_from_tool_call("move_task", r#"{"task": "report"}"#).is_err());
+    }
+
+    #[test]
+    fn test_bad_calls_are_errors() {
+        assert!(command_from_tool_call("drop_task", "{}").is_err());
+        assert!(command_from_tool_call("drop_task", "not json").is_err());
+        assert!(command_from_tool_call("launch", r#"{"task": "x"}"#).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// Chinese day words and the offset from today they mean
+const CHINESE_DAYS: &[(&str, i64)] = &[
+    ("今天", 0),
+    ("明天", 1),
+    ("后天", 2),
+    ("大后天", 3),
+    ("昨天", -1),
+    ("前天", -2),
+];
+
+/// A Chinese weekday such as 周五, 星期五, or 礼拜天
+fn chinese_weekday(input: &str) -> Option<Weekday> {
+    let day = ["周", "星期", "礼拜"]
+        .iter()
+        .find_map(|prefix| input.strip_prefix(prefix))?;
+    match day {
+        "一" => Some(Weekday::Mon),
+        "二" => Some(Weekday::Tue),
+        "三" => Some(Weekday::Wed),
+        "四" => Some(Weekday::Thu),
+        "五" => Some(Weekday::Fri),
+        "六" => Some(Weekday::Sat),
+        "日" | "天" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Resolve `YYYY-MM-DD`, `today`/`tomorrow`/`yesterday`, a `+N`/`-N` day
+/// offset, or a weekday name meaning its next occurrence after `today`.
+/// Chinese day words (明天, 周五) work too.
+pub fn parse_date(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    if let Some((_, days)) = CHINESE_DAYS.iter().find(|(word, _)| *word == input) {
+        return Some(today + Duration::days(*days));
+    }
+    match input.as_str() {
+        "today" => Some(today),
+        "tomorrow" => Some(today + Duration::days(1)),
+        "yesterday" => Some(today - Duration::days(1)),
+        other => {
+            if let Some(days) = other.strip_prefix('+') {
+                return days.parse::<i64>().ok().map(|d| today + Duration::days(d));
+            }
+            if let Some(days) = other.strip_prefix('-') {
+                return days.parse::<i64>().ok().map(|d| today - Duration::days(d));
+            }
+            if let Some(weekday) = other
+                .parse::<Weekday>()
+                .ok()
+                .or_else(|| chinese_weekday(other))
+            {
+                let ahead = (weekday.num_days_from_monday() + 7
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                return Some(today + Duration::days(if ahead == 0 { 7 } else { ahead as i64 }));
+            }
+            NaiveDate::parse_from_str(other, "%Y-%m-%d").ok()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 3).unwrap()
+    }
+
+    #[test]
+    fn test_parse_date_weekday_is_next_occurrence() {
+        // today() is a Monday
+        assert_eq!(
+            parse_date("friday", today()),
+            NaiveDate::from_ymd_opt(2024, 6, 7)
+        );
+        assert_eq!(
+            parse_date("Mon", today()),
+            NaiveDate::from_ymd_opt(2024, 6, 10)
+        );
+    }
+
+    #[test]
+    fn test_parse_date_chinese_words() {
+        // today() is a Monday
+        assert_eq!(
+            parse_date("明天", today()),
+            NaiveDate::from_ymd_opt(2024, 6, 4)
+        );
+        assert_eq!(
+            parse_date("前天", today()),
+            NaiveDate::from_ymd_opt(2024, 6, 1)
+        );
+        assert_eq!(
+            parse_date("周五", today()),
+            NaiveDate::from_ymd_opt(2024, 6, 7)
+        );
+        assert_eq!(
+            parse_date("星期一", today()),
+            NaiveDate::from_ymd_opt(2024, 6, 10)
+        );
+        assert_eq!(
+            parse_date("礼拜天", today()),
+            NaiveDate::from_ymd_opt(2024, 6, 9)
+        );
+        assert_eq!(parse_date("周八", today()), None);
+    }
+}
@@ -0,0 +1,508 @@
+use crate::clock;
+use crate::config::Config;
+use crate::models::task::Task;
+use crate::parser::date::parse_date;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Words read as priorities unless `priority_words` is configured
+pub const PRIORITY_WORDS: &[(&str, &str)] = &[
+    ("critical", "u3i3"),
+    ("urgent", "u3i2"),
+    ("asap", "u3i2"),
+    ("important", "u2i3"),
+    ("someday", "u1i2"),
+    ("trivial", "u1i1"),
+];
+
+/// A task line split into its title and metadata tokens
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskInput {
+    /// The other words; `#tags` and `+projects` stay in it too, where
+    /// filters and `ai_redact` look for them
+    pub title: String,
+    pub priority: Option<(u8, u8)>,
+    pub estimate: Option<u32>,
+    /// `#tag` words, lowercase
+    pub tags: Vec<String>,
+    /// The first `+project` word, without the `+`
+    pub project: Option<String>,
+    /// Day from an `@date` token
+    pub date: Option<NaiveDate>,
+    /// Deadline from a `due:date` token
+    pub due: Option<NaiveDate>,
+}
+
+impl TaskInput {
+    pub fn urgency(&self) -> u8 {
+        self.priority.map_or(1, |(urgency, _)| urgency)
+    }
+
+    pub fn importance(&self) -> u8 {
+        self.priority.map_or(1, |(_, importance)| importance)
+    }
+
+    /// A new task on its `@date`, else on `date`; without a priority, a
+    /// deadline sets the urgency
+    pub fn into_task(self, date: NaiveDate) -> Task {
+        let (urgency, importance) = (self.urgency(), self.importance());
+        let mut task = Task::new(self.title, urgency, importance, self.date.unwrap_or(date));
+        task.estimate_minutes = self.estimate;
+        task.due = self.due;
+        task.urgency_from_due = self.priority.is_none() && self.due.is_some();
+        task.refresh_due_urgency(clock::today());
+        task
+    }
+}
+
+/// Split a task line into title and metadata: priority (`u2i3`, `!!$$`,
+/// `!urgent`, or a bare word like `urgent` at the very end), `~30m`, `#tag`,
+/// `+project`, `@fri`, and `due:2024-06-14`. Dates are resolved against
+/// `today`; tokens that do not parse stay in the title, and the last of a
+/// repeated token wins. `"quoted phrases"` and `\`-escaped words are always
+/// title text.
+pub fn parse_input(input: &str, today: NaiveDate) -> TaskInput {
+    let mut parsed = TaskInput::default();
+    let mut title_parts = Vec::new();
+
+    let words = tokenize(input);
+    let count = words.len();
+    for (i, (word, literal)) in words.into_iter().enumerate() {
+        if literal {
+            title_parts.push(word);
+        } else if let Some(priority) = parse_priority_token(&word, i + 1 == count) {
+            parsed.priority = Some(priority);
+        } else if let Some(minutes) = parse_estimate(&word) {
+            parsed.estimate = Some(minutes);
+        } else if let Some(date) = word.strip_prefix('@').and_then(|d| parse_date(d, today)) {
+            parsed.date = Some(date);
+        } else if let Some(due) = word.strip_prefix("due:").and_then(|d| parse_date(d, today)) {
+            parsed.due = Some(due);
+        } else {
+            if let Some(tag) = word.strip_prefix('#').filter(|t| is_label(t)) {
+                parsed.tags.push(format!("#{}", tag.to_lowercase()));
+            } else if let Some(project) = word.strip_prefix('+').filter(|p| is_label(p)) {
+                parsed.project.get_or_insert_with(|| project.to_string());
+            }
+            title_parts.push(word);
+        }
+    }
+    parsed.title = title_parts.join(" ");
+    parsed
+}
+
+/// Split `input` at unquoted whitespace; each word is flagged literal when
+/// any of it was quoted or escaped
+fn tokenize(input: &str) -> Vec<(String, bool)> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let (mut literal, mut quoted) = (false, false);
+    let mut chars = input.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                word.push(chars.next().unwrap_or('\\'));
+                literal = true;
+            }
+            '"' => {
+                quoted = !quoted;
+                literal = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if !word.is_empty() {
+                    words.push((std::mem::take(&mut word), literal));
+                }
+                literal = false;
+            }
+            c => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push((word, literal));
+    }
+    words
+}
+
+/// Whether `parse_input` would read `word` as metadata rather than title;
+/// `last` when nothing follows it
+fn is_metadata(word: &str, last: bool, today: NaiveDate) -> bool {
+    let is_date = |d: Option<&str>| d.and_then(|d| parse_date(d, today)).is_some();
+    parse_priority_token(word, last).is_some()
+        || parse_estimate(word).is_some()
+        || is_date(word.strip_prefix('@'))
+        || is_date(word.strip_prefix("due:"))
+}
+
+/// `title` as input that `parse_input` reads back to the same title: words
+/// that look like metadata, quotes, backslashes, and extra whitespace are
+/// escaped with `\`
+pub fn escape_title(title: &str, today: NaiveDate) -> String {
+    let chars: Vec<char> = title.chars().collect();
+    let mut escaped = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let after_space = i > 0 && chars[i - 1] == ' ';
+        let special = matches!(c, '\\' | '"') || (c.is_whitespace() && c != ' ');
+        if c == ' ' {
+            // A lone space between words separates them; any other is kept
+            if i == 0 || after_space || i + 1 == chars.len() {
+                escaped.push('\\');
+            }
+        } else if (i == 0 || after_space) && !special {
+            // One `\` makes the whole word literal; a word whose first
+            // character is escaped anyway must not get a second
+            let word: String = chars[i..].iter().take_while(|&&c| c != ' ').collect();
+            let last = i + word.chars().count() == chars.len();
+            if is_metadata(&word, last, today) {
+                escaped.push('\\');
+            }
+        }
+        if special {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// A tag or project name: starts with a letter, no trailing punctuation
+fn is_label(name: &str) -> bool {
+    name.starts_with(char::is_alphabetic)
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '/'))
+}
+
+/// Parse a priority token: `u2i3` shorthand, `!!$$` symbols, or a word like
+/// `urgent` or `!important`
+pub fn parse_priority(input: &str) -> Option<(u8, u8)> {
+    parse_notation(input).or_else(|| parse_priority_word(input, priority_words()))
+}
+
+/// A priority in a task line: notation or a `!word` anywhere, a bare word
+/// only at the end (`last`), so "Fix critical bug" keeps its "critical"
+pub fn parse_priority_token(word: &str, last: bool) -> Option<(u8, u8)> {
+    if last || word.starts_with('!') {
+        parse_priority(word)
+    } else {
+        parse_notation(word)
+    }
+}
+
+/// Configured priority words; `PRIORITY_WORDS` until `configure` is called
+static WORDS: OnceLock<HashMap<String, (u8, u8)>> = OnceLock::new();
+
+/// Read priority words from `config`'s `priority_words` instead of the
+/// built-in ones. Call once at startup, before any input is parsed
+pub fn configure(config: &Config) {
+    if let Some(words) = &config.priority_words {
+        let _ = WORDS.set(word_map(
+            words.iter().map(|(w, p)| (w.as_str(), p.as_str())),
+        ));
+    }
+}
+
+fn priority_words() -> &'static HashMap<String, (u8, u8)> {
+    WORDS.get_or_init(|| word_map(PRIORITY_WORDS.iter().copied()))
+}
+
+/// Lowercase words to priorities; entries without valid notation are skipped
+pub fn word_map<'a>(
+    words: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> HashMap<String, (u8, u8)> {
+    words
+        .into_iter()
+        .filter_map(|(word, priority)| Some((word.to_lowercase(), parse_notation(priority)?)))
+        .collect()
+}
+
+/// A priority word, optionally prefixed with `!`, looked up in `words`
+pub fn parse_priority_word(input: &str, words: &HashMap<String, (u8, u8)>) -> Option<(u8, u8)> {
+    let word = input.strip_prefix('!').unwrap_or(input);
+    if word.is_empty() || !word.chars().all(char::is_alphabetic) {
+        return None;
+    }
+    words.get(&word.to_lowercase()).copied()
+}
+
+fn parse_notation(input: &str) -> Option<(u8, u8)> {
+    let mut urgency = 0;
+    let mut importance = 0;
+
+    // Check for shorthand notation (e.g., u2i3, i3u1)
+    if let Some((u, i)) = parse_shorthand(input) {
+        return Some((u, i));
+    }
+
+    // Check for symbol notation (e.g., !!$$)
+    for c in input.chars() {
+        match c {
+            '!' => urgency += 1,
+            '$' => importance += 1,
+            _ => return None, // If contains other chars, it's not a priority string
+        }
+    }
+
+    if urgency > 0 || importance > 0 {
+        // Default to 1 if not specified but the other is
+        let u = if urgency == 0 { 1 } else { urgency };
+        let i = if importance == 0 { 1 } else { importance };
+        Some((u.clamp(1, 3), i.clamp(1, 3)))
+    } else {
+        None
+    }
+}
+
+/// Parse an estimate token such as `~30`, `~30m`, or `~2h` into minutes.
+pub fn parse_estimate(input: &str) -> Option<u32> {
+    let rest = input.strip_prefix('~')?;
+    let (digits, factor) = if let Some(hours) = rest.strip_suffix('h') {
+        (hours, 60)
+    } else if let Some(mins) = rest.strip_suffix('m') {
+        (mins, 1)
+    } else {
+        (rest, 1)
+    };
+
+    let value: u32 = digits.parse().ok()?;
+    if value == 0 {
+        return None;
+    }
+    value.checked_mul(factor)
+}
+
+fn parse_shorthand(input: &str) -> Option<(u8, u8)> {
+    let lower = input.to_lowercase();
+    if !lower.contains('u') || !lower.contains('i') {
+        return None;
+    }
+
+    let mut u: Option<u8> = None;
+    let mut i: Option<u8> = None;
+
+    // Find 'u' followed by a digit
+    if let Some(u_idx) = lower.find('u') {
+        if u_idx + 1 < lower.len() {
+            let next_char = lower.chars().nth(u_idx + 1)?;
+            if next_char.is_ascii_digit() {
+                u = next_char.to_digit(10).map(|d| d as u8);
+            }
+        }
+    }
+
+    // Find 'i' followed by a digit
+    if let Some(i_idx) = lower.find('i') {
+        if i_idx + 1 < lower.len() {
+            let next_char = lower.chars().nth(i_idx + 1)?;
+            if next_char.is_ascii_digit() {
+                i = next_char.to_digit(10).map(|d| d as u8);
+            }
+        }
+    }
+
+    // Both must be found with valid digits
+    match (u, i) {
+        (Some(urgency), Some(importance)) => Some((urgency.clamp(1, 3), importance.clamp(1, 3))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_symbol_parsing() {
+        assert_eq!(parse_priority("!!!$$$"), Some((3, 3)));
+        assert_eq!(parse_priority("!$"), Some((1, 1)));
+        assert_eq!(parse_priority("!!"), Some((2, 1))); // Default importance 1
+        assert_eq!(parse_priority("$$"), Some((1, 2))); // Default urgency 1
+    }
+
+    #[test]
+    fn test_shorthand_parsing() {
+        assert_eq!(parse_priority("u3i3"), Some((3, 3)));
+        assert_eq!(parse_priority("i2u1"), Some((1, 2)));
+        assert_eq!(parse_priority("u2i2"), Some((2, 2)));
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(parse_priority("abc"), None);
+        assert_eq!(parse_priority("task!"), None); // Contains letters
+    }
+
+    #[test]
+    fn test_priority_words() {
+        let words = word_map(PRIORITY_WORDS.iter().copied());
+        assert_eq!(parse_priority_word("urgent", &words), Some((3, 2)));
+        assert_eq!(parse_priority_word("!Important", &words), Some((2, 3)));
+        assert_eq!(parse_priority_word("Critical", &words), Some((3, 3)));
+        assert_eq!(parse_priority_word("someday", &words), Some((1, 2)));
+        assert_eq!(parse_priority_word("urgently", &words), None);
+        assert_eq!(parse_priority_word("!", &words), None);
+
+        let custom = word_map([("hot", "!!!$"), ("bad", "urgent")]);
+        assert_eq!(parse_priority_word("HOT", &custom), Some((3, 1)));
+        assert_eq!(parse_priority_word("bad", &custom), None);
+        assert_eq!(parse_priority_word("urgent", &custom), None);
+    }
+
+    #[test]
+    fn test_parse_input_metadata() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let parsed = parse_input(
+            "Draft slides #work +launch u3i2 ~45m @fri due:2024-06-20",
+            today,
+        );
+        assert_eq!(parsed.title, "Draft slides #work +launch");
+        assert_eq!(parsed.priority, Some((3, 2)));
+        assert_eq!(parsed.estimate, Some(45));
+        assert_eq!(parsed.tags, vec!["#work"]);
+        assert_eq!(parsed.project.as_deref(), Some("launch"));
+        assert_eq!(parsed.date, "2024-06-14".parse().ok());
+        assert_eq!(parsed.due, "2024-06-20".parse().ok());
+
+        let task = parsed.into_task(today);
+        assert_eq!(task.date, "2024-06-14".parse().unwrap());
+        assert_eq!((task.urgency, task.importance), (3, 2));
+        // Tags are lowercased; the first project wins
+        let parsed = parse_input("Ship +web +api #Q3 #home", today);
+        assert_eq!(parsed.tags, vec!["#q3", "#home"]);
+        assert_eq!(parsed.project.as_deref(), Some("web"));
+        assert_eq!(parsed.title, "Ship +web +api #Q3 #home");
+    }
+
+    #[test]
+    fn test_parse_input_priority_words_only_at_the_end() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let parse = |input| {
+            let parsed = parse_input(input, today);
+            (parsed.title, parsed.priority)
+        };
+        assert_eq!(parse("Fix critical bug"), ("Fix critical bug".into(), None));
+        assert_eq!(parse("Fix bug critical"), ("Fix bug".into(), Some((3, 3))));
+        assert_eq!(
+            parse("Call !urgent the bank"),
+            ("Call the bank".into(), Some((3, 2)))
+        );
+        assert_eq!(escape_title("Fix critical bug", today), "Fix critical bug");
+        assert_eq!(
+            escape_title("Fix bug critical", today),
+            r"Fix bug \critical"
+        );
+    }
+
+    #[test]
+    fn test_parse_input_keeps_unparsed_tokens() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let parsed = parse_input("Email bob@example.com @home re #1 due:soon", today);
+        assert_eq!(parsed.title, "Email bob@example.com @home re #1 due:soon");
+        assert_eq!(
+            (parsed.date, parsed.due, parsed.priority),
+            (None, None, None)
+        );
+        assert!(parsed.tags.is_empty());
+    }
+
+    #[test]
+    fn test_parse_input_quoting() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let parsed = parse_input("\"email u2i3 spec\" u3i3 ~15m", today);
+        assert_eq!(parsed.title, "email u2i3 spec");
+        assert_eq!(parsed.priority, Some((3, 3)));
+        assert_eq!(parsed.estimate, Some(15));
+
+        let parsed = parse_input(r#"Read \"Dune\" \urgent \@fri"#, today);
+        assert_eq!(parsed.title, r#"Read "Dune" urgent @fri"#);
+        assert_eq!((parsed.priority, parsed.date), (None, None));
+
+        let parsed = parse_input("\"#work\" notes #home", today);
+        assert_eq!(parsed.title, "#work notes #home");
+        assert_eq!(parsed.tags, vec!["#home"]);
+    }
+
+    #[test]
+    fn test_escape_title_round_trip() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        for title in [
+            "email u2i3 spec",
+            "Plan ~30m standup #work +launch",
+            "Buy milk @fri due:monday !!$$",
+            r#"Say "hi" to C:\Users"#,
+            "  spaced  out\ttabs ",
+            "urgent",
+            "\"u2i3\" spec",
+            "\turgent",
+            "",
+        ] {
+            let escaped = escape_title(title, today);
+            assert_eq!(parse_input(&escaped, today).title, title, "{}", escaped);
+        }
+        assert_eq!(escape_title("email u2i3 spec", today), r"email \u2i3 spec");
+        assert_eq!(escape_title("Call mom #home", today), "Call mom #home");
+    }
+
+    #[test]
+    fn test_estimate_parsing() {
+        assert_eq!(parse_estimate("~30"), Some(30));
+        assert_eq!(parse_estimate("~45m"), Some(45));
+        assert_eq!(parse_estimate("~2h"), Some(120));
+        assert_eq!(parse_estimate("~0"), None);
+        assert_eq!(parse_estimate("~"), None);
+        assert_eq!(parse_estimate("30m"), None);
+        assert_eq!(parse_estimate("~abc"), None);
+    }
+
+    #[test]
+    fn test_edge_cases() {
+        // Fix #2: These should not crash
+        assert_eq!(parse_priority("ui"), None);
+        assert_eq!(parse_priority("iu"), None);
+        assert_eq!(parse_priority("u"), None);
+        assert_eq!(parse_priority("i"), None);
+        assert_eq!(parse_priority(""), None);
+    }
+
+    /// Titles made of arbitrary text and of words that look like metadata
+    fn any_title() -> impl Strategy<Value = String> {
+        let word = prop_oneof![
+            any::<String>(),
+            prop::sample::select(vec![
+                "urgent",
+                "u2i3",
+                "i3u1",
+                "!!$$",
+                "~30m",
+                "@fri",
+                "due:monday",
+                "#work",
+                "+launch",
+                "\"",
+                "\\",
+                " ",
+                "\t",
+                "\n",
+            ])
+            .prop_map(String::from),
+        ];
+        prop::collection::vec(word, 0..8).prop_map(|words| words.concat())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_title_survives_priority_suffix(
+            title in any_title(),
+            urgency in 1u8..=3,
+            importance in 1u8..=3,
+        ) {
+            let today: NaiveDate = "2024-06-12".parse().unwrap();
+            let line = format!("{} u{}i{}", escape_title(&title, today), urgency, importance);
+            let parsed = parse_input(&line, today);
+            prop_assert_eq!(&parsed.title, &title);
+            prop_assert_eq!(parsed.priority, Some((urgency, importance)));
+            prop_assert_eq!((parsed.estimate, parsed.date, parsed.due), (None, None, None));
+        }
+    }
+}
@@ -1,2 +1,4 @@
 pub mod input;
 pub mod ai_commands;
+pub mod ai_tools;
+pub mod date;
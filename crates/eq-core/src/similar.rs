@@ -0,0 +1,422 @@
+use crate::models::store::TaskStore;
+use crate::models::task::{Task, TaskStatus};
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Share of words two titles must have in common to count as the same task
+pub const SIMILARITY_THRESHOLD: f32 = 0.6;
+
+const STOPWORDS: &[&str] = &[
+    "a", "about", "an", "and", "at", "for", "in", "my", "of", "on", "or", "re", "the", "to", "with",
+];
+
+/// Lowercase content words of a title, with a plural `s` dropped
+fn words(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|w| !w.is_empty() && !STOPWORDS.contains(&w.as_str()))
+        .map(|w| match w.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem.to_string(),
+            _ => w,
+        })
+        .collect()
+}
+
+/// Words shared by two titles over the words in either, 0.0 to 1.0
+pub fn similarity(a: &str, b: &str) -> f32 {
+    let (a, b) = (words(a), words(b));
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / a.union(&b).count() as f32
+}
+
+/// The pending task most like `title`, if any is close enough
+pub fn find_similar<'a>(tasks: &'a [Task], title: &str) -> Option<&'a Task> {
+    tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending)
+        .map(|t| (t, similarity(&t.title, title)))
+        .filter(|(_, score)| *score >= SIMILARITY_THRESHOLD)
+        .max_by(|(a, x), (b, y)| x.total_cmp(y).then(a.date.cmp(&b.date)))
+        .map(|(t, _)| t)
+}
+
+/// Score a title fragment must reach to pick out a task
+pub const MATCH_THRESHOLD: f32 = 0.6;
+
+/// Runners-up scoring within this of the best make a fragment ambiguous
+const AMBIGUITY_MARGIN: f32 = 0.05;
+
+/// Shortest fragment that counts as a substring match; shorter ones turn
+/// up inside almost any title
+const MIN_SUBSTRING_CHARS: usize = 3;
+
+/// How a title fragment resolved against a set of tasks
+#[derive(Debug, PartialEq)]
+pub enum TitleMatch<'a> {
+    Found(&'a Task),
+    /// Tasks that matched about equally well, best first
+    Ambiguous(Vec<&'a Task>),
+    NotFound,
+}
+
+/// Characters that must change to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Same word, allowing a typo: one edit, or two in words of 7+ letters
+fn word_matches(a: &str, b: &str) -> bool {
+    let allowed = match a.chars().count().min(b.chars().count()) {
+        0..=3 => 0,
+        4..=6 => 1,
+        _ => 2,
+    };
+    edit_distance(a, b) <= allowed
+}
+
+/// Lowercase words of `text` with punctuation removed
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// How well `fragment` names `title`, 0.0 to 1.0: 1.0 when equal ignoring
+/// case and punctuation, 0.9 when contained in the title and at least
+/// `MIN_SUBSTRING_CHARS` long, else the share of content words the two
+/// have in common in any order, typos allowed
+pub fn match_score(title: &str, fragment: &str) -> f32 {
+    let (title_text, fragment_text) = (normalize(title), normalize(fragment));
+    if fragment_text.is_empty() {
+        return 0.0;
+    }
+    if title_text == fragment_text {
+        return 1.0;
+    }
+    if fragment_text.chars().count() >= MIN_SUBSTRING_CHARS && title_text.contains(&fragment_text) {
+        return 0.9;
+    }
+    let (title_words, fragment_words) = (words(title), words(fragment));
+    if title_words.is_empty() || fragment_words.is_empty() {
+        return 0.0;
+    }
+    let shared = fragment_words
+        .iter()
+        .filter(|f| title_words.iter().any(|t| word_matches(f, t)))
+        .count();
+    // Capped below a substring match, which is the stronger signal
+    (2.0 * shared as f32 / (title_words.len() + fragment_words.len()) as f32).min(0.85)
+}
+
+/// The task `fragment` names, scored by `match_score`; ambiguous when
+/// another task scores about as well as the best
+pub fn match_title<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    fragment: &str,
+) -> TitleMatch<'a> {
+    let mut scored: Vec<(&Task, f32)> = tasks
+        .into_iter()
+        .map(|t| (t, match_score(&t.title, fragment)))
+        .filter(|(_, score)| *score >= MATCH_THRESHOLD)
+        .collect();
+    scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    let Some(&(best, top)) = scored.first() else {
+        return TitleMatch::NotFound;
+    };
+    let close: Vec<&Task> = scored
+        .iter()
+        .take_while(|(_, score)| top - score < AMBIGUITY_MARGIN)
+        .map(|(t, _)| *t)
+        .collect();
+    if close.len() > 1 {
+        TitleMatch::Ambiguous(close)
+    } else {
+        TitleMatch::Found(best)
+    }
+}
+
+/// How a new task repeats one already pending
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// The same title on the same day: bump the existing task's priority
+    Same,
+    /// A similar pending task: merge the new one into it
+    Similar,
+}
+
+impl Repeat {
+    /// Question put to the user before folding `incoming` into `existing`
+    pub fn prompt(self, existing: &Task, incoming: &Task) -> String {
+        match self {
+            Repeat::Same => bump_prompt(existing, incoming),
+            Repeat::Similar => duplicate_prompt(existing),
+        }
+    }
+
+    /// Fold `incoming` into the task `into` instead of adding it, as one
+    /// undo step: a bump for `Same`, a merge for `Similar`
+    pub fn apply(self, store: &mut TaskStore, into: Uuid, incoming: &Task) -> bool {
+        match self {
+            Repeat::Same => bump_into(store, into, incoming),
+            Repeat::Similar => merge_into(store, into, incoming),
+        }
+    }
+
+    /// What was done, e.g. "Bumped: Review PR"
+    pub fn done_message(self, title: &str) -> String {
+        match self {
+            Repeat::Same => format!("Bumped: {}", title),
+            Repeat::Similar => format!("Merged into: {}", title),
+        }
+    }
+}
+
+/// The pending task `incoming` repeats: the same title on its day first,
+/// else the most similar pending task
+pub fn find_repeat<'a>(tasks: &'a [Task], incoming: &Task) -> Option<(&'a Task, Repeat)> {
+    find_duplicate(tasks, &incoming.title, incoming.date)
+        .map(|t| (t, Repeat::Same))
+        .or_else(|| find_similar(tasks, &incoming.title).map(|t| (t, Repeat::Similar)))
+}
+
+/// Most pending tasks sent along when the AI is asked for a repeat
+pub const REPEAT_CANDIDATES: usize = 30;
+
+/// Pending tasks the AI compares a new title against, nearest to `date`
+/// first
+pub fn repeat_candidates(tasks: &[Task], date: NaiveDate) -> Vec<&Task> {
+    let mut pending: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending)
+        .collect();
+    pending.sort_by_key(|t| (t.date - date).num_days().abs());
+    pending.truncate(REPEAT_CANDIDATES);
+    pending
+}
+
+/// Warning shown when a new task looks like `existing`
+fn duplicate_prompt(existing: &Task) -> String {
+    format!(
+        "This looks like \"{}\" from {} — merge instead?",
+        existing.title,
+        existing.date.format("%a %b %-d")
+    )
+}
+
+/// Fold `incoming` into the task `into` instead of adding it: the higher
+/// priority wins and the task moves to `incoming`'s day, as one undo step
+fn merge_into(store: &mut TaskStore, into: Uuid, incoming: &Task) -> bool {
+    let Some(existing) = store.tasks.iter().find(|t| t.id == into) else {
+        return false;
+    };
+    let title = existing.title.clone();
+    let urgency = existing.urgency.max(incoming.urgency);
+    let importance = existing.importance.max(incoming.importance);
+    let move_to = (existing.date != incoming.date).then_some(incoming.date);
+
+    store.journal.begin_batch();
+    store.update_task(into, title, urgency, importance);
+    if let Some(date) = move_to {
+        store.move_task_to_date(into, date);
+    }
+    if let Some(task) = store.tasks.iter_mut().find(|t| t.id == into) {
+        task.estimate_minutes = task.estimate_minutes.or(incoming.estimate_minutes);
+    }
+    store.journal.end_batch("merge");
+    true
+}
+
+/// A pending task on `date` with the same title as `title`, ignoring case
+/// and punctuation: most likely the same task added again
+pub fn find_duplicate<'a>(tasks: &'a [Task], title: &str, date: NaiveDate) -> Option<&'a Task> {
+    let title = normalize(title);
+    if title.is_empty() {
+        return None;
+    }
+    tasks
+        .iter()
+        .find(|t| t.status == TaskStatus::Pending && t.date == date && normalize(&t.title) == title)
+}
+
+/// Priority `existing` takes when `incoming` repeats it: the higher of the
+/// two, or one step more urgent (then more important) when that changes
+/// nothing, since adding a task twice means it is pressing
+pub fn bumped_priority(existing: &Task, incoming: &Task) -> (u8, u8) {
+    let urgency = existing.urgency.max(incoming.urgency);
+    let importance = existing.importance.max(incoming.importance);
+    if (urgency, importance) != (existing.urgency, existing.importance) {
+        (urgency, importance)
+    } else if urgency < 3 {
+        (urgency + 1, importance)
+    } else {
+        (urgency, (importance + 1).min(3))
+    }
+}
+
+/// Warning shown when a new task repeats `existing` on the same day
+fn bump_prompt(existing: &Task, incoming: &Task) -> String {
+    let (urgency, importance) = bumped_priority(existing, incoming);
+    format!(
+        "\"{}\" is already on {} — bump it to u{}i{} instead?",
+        existing.title,
+        existing.date.format("%a %b %-d"),
+        urgency,
+        importance
+    )
+}
+
+/// Raise the task `into` to `bumped_priority` instead of adding `incoming`,
+/// as one undo step
+fn bump_into(store: &mut TaskStore, into: Uuid, incoming: &Task) -> bool {
+    let Some(existing) = store.tasks.iter().find(|t| t.id == into) else {
+        return false;
+    };
+    let title = existing.title.clone();
+    let (urgency, importance) = bumped_priority(existing, incoming);
+
+    store.journal.begin_batch();
+    store.update_task(into, title, urgency, importance);
+    if let Some(task) = store.tasks.iter_mut().find(|t| t.id == into) {
+        task.estimate_minutes = task.estimate_minutes.or(incoming.estimate_minutes);
+    }
+    store.journal.end_batch("bump");
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(title: &str) -> Task {
+        Task::new(
+            title.into(),
+            2,
+            2,
+            NaiveDate::from_ymd_opt(2024, 6, 4).unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_similarity_ignores_case_punctuation_and_filler() {
+        assert_eq!(similarity("Email Prof. Imai", "email prof imai"), 1.0);
+        assert_eq!(similarity("Reply to the emails", "reply email"), 1.0);
+        assert!(similarity("Buy milk", "Buy oat milk") >= SIMILARITY_THRESHOLD);
+        assert!(similarity("Email Bob", "Email Prof. Imai") < SIMILARITY_THRESHOLD);
+        assert_eq!(similarity("", "anything"), 0.0);
+    }
+
+    #[test]
+    fn test_find_similar_skips_finished_tasks() {
+        let mut done = task("Email Prof. Imai");
+        done.complete();
+        let tasks = vec![
+            done,
+            task("Write report"),
+            task("Email Prof Imai re agenda"),
+        ];
+
+        let found = find_similar(&tasks, "email prof. imai about the agenda").unwrap();
+        assert_eq!(found.title, "Email Prof Imai re agenda");
+        assert!(find_similar(&tasks, "Book flights").is_none());
+        assert_eq!(
+            duplicate_prompt(found),
+            "This looks like \"Email Prof Imai re agenda\" from Tue Jun 4 — merge instead?"
+        );
+    }
+
+    #[test]
+    fn test_match_score_tolerates_wording() {
+        assert_eq!(match_score("Fix server crash", "fix server crash"), 1.0);
+        assert_eq!(match_score("Email Bob re: invoice", "bob re invoice"), 0.9);
+        assert!(match_score("fix server crash", "Fix the server crash bug") >= MATCH_THRESHOLD);
+        assert!(match_score("Crash server fix", "fix server crash") >= MATCH_THRESHOLD);
+        assert!(match_score("Schedule dentist", "shcedule dentist") >= MATCH_THRESHOLD);
+        assert!(match_score("Write report", "Book flights") < MATCH_THRESHOLD);
+        assert!(match_score("Write report", "r") < MATCH_THRESHOLD);
+        assert!(match_score("Write report", "re") < MATCH_THRESHOLD);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_match_title_reports_ambiguity() {
+        let tasks = vec![
+            task("Email Bob"),
+            task("Mail rent check"),
+            task("Write report"),
+        ];
+        assert_eq!(
+            match_title(&tasks, "Write the report!"),
+            TitleMatch::Found(&tasks[2])
+        );
+        assert_eq!(
+            match_title(&tasks, "mail"),
+            TitleMatch::Ambiguous(vec![&tasks[0], &tasks[1]])
+        );
+        assert_eq!(
+            match_title(&tasks, "mail rent check"),
+            TitleMatch::Found(&tasks[1])
+        );
+        assert_eq!(match_title(&tasks, "book flights"), TitleMatch::NotFound);
+    }
+
+    #[test]
+    fn test_find_duplicate_needs_same_title_and_day() {
+        let day = NaiveDate::from_ymd_opt(2024, 6, 4).unwrap();
+        let mut tomorrow = task("Review PR");
+        tomorrow.date = day.succ_opt().unwrap();
+        let tasks = vec![tomorrow, task("Review PR #12"), task("Review PR")];
+
+        let found = find_duplicate(&tasks, "review pr!", day).unwrap();
+        assert_eq!(found.id, tasks[2].id);
+        assert!(find_duplicate(&tasks, "Review", day).is_none());
+        assert!(find_duplicate(&tasks, "", day).is_none());
+    }
+
+    #[test]
+    fn test_find_repeat_prefers_same_day() {
+        let tasks = vec![task("Email Prof Imai re agenda"), task("Review PR")];
+
+        let (found, kind) = find_repeat(&tasks, &task("review pr")).unwrap();
+        assert_eq!((found.id, kind), (tasks[1].id, Repeat::Same));
+        let (found, kind) = find_repeat(&tasks, &task("email prof imai")).unwrap();
+        assert_eq!((found.id, kind), (tasks[0].id, Repeat::Similar));
+        assert!(find_repeat(&tasks, &task("Book flights")).is_none());
+        assert_eq!(Repeat::Same.done_message("Review PR"), "Bumped: Review PR");
+    }
+
+    #[test]
+    fn test_bumped_priority_always_rises() {
+        let existing = task("Review PR");
+        let mut incoming = task("Review PR");
+        assert_eq!(bumped_priority(&existing, &incoming), (3, 2));
+        incoming.importance = 3;
+        assert_eq!(bumped_priority(&existing, &incoming), (2, 3));
+        assert_eq!(
+            bump_prompt(&existing, &incoming),
+            "\"Review PR\" is already on Tue Jun 4 — bump it to u2i3 instead?"
+        );
+
+        let mut top = task("Review PR");
+        top.urgency = 3;
+        assert_eq!(bumped_priority(&top, &top), (3, 3));
+        top.importance = 3;
+        assert_eq!(bumped_priority(&top, &top), (3, 3));
+    }
+}
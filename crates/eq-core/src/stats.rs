@@ -0,0 +1,1092 @@
+use crate::clock;
+use crate::config::WipLimits;
+use crate::models::focus::FocusSession;
+use crate::models::log::{EventAction, LogEvent};
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Monday of the week containing `date`.
+pub fn week_start(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(date.weekday().num_days_from_monday() as i64)
+}
+
+/// Outcome counts for a single day of the burndown report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayBurndown {
+    pub date: NaiveDate,
+    pub planned: usize,
+    pub completed: usize,
+    pub dropped: usize,
+    pub carried_over: usize,
+}
+
+impl DayBurndown {
+    /// Planned tasks that are still pending on this day.
+    pub fn open(&self) -> usize {
+        self.planned
+            .saturating_sub(self.completed + self.dropped + self.carried_over)
+    }
+}
+
+/// Collect the `(from, to)` date moves recorded for each task in the event log.
+fn moves_by_task(events: &[LogEvent]) -> HashMap<Uuid, Vec<(NaiveDate, NaiveDate)>> {
+    let mut moves: HashMap<Uuid, Vec<(NaiveDate, NaiveDate)>> = HashMap::new();
+    for event in events {
+        if let Some(dates) = event.moved_dates() {
+            moves.entry(event.task_id).or_default().push(dates);
+        }
+    }
+    moves
+}
+
+/// What the event log says about one task being put off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Postponements {
+    /// Earliest day the task was moved off.
+    pub first_from: Option<NaiveDate>,
+    /// Times it was moved to another day.
+    pub moves: usize,
+}
+
+/// Add the `Moved` events among `events` to each task's postponements.
+pub fn record_postponements(postponements: &mut HashMap<Uuid, Postponements>, events: &[LogEvent]) {
+    for event in events {
+        if let Some((from, _)) = event.moved_dates() {
+            let entry = postponements.entry(event.task_id).or_default();
+            entry.first_from = Some(entry.first_from.map_or(from, |first| first.min(from)));
+            entry.moves += 1;
+        }
+    }
+}
+
+/// Days `task` has been carried forward: from the first day it was planned
+/// for, the earliest day it was moved off, to its current date. Days before
+/// the task was created don't count, so one added for a past day and then
+/// moved is not older than it is.
+pub fn carried_days(task: &Task, postponements: Option<&Postponements>) -> i64 {
+    let Some(first) = postponements.and_then(|p| p.first_from) else {
+        return 0;
+    };
+    let first = first.max(clock::date_of(task.created_at));
+    (task.date - first).num_days().max(0)
+}
+
+/// Pending tasks carried forward at least `min_days`, longest first.
+pub fn stale_tasks<'a>(
+    tasks: &'a [Task],
+    postponements: &HashMap<Uuid, Postponements>,
+    min_days: i64,
+) -> Vec<(&'a Task, i64)> {
+    let mut stale: Vec<(&Task, i64)> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending)
+        .map(|t| (t, carried_days(t, postponements.get(&t.id))))
+        .filter(|(_, days)| *days >= min_days)
+        .collect();
+    stale.sort_by_key(|(t, days)| (std::cmp::Reverse(*days), t.date));
+    stale
+}
+
+/// Build the seven-day burndown starting at `week_start`.
+///
+/// A task counts as planned for every day it was scheduled on, either as its
+/// current date or as one end of a `Moved` event. A task that was moved off a
+/// day and now lives elsewhere counts as carried over from that day.
+pub fn weekly_burndown(
+    tasks: &[Task],
+    events: &[LogEvent],
+    week_start: NaiveDate,
+) -> Vec<DayBurndown> {
+    let moves = moves_by_task(events);
+
+    (0..7)
+        .map(|offset| {
+            let date = week_start + Duration::days(offset);
+            let mut day = DayBurndown {
+                date,
+                planned: 0,
+                completed: 0,
+                dropped: 0,
+                carried_over: 0,
+            };
+
+            for task in tasks {
+                let task_moves = moves.get(&task.id).map(Vec::as_slice).unwrap_or(&[]);
+                let scheduled_here = task.date == date
+                    || task_moves
+                        .iter()
+                        .any(|(from, to)| *from == date || *to == date);
+                if !scheduled_here {
+                    continue;
+                }
+
+                day.planned += 1;
+                if task.date == date {
+                    match task.status {
+                        TaskStatus::Completed => day.completed += 1,
+                        TaskStatus::Dropped => day.dropped += 1,
+                        TaskStatus::Pending => {}
+                    }
+                } else if task_moves.iter().any(|(from, _)| *from == date) {
+                    day.carried_over += 1;
+                }
+            }
+
+            day
+        })
+        .collect()
+}
+
+/// Default number of past weeks sampled when computing velocity.
+pub const VELOCITY_WEEKS: usize = 4;
+
+/// Average completed work for one weekday over recent weeks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Velocity {
+    pub weekday: Weekday,
+    pub samples: usize,
+    pub avg_tasks: f64,
+    pub avg_minutes: f64,
+}
+
+/// Average completed tasks and estimate-minutes on `date`'s weekday across
+/// up to `weeks` prior weeks.
+///
+/// Weeks before the first recorded task are skipped so a young store isn't
+/// dragged down by empty history. Returns `None` when nothing can be sampled.
+pub fn weekday_velocity(tasks: &[Task], date: NaiveDate, weeks: usize) -> Option<Velocity> {
+    let earliest = tasks.iter().map(|t| t.date).min()?;
+    let samples: Vec<NaiveDate> = (1..=weeks as i64)
+        .map(|k| date - Duration::weeks(k))
+        .filter(|d| *d >= earliest)
+        .collect();
+    if samples.is_empty() {
+        return None;
+    }
+
+    let done: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Completed && samples.contains(&t.date))
+        .collect();
+    let minutes: u32 = done.iter().filter_map(|t| t.estimate_minutes).sum();
+    let n = samples.len() as f64;
+
+    Some(Velocity {
+        weekday: date.weekday(),
+        samples: samples.len(),
+        avg_tasks: done.len() as f64 / n,
+        avg_minutes: minutes as f64 / n,
+    })
+}
+
+/// Work planned for a day measured against historical velocity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Forecast {
+    pub date: NaiveDate,
+    pub planned_tasks: usize,
+    pub planned_minutes: u32,
+    pub velocity: Velocity,
+}
+
+impl Forecast {
+    /// Whether the day holds more than is usually finished on that weekday.
+    pub fn is_overloaded(&self) -> bool {
+        self.too_many_tasks() || self.too_many_minutes()
+    }
+
+    fn too_many_tasks(&self) -> bool {
+        self.velocity.avg_tasks > 0.0 && self.planned_tasks as f64 > self.velocity.avg_tasks.ceil()
+    }
+
+    fn too_many_minutes(&self) -> bool {
+        self.velocity.avg_minutes > 0.0 && self.planned_minutes as f64 > self.velocity.avg_minutes
+    }
+
+    /// A one-line warning when the day is overloaded, quoting tasks or
+    /// minutes after whichever went over.
+    pub fn warning(&self) -> Option<String> {
+        let weekday = self.date.format("%A");
+        let load = if self.too_many_tasks() {
+            format!(
+                "{} planned for {}; you usually finish {:.1}",
+                count_of(self.planned_tasks, "task"),
+                weekday,
+                self.velocity.avg_tasks
+            )
+        } else if self.too_many_minutes() {
+            format!(
+                "{} min planned for {}; you usually finish {:.0} min",
+                self.planned_minutes, weekday, self.velocity.avg_minutes
+            )
+        } else {
+            return None;
+        };
+        Some(format!(
+            "⚠ {} (last {} {}s)",
+            load, self.velocity.samples, weekday
+        ))
+    }
+}
+
+/// "1 task", "2 tasks"
+fn count_of(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", n, noun)
+    }
+}
+
+/// Compare the non-dropped tasks on `date` with that weekday's velocity.
+pub fn forecast(tasks: &[Task], date: NaiveDate, weeks: usize) -> Option<Forecast> {
+    let velocity = weekday_velocity(tasks, date, weeks)?;
+    let planned: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.date == date && t.status != TaskStatus::Dropped)
+        .collect();
+
+    Some(Forecast {
+        date,
+        planned_tasks: planned.len(),
+        planned_minutes: planned.iter().filter_map(|t| t.estimate_minutes).sum(),
+        velocity,
+    })
+}
+
+/// A quadrant holding more pending tasks on a day than its WIP limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WipOverflow {
+    pub quadrant: Quadrant,
+    pub pending: usize,
+    pub limit: usize,
+}
+
+impl WipOverflow {
+    /// A one-line warning naming the quadrant and its limit.
+    pub fn warning(&self) -> String {
+        format!(
+            "⚠ {} pending tasks in {}; your limit is {}",
+            self.pending, self.quadrant, self.limit
+        )
+    }
+}
+
+/// Check the pending tasks of one day's `tasks` that fall in `quadrant`
+/// against `limits`.
+pub fn wip_overflow<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    quadrant: Quadrant,
+    limits: &WipLimits,
+) -> Option<WipOverflow> {
+    let limit = limits.limit(quadrant)?;
+    let pending = tasks
+        .into_iter()
+        .filter(|t| t.status == TaskStatus::Pending && t.quadrant() == quadrant)
+        .count();
+    (pending > limit).then_some(WipOverflow {
+        quadrant,
+        pending,
+        limit,
+    })
+}
+
+/// Share of a day's pending tasks that may be DO FIRST before a re-triage
+/// is suggested, when the config sets none.
+pub const DO_FIRST_SHARE: f64 = 0.6;
+
+/// Fewest pending tasks a day needs before its DO FIRST share is judged.
+const TRIAGE_MIN_TASKS: usize = 5;
+
+/// A day where most pending tasks claim to be urgent and important.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DoFirstOverload {
+    pub do_first: usize,
+    pub pending: usize,
+}
+
+impl DoFirstOverload {
+    /// The re-triage nudge.
+    pub fn nudge(&self) -> String {
+        format!(
+            "{} of {} tasks are 'urgent & important' — re-triage?",
+            self.do_first, self.pending
+        )
+    }
+}
+
+/// Check whether more than `share` of one day's pending `tasks` are in
+/// DO FIRST.
+pub fn do_first_overload<'a>(
+    tasks: impl IntoIterator<Item = &'a Task>,
+    share: f64,
+) -> Option<DoFirstOverload> {
+    let pending: Vec<&Task> = tasks
+        .into_iter()
+        .filter(|t| t.status == TaskStatus::Pending)
+        .collect();
+    let do_first = pending
+        .iter()
+        .filter(|t| t.quadrant() == Quadrant::DoFirst)
+        .count();
+    let overloaded =
+        pending.len() >= TRIAGE_MIN_TASKS && do_first as f64 > share * pending.len() as f64;
+    overloaded.then_some(DoFirstOverload {
+        do_first,
+        pending: pending.len(),
+    })
+}
+
+/// How far through a day's plan the user is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayProgress {
+    pub done: usize,
+    pub planned: usize,
+    /// Estimated minutes of the tasks still pending.
+    pub remaining_minutes: u32,
+}
+
+impl DayProgress {
+    /// Whole-number percentage of planned tasks that are done.
+    pub fn percent(&self) -> u32 {
+        if self.planned == 0 {
+            return 0;
+        }
+        (self.done * 100 / self.planned) as u32
+    }
+}
+
+/// Progress through the non-dropped tasks scheduled on `date`.
+pub fn day_progress(tasks: &[Task], date: NaiveDate) -> DayProgress {
+    let mut progress = DayProgress {
+        done: 0,
+        planned: 0,
+        remaining_minutes: 0,
+    };
+    for task in tasks.iter().filter(|t| t.date == date) {
+        match task.status {
+            TaskStatus::Completed => progress.done += 1,
+            TaskStatus::Pending => progress.remaining_minutes += task.estimate_minutes.unwrap_or(0),
+            TaskStatus::Dropped => continue,
+        }
+        progress.planned += 1;
+    }
+    progress
+}
+
+/// What became of a task planned for a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DayOutcome {
+    Completed,
+    Dropped,
+    /// Put off to this day.
+    Moved(NaiveDate),
+    /// Still open when the day ended.
+    Unfinished,
+}
+
+/// A past day's plan as it stood that morning against what got done.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DayComparison<'a> {
+    pub date: NaiveDate,
+    /// Tasks pending on the day as it began, highest score first.
+    pub planned: Vec<(&'a Task, DayOutcome)>,
+    /// Tasks completed on the day that weren't planned for it that morning.
+    pub unplanned: Vec<&'a Task>,
+}
+
+impl DayComparison<'_> {
+    /// Planned tasks that got `outcome`.
+    pub fn count(&self, outcome: fn(&DayOutcome) -> bool) -> usize {
+        self.planned.iter().filter(|(_, o)| outcome(o)).count()
+    }
+
+    /// A one-line summary, e.g. "Planned 8, completed 5 (62%); 2 done unplanned".
+    pub fn summary(&self) -> String {
+        let done = self.count(|o| *o == DayOutcome::Completed);
+        let percent = (done * 100).checked_div(self.planned.len()).unwrap_or(0);
+        let mut line = format!(
+            "Planned {}, completed {} ({}%)",
+            self.planned.len(),
+            done,
+            percent
+        );
+        if !self.unplanned.is_empty() {
+            line.push_str(&format!("; {} done unplanned", self.unplanned.len()));
+        }
+        line
+    }
+}
+
+/// Rebuild the plan for `date` as it stood when the day began, from the
+/// tasks and the event log, and what became of each task.
+///
+/// A task was planned if it existed before the day, wasn't finished yet, and
+/// was on the day then: on its current date unless a move made since took it
+/// elsewhere, in which case on that move's starting day.
+pub fn compare_day<'a>(
+    tasks: &'a [Task],
+    events: &[LogEvent],
+    date: NaiveDate,
+) -> DayComparison<'a> {
+    let mut by_task: HashMap<Uuid, Vec<&LogEvent>> = HashMap::new();
+    for event in events {
+        by_task.entry(event.task_id).or_default().push(event);
+    }
+    let day_of = |event: &&LogEvent| clock::date_of(event.timestamp);
+
+    let mut comparison = DayComparison {
+        date,
+        planned: Vec::new(),
+        unplanned: Vec::new(),
+    };
+    for task in tasks {
+        let task_events = by_task.get(&task.id).map(Vec::as_slice).unwrap_or(&[]);
+        let completed_on = task
+            .completed_at
+            .filter(|_| task.status == TaskStatus::Completed)
+            .map(clock::date_of);
+        let dropped_on = task_events
+            .iter()
+            .filter(|e| e.action == EventAction::Dropped)
+            .map(day_of)
+            .max()
+            .filter(|_| task.status == TaskStatus::Dropped);
+
+        let finished_before = completed_on.or(dropped_on).is_some_and(|d| d < date);
+        let existed = clock::date_of(task.created_at) < date;
+        let first_move_since = task_events
+            .iter()
+            .filter(|e| day_of(e) >= date)
+            .filter_map(|e| Some((e.timestamp, e.moved_dates()?)))
+            .min_by_key(|(at, _)| *at)
+            .map(|(_, dates)| dates);
+        let morning_date = first_move_since.map_or(task.date, |(from, _)| from);
+
+        if existed && !finished_before && morning_date == date {
+            let outcome = if completed_on == Some(date) {
+                DayOutcome::Completed
+            } else if dropped_on == Some(date) {
+                DayOutcome::Dropped
+            } else if let Some((_, to)) = first_move_since.filter(|_| task.date != date) {
+                DayOutcome::Moved(to)
+            } else {
+                DayOutcome::Unfinished
+            };
+            comparison.planned.push((task, outcome));
+        } else if completed_on == Some(date) {
+            comparison.unplanned.push(task);
+        }
+    }
+    comparison
+        .planned
+        .sort_by_key(|(t, _)| std::cmp::Reverse(t.score()));
+    comparison
+}
+
+/// Completed work for one week, split by quadrant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuadrantBalance {
+    pub week_start: NaiveDate,
+    pub counts: HashMap<Quadrant, usize>,
+    pub minutes: HashMap<Quadrant, u32>,
+}
+
+impl QuadrantBalance {
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Percentage of the week's completed tasks that fell in `quadrant`.
+    pub fn share(&self, quadrant: Quadrant) -> f64 {
+        let total = self.total();
+        if total == 0 {
+            return 0.0;
+        }
+        self.counts.get(&quadrant).copied().unwrap_or(0) as f64 / total as f64 * 100.0
+    }
+
+    /// Estimated minutes of the week's completed tasks.
+    pub fn total_minutes(&self) -> u32 {
+        self.minutes.values().sum()
+    }
+
+    pub fn minutes_in(&self, quadrant: Quadrant) -> u32 {
+        self.minutes.get(&quadrant).copied().unwrap_or(0)
+    }
+
+    /// Percentage of the week's estimated minutes spent in `quadrant`.
+    pub fn minute_share(&self, quadrant: Quadrant) -> f64 {
+        let total = self.total_minutes();
+        if total == 0 {
+            return 0.0;
+        }
+        self.minutes_in(quadrant) as f64 / total as f64 * 100.0
+    }
+}
+
+/// Tally the tasks completed in the week beginning at `week_start`.
+pub fn quadrant_balance(tasks: &[Task], week_start: NaiveDate) -> QuadrantBalance {
+    let week_end = week_start + Duration::days(7);
+    let mut counts: HashMap<Quadrant, usize> = HashMap::new();
+    let mut minutes: HashMap<Quadrant, u32> = HashMap::new();
+
+    for task in tasks {
+        if task.status != TaskStatus::Completed || task.date < week_start || task.date >= week_end {
+            continue;
+        }
+        *counts.entry(task.quadrant()).or_default() += 1;
+        if let Some(m) = task.estimate_minutes {
+            *minutes.entry(task.quadrant()).or_default() += m;
+        }
+    }
+
+    QuadrantBalance {
+        week_start,
+        counts,
+        minutes,
+    }
+}
+
+/// Weekly balances for the `weeks` weeks ending with the one containing `today`,
+/// oldest first.
+pub fn balance_trend(tasks: &[Task], today: NaiveDate, weeks: usize) -> Vec<QuadrantBalance> {
+    let current = week_start(today);
+    (0..weeks as i64)
+        .rev()
+        .map(|k| quadrant_balance(tasks, current - Duration::weeks(k)))
+        .collect()
+}
+
+/// Flag the Eisenhower anti-patterns visible in a week's completed work.
+pub fn balance_insights(balance: &QuadrantBalance) -> Vec<String> {
+    let mut insights = Vec::new();
+    if balance.total() == 0 {
+        return insights;
+    }
+
+    let firefighting = balance.share(Quadrant::DoFirst);
+    let strategic = balance.share(Quadrant::Schedule);
+    let low_value = balance.share(Quadrant::Delegate) + balance.share(Quadrant::Drop);
+
+    if firefighting > 50.0 {
+        insights.push(format!(
+            "Firefighting: {:.0}% of completed work was DO FIRST. Schedule important work before it turns urgent.",
+            firefighting
+        ));
+    }
+    if strategic < 25.0 {
+        insights.push(format!(
+            "Little strategic work: only {:.0}% was SCHEDULE (important, not urgent).",
+            strategic
+        ));
+    }
+    if low_value > 30.0 {
+        insights.push(format!(
+            "{:.0}% of completed work was DELEGATE or DROP. Delegate, batch, or decline more of it.",
+            low_value
+        ));
+    }
+    let low_value_time =
+        balance.minute_share(Quadrant::Delegate) + balance.minute_share(Quadrant::Drop);
+    if low_value_time > 30.0 {
+        insights.push(format!(
+            "{:.0}% of estimated time ({} min) went to DELEGATE or DROP tasks.",
+            low_value_time,
+            balance.minutes_in(Quadrant::Delegate) + balance.minutes_in(Quadrant::Drop)
+        ));
+    }
+    if firefighting > strategic && strategic > 0.0 {
+        insights.push(String::from(
+            "Urgent work outweighs strategic work; block time for SCHEDULE tasks first.",
+        ));
+    }
+
+    insights
+}
+
+/// Focus time as "1h 05m", or "25m" under an hour.
+pub fn format_duration(secs: u64) -> String {
+    let hours = secs / 3600;
+    let mins = (secs % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, mins)
+    } else {
+        format!("{}m", mins)
+    }
+}
+
+/// Aggregated pomodoro statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FocusStats {
+    pub session_count: usize,
+    pub total_secs: u64,
+    pub longest_secs: u64,
+    /// Focus seconds per day for the requested window, oldest first.
+    pub per_day: Vec<(NaiveDate, u64)>,
+    pub per_quadrant: HashMap<Quadrant, u64>,
+    /// Focus seconds per task, most focused first.
+    pub per_task: Vec<(Uuid, u64)>,
+    /// Mean number of sessions spent on tasks that ended up completed.
+    pub avg_sessions_to_complete: Option<f64>,
+}
+
+/// Summarise `sessions`, with a per-day series covering the `days` days up to `today`.
+pub fn focus_stats(
+    sessions: &[FocusSession],
+    tasks: &[Task],
+    today: NaiveDate,
+    days: usize,
+) -> FocusStats {
+    let tasks_by_id: HashMap<Uuid, &Task> = tasks.iter().map(|t| (t.id, t)).collect();
+
+    let mut per_quadrant: HashMap<Quadrant, u64> = HashMap::new();
+    let mut per_task: HashMap<Uuid, u64> = HashMap::new();
+    let mut sessions_per_task: HashMap<Uuid, usize> = HashMap::new();
+
+    for session in sessions {
+        let Some(task_id) = session.task_id else {
+            continue;
+        };
+        *per_task.entry(task_id).or_default() += session.duration_secs;
+        *sessions_per_task.entry(task_id).or_default() += 1;
+        if let Some(task) = tasks_by_id.get(&task_id) {
+            *per_quadrant.entry(task.quadrant()).or_default() += session.duration_secs;
+        }
+    }
+
+    let per_day = (0..days as i64)
+        .rev()
+        .map(|k| {
+            let date = today - Duration::days(k);
+            let secs: u64 = sessions
+                .iter()
+                .filter(|s| s.date == date)
+                .map(|s| s.duration_secs)
+                .sum();
+            (date, secs)
+        })
+        .collect();
+
+    let mut per_task: Vec<(Uuid, u64)> = per_task.into_iter().collect();
+    per_task.sort_by_key(|(_, secs)| std::cmp::Reverse(*secs));
+
+    let completed_counts: Vec<usize> = sessions_per_task
+        .iter()
+        .filter(|(id, _)| {
+            tasks_by_id
+                .get(*id)
+                .is_some_and(|t| t.status == TaskStatus::Completed)
+        })
+        .map(|(_, count)| *count)
+        .collect();
+    let avg_sessions_to_complete = if completed_counts.is_empty() {
+        None
+    } else {
+        Some(completed_counts.iter().sum::<usize>() as f64 / completed_counts.len() as f64)
+    };
+
+    FocusStats {
+        session_count: sessions.len(),
+        total_secs: sessions.iter().map(|s| s.duration_secs).sum(),
+        longest_secs: sessions.iter().map(|s| s.duration_secs).max().unwrap_or(0),
+        per_day,
+        per_quadrant,
+        per_task,
+        avg_sessions_to_complete,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::log::EventAction;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn moved(task: &Task, from: NaiveDate, to: NaiveDate) -> LogEvent {
+        LogEvent::new(
+            EventAction::Moved,
+            task.id,
+            format!("Moved: {} -> {}", from, to),
+        )
+    }
+
+    #[test]
+    fn test_week_start_is_monday() {
+        // 2024-06-05 is a Wednesday
+        assert_eq!(week_start(date("2024-06-05")), date("2024-06-03"));
+        assert_eq!(week_start(date("2024-06-03")), date("2024-06-03"));
+        assert_eq!(week_start(date("2024-06-09")), date("2024-06-03"));
+    }
+
+    #[test]
+    fn test_burndown_counts_outcomes() {
+        let monday = date("2024-06-03");
+        let mut done = Task::new("Done".into(), 2, 2, monday);
+        done.complete();
+        let mut dropped = Task::new("Dropped".into(), 1, 1, monday);
+        dropped.drop_task();
+        let pending = Task::new("Pending".into(), 1, 2, monday);
+
+        let days = weekly_burndown(&[done, dropped, pending], &[], monday);
+        assert_eq!(days.len(), 7);
+        assert_eq!(days[0].planned, 3);
+        assert_eq!(days[0].completed, 1);
+        assert_eq!(days[0].dropped, 1);
+        assert_eq!(days[0].open(), 1);
+        assert_eq!(days[1].planned, 0);
+    }
+
+    #[test]
+    fn test_burndown_tracks_carry_over() {
+        let monday = date("2024-06-03");
+        let tuesday = date("2024-06-04");
+        let mut task = Task::new("Report".into(), 2, 3, monday);
+        let events = vec![moved(&task, monday, tuesday)];
+        task.date = tuesday;
+        task.complete();
+
+        let days = weekly_burndown(&[task], &events, monday);
+        assert_eq!(days[0].planned, 1);
+        assert_eq!(days[0].carried_over, 1);
+        assert_eq!(days[0].completed, 0);
+        assert_eq!(days[1].planned, 1);
+        assert_eq!(days[1].completed, 1);
+    }
+
+    #[test]
+    fn test_day_progress_ignores_dropped_tasks() {
+        let day = date("2024-06-12");
+        let mut done = Task::new("Done".into(), 2, 2, day);
+        done.estimate_minutes = Some(60);
+        done.complete();
+        let mut open = Task::new("Open".into(), 2, 2, day);
+        open.estimate_minutes = Some(45);
+        let mut dropped = Task::new("Dropped".into(), 2, 2, day);
+        dropped.drop_task();
+        let other_day = Task::new("Tomorrow".into(), 2, 2, date("2024-06-13"));
+
+        let progress = day_progress(&[done, open, dropped, other_day], day);
+        assert_eq!(progress.done, 1);
+        assert_eq!(progress.planned, 2);
+        assert_eq!(progress.percent(), 50);
+        assert_eq!(progress.remaining_minutes, 45);
+        assert_eq!(day_progress(&[], day).percent(), 0);
+    }
+
+    #[test]
+    fn test_velocity_averages_matching_weekdays() {
+        let today = date("2024-06-19"); // Wednesday
+        let mut tasks = Vec::new();
+        for (day, count) in [("2024-06-12", 4), ("2024-06-05", 2)] {
+            for _ in 0..count {
+                let mut t = Task::new("Done".into(), 2, 2, date(day));
+                t.estimate_minutes = Some(30);
+                t.complete();
+                tasks.push(t);
+            }
+        }
+        // Same week, different weekday: not sampled
+        let mut other = Task::new("Thursday".into(), 2, 2, date("2024-06-13"));
+        other.complete();
+        tasks.push(other);
+
+        let v = weekday_velocity(&tasks, today, VELOCITY_WEEKS).unwrap();
+        assert_eq!(v.weekday, Weekday::Wed);
+        assert_eq!(v.samples, 2); // weeks before the first task are skipped
+        assert_eq!(v.avg_tasks, 3.0);
+        assert_eq!(v.avg_minutes, 90.0);
+    }
+
+    #[test]
+    fn test_forecast_flags_overload() {
+        let today = date("2024-06-19");
+        let mut tasks = Vec::new();
+        let mut past = Task::new("Past".into(), 2, 2, date("2024-06-12"));
+        past.complete();
+        tasks.push(past);
+
+        tasks.push(Task::new("One".into(), 2, 2, today));
+        let f = forecast(&tasks, today, VELOCITY_WEEKS).unwrap();
+        assert!(!f.is_overloaded());
+
+        tasks.push(Task::new("Two".into(), 2, 2, today));
+        let f = forecast(&tasks, today, VELOCITY_WEEKS).unwrap();
+        assert!(f.is_overloaded());
+        assert!(f.warning().unwrap().contains("2 tasks planned"));
+    }
+
+    #[test]
+    fn test_forecast_warning_follows_minutes() {
+        let today = date("2024-06-19");
+        let mut past = Task::new("Past".into(), 2, 2, date("2024-06-12"));
+        past.estimate_minutes = Some(30);
+        past.complete();
+        let mut long = Task::new("Long".into(), 2, 2, today);
+        long.estimate_minutes = Some(90);
+
+        let f = forecast(&[past, long], today, VELOCITY_WEEKS).unwrap();
+        let warning = f.warning().unwrap();
+        assert!(warning.contains("90 min planned"), "{}", warning);
+        assert!(warning.contains("usually finish 30 min"), "{}", warning);
+        assert!(!warning.contains("tasks"), "{}", warning);
+    }
+
+    #[test]
+    fn test_quadrant_balance_shares() {
+        let monday = date("2024-06-03");
+        let mut tasks = Vec::new();
+        for (u, i) in [(3, 3), (3, 3), (3, 3), (1, 3)] {
+            let mut t = Task::new("Done".into(), u, i, monday);
+            t.complete();
+            tasks.push(t);
+        }
+        // Outside the week and still pending: ignored
+        let mut next_week = Task::new("Later".into(), 1, 3, date("2024-06-10"));
+        next_week.complete();
+        tasks.push(next_week);
+        tasks.push(Task::new("Pending".into(), 3, 1, monday));
+
+        let balance = quadrant_balance(&tasks, monday);
+        assert_eq!(balance.total(), 4);
+        assert_eq!(balance.share(Quadrant::DoFirst), 75.0);
+        assert_eq!(balance.share(Quadrant::Schedule), 25.0);
+
+        let insights = balance_insights(&balance);
+        assert!(insights.iter().any(|i| i.starts_with("Firefighting")));
+    }
+
+    #[test]
+    fn test_quadrant_balance_minutes() {
+        let monday = date("2024-06-03");
+        let mut tasks = Vec::new();
+        for (u, i, minutes) in [(3, 3, 30), (1, 3, 30), (3, 1, 60), (1, 1, 30)] {
+            let mut t = Task::new("Done".into(), u, i, monday);
+            t.estimate_minutes = Some(minutes);
+            t.complete();
+            tasks.push(t);
+        }
+
+        let balance = quadrant_balance(&tasks, monday);
+        assert_eq!(balance.total_minutes(), 150);
+        assert_eq!(balance.minutes_in(Quadrant::Delegate), 60);
+        assert_eq!(balance.minute_share(Quadrant::Delegate), 40.0);
+
+        let insights = balance_insights(&balance);
+        assert!(insights
+            .iter()
+            .any(|i| i.starts_with("60% of estimated time (90 min)")));
+    }
+
+    #[test]
+    fn test_balance_trend_is_oldest_first() {
+        let today = date("2024-06-19");
+        let trend = balance_trend(&[], today, 3);
+        let starts: Vec<NaiveDate> = trend.iter().map(|b| b.week_start).collect();
+        assert_eq!(
+            starts,
+            vec![date("2024-06-03"), date("2024-06-10"), date("2024-06-17")]
+        );
+        assert!(balance_insights(&trend[0]).is_empty());
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(25 * 60), "25m");
+        assert_eq!(format_duration(3600 + 5 * 60), "1h 05m");
+    }
+
+    #[test]
+    fn test_focus_stats() {
+        let today = date("2024-06-05");
+        let mut done = Task::new("Write".into(), 3, 3, today);
+        done.complete();
+        let open = Task::new("Plan".into(), 1, 3, today);
+
+        let sessions = vec![
+            FocusSession::new(Some(done.id), today, 1500),
+            FocusSession::new(Some(done.id), date("2024-06-04"), 1500),
+            FocusSession::new(Some(open.id), today, 600),
+            FocusSession::new(None, today, 300),
+        ];
+
+        let stats = focus_stats(&sessions, &[done.clone(), open.clone()], today, 3);
+        assert_eq!(stats.session_count, 4);
+        assert_eq!(stats.total_secs, 3900);
+        assert_eq!(stats.longest_secs, 1500);
+        assert_eq!(
+            stats.per_day,
+            vec![
+                (date("2024-06-03"), 0),
+                (date("2024-06-04"), 1500),
+                (date("2024-06-05"), 2400),
+            ]
+        );
+        assert_eq!(stats.per_quadrant.get(&Quadrant::DoFirst), Some(&3000));
+        assert_eq!(stats.per_quadrant.get(&Quadrant::Schedule), Some(&600));
+        assert_eq!(stats.per_task[0], (done.id, 3000));
+        assert_eq!(stats.avg_sessions_to_complete, Some(2.0));
+    }
+
+    #[test]
+    fn test_wip_overflow_counts_pending_tasks_in_the_quadrant() {
+        let day = date("2024-06-04");
+        let mut done = Task::new("Shipped".into(), 3, 3, day);
+        done.complete();
+        let tasks = vec![
+            Task::new("Fix bug".into(), 3, 3, day),
+            Task::new("Review PR".into(), 2, 2, day),
+            Task::new("Read paper".into(), 1, 3, day),
+            done,
+        ];
+        let limits = WipLimits {
+            do_first: Some(1),
+            schedule: Some(1),
+            ..WipLimits::default()
+        };
+
+        let over = wip_overflow(&tasks, Quadrant::DoFirst, &limits).unwrap();
+        assert_eq!((over.pending, over.limit), (2, 1));
+        assert_eq!(
+            over.warning(),
+            "⚠ 2 pending tasks in DO FIRST; your limit is 1"
+        );
+        assert_eq!(wip_overflow(&tasks, Quadrant::Schedule, &limits), None);
+        assert_eq!(wip_overflow(&tasks, Quadrant::Drop, &limits), None);
+    }
+
+    #[test]
+    fn test_do_first_overload_needs_a_full_day() {
+        let day = date("2024-06-04");
+        let mut tasks: Vec<Task> = (0..4)
+            .map(|i| Task::new(format!("Urgent {}", i), 3, 3, day))
+            .collect();
+        assert_eq!(do_first_overload(&tasks, DO_FIRST_SHARE), None);
+
+        tasks.push(Task::new("Read paper".into(), 1, 3, day));
+        let overload = do_first_overload(&tasks, DO_FIRST_SHARE).unwrap();
+        assert_eq!(
+            overload.nudge(),
+            "4 of 5 tasks are 'urgent & important' — re-triage?"
+        );
+        assert_eq!(do_first_overload(&tasks, 0.8), None);
+
+        tasks[0].complete();
+        tasks.push(Task::new("Water plants".into(), 1, 1, day));
+        assert_eq!(do_first_overload(&tasks, DO_FIRST_SHARE), None);
+    }
+
+    #[test]
+    fn test_carried_days_count_from_the_first_planned_day() {
+        let _ = clock::set_time_zone("UTC");
+        let created = date("2024-06-01");
+        let mut task = Task::new("Call the bank".into(), 2, 3, date("2024-06-20"));
+        task.created_at = created.and_hms_opt(12, 0, 0).unwrap().and_utc();
+        let events = vec![
+            moved(&task, date("2024-06-03"), date("2024-06-10")),
+            moved(&task, date("2024-06-10"), date("2024-06-20")),
+        ];
+        let mut postponements = HashMap::new();
+        record_postponements(&mut postponements, &events);
+
+        let found = postponements[&task.id];
+        assert_eq!(found.first_from, Some(date("2024-06-03")));
+        assert_eq!(found.moves, 2);
+        assert_eq!(carried_days(&task, Some(&found)), 17);
+        assert_eq!(carried_days(&task, None), 0);
+
+        // Added on the 5th for the 3rd: the days before it existed don't count
+        task.created_at = date("2024-06-05").and_hms_opt(12, 0, 0).unwrap().and_utc();
+        assert_eq!(carried_days(&task, Some(&found)), 15);
+
+        let fresh = Task::new("New".into(), 2, 2, date("2024-06-20"));
+        let tasks = vec![fresh, task];
+        let stale = stale_tasks(&tasks, &postponements, 14);
+        assert_eq!(stale.len(), 1);
+        assert_eq!(stale[0].1, 15);
+        assert!(stale_tasks(&tasks, &postponements, 16).is_empty());
+    }
+
+    #[test]
+    fn test_compare_day_rebuilds_the_morning_plan() {
+        let _ = clock::set_time_zone("UTC");
+        let day = date("2024-06-04");
+        let at = |d: &str, hour: u32| date(d).and_hms_opt(hour, 0, 0).unwrap().and_utc();
+        let task = |title: &str, on: &str, created: &str| {
+            let mut task = Task::new(title.into(), 2, 2, date(on));
+            task.created_at = at(created, 8);
+            task
+        };
+        let mut done = task("Ship fix", "2024-06-04", "2024-06-03");
+        done.complete_at(at("2024-06-04", 15));
+        let mut put_off = task("Write report", "2024-06-06", "2024-06-02");
+        let mut pulled_in = task("Call bank", "2024-06-04", "2024-06-01");
+        pulled_in.complete_at(at("2024-06-04", 11));
+        let mut dropped = task("Old idea", "2024-06-04", "2024-06-01");
+        dropped.status = TaskStatus::Dropped;
+        let open = task("Read paper", "2024-06-04", "2024-06-03");
+        let added_today = task("Hotfix", "2024-06-04", "2024-06-04");
+        let mut finished_earlier = task("Book flights", "2024-06-04", "2024-06-01");
+        finished_earlier.complete_at(at("2024-06-02", 9));
+        put_off.urgency = 3;
+
+        let moved_at = |task: &Task, when, from: &str, to: &str| {
+            LogEvent::at(
+                when,
+                EventAction::Moved,
+                task.id,
+                format!("Moved: {} -> {}", from, to),
+            )
+        };
+        let events = vec![
+            moved_at(&put_off, at("2024-06-02", 9), "2024-06-03", "2024-06-04"),
+            moved_at(&put_off, at("2024-06-04", 17), "2024-06-04", "2024-06-06"),
+            moved_at(&pulled_in, at("2024-06-04", 10), "2024-06-10", "2024-06-04"),
+            LogEvent::at(
+                at("2024-06-04", 12),
+                EventAction::Dropped,
+                dropped.id,
+                "Dropped".into(),
+            ),
+        ];
+        let tasks = vec![
+            done,
+            put_off,
+            pulled_in,
+            dropped,
+            open,
+            added_today,
+            finished_earlier,
+        ];
+
+        let day_view = compare_day(&tasks, &events, day);
+        let planned: Vec<(&str, DayOutcome)> = day_view
+            .planned
+            .iter()
+            .map(|(t, o)| (t.title.as_str(), *o))
+            .collect();
+        assert_eq!(
+            planned,
+            vec![
+                ("Write report", DayOutcome::Moved(date("2024-06-06"))),
+                ("Ship fix", DayOutcome::Completed),
+                ("Old idea", DayOutcome::Dropped),
+                ("Read paper", DayOutcome::Unfinished),
+            ]
+        );
+        let unplanned: Vec<&str> = day_view
+            .unplanned
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(unplanned, vec!["Call bank"]);
+        assert_eq!(
+            day_view.summary(),
+            "Planned 4, completed 1 (25%); 1 done unplanned"
+        );
+    }
+}
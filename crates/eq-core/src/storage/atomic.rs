@@ -0,0 +1,16 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Write to a `.tmp` file beside `path` and rename it over `path`, so a
+/// crash never leaves it half written
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("tmp");
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(content.as_bytes())?;
+    file.sync_all()?; // Ensure written to disk
+    fs::rename(tmp_path, path)
+}
@@ -0,0 +1,30 @@
+use crate::storage::paths::store_lock_path;
+use std::fs::{File, OpenOptions};
+use std::io;
+
+/// Exclusive hold on the task store, released when dropped
+pub struct StoreLock {
+    _file: File,
+}
+
+/// Wait until no other eq process holds the task store, then hold it.
+/// Hold it from loading the store until saving it, so a CLI command and
+/// `eq serve` never overwrite each other's changes; the TUI holds it for
+/// each save and merges in what changed first. Only enforced on Unix
+pub fn lock_store() -> io::Result<StoreLock> {
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(store_lock_path()?)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        // SAFETY: flock only acts on the descriptor, which `file` keeps open
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    tracing::trace!("holding the store lock");
+    Ok(StoreLock { _file: file })
+}
@@ -0,0 +1,106 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use directories::ProjectDirs;
+
+const ENV_DATA_DIR: &str = "EQ_DATA_DIR";
+const QUALIFIER: &str = "dev";
+const ORGANIZATION: &str = "quad_tasks";
+const APPLICATION: &str = "eq";
+
+/// Resolve the base directory for all persisted data.
+pub fn data_dir() -> io::Result<PathBuf> {
+    let path = determine_data_dir()?;
+    fs::create_dir_all(&path)?;
+    Ok(path)
+}
+
+/// Path to the primary tasks JSON file.
+pub fn tasks_file_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("tasks.json"))
+}
+
+/// Finished tasks from months back, read only when history is needed.
+pub fn archive_file_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("archive.json"))
+}
+
+/// Lock file held while a process reads and rewrites the tasks file.
+pub fn store_lock_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("tasks.lock"))
+}
+
+/// Path to the chat history JSON file.
+pub fn chat_history_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("chat_history.json"))
+}
+
+/// Path to the event history log file.
+pub fn history_log_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("history.jsonl"))
+}
+
+/// Path to the focus session log file.
+pub fn focus_sessions_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("focus_sessions.jsonl"))
+}
+
+/// What `eq caldav` knows of the server's copy of each task.
+pub fn caldav_state_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("caldav.json"))
+}
+
+/// Todoist's open tasks as of the last `eq import --from todoist`.
+pub fn todoist_state_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("todoist.json"))
+}
+
+/// Path to the user configuration file.
+pub fn config_path() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("config.json"))
+}
+
+/// Directory for generated Markdown journal files.
+pub fn journal_dir() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("journal"))
+}
+
+/// Directory of the user's own quote files, merged into the quote bank.
+pub fn quotes_dir() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("quotes"))
+}
+
+/// Directory of saved AI replies, reused for identical requests.
+pub fn cache_dir() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("cache"))
+}
+
+/// Directory of debug logs written when `EQ_LOG` is set.
+pub fn log_dir() -> io::Result<PathBuf> {
+    Ok(data_dir()?.join("logs"))
+}
+
+/// Today's counts and top tasks, read by `eq status` for status bars.
+pub fn summary_cache_path() -> io::Result<PathBuf> {
+    Ok(cache_dir()?.join("status.json"))
+}
+
+fn determine_data_dir() -> io::Result<PathBuf> {
+    // Priority 1: Explicit environment variable override
+    if let Some(env_dir) = env::var_os(ENV_DATA_DIR) {
+        return Ok(PathBuf::from(env_dir));
+    }
+
+    // Priority 2: OS-standard application data directory
+    if let Some(project_dirs) = ProjectDirs::from(QUALIFIER, ORGANIZATION, APPLICATION) {
+        return Ok(project_dirs.data_local_dir().to_path_buf());
+    }
+
+    // Should never reach here on modern systems, but provide helpful error
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        "Could not determine data directory. Please set EQ_DATA_DIR environment variable.",
+    ))
+}
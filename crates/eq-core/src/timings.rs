@@ -0,0 +1,141 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// Count, total, worst, and latest of one kind of timed work, for
+/// `eq --timings`. Always kept, as recording is a few atomic adds
+pub struct Counter {
+    count: AtomicU64,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+    last_nanos: AtomicU64,
+}
+
+/// A counter's figures at one moment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+    pub last: Duration,
+}
+
+impl Reading {
+    pub fn average(&self) -> Duration {
+        self.total.div_f64(self.count.max(1) as f64)
+    }
+}
+
+impl Counter {
+    pub const fn new() -> Self {
+        Self {
+            count: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            max_nanos: AtomicU64::new(0),
+            last_nanos: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record(&self, elapsed: Duration) {
+        let nanos = elapsed.as_nanos().min(u64::MAX as u128) as u64;
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+        self.last_nanos.store(nanos, Ordering::Relaxed);
+    }
+
+    /// `None` until something has been recorded
+    pub fn read(&self) -> Option<Reading> {
+        let count = self.count.load(Ordering::Relaxed);
+        (count > 0).then(|| Reading {
+            count,
+            total: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            max: Duration::from_nanos(self.max_nanos.load(Ordering::Relaxed)),
+            last: Duration::from_nanos(self.last_nanos.load(Ordering::Relaxed)),
+        })
+    }
+}
+
+impl Default for Counter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reading `tasks.json` into a store
+pub static LOADS: Counter = Counter::new();
+/// Writing the store out
+pub static SAVES: Counter = Counter::new();
+/// Drawing one TUI frame
+pub static FRAMES: Counter = Counter::new();
+
+/// Tasks in the store at the last load
+static TASKS_LOADED: AtomicUsize = AtomicUsize::new(0);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Show timings from now on: a report on exit and the TUI's debug footer
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub(crate) fn record_load(elapsed: Duration, tasks: usize) {
+    LOADS.record(elapsed);
+    TASKS_LOADED.store(tasks, Ordering::Relaxed);
+}
+
+pub fn tasks_loaded() -> usize {
+    TASKS_LOADED.load(Ordering::Relaxed)
+}
+
+/// Milliseconds with a tenth, as the report shows them
+pub fn millis(duration: Duration) -> String {
+    format!("{:.1} ms", duration.as_secs_f64() * 1000.0)
+}
+
+/// One line per counter that has recorded anything
+pub fn report() -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(load) = LOADS.read() {
+        lines.push(format!(
+            "load   {} ({} tasks)",
+            millis(load.last),
+            tasks_loaded()
+        ));
+    }
+    for (name, counter) in [("save", &SAVES), ("frame", &FRAMES)] {
+        if let Some(reading) = counter.read() {
+            lines.push(format!(
+                "{:<6} {} avg, {} max ({}x)",
+                name,
+                millis(reading.average()),
+                millis(reading.max),
+                reading.count
+            ));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_keeps_average_max_and_last() {
+        let counter = Counter::new();
+        assert_eq!(counter.read(), None);
+        for ms in [4, 10, 1] {
+            counter.record(Duration::from_millis(ms));
+        }
+        let reading = counter.read().unwrap();
+        assert_eq!(reading.count, 3);
+        assert_eq!(reading.average(), Duration::from_millis(5));
+        assert_eq!(reading.max, Duration::from_millis(10));
+        assert_eq!(reading.last, Duration::from_millis(1));
+        assert_eq!(millis(reading.max), "10.0 ms");
+    }
+}
@@ -0,0 +1,115 @@
+use super::provider::{Completion, CompletionRequest};
+use crate::storage::paths::cache_dir;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a reply is reused when `ai_cache_ttl_secs` is not set
+pub const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// 64-bit FNV-1a; unlike `DefaultHasher` it is the same in every build
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Key for everything that shapes a reply: the provider, model, sampling,
+/// system prompt (which holds the task list), and conversation
+pub fn cache_key(provider: &str, request: &CompletionRequest) -> String {
+    let key = json!({
+        "provider": provider,
+        "model": request.model,
+        "system": request.system,
+        "messages": request.messages,
+        "temperature": request.temperature,
+        "max_tokens": request.max_tokens,
+        "tools": request.tools,
+    });
+    format!("{:016x}", fnv1a(key.to_string().as_bytes()))
+}
+
+fn entry_path(dir: &Path, key: &str) -> PathBuf {
+    dir.join(format!("{}.json", key))
+}
+
+/// Age of a cache file, from its modification time
+fn age(path: &Path) -> Option<Duration> {
+    fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()
+}
+
+/// The reply saved under `key`, unless it is older than `ttl`
+pub fn load(key: &str, ttl: Duration) -> Option<Completion> {
+    let path = entry_path(&cache_dir().ok()?, key);
+    if age(&path)? >= ttl {
+        return None;
+    }
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+/// Save a reply under `key` and delete entries older than `ttl`; a failure
+/// only costs a cache miss later
+pub fn store(key: &str, completion: &Completion, ttl: Duration) {
+    let Ok(dir) = cache_dir() else {
+        return;
+    };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    prune(&dir, ttl);
+    if let Ok(json) = serde_json::to_string(completion) {
+        let _ = fs::write(entry_path(&dir, key), json);
+    }
+}
+
+fn prune(dir: &Path, ttl: Duration) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if age(&path).filter(|age| *age < ttl).is_none() {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::ChatMessage;
+
+    fn request<'a>(system: &'a str, messages: &'a [ChatMessage]) -> CompletionRequest<'a> {
+        CompletionRequest {
+            model: None,
+            system,
+            messages,
+            temperature: 0.5,
+            max_tokens: 600,
+            tools: None,
+        }
+    }
+
+    #[test]
+    fn test_cache_key_covers_prompt_and_conversation() {
+        let messages = vec![ChatMessage {
+            role: "user".into(),
+            content: "What first?".into(),
+        }];
+        let key = cache_key("openai", &request("tasks: []", &messages));
+        assert_eq!(key.len(), 16);
+        assert_eq!(key, cache_key("openai", &request("tasks: []", &messages)));
+        assert_ne!(
+            key,
+            cache_key("anthropic", &request("tasks: []", &messages))
+        );
+        assert_ne!(key, cache_key("openai", &request("tasks: [1]", &messages)));
+        assert_ne!(key, cache_key("openai", &request("tasks: []", &[])));
+    }
+
+    #[test]
+    fn test_fnv1a_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
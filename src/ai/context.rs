@@ -0,0 +1,130 @@
+use crate::ai::redact::Redactor;
+use crate::ai::ChatMessage;
+use crate::models::task::{Task, TaskStatus};
+use chrono::NaiveDate;
+
+/// Most recent messages always sent word for word
+pub const KEEP_RECENT: usize = 10;
+/// Unsummarized messages allowed before older ones are folded into the summary
+pub const SUMMARIZE_AFTER: usize = 2 * KEEP_RECENT;
+
+/// System prompt for condensing older chat messages
+pub const SUMMARY_SYSTEM: &str = "You condense conversations between a user and their \
+    Eisenhower-matrix task assistant. Keep decisions, commitments, deadlines, and open \
+    questions; drop pleasantries. Reply with at most 8 short bullet points.";
+
+/// Tasks worth sending with a chat message: everything on `date`, plus
+/// pending work left on earlier days
+pub fn relevant_tasks(tasks: &[Task], date: NaiveDate) -> Vec<&Task> {
+    tasks
+        .iter()
+        .filter(|t| t.date == date || (t.date < date && t.status == TaskStatus::Pending))
+        .collect()
+}
+
+/// Task JSON for the system prompt, limited to `relevant_tasks` and passed
+/// through `redactor`, with the number of tasks it withheld
+pub fn task_context(tasks: &[Task], date: NaiveDate, redactor: &Redactor) -> (String, usize) {
+    let (tasks, withheld) = redactor.apply(relevant_tasks(tasks, date));
+    let json = serde_json::to_string_pretty(&tasks).unwrap_or_default();
+    (json, withheld)
+}
+
+/// `context` with the summary of earlier messages appended, if there is one
+pub fn with_summary(context: String, summary: Option<&str>) -> String {
+    match summary {
+        Some(summary) => format!(
+            "{}\n\n## EARLIER IN THIS CONVERSATION\n{}",
+            context, summary
+        ),
+        None => context,
+    }
+}
+
+/// How many leading messages to fold into the summary now, or `None` while
+/// fewer than `SUMMARIZE_AFTER` are left unsummarized after `covered`
+pub fn summary_due(len: usize, covered: usize) -> Option<usize> {
+    (len.saturating_sub(covered) > SUMMARIZE_AFTER).then(|| len - KEEP_RECENT)
+}
+
+/// Chat message asking to merge `previous` with the messages it does not
+/// cover yet
+pub fn summary_prompt(previous: Option<&str>, messages: &[ChatMessage]) -> String {
+    let mut prompt = String::new();
+    if let Some(summary) = previous {
+        prompt.push_str(&format!("Summary so far:\n{}\n\n", summary));
+    }
+    prompt.push_str("Conversation to fold in:\n");
+    for message in messages {
+        prompt.push_str(&format!("{}: {}\n", message.role, message.content));
+    }
+    prompt.push_str("\nWrite the updated summary.");
+    prompt
+}
+
+/// Messages to send: those after the `covered` summarized ones, capped at
+/// `SUMMARIZE_AFTER` and starting with a user message as providers require
+pub fn trimmed_history(history: &[ChatMessage], covered: usize) -> Vec<ChatMessage> {
+    let mut start = covered
+        .max(history.len().saturating_sub(SUMMARIZE_AFTER))
+        .min(history.len());
+    while start + 1 < history.len() && history[start].role != "user" {
+        start += 1;
+    }
+    history[start..].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chat(len: usize) -> Vec<ChatMessage> {
+        (0..len)
+            .map(|i| ChatMessage {
+                role: if i % 2 == 0 { "user" } else { "assistant" }.to_string(),
+                content: format!("message {}", i),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_relevant_tasks_skip_old_finished_and_future_work() {
+        let today: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut old_done = Task::new("old done".into(), 2, 2, "2024-06-10".parse().unwrap());
+        old_done.complete();
+        let mut done_today = Task::new("done today".into(), 2, 2, today);
+        done_today.complete();
+        let tasks = vec![
+            Task::new("overdue".into(), 1, 1, "2024-06-01".parse().unwrap()),
+            Task::new("today".into(), 1, 1, today),
+            Task::new("next week".into(), 3, 3, "2024-06-19".parse().unwrap()),
+            old_done,
+            done_today,
+        ];
+
+        let titles: Vec<&str> = relevant_tasks(&tasks, today)
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["overdue", "today", "done today"]);
+    }
+
+    #[test]
+    fn test_summary_due_keeps_recent_messages() {
+        assert_eq!(summary_due(SUMMARIZE_AFTER, 0), None);
+        assert_eq!(summary_due(SUMMARIZE_AFTER + 1, 0), Some(11));
+        assert_eq!(summary_due(30, 11), None);
+    }
+
+    #[test]
+    fn test_trimmed_history_starts_with_user() {
+        let history = chat(25);
+        let sent = trimmed_history(&history, 0);
+        assert_eq!(sent.len(), 19);
+        assert_eq!(sent[0].role, "user");
+        assert_eq!(sent.last().unwrap().content, "message 24");
+
+        assert_eq!(trimmed_history(&history, 21).len(), 3);
+        assert_eq!(trimmed_history(&chat(3), 0).len(), 3);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::config::Config;
+use reqwest::Client;
+use reqwest::{Certificate, NoProxy, Proxy};
+use std::error::Error;
+use std::fs;
+use std::time::Duration;
+
+/// Seconds an AI request may take when `ai_timeout_secs` is not set
+const DEFAULT_TIMEOUT_SECS: u64 = 60;
+
+/// Proxy variables checked in order, after the `ai_proxy` config key
+const PROXY_VARS: &[&str] = &["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"];
+
+/// Shown after certificate errors
+const CA_HINT: &str = "If your network inspects TLS traffic, set \"ai_ca_cert\" in config.json \
+     to your organization's root certificate (PEM).";
+
+/// Proxy for AI requests: the configured one, else the first proxy variable
+/// set; `env` looks up environment variables so this can be tested
+pub fn proxy_url(configured: Option<&str>, env: impl Fn(&str) -> Option<String>) -> Option<String> {
+    configured
+        .map(str::to_string)
+        .or_else(|| PROXY_VARS.iter().find_map(|var| env(var)))
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// HTTP client with the configured timeout, proxy, and extra root certificate
+pub fn build_client(config: &Config) -> Result<Client, String> {
+    let timeout = config.ai_timeout_secs.unwrap_or(DEFAULT_TIMEOUT_SECS);
+    let mut builder = Client::builder().timeout(Duration::from_secs(timeout));
+
+    if let Some(url) = proxy_url(config.ai_proxy.as_deref(), |var| std::env::var(var).ok()) {
+        let proxy = Proxy::all(&url)
+            .map_err(|e| format!("Invalid proxy '{}': {}", url, e))?
+            .no_proxy(NoProxy::from_env());
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(path) = &config.ai_ca_cert {
+        let pem =
+            fs::read(path).map_err(|e| format!("Cannot read ai_ca_cert '{}': {}", path, e))?;
+        let cert = Certificate::from_pem(&pem)
+            .map_err(|e| format!("ai_ca_cert '{}' is not a PEM certificate: {}", path, e))?;
+        builder = builder.add_root_certificate(cert);
+    }
+
+    builder
+        .build()
+        .map_err(|e| format!("Failed to set up the HTTP client: {}", e))
+}
+
+/// A failed request as one readable line: every cause in the chain, plus a
+/// hint when a certificate was rejected
+pub fn network_error(error: &reqwest::Error) -> String {
+    let mut causes = vec![error.to_string()];
+    let mut source = error.source();
+    while let Some(cause) = source {
+        causes.push(cause.to_string());
+        source = cause.source();
+    }
+    explain(causes)
+}
+
+fn explain(mut causes: Vec<String>) -> String {
+    causes.dedup();
+    let message = causes.join(": ");
+    let lower = message.to_lowercase();
+    if lower.contains("certificate") || lower.contains("unknown issuer") {
+        format!("TLS Error: {}. {}", message, CA_HINT)
+    } else {
+        format!("Network Error: {}", message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_url_precedence() {
+        let env = |var: &str| match var {
+            "ALL_PROXY" => Some("socks5://proxy:1080".to_string()),
+            "https_proxy" => Some("http://corp:3128".to_string()),
+            _ => None,
+        };
+        assert_eq!(proxy_url(None, env), Some("http://corp:3128".into()));
+        assert_eq!(
+            proxy_url(Some("http://mine:8080"), env),
+            Some("http://mine:8080".into())
+        );
+        assert_eq!(proxy_url(None, |_| None), None);
+        assert_eq!(proxy_url(Some(" "), |_| None), None);
+    }
+
+    #[test]
+    fn test_certificate_errors_get_a_hint() {
+        let tls = explain(vec![
+            "error sending request".into(),
+            "invalid peer certificate: UnknownIssuer".into(),
+        ]);
+        assert!(tls.starts_with("TLS Error: error sending request: invalid peer certificate"));
+        assert!(tls.contains("ai_ca_cert"));
+
+        let refused = explain(vec![
+            "error sending request".into(),
+            "Connection refused".into(),
+        ]);
+        assert_eq!(
+            refused,
+            "Network Error: error sending request: Connection refused"
+        );
+    }
+}
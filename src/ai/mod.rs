@@ -0,0 +1,529 @@
+pub mod cache;
+pub mod context;
+pub mod http;
+pub mod priority;
+pub mod provider;
+pub mod redact;
+pub mod repeat;
+pub mod stream;
+
+use serde::{Deserialize, Serialize};
+
+use crate::clock;
+use crate::config::{Config, ModelSettings};
+use crate::parser::ai_commands::{parse_commands, AICommand};
+use crate::parser::ai_tools::{command_from_tool_call, tool_definitions, TOOL_INSTRUCTIONS};
+use crate::quotes::user_quotes;
+use cache::cache_key;
+use context::{summary_prompt, SUMMARY_SYSTEM};
+use http::build_client;
+use provider::{
+    complete, complete_streaming, select_provider, CompletionRequest, Provider, ProviderSettings,
+};
+use redact::Redactor;
+use reqwest::Client;
+use std::fs;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::runtime::{Builder, Runtime};
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::task::AbortHandle;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+pub enum AIResponse {
+    /// The next piece of reply text, sent while it streams in; `Success`
+    /// still follows with all of it
+    Partial(String),
+    /// Reply text and its suggested task changes: tool calls when the
+    /// provider made any, otherwise `[ADD]`-style tags in the text
+    Success {
+        content: String,
+        commands: Vec<AICommand>,
+    },
+    Error(String),
+}
+
+pub struct AIClient {
+    provider: Arc<dyn Provider>,
+    client: Client,
+    planning: ModelSettings,
+    quote: ModelSettings,
+    /// User template replacing the built-in system prompt
+    prompt_template: Option<Arc<str>>,
+    /// How long saved replies are reused; zero turns the cache off
+    cache_ttl: Duration,
+    /// Applied to every message before it is sent
+    redactor: Redactor,
+    /// Where requests run; checked when the client is made
+    runtime: &'static Runtime,
+}
+
+/// Temperature and token limit for chat and planning when not configured
+const PLANNING_DEFAULTS: (f32, u32) = (0.5, 600);
+/// Lower temperature for accurate quote retrieval
+const QUOTE_DEFAULTS: (f32, u32) = (0.3, 150);
+
+/// Model, temperature, and token limit for one request: per-conversation
+/// `overrides` beat the configured mode settings, which beat the defaults
+pub fn request_settings(
+    configured: &ModelSettings,
+    overrides: &ModelSettings,
+    defaults: (f32, u32),
+) -> (Option<String>, f32, u32) {
+    let settings = configured.overridden_by(overrides);
+    (
+        settings.model,
+        settings.temperature.unwrap_or(defaults.0),
+        settings.max_tokens.unwrap_or(defaults.1),
+    )
+}
+
+/// Curated, verified quotes from Paul Graham's essays
+/// These are exact quotes with sources for attribution
+pub(crate) const PAUL_GRAHAM_QUOTES: &[(&str, &str)] = &[
+    // From "How to Do Great Work"
+    ("The way to figure out what to work on is by working. If you're not sure what to work on, guess. But pick something and get going.", "How to Do Great Work"),
+    ("Develop a habit of working on your own projects. Don't let 'work' mean something other people tell you to do.", "How to Do Great Work"),
+    ("The three most powerful motives are curiosity, delight, and the desire to do something impressive. Sometimes they converge, and that combination is the most powerful of all.", "How to Do Great Work"),
+    ("Writing a page a day doesn't sound like much, but if you do it every day you'll write a book a year. That's the key: consistency.", "How to Do Great Work"),
+    ("People who do great things don't get a lot done every day. They get something done, rather than nothing.", "How to Do Great Work"),
+    ("Work doesn't just happen when you're trying to. There's a kind of undirected thinking you do when walking or taking a shower or lying in bed that can be very powerful.", "How to Do Great Work"),
+    ("It's usually a mistake to lie to yourself if you want to do great work, but this is one of the rare cases where it isn't. When I'm reluctant to start work in the morning, I often trick myself by saying 'I'll just read over what I've got so far.'", "How to Do Great Work"),
+    ("Try to finish what you start, though, even if it turns out to be more work than you expected. Finishing things is not just an exercise in tidiness or self-discipline.", "How to Do Great Work"),
+    ("The reason we're surprised is that we underestimate the cumulative effect of work.", "How to Do Great Work"),
+    ("Curiosity is the best guide. Your curiosity never lies, and it knows more than you do about what's worth paying attention to.", "How to Do Great Work"),
+    ("If you made it this far, you must be interested in doing great work. And if so you're already further along than you might realize.", "How to Do Great Work"),
+    ("Don't worry about being presumptuous. You don't have to tell anyone. And if it's too hard and you fail, so what? Lots of people have worse problems than that.", "How to Do Great Work"),
+    ("The discoveries are out there, waiting to be made. Why not by you?", "How to Do Great Work"),
+    
+    // From "Keep Your Identity Small"
+    ("The more labels you have for yourself, the dumber they make you.", "Keep Your Identity Small"),
+    ("If people can't think clearly about anything that has become part of their identity, then all other things being equal, the best plan is to let as few things into your identity as possible.", "Keep Your Identity Small"),
+    
+    // From "Do Things That Don't Scale"
+    ("Actually startups take off because the founders make them take off.", "Do Things That Don't Scale"),
+    ("The question to ask about an early stage startup is not 'is this company taking over the world?' but 'how big could this company get if the founders did the right things?'", "Do Things That Don't Scale"),
+    ("I have never once seen a startup lured down a blind alley by trying too hard to make their initial users happy.", "Do Things That Don't Scale"),
+    ("It's not enough just to do something extraordinary initially. You have to make an extraordinary effort initially.", "Do Things That Don't Scale"),
+    
+    // From "Maker's Schedule, Manager's Schedule"
+    ("When you're operating on the maker's schedule, meetings are a disaster. A single meeting can blow a whole afternoon, by breaking it into two pieces each too small to do anything hard in.", "Maker's Schedule, Manager's Schedule"),
+    ("For someone on the maker's schedule, having a meeting is like throwing an exception. It doesn't merely cause you to switch from one task to another; it changes the mode in which you work.", "Maker's Schedule, Manager's Schedule"),
+    ("Don't your spirits rise at the thought of having an entire day free to work, with no appointments at all?", "Maker's Schedule, Manager's Schedule"),
+    
+    // From "How to Start a Startup"
+    ("What matters is not ideas, but the people who have them. Good people can fix bad ideas, but good ideas can't save bad people.", "How to Start a Startup"),
+    ("The smarter they are, the less pressure they feel to act smart. So as a rule you can recognize genuinely smart people by their ability to say things like 'I don't know,' 'Maybe you're right,' and 'I don't understand x well enough.'", "How to Start a Startup"),
+    ("It's worth trying very, very hard to make technology easy to use. Hackers are so used to computers that they have no idea how horrifying software seems to normal people.", "How to Start a Startup"),
+    ("In technology, the low end always eats the high end. It's easier to make an inexpensive product more powerful than to make a powerful product cheaper.", "How to Start a Startup"),
+    
+    // From "The Bus Ticket Theory of Genius"
+    ("If I had to put the recipe for genius into one sentence, that might be it: to have a disinterested obsession with something that matters.", "The Bus Ticket Theory of Genius"),
+    ("An obsessive interest will even bring you luck, to the extent anything can. Chance, as Pasteur said, favors the prepared mind, and if there's one thing an obsessed mind is, it's prepared.", "The Bus Ticket Theory of Genius"),
+    ("Perhaps the reason people have fewer new ideas as they get older is not simply that they're losing their edge. It may also be because once you become established, you can no longer mess about with irresponsible side projects.", "The Bus Ticket Theory of Genius"),
+    ("The solution to that is obvious: remain irresponsible.", "The Bus Ticket Theory of Genius"),
+];
+
+impl AIClient {
+    /// Client for the provider picked by the saved config and environment
+    pub fn new() -> Option<Self> {
+        Self::from_config(&Config::load_or_default()).ok()
+    }
+
+    /// Client for the provider named in `config`, overridden by `EQ_AI_*`
+    /// variables; the error says which key or setting is missing
+    pub fn from_config(config: &Config) -> Result<Self, String> {
+        let settings = ProviderSettings {
+            provider: config.ai_provider.clone(),
+            base_url: config.ai_base_url.clone(),
+            model: config.ai_model.clone(),
+            azure_deployment: config.ai_azure_deployment.clone(),
+            azure_api_version: config.ai_azure_api_version.clone(),
+        };
+        let provider = select_provider(&settings, |var| std::env::var(var).ok())?;
+        let client = build_client(config)?;
+        let redactor = Redactor::new(&config.ai_redact)?;
+        let prompt_template = config
+            .ai_system_prompt
+            .as_ref()
+            .map(|path| {
+                fs::read_to_string(path)
+                    .map(Arc::<str>::from)
+                    .map_err(|e| format!("Cannot read ai_system_prompt '{}': {}", path, e))
+            })
+            .transpose()?;
+        Ok(Self {
+            provider: Arc::from(provider),
+            client,
+            planning: config.ai_planning.clone(),
+            quote: config.ai_quote.clone(),
+            prompt_template,
+            cache_ttl: config
+                .ai_cache_ttl_secs
+                .map_or(cache::DEFAULT_TTL, Duration::from_secs),
+            redactor,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Name of the provider in use, e.g. `anthropic`
+    pub fn provider_name(&self) -> &str {
+        self.provider.name()
+    }
+
+    /// The `ai_redact` rules, for building task context
+    pub fn redactor(&self) -> &Redactor {
+        &self.redactor
+    }
+
+    /// `history` as it may be sent, per `redactor`
+    fn redacted(&self, history: Vec<ChatMessage>) -> Vec<ChatMessage> {
+        history
+            .into_iter()
+            .map(|message| ChatMessage {
+                content: self.redactor.redact_text(&message.content),
+                ..message
+            })
+            .collect()
+    }
+
+    /// Ask for a reply in the background; `overrides` are the conversation's
+    /// own model settings, e.g. from `/model` in the chat. The text is sent
+    /// piece by piece as `Partial` while it streams in, then whole. A saved
+    /// reply to the same request is reused unless `use_cache` is false.
+    /// Aborting the returned handle drops the request, including any
+    /// pending retries.
+    pub fn send_message(
+        &self,
+        history: Vec<ChatMessage>,
+        context: String,
+        overrides: &ModelSettings,
+        use_cache: bool,
+        sender: UnboundedSender<AIResponse>,
+    ) -> AbortHandle {
+        let provider = Arc::clone(&self.provider);
+        let client = self.client.clone();
+        let planning = request_settings(&self.planning, overrides, PLANNING_DEFAULTS);
+        let quote = request_settings(&self.quote, overrides, QUOTE_DEFAULTS);
+        let template = self.prompt_template.clone();
+        let ttl = self.cache_ttl;
+        let history = self.redacted(history);
+
+        let task = self.runtime.spawn(async move {
+            let is_quote_request = history
+                .last()
+                .map(|m| m.content.trim().eq_ignore_ascii_case("quote"))
+                .unwrap_or(false);
+
+            // Use different parameters for quote mode vs regular chat
+            let (model, temperature, max_tokens) = if is_quote_request {
+                quote
+            } else {
+                planning
+            };
+
+            let mut system_prompt = build_system_prompt(&context, template.as_deref());
+            let tools = provider.supports_tools().then(tool_definitions);
+            if tools.is_some() {
+                system_prompt.push_str(TOOL_INSTRUCTIONS);
+            }
+            let request = CompletionRequest {
+                model: model.as_deref(),
+                system: &system_prompt,
+                messages: &history,
+                temperature,
+                max_tokens,
+                tools: tools.as_ref(),
+            };
+
+            // Quotes should vary, so they are never cached
+            let key =
+                (!is_quote_request && !ttl.is_zero()).then(|| cache_key(provider.name(), &request));
+            let saved = key
+                .as_deref()
+                .filter(|_| use_cache)
+                .and_then(|key| cache::load(key, ttl));
+            let result = match saved {
+                Some(completion) => {
+                    tracing::debug!("reply taken from the cache");
+                    Ok(completion)
+                }
+                None => {
+                    let on_text = |text: &str| {
+                        let _ = sender.send(AIResponse::Partial(text.to_string()));
+                    };
+                    let result =
+                        complete_streaming(provider.as_ref(), &client, &request, on_text).await;
+                    if let (Some(key), Ok(completion)) = (&key, &result) {
+                        cache::store(key, completion, ttl);
+                    }
+                    result
+                }
+            };
+
+            let response = match result {
+                Ok(completion) => {
+                    let mut content = completion.text;
+                    let mut commands = Vec::new();
+                    for call in &completion.tool_calls {
+                        match command_from_tool_call(&call.name, &call.arguments) {
+                            Ok(command) => commands.push(command),
+                            Err(err) => content.push_str(&format!("\n(Skipped tool call {})", err)),
+                        }
+                    }
+                    if commands.is_empty() {
+                        commands = parse_commands(&content);
+                    }
+                    AIResponse::Success { content, commands }
+                }
+                Err(err) => AIResponse::Error(err),
+            };
+            if sender.send(response).is_err() {
+                tracing::warn!("AI reply dropped: nothing is waiting for it any more");
+            }
+        });
+        task.abort_handle()
+    }
+
+    /// Fold `messages` into the `previous` summary in the background
+    pub fn summarize(
+        &self,
+        previous: Option<String>,
+        messages: Vec<ChatMessage>,
+        sender: UnboundedSender<Result<String, String>>,
+    ) -> AbortHandle {
+        let prompt = summary_prompt(previous.as_deref(), &messages);
+        self.ask_once(SUMMARY_SYSTEM, prompt, sender)
+    }
+
+    /// Ask one question outside the chat in the background, with `system`
+    /// as the system prompt, no task context, and the planning model; the
+    /// trimmed reply text is sent back
+    pub fn ask_once(
+        &self,
+        system: &'static str,
+        prompt: String,
+        sender: UnboundedSender<Result<String, String>>,
+    ) -> AbortHandle {
+        let provider = Arc::clone(&self.provider);
+        let client = self.client.clone();
+        let (model, temperature, max_tokens) =
+            request_settings(&self.planning, &ModelSettings::default(), PLANNING_DEFAULTS);
+        let history = self.redacted(vec![ChatMessage {
+            role: "user".to_string(),
+            content: prompt,
+        }]);
+
+        let task = self.runtime.spawn(async move {
+            let request = CompletionRequest {
+                model: model.as_deref(),
+                system,
+                messages: &history,
+                temperature,
+                max_tokens,
+                tools: None,
+            };
+            let reply = complete(provider.as_ref(), &client, &request)
+                .await
+                .map(|completion| completion.text.trim().to_string());
+            if sender.send(reply).is_err() {
+                tracing::warn!("AI reply dropped: nothing is waiting for it any more");
+            }
+        });
+        task.abort_handle()
+    }
+}
+
+/// Runtime for AI and network requests, shared by all clients and started
+/// on first use; the error says why it could not start
+pub fn runtime() -> Result<&'static Runtime, String> {
+    static RUNTIME: OnceLock<Result<Runtime, String>> = OnceLock::new();
+    RUNTIME
+        .get_or_init(|| {
+            Builder::new_multi_thread()
+                .worker_threads(2)
+                .thread_name("eq-ai")
+                .enable_all()
+                .build()
+                .map_err(|e| format!("Failed to start the async runtime: {}", e))
+        })
+        .as_ref()
+        .map_err(Clone::clone)
+}
+
+/// Fill a user prompt template: `{tasks}` is the task JSON, `{quotes}` the
+/// quote bank, one per line, and `{today}` the weekday and date
+pub fn render_prompt_template(template: &str, tasks: &str, quotes: &str, today: &str) -> String {
+    template
+        .replace("{tasks}", tasks)
+        .replace("{quotes}", quotes)
+        .replace("{today}", today)
+}
+
+fn build_system_prompt(context: &str, template: Option<&str>) -> String {
+    // Build the quote bank string from the curated quotes
+    let quote_bank: String = PAUL_GRAHAM_QUOTES
+        .iter()
+        .map(|(quote, source)| format!("- \"{}\" — Paul Graham, {}", quote, source))
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Quotes the user added to the data dir's quotes folder
+    let own_quotes: String = user_quotes()
+        .iter()
+        .map(|quote| format!("\n- {}", quote))
+        .collect();
+    let today = clock::today().format("%A %Y-%m-%d").to_string();
+    if let Some(template) = template {
+        let quotes = format!("{}{}", quote_bank, own_quotes);
+        return render_prompt_template(template, context, &quotes, &today);
+    }
+    let own_quotes = if own_quotes.is_empty() {
+        own_quotes
+    } else {
+        format!("\n\n### THE USER'S OWN QUOTES:{}", own_quotes)
+    };
+
+    format!(
+        r#"You are Xiaolong's executive assistant specializing in the Eisenhower Matrix methodology. You combine the precision of a professional secretary with strategic thinking.
+
+## CORE RESPONSIBILITIES
+
+### Task Decomposition (GTD-Inspired)
+When the user describes a goal or project:
+1. Identify the **next physical action** — what's the very first concrete step that takes < 30 min?
+2. Break larger tasks into 15-45 minute actionable chunks
+3. Surface hidden dependencies: "Before X, you need Y"
+4. Question scope: "Is this actually one task or three?"
+5. Suggest time-boxing: "This looks like a 2-hour deep work block"
+
+### Priority Assessment
+Apply these criteria rigorously:
+
+**Urgency (1-3):**
+- 3: Due within 24h OR blocks others OR external deadline today
+- 2: Due this week OR has scheduling constraint  
+- 1: No time pressure, flexible timing
+
+**Importance (1-3):**
+- 3: Directly advances key goals (research, thesis, career), high-stakes, or irreversible
+- 2: Contributes meaningfully but not critical path
+- 1: Nice-to-have, low impact if skipped
+
+### Challenge Low-Value Work
+- For Q3 (Delegate): "Can this be delegated, automated, batched, or declined?"
+- For Q4 (Drop): "Why is this on your list? Should it be dropped entirely?"
+- Spot "urgency theater" — tasks that feel urgent but aren't truly important
+
+## OUTPUT FORMAT
+When suggesting tasks, use exactly:
+[ADD] Task name u<1-3>i<1-3>
+
+Examples:
+[ADD] Draft email to Prof. Imai re: meeting agenda u2i3
+[ADD] Review evalITR test failures u3i2
+[ADD] Organize Obsidian research notes u1i2
+[ADD] Buy groceries u2i1
+
+To reschedule an existing task, use:
+[MOVE] Task name -> <YYYY-MM-DD, tomorrow, +N days, or a weekday like friday>
+
+## QUOTE COMMAND
+When user says "quote" (case-insensitive), respond with ONE quote from the verified bank below, when using quote not from the bank, make sure it is a verified quote.
+- Select randomly from the bank; don't repeat recent selections
+- For variety, select quotes from authors across domains and eras: scientists, philosophers, artists, business people, etc.
+- Output format: "[quote text]" — [author], [essay title]
+- Rotate languages when using non-PG quotes: include Seneca, and others
+- NEVER invent or paraphrase quotes; use exact wording from the bank
+
+### VERIFIED PAUL GRAHAM QUOTE BANK:
+{}
+
+### ALTERNATE VERIFIED QUOTES (for variety):
+- "事上磨练" — 王阳明 (Practice and refine yourself through action)
+- "天下古今之庸人，皆以一惰字致败" — 曾国藩 (Mediocrity stems from laziness)
+- "It is not that we have a short time to live, but that we waste a lot of it." — Seneca
+- "予定は決意の半分である" — 松下幸之助 (A plan is half the commitment)
+- "The best time to plant a tree was 20 years ago. The second best time is now."{}
+
+## CURRENT TASKS IN SYSTEM (today is {}):
+{}
+
+## STYLE GUIDELINES
+- Be direct and concise; no filler phrases like "Great question!"
+- One clear recommendation per response when possible
+- Ask ONE clarifying question if the task is too vague to decompose
+- Match the user's language (English/Chinese) when appropriate
+- For complex planning, use structured output with clear next actions"#,
+        quote_bank,
+        own_quotes,
+        today,
+        context
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_bank_not_empty() {
+        assert!(!PAUL_GRAHAM_QUOTES.is_empty());
+        assert!(PAUL_GRAHAM_QUOTES.len() >= 20);
+    }
+
+    #[test]
+    fn test_quotes_have_sources() {
+        for (quote, source) in PAUL_GRAHAM_QUOTES {
+            assert!(!quote.is_empty());
+            assert!(!source.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_request_settings_precedence() {
+        let configured = ModelSettings {
+            model: Some("gpt-4o".into()),
+            temperature: Some(0.7),
+            max_tokens: None,
+        };
+        let overrides = ModelSettings {
+            model: Some("gpt-4o-mini".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            request_settings(&configured, &overrides, PLANNING_DEFAULTS),
+            (Some("gpt-4o-mini".into()), 0.7, 600)
+        );
+        let unset = ModelSettings::default();
+        assert_eq!(
+            request_settings(&unset, &unset, QUOTE_DEFAULTS),
+            (None, 0.3, 150)
+        );
+    }
+
+    #[test]
+    fn test_system_prompt_includes_quotes() {
+        let prompt = build_system_prompt("[]", None);
+        assert!(prompt.contains("Paul Graham"));
+        assert!(prompt.contains("How to Do Great Work"));
+    }
+
+    #[test]
+    fn test_prompt_template_placeholders() {
+        let prompt = render_prompt_template(
+            "Hoy es {today}.\nTareas: {tasks}\n{quotes}",
+            "[]",
+            "- \"Ship it\" — Anonymous",
+            "Monday 2024-06-10",
+        );
+        assert_eq!(
+            prompt,
+            "Hoy es Monday 2024-06-10.\nTareas: []\n- \"Ship it\" — Anonymous"
+        );
+    }
+}
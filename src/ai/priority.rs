@@ -0,0 +1,42 @@
+use crate::parser::input::parse_priority;
+
+/// Chat message asking for the urgency and importance of a new task
+pub fn priority_prompt(title: &str) -> String {
+    format!(
+        "Rate this new task with the urgency and importance rubric: \"{}\". \
+         Reply with one line only: u<1-3>i<1-3> — one short sentence of reasoning.",
+        title.trim()
+    )
+}
+
+/// Urgency, importance, and reasoning from a reply like `u3i2 — due tomorrow`
+pub fn parse_priority_suggestion(reply: &str) -> Option<(u8, u8, String)> {
+    let mut words = reply.split_whitespace();
+    let (urgency, importance) = words
+        .by_ref()
+        .find_map(|word| parse_priority(word.trim_matches(|c: char| !c.is_alphanumeric())))?;
+    let reason = words
+        .collect::<Vec<_>>()
+        .join(" ")
+        .trim_start_matches(|c: char| c == '—' || c == '-' || c == ':' || c.is_whitespace())
+        .to_string();
+    Some((urgency, importance, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_priority_suggestion() {
+        assert_eq!(
+            parse_priority_suggestion("u3i2 — the deadline is Friday."),
+            Some((3, 2, "the deadline is Friday.".to_string()))
+        );
+        assert_eq!(
+            parse_priority_suggestion("**u1i3**: advances the thesis"),
+            Some((1, 3, "advances the thesis".to_string()))
+        );
+        assert_eq!(parse_priority_suggestion("No idea, sorry"), None);
+    }
+}
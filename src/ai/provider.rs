@@ -0,0 +1,940 @@
+use super::http::network_error;
+use super::stream::EventLines;
+use super::ChatMessage;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Client, RequestBuilder, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::time::Duration;
+
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const OPENROUTER_BASE_URL: &str = "https://openrouter.ai/api/v1";
+const ANTHROPIC_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const GEMINI_BASE_URL: &str = "https://generativelanguage.googleapis.com/v1beta";
+/// GA Azure OpenAI data-plane version, used unless another is configured
+const AZURE_API_VERSION: &str = "2024-10-21";
+
+/// Tries per request when the network or server fails transiently
+pub const MAX_ATTEMPTS: u32 = 4;
+/// Wait before the first retry; doubles with each further one
+const RETRY_BASE: Duration = Duration::from_millis(500);
+/// Longest `Retry-After` honored before trying again
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+const ENV_PROVIDER: &str = "EQ_AI_PROVIDER";
+const ENV_BASE_URL: &str = "EQ_AI_BASE_URL";
+const ENV_MODEL: &str = "EQ_AI_MODEL";
+const ENV_API_KEY: &str = "EQ_AI_API_KEY";
+
+/// Provider names accepted by `EQ_AI_PROVIDER` and the `ai_provider` config key
+pub const PROVIDER_NAMES: &[&str] = &[
+    "openai",
+    "anthropic",
+    "gemini",
+    "openrouter",
+    "azure",
+    "compatible",
+];
+
+/// Shown when no provider can be set up
+pub const SETUP_HINT: &str = "No AI provider configured. Set OPENAI_API_KEY, ANTHROPIC_API_KEY, \
+     GEMINI_API_KEY, or OPENROUTER_API_KEY (or EQ_AI_PROVIDER / EQ_AI_BASE_URL).";
+
+/// One chat completion: the system prompt, the conversation, and sampling
+pub struct CompletionRequest<'a> {
+    /// Overrides the provider's default model
+    pub model: Option<&'a str>,
+    pub system: &'a str,
+    pub messages: &'a [ChatMessage],
+    pub temperature: f32,
+    pub max_tokens: u32,
+    /// Tool definitions to offer, for providers that support them
+    pub tools: Option<&'a Value>,
+}
+
+/// A function the model asked to call; `arguments` is its raw JSON
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCall {
+    pub name: String,
+    pub arguments: String,
+}
+
+/// Reply text plus any tool calls
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Completion {
+    pub text: String,
+    pub tool_calls: Vec<ToolCall>,
+}
+
+impl Completion {
+    fn text(text: String) -> Self {
+        Self {
+            text,
+            tool_calls: Vec::new(),
+        }
+    }
+}
+
+/// A chat-completion backend
+pub trait Provider: Send + Sync {
+    /// Short label, e.g. for the chat window title
+    fn name(&self) -> &str;
+
+    /// Whether `CompletionRequest::tools` are sent; without them the model
+    /// falls back to text tags
+    fn supports_tools(&self) -> bool {
+        false
+    }
+
+    /// The HTTP request for `request`, and the JSON body to post with it
+    fn build(&self, client: &Client, request: &CompletionRequest) -> (RequestBuilder, Value);
+
+    /// The reply in a successful response, or a readable error
+    fn parse(&self, json: &Value) -> Result<Completion, String>;
+
+    /// `build` asking for the reply as server-sent events; `None` when the
+    /// provider only answers in one piece
+    fn build_stream(
+        &self,
+        _client: &Client,
+        _request: &CompletionRequest,
+    ) -> Option<(RequestBuilder, Value)> {
+        None
+    }
+
+    /// Fold one streamed event into `reply` and return the text it added
+    fn parse_event(&self, _event: &Value, _reply: &mut Completion) -> Result<String, String> {
+        Ok(String::new())
+    }
+}
+
+/// Send `request` through `provider` and return the reply or a readable error
+pub async fn complete(
+    provider: &dyn Provider,
+    client: &Client,
+    request: &CompletionRequest<'_>,
+) -> Result<Completion, String> {
+    let (http, body) = provider.build(client, request);
+    tracing::debug!(
+        provider = provider.name(),
+        model = ?request.model,
+        messages = request.messages.len(),
+        "sending completion request"
+    );
+    let result = post_json(http, &body)
+        .await
+        .and_then(|json| provider.parse(&json));
+    log_outcome(&result);
+    result
+}
+
+/// `complete`, handing each piece of the reply text to `on_text` as it
+/// arrives; providers that can't stream pass the whole reply at once
+pub async fn complete_streaming(
+    provider: &dyn Provider,
+    client: &Client,
+    request: &CompletionRequest<'_>,
+    mut on_text: impl FnMut(&str),
+) -> Result<Completion, String> {
+    let Some((http, body)) = provider.build_stream(client, request) else {
+        let completion = complete(provider, client, request).await?;
+        on_text(&completion.text);
+        return Ok(completion);
+    };
+    tracing::debug!(
+        provider = provider.name(),
+        model = ?request.model,
+        messages = request.messages.len(),
+        "streaming completion request"
+    );
+    let result = match send(http, &body).await {
+        Ok(response) => read_stream(provider, response, &mut on_text).await,
+        Err(err) => Err(err),
+    };
+    log_outcome(&result);
+    result
+}
+
+fn log_outcome(result: &Result<Completion, String>) {
+    match result {
+        Ok(completion) => tracing::debug!(
+            chars = completion.text.len(),
+            tool_calls = completion.tool_calls.len(),
+            "completion received"
+        ),
+        Err(err) => tracing::warn!(%err, "completion failed"),
+    }
+}
+
+/// Read a streamed reply to the end, passing on its text piece by piece
+async fn read_stream(
+    provider: &dyn Provider,
+    mut response: Response,
+    on_text: &mut impl FnMut(&str),
+) -> Result<Completion, String> {
+    let mut lines = EventLines::default();
+    let mut reply = Completion::default();
+    while let Some(chunk) = response.chunk().await.map_err(|e| network_error(&e))? {
+        for data in lines.push(&chunk) {
+            if data == "[DONE]" {
+                continue;
+            }
+            let event: Value = serde_json::from_str(&data)
+                .map_err(|_| "Failed to parse API response".to_string())?;
+            let text = provider.parse_event(&event, &mut reply)?;
+            if !text.is_empty() {
+                on_text(&text);
+            }
+        }
+    }
+    if reply.text.is_empty() && reply.tool_calls.is_empty() {
+        return Err("Failed to parse API response".to_string());
+    }
+    Ok(reply)
+}
+
+/// Provider choice before keys are looked up: from config, overridden by env
+#[derive(Debug, Clone, Default)]
+pub struct ProviderSettings {
+    pub provider: Option<String>,
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    /// Azure deployment name; the model name is used when unset
+    pub azure_deployment: Option<String>,
+    /// Azure `api-version` query parameter
+    pub azure_api_version: Option<String>,
+}
+
+/// Rate limits and server errors, which usually pass on their own
+fn is_transient(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Wait before retry number `attempt` (from 1): 0.5s, 1s, 2s, ...
+pub fn backoff(attempt: u32) -> Duration {
+    RETRY_BASE * 2u32.pow(attempt.saturating_sub(1))
+}
+
+/// The server's `Retry-After` in seconds, capped at `MAX_RETRY_AFTER`
+fn retry_after(response: &Response) -> Option<Duration> {
+    let seconds = response.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    let wait = Duration::from_secs(seconds.trim().parse().ok()?);
+    Some(wait.min(MAX_RETRY_AFTER))
+}
+
+/// POST `body` and hand back the JSON reply, turning failures into messages
+async fn post_json(request: RequestBuilder, body: &Value) -> Result<Value, String> {
+    send(request, body)
+        .await?
+        .json::<Value>()
+        .await
+        .map_err(|_| "Failed to parse API response".to_string())
+}
+
+/// POST `body` and hand back the successful response, its body unread.
+/// Connection failures, timeouts, 429s, and 5xx responses are retried with
+/// exponential backoff up to `MAX_ATTEMPTS` times.
+async fn send(request: RequestBuilder, body: &Value) -> Result<Response, String> {
+    let mut attempt = 1;
+    loop {
+        let http = request
+            .try_clone()
+            .ok_or_else(|| "Request cannot be retried".to_string())?;
+        let retrying = attempt < MAX_ATTEMPTS;
+        let wait = match http.json(body).send().await {
+            Ok(response) if response.status().is_success() => return Ok(response),
+            Ok(response) if retrying && is_transient(response.status()) => {
+                retry_after(&response).unwrap_or_else(|| backoff(attempt))
+            }
+            Ok(response) if attempt > 1 => {
+                return Err(format!(
+                    "API Error: {} (after {} attempts)",
+                    response.status(),
+                    attempt
+                ));
+            }
+            Ok(response) => return Err(format!("API Error: {}", response.status())),
+            Err(e) if retrying && (e.is_connect() || e.is_timeout()) => backoff(attempt),
+            Err(e) => return Err(network_error(&e)),
+        };
+        tracing::debug!(attempt, ?wait, "retrying the request");
+        tokio::time::sleep(wait).await;
+        attempt += 1;
+    }
+}
+
+/// OpenAI and anything speaking its `/chat/completions` API
+pub struct OpenAiCompatible {
+    name: String,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    tools: bool,
+}
+
+impl OpenAiCompatible {
+    pub fn new(name: &str, base_url: &str, api_key: Option<String>, model: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model: model.to_string(),
+            tools: false,
+        }
+    }
+
+    /// Offer tool definitions; many self-hosted servers reject them
+    pub fn with_tools(mut self) -> Self {
+        self.tools = true;
+        self
+    }
+}
+
+impl Provider for OpenAiCompatible {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.tools
+    }
+
+    fn build(&self, client: &Client, request: &CompletionRequest) -> (RequestBuilder, Value) {
+        let mut body = openai_body(request, self.tools);
+        body["model"] = json!(request.model.unwrap_or(&self.model));
+
+        let mut http = client.post(format!("{}/chat/completions", self.base_url));
+        if let Some(key) = &self.api_key {
+            http = http.header("Authorization", format!("Bearer {}", key));
+        }
+        (http, body)
+    }
+
+    fn parse(&self, json: &Value) -> Result<Completion, String> {
+        openai_reply(json)
+    }
+
+    fn build_stream(
+        &self,
+        client: &Client,
+        request: &CompletionRequest,
+    ) -> Option<(RequestBuilder, Value)> {
+        let (http, mut body) = self.build(client, request);
+        body["stream"] = json!(true);
+        Some((http, body))
+    }
+
+    fn parse_event(&self, event: &Value, reply: &mut Completion) -> Result<String, String> {
+        openai_event(event, reply)
+    }
+}
+
+/// `/chat/completions` request body without the model, which Azure takes
+/// from the URL instead
+fn openai_body(request: &CompletionRequest, tools: bool) -> Value {
+    let mut messages = vec![json!({ "role": "system", "content": request.system })];
+    messages.extend(
+        request
+            .messages
+            .iter()
+            .map(|m| json!({ "role": m.role, "content": m.content })),
+    );
+    let mut body = json!({
+        "temperature": request.temperature,
+        "presence_penalty": 0.2,
+        "frequency_penalty": 0.3,
+        "max_tokens": request.max_tokens,
+        "messages": messages,
+    });
+    if let (true, Some(tools)) = (tools, request.tools) {
+        body["tools"] = tools.clone();
+        body["tool_choice"] = json!("auto");
+    }
+    body
+}
+
+/// Azure OpenAI: one deployment per model, addressed by URL, with an
+/// `api-key` header instead of a bearer token
+pub struct AzureOpenAi {
+    endpoint: String,
+    deployment: String,
+    api_version: String,
+    api_key: String,
+}
+
+impl AzureOpenAi {
+    pub fn new(endpoint: &str, deployment: &str, api_version: &str, api_key: String) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            deployment: deployment.to_string(),
+            api_version: api_version.to_string(),
+            api_key,
+        }
+    }
+
+    /// Chat completions URL of the deployment
+    pub fn url(&self) -> String {
+        format!(
+            "{}/openai/deployments/{}/chat/completions?api-version={}",
+            self.endpoint, self.deployment, self.api_version
+        )
+    }
+}
+
+impl Provider for AzureOpenAi {
+    fn name(&self) -> &str {
+        "azure"
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn build(&self, client: &Client, request: &CompletionRequest) -> (RequestBuilder, Value) {
+        let http = client.post(self.url()).header("api-key", &self.api_key);
+        (http, openai_body(request, true))
+    }
+
+    fn parse(&self, json: &Value) -> Result<Completion, String> {
+        openai_reply(json)
+    }
+
+    fn build_stream(
+        &self,
+        client: &Client,
+        request: &CompletionRequest,
+    ) -> Option<(RequestBuilder, Value)> {
+        let (http, mut body) = self.build(client, request);
+        body["stream"] = json!(true);
+        Some((http, body))
+    }
+
+    fn parse_event(&self, event: &Value, reply: &mut Completion) -> Result<String, String> {
+        openai_event(event, reply)
+    }
+}
+
+/// Message text and tool calls; the text is null when the model only
+/// calls tools
+fn openai_reply(json: &Value) -> Result<Completion, String> {
+    let message = &json["choices"][0]["message"];
+    let tool_calls: Vec<ToolCall> = message["tool_calls"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|call| {
+            Some(ToolCall {
+                name: call["function"]["name"].as_str()?.to_string(),
+                arguments: call["function"]["arguments"].as_str()?.to_string(),
+            })
+        })
+        .collect();
+    match message["content"].as_str() {
+        Some(text) => Ok(Completion {
+            text: text.to_string(),
+            tool_calls,
+        }),
+        None if !tool_calls.is_empty() => Ok(Completion {
+            text: String::new(),
+            tool_calls,
+        }),
+        None => Err("Failed to parse API response".to_string()),
+    }
+}
+
+/// One streamed `/chat/completions` chunk: text is appended, and tool calls
+/// arrive as pieces of name and arguments keyed by their index
+fn openai_event(event: &Value, reply: &mut Completion) -> Result<String, String> {
+    if let Some(message) = event["error"]["message"].as_str() {
+        return Err(format!("API Error: {}", message));
+    }
+    let delta = &event["choices"][0]["delta"];
+    for call in delta["tool_calls"].as_array().into_iter().flatten() {
+        let index = call["index"].as_u64().unwrap_or(0) as usize;
+        if reply.tool_calls.len() <= index {
+            reply.tool_calls.resize_with(index + 1, ToolCall::default);
+        }
+        let function = &call["function"];
+        let tool_call = &mut reply.tool_calls[index];
+        tool_call
+            .name
+            .push_str(function["name"].as_str().unwrap_or_default());
+        tool_call
+            .arguments
+            .push_str(function["arguments"].as_str().unwrap_or_default());
+    }
+    let text = delta["content"].as_str().unwrap_or_default();
+    reply.text.push_str(text);
+    Ok(text.to_string())
+}
+
+/// Anthropic's Messages API
+pub struct Anthropic {
+    api_key: String,
+    model: String,
+}
+
+impl Provider for Anthropic {
+    fn name(&self) -> &str {
+        "anthropic"
+    }
+
+    fn build(&self, client: &Client, request: &CompletionRequest) -> (RequestBuilder, Value) {
+        // The system prompt is a top-level field; the conversation must open
+        // with a user turn and hold only user/assistant turns
+        let messages: Vec<Value> = request
+            .messages
+            .iter()
+            .skip_while(|m| m.role != "user")
+            .filter(|m| m.role == "user" || m.role == "assistant")
+            .map(|m| json!({ "role": m.role, "content": m.content }))
+            .collect();
+        let body = json!({
+            "model": request.model.unwrap_or(&self.model),
+            "system": request.system,
+            // Anthropic accepts 0.0-1.0
+            "temperature": request.temperature.min(1.0),
+            "max_tokens": request.max_tokens,
+            "messages": messages,
+        });
+
+        let http = client
+            .post(ANTHROPIC_URL)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION);
+        (http, body)
+    }
+
+    fn parse(&self, json: &Value) -> Result<Completion, String> {
+        anthropic_reply(json).map(Completion::text)
+    }
+
+    fn build_stream(
+        &self,
+        client: &Client,
+        request: &CompletionRequest,
+    ) -> Option<(RequestBuilder, Value)> {
+        let (http, mut body) = self.build(client, request);
+        body["stream"] = json!(true);
+        Some((http, body))
+    }
+
+    fn parse_event(&self, event: &Value, reply: &mut Completion) -> Result<String, String> {
+        anthropic_event(event, reply)
+    }
+}
+
+/// One streamed Messages event; only text deltas carry reply text
+fn anthropic_event(event: &Value, reply: &mut Completion) -> Result<String, String> {
+    match event["type"].as_str() {
+        Some("error") => Err(format!(
+            "API Error: {}",
+            event["error"]["message"]
+                .as_str()
+                .unwrap_or("the stream failed")
+        )),
+        Some("content_block_delta") if event["delta"]["type"] == "text_delta" => {
+            let text = event["delta"]["text"].as_str().unwrap_or_default();
+            reply.text.push_str(text);
+            Ok(text.to_string())
+        }
+        _ => Ok(String::new()),
+    }
+}
+
+fn anthropic_reply(json: &Value) -> Result<String, String> {
+    let text: Vec<&str> = json["content"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|block| block["type"] == "text")
+        .filter_map(|block| block["text"].as_str())
+        .collect();
+    if text.is_empty() {
+        return Err("Failed to parse API response".to_string());
+    }
+    Ok(text.concat())
+}
+
+/// Google's Gemini `generateContent` API
+pub struct Gemini {
+    api_key: String,
+    model: String,
+}
+
+impl Provider for Gemini {
+    fn name(&self) -> &str {
+        "gemini"
+    }
+
+    fn build(&self, client: &Client, request: &CompletionRequest) -> (RequestBuilder, Value) {
+        self.build_for(client, request, "generateContent")
+    }
+
+    fn parse(&self, json: &Value) -> Result<Completion, String> {
+        gemini_reply(json).map(Completion::text)
+    }
+
+    fn build_stream(
+        &self,
+        client: &Client,
+        request: &CompletionRequest,
+    ) -> Option<(RequestBuilder, Value)> {
+        Some(self.build_for(client, request, "streamGenerateContent?alt=sse"))
+    }
+
+    fn parse_event(&self, event: &Value, reply: &mut Completion) -> Result<String, String> {
+        if let Some(message) = event["error"]["message"].as_str() {
+            return Err(format!("API Error: {}", message));
+        }
+        // Each event is a whole response holding the next piece of text
+        let text = gemini_reply(event).unwrap_or_default();
+        reply.text.push_str(&text);
+        Ok(text)
+    }
+}
+
+impl Gemini {
+    /// Request to the model's `method`, e.g. `generateContent`
+    fn build_for(
+        &self,
+        client: &Client,
+        request: &CompletionRequest,
+        method: &str,
+    ) -> (RequestBuilder, Value) {
+        // Gemini calls the assistant role "model"
+        let contents: Vec<Value> = request
+            .messages
+            .iter()
+            .filter(|m| m.role != "system")
+            .map(|m| {
+                let role = if m.role == "assistant" {
+                    "model"
+                } else {
+                    "user"
+                };
+                json!({ "role": role, "parts": [{ "text": m.content }] })
+            })
+            .collect();
+        let body = json!({
+            "systemInstruction": { "parts": [{ "text": request.system }] },
+            "contents": contents,
+            "generationConfig": {
+                "temperature": request.temperature,
+                "maxOutputTokens": request.max_tokens,
+            },
+        });
+
+        let http = client
+            .post(format!(
+                "{}/models/{}:{}",
+                GEMINI_BASE_URL,
+                request.model.unwrap_or(&self.model),
+                method
+            ))
+            .header("x-goog-api-key", &self.api_key);
+        (http, body)
+    }
+}
+
+fn gemini_reply(json: &Value) -> Result<String, String> {
+    let text: Vec<&str> = json["candidates"][0]["content"]["parts"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|part| part["text"].as_str())
+        .collect();
+    if text.is_empty() {
+        return Err("Failed to parse API response".to_string());
+    }
+    Ok(text.concat())
+}
+
+/// Pick a provider from `settings`, with `EQ_AI_*` variables taking priority.
+///
+/// Without an explicit choice, a set `EQ_AI_BASE_URL` means an
+/// OpenAI-compatible endpoint; otherwise the first API key found wins.
+/// `env` looks up environment variables so selection can be tested.
+pub fn select_provider(
+    settings: &ProviderSettings,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Box<dyn Provider>, String> {
+    let provider = env(ENV_PROVIDER).or_else(|| settings.provider.clone());
+    let base_url = env(ENV_BASE_URL).or_else(|| settings.base_url.clone());
+    let model_name = env(ENV_MODEL).or_else(|| settings.model.clone());
+    let model = |default: &str| model_name.clone().unwrap_or_else(|| default.to_string());
+    let key = |var: &str| env(var).ok_or_else(|| format!("{} is not set", var));
+
+    let name = match provider {
+        Some(name) => name.trim().to_lowercase(),
+        None if base_url.is_some() => "compatible".to_string(),
+        None => [
+            ("OPENAI_API_KEY", "openai"),
+            ("ANTHROPIC_API_KEY", "anthropic"),
+            ("GEMINI_API_KEY", "gemini"),
+            ("OPENROUTER_API_KEY", "openrouter"),
+            ("AZURE_OPENAI_API_KEY", "azure"),
+        ]
+        .iter()
+        .find(|(var, _)| env(var).is_some())
+        .map(|(_, name)| name.to_string())
+        .ok_or_else(|| SETUP_HINT.to_string())?,
+    };
+
+    match name.as_str() {
+        "openai" => Ok(Box::new(
+            OpenAiCompatible::new(
+                "openai",
+                base_url.as_deref().unwrap_or(OPENAI_BASE_URL),
+                Some(key("OPENAI_API_KEY")?),
+                &model("gpt-4o"),
+            )
+            .with_tools(),
+        )),
+        "anthropic" | "claude" => Ok(Box::new(Anthropic {
+            api_key: key("ANTHROPIC_API_KEY")?,
+            model: model("claude-sonnet-4-5"),
+        })),
+        "gemini" | "google" => Ok(Box::new(Gemini {
+            api_key: env("GEMINI_API_KEY")
+                .or_else(|| env("GOOGLE_API_KEY"))
+                .ok_or("GEMINI_API_KEY is not set")?,
+            model: model("gemini-2.0-flash"),
+        })),
+        "openrouter" => Ok(Box::new(
+            OpenAiCompatible::new(
+                "openrouter",
+                base_url.as_deref().unwrap_or(OPENROUTER_BASE_URL),
+                Some(key("OPENROUTER_API_KEY")?),
+                &model("openai/gpt-4o"),
+            )
+            .with_tools(),
+        )),
+        "azure" | "azure-openai" => {
+            let endpoint = base_url
+                .or_else(|| env("AZURE_OPENAI_ENDPOINT"))
+                .ok_or("AZURE_OPENAI_ENDPOINT is not set")?;
+            let deployment = env("AZURE_OPENAI_DEPLOYMENT")
+                .or_else(|| settings.azure_deployment.clone())
+                .or(model_name)
+                .ok_or("AZURE_OPENAI_DEPLOYMENT is not set")?;
+            let api_version = env("AZURE_OPENAI_API_VERSION")
+                .or_else(|| settings.azure_api_version.clone())
+                .unwrap_or_else(|| AZURE_API_VERSION.to_string());
+            Ok(Box::new(AzureOpenAi::new(
+                &endpoint,
+                &deployment,
+                &api_version,
+                key("AZURE_OPENAI_API_KEY")?,
+            )))
+        }
+        "compatible" | "openai-compatible" => {
+            let base_url = base_url.ok_or_else(|| format!("{} is not set", ENV_BASE_URL))?;
+            let model = model_name.ok_or_else(|| format!("{} is not set", ENV_MODEL))?;
+            // Local servers often need no key
+            Ok(Box::new(OpenAiCompatible::new(
+                "compatible",
+                &base_url,
+                env(ENV_API_KEY),
+                &model,
+            )))
+        }
+        other => Err(format!(
+            "Unknown AI provider '{}' ({})",
+            other,
+            PROVIDER_NAMES.join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn selected(settings: &ProviderSettings, vars: &[(&str, &str)]) -> Result<String, String> {
+        select_provider(settings, env(vars)).map(|p| p.name().to_string())
+    }
+
+    #[test]
+    fn test_first_key_found_wins() {
+        let settings = ProviderSettings::default();
+        assert_eq!(
+            selected(&settings, &[("ANTHROPIC_API_KEY", "k")]),
+            Ok("anthropic".into())
+        );
+        assert_eq!(
+            selected(
+                &settings,
+                &[("GEMINI_API_KEY", "k"), ("OPENAI_API_KEY", "k")]
+            ),
+            Ok("openai".into())
+        );
+        assert_eq!(selected(&settings, &[]), Err(SETUP_HINT.to_string()));
+    }
+
+    #[test]
+    fn test_env_overrides_config() {
+        let settings = ProviderSettings {
+            provider: Some("openai".into()),
+            ..Default::default()
+        };
+        let vars = [
+            ("EQ_AI_PROVIDER", "gemini"),
+            ("GEMINI_API_KEY", "k"),
+            ("OPENAI_API_KEY", "k"),
+        ];
+        assert_eq!(selected(&settings, &vars), Ok("gemini".into()));
+    }
+
+    #[test]
+    fn test_named_provider_needs_its_key() {
+        let vars = [("EQ_AI_PROVIDER", "anthropic"), ("OPENAI_API_KEY", "k")];
+        assert_eq!(
+            selected(&ProviderSettings::default(), &vars),
+            Err("ANTHROPIC_API_KEY is not set".into())
+        );
+        let vars = [("EQ_AI_PROVIDER", "mystery")];
+        assert!(selected(&ProviderSettings::default(), &vars).is_err());
+    }
+
+    #[test]
+    fn test_base_url_selects_compatible_endpoint() {
+        let vars = [
+            ("EQ_AI_BASE_URL", "http://localhost:11434/v1"),
+            ("EQ_AI_MODEL", "llama3"),
+        ];
+        assert_eq!(
+            selected(&ProviderSettings::default(), &vars),
+            Ok("compatible".into())
+        );
+        let no_model = [("EQ_AI_BASE_URL", "http://localhost:11434/v1")];
+        assert!(selected(&ProviderSettings::default(), &no_model).is_err());
+    }
+
+    #[test]
+    fn test_azure_needs_endpoint_and_key() {
+        let vars = [
+            ("AZURE_OPENAI_API_KEY", "k"),
+            ("AZURE_OPENAI_ENDPOINT", "https://corp.openai.azure.com/"),
+        ];
+        let settings = ProviderSettings {
+            azure_deployment: Some("gpt-4o-prod".into()),
+            ..Default::default()
+        };
+        assert_eq!(selected(&settings, &vars), Ok("azure".into()));
+        assert!(selected(&settings, &vars[..1]).is_err());
+
+        let azure = AzureOpenAi::new(
+            "https://corp.openai.azure.com/",
+            "gpt-4o-prod",
+            "2024-10-21",
+            "k".into(),
+        );
+        assert_eq!(
+            azure.url(),
+            "https://corp.openai.azure.com/openai/deployments/gpt-4o-prod/chat/completions\
+             ?api-version=2024-10-21"
+        );
+    }
+
+    #[test]
+    fn test_reply_parsing() {
+        let openai = json!({ "choices": [{ "message": { "content": "hi" } }] });
+        assert_eq!(openai_reply(&openai), Ok(Completion::text("hi".into())));
+
+        let anthropic = json!({ "content": [
+            { "type": "text", "text": "a" },
+            { "type": "text", "text": "b" }
+        ] });
+        assert_eq!(anthropic_reply(&anthropic), Ok("ab".into()));
+
+        let gemini = json!({ "candidates": [{ "content": { "parts": [{ "text": "g" }] } }] });
+        assert_eq!(gemini_reply(&gemini), Ok("g".into()));
+        assert!(gemini_reply(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_openai_tool_calls() {
+        let reply = json!({ "choices": [{ "message": {
+            "content": null,
+            "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": { "name": "complete_task", "arguments": "{\"task\":\"#1\"}" }
+            }]
+        } }] });
+        assert_eq!(
+            openai_reply(&reply),
+            Ok(Completion {
+                text: String::new(),
+                tool_calls: vec![ToolCall {
+                    name: "complete_task".into(),
+                    arguments: r##"{"task":"#1"}"##.into(),
+                }],
+            })
+        );
+        assert!(openai_reply(&json!({ "choices": [{ "message": {} }] })).is_err());
+    }
+
+    #[test]
+    fn test_streamed_events_build_the_reply() {
+        let mut reply = Completion::default();
+        let text = json!({ "choices": [{ "delta": { "content": "Hel" } }] });
+        assert_eq!(openai_event(&text, &mut reply), Ok("Hel".into()));
+        for piece in [r#"{"task""#, r##":"#1"}"##] {
+            let call = json!({ "choices": [{ "delta": { "tool_calls": [
+                { "index": 0, "function": { "arguments": piece } }
+            ] } }] });
+            assert_eq!(openai_event(&call, &mut reply), Ok(String::new()));
+        }
+        reply.tool_calls[0].name = "complete_task".into();
+        assert_eq!(reply.tool_calls[0].arguments, r##"{"task":"#1"}"##);
+        assert_eq!(reply.text, "Hel");
+
+        let mut reply = Completion::default();
+        let delta = json!({ "type": "content_block_delta",
+            "delta": { "type": "text_delta", "text": "Hi" } });
+        assert_eq!(anthropic_event(&delta, &mut reply), Ok("Hi".into()));
+        let ping = json!({ "type": "ping" });
+        assert_eq!(anthropic_event(&ping, &mut reply), Ok(String::new()));
+        let overloaded = json!({ "type": "error", "error": { "message": "Overloaded" } });
+        assert_eq!(
+            anthropic_event(&overloaded, &mut reply),
+            Err("API Error: Overloaded".into())
+        );
+        assert_eq!(reply.text, "Hi");
+    }
+
+    #[test]
+    fn test_only_hosted_openai_apis_get_tools() {
+        let supports = |vars: &[(&str, &str)]| {
+            select_provider(&ProviderSettings::default(), env(vars)).map(|p| p.supports_tools())
+        };
+        assert_eq!(supports(&[("OPENAI_API_KEY", "k")]), Ok(true));
+        assert_eq!(supports(&[("ANTHROPIC_API_KEY", "k")]), Ok(false));
+        let local = [
+            ("EQ_AI_BASE_URL", "http://localhost:11434/v1"),
+            ("EQ_AI_MODEL", "llama3"),
+        ];
+        assert_eq!(supports(&local), Ok(false));
+    }
+
+    #[test]
+    fn test_retry_policy() {
+        assert_eq!(backoff(1), Duration::from_millis(500));
+        assert_eq!(backoff(3), Duration::from_secs(2));
+        assert!(is_transient(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_transient(StatusCode::BAD_GATEWAY));
+        assert!(!is_transient(StatusCode::UNAUTHORIZED));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::config::RedactionRules;
+use crate::models::task::Task;
+use regex::Regex;
+
+/// Stands in for text matched by a redaction pattern
+pub const REDACTED: &str = "[redacted]";
+
+/// Punctuation that may follow a tag in running text
+const TAG_BOUNDARY: &[char] = &[',', '.', ';', ':', '"', '(', ')'];
+
+/// Compiled `ai_redact` rules, applied to everything sent to a provider
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    /// Lowercase, with the leading `#`
+    tags: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl Redactor {
+    /// Compile `rules`; an invalid pattern is an error so nothing is sent
+    /// unredacted by mistake
+    pub fn new(rules: &RedactionRules) -> Result<Self, String> {
+        let tags = rules
+            .exclude_tags
+            .iter()
+            .map(|tag| format!("#{}", tag.trim().trim_start_matches('#')).to_lowercase())
+            .collect();
+        let patterns = rules
+            .patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern)
+                    .map_err(|e| format!("Invalid ai_redact pattern '{}': {}", pattern, e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { tags, patterns })
+    }
+
+    /// Whether `text` carries one of the excluded tags
+    fn mentions_tag(&self, text: &str) -> bool {
+        text.split(|c: char| c.is_whitespace() || TAG_BOUNDARY.contains(&c))
+            .any(|word| self.tags.contains(&word.to_lowercase()))
+    }
+
+    /// Whether `task` must not be sent at all
+    pub fn withholds(&self, task: &Task) -> bool {
+        self.mentions_tag(&task.title)
+    }
+
+    /// `text` with every pattern match replaced by `REDACTED`
+    pub fn mask(&self, text: &str) -> String {
+        self.patterns
+            .iter()
+            .fold(text.to_string(), |text, pattern| {
+                pattern.replace_all(&text, REDACTED).into_owned()
+            })
+    }
+
+    /// A message as sent: lines mentioning an excluded tag are dropped and
+    /// pattern matches masked
+    pub fn redact_text(&self, text: &str) -> String {
+        let kept: Vec<&str> = text
+            .lines()
+            .filter(|line| !self.mentions_tag(line))
+            .collect();
+        self.mask(&kept.join("\n"))
+    }
+
+    /// The tasks that may be sent, with their text masked, and how many
+    /// were withheld
+    pub fn apply<'a>(&self, tasks: impl IntoIterator<Item = &'a Task>) -> (Vec<Task>, usize) {
+        let mut withheld = 0;
+        let mut kept = Vec::new();
+        for task in tasks {
+            if self.withholds(task) {
+                withheld += 1;
+                continue;
+            }
+            let mut task = task.clone();
+            task.title = self.mask(&task.title);
+            task.drop_reason = task.drop_reason.map(|reason| self.mask(&reason));
+            kept.push(task);
+        }
+        (kept, withheld)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redactor(tags: &[&str], patterns: &[&str]) -> Redactor {
+        Redactor::new(&RedactionRules {
+            exclude_tags: tags.iter().map(|t| t.to_string()).collect(),
+            patterns: patterns.iter().map(|p| p.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tagged_tasks_are_withheld() {
+        let date = "2024-06-12".parse().unwrap();
+        let tasks = vec![
+            Task::new("Therapy session #Private".into(), 2, 3, date),
+            Task::new("Call Dr. Imai at 555-0134".into(), 3, 2, date),
+            Task::new("Ship the #privateer release".into(), 3, 3, date),
+        ];
+        let (kept, withheld) = redactor(&["private"], &[r"\d{3}-\d{4}"]).apply(&tasks);
+        assert_eq!(withheld, 1);
+        let titles: Vec<&str> = kept.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec!["Call Dr. Imai at [redacted]", "Ship the #privateer release"]
+        );
+    }
+
+    #[test]
+    fn test_redact_text_drops_tagged_lines() {
+        let prompt = "Audit these:\n- Lawyer call #private\n- Expense report for ACME";
+        assert_eq!(
+            redactor(&["#private"], &["(?i)acme"]).redact_text(prompt),
+            "Audit these:\n- Expense report for [redacted]"
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_an_error() {
+        let rules = RedactionRules {
+            exclude_tags: Vec::new(),
+            patterns: vec!["(unclosed".into()],
+        };
+        assert!(Redactor::new(&rules).is_err());
+    }
+}
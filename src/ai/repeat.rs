@@ -0,0 +1,56 @@
+use crate::models::task::Task;
+
+/// System prompt for the duplicate check; short, since any model will do
+pub const REPEAT_SYSTEM: &str = "You spot repeated entries in a to-do list. \
+    Answer with a single number or the word none, nothing else.";
+
+/// Ask whether `title` is the same piece of work as one of `candidates`
+pub fn repeat_prompt(title: &str, candidates: &[&Task]) -> String {
+    let list: String = candidates
+        .iter()
+        .enumerate()
+        .map(|(i, task)| format!("{}. {}\n", i + 1, task.title))
+        .collect();
+    format!(
+        "Pending tasks:\n{}\nNew task: \"{}\"\n\nIs the new task the same piece of work as \
+         one of the pending tasks, even if worded differently? Reply with its number, or none.",
+        list,
+        title.trim()
+    )
+}
+
+/// Index into the candidates of the one a reply like `3` or `#3.` names;
+/// `None` for "none" or a number out of range
+pub fn parse_repeat_reply(reply: &str, count: usize) -> Option<usize> {
+    let number: usize = reply
+        .split(|c: char| !c.is_ascii_digit())
+        .find(|word| !word.is_empty())?
+        .parse()
+        .ok()?;
+    (1..=count).contains(&number).then(|| number - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_repeat_prompt_numbers_candidates() {
+        let day = NaiveDate::from_ymd_opt(2024, 6, 4).unwrap();
+        let email = Task::new("Email Prof. Imai".into(), 2, 3, day);
+        let report = Task::new("Write report".into(), 2, 2, day);
+        let prompt = repeat_prompt("Reply to Imai's message", &[&email, &report]);
+        assert!(prompt.contains("1. Email Prof. Imai\n2. Write report\n"));
+        assert!(prompt.contains("New task: \"Reply to Imai's message\""));
+    }
+
+    #[test]
+    fn test_parse_repeat_reply() {
+        assert_eq!(parse_repeat_reply("2", 3), Some(1));
+        assert_eq!(parse_repeat_reply("#1.", 3), Some(0));
+        assert_eq!(parse_repeat_reply("none", 3), None);
+        assert_eq!(parse_repeat_reply("4", 3), None);
+        assert_eq!(parse_repeat_reply("0", 3), None);
+    }
+}
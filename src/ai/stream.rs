@@ -0,0 +1,42 @@
+/// Splits a streamed response body into the `data:` payloads of its
+/// server-sent events. Chunks can end mid-line, so the unfinished tail is
+/// kept for the next one.
+#[derive(Debug, Default)]
+pub struct EventLines {
+    pending: Vec<u8>,
+}
+
+impl EventLines {
+    /// Payloads of the lines `chunk` completes, in order
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(chunk);
+        let mut payloads = Vec::new();
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(payload) = line.trim_end().strip_prefix("data:") {
+                payloads.push(payload.trim_start().to_string());
+            }
+        }
+        payloads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_lines_join_split_chunks() {
+        let mut lines = EventLines::default();
+        assert!(lines.push(b"event: message\ndata: {\"a\"").is_empty());
+        assert_eq!(
+            lines.push(b":1}\r\n\r\ndata: [DONE]\n"),
+            vec!["{\"a\":1}", "[DONE]"]
+        );
+        // A multi-byte character cut between chunks survives
+        let snowman = "data: \u{2603}\n".as_bytes();
+        assert!(lines.push(&snowman[..8]).is_empty());
+        assert_eq!(lines.push(&snowman[8..]), vec!["\u{2603}"]);
+    }
+}
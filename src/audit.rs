@@ -0,0 +1,63 @@
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use chrono::NaiveDate;
+
+/// Pending DELEGATE and DROP tasks on `date`, DELEGATE first, then by score
+pub fn audit_tasks(tasks: &[Task], date: NaiveDate) -> Vec<&Task> {
+    let mut audited: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending && t.date == date)
+        .filter(|t| matches!(t.quadrant(), Quadrant::Delegate | Quadrant::Drop))
+        .collect();
+    audited.sort_by_key(|t| (t.quadrant() == Quadrant::Drop, std::cmp::Reverse(t.score())));
+    audited
+}
+
+/// Chat message asking the assistant to challenge each task and propose
+/// [DROP] or [EDIT] lines for confirmation
+pub fn audit_prompt(tasks: &[&Task]) -> String {
+    let mut prompt = String::from("Audit my DELEGATE and DROP tasks:\n");
+    for task in tasks {
+        prompt.push_str(&format!(
+            "- {} ({}, u{}i{})\n",
+            task.title,
+            task.quadrant(),
+            task.urgency,
+            task.importance
+        ));
+    }
+    prompt.push_str(
+        "Challenge each in one line: can it be automated, delegated, declined, or batched? \
+         Then propose [DROP] <exact title> for what should go and \
+         [EDIT] <exact title> u<1-3>i<1-3> for what deserves a different priority. \
+         Leave alone what should stay.",
+    );
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_audit_covers_delegate_then_drop() {
+        let today = date("2024-06-12");
+        let mut done = Task::new("done".into(), 3, 1, today);
+        done.complete();
+        let tasks = vec![
+            Task::new("scroll feeds".into(), 1, 1, today),
+            Task::new("ship".into(), 3, 3, today),
+            Task::new("status meeting".into(), 3, 1, today),
+            Task::new("other day".into(), 3, 1, date("2024-06-13")),
+            done,
+        ];
+
+        let audited = audit_tasks(&tasks, today);
+        let titles: Vec<&str> = audited.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["status meeting", "scroll feeds"]);
+        assert!(audit_prompt(&audited).contains("- status meeting (DELEGATE, u3i1)"));
+    }
+}
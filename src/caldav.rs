@@ -0,0 +1,862 @@
+use crate::ai::runtime;
+use crate::clock;
+use crate::config::CalDavAccount;
+use crate::models::store::TaskStore;
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use crate::parser::input::{parse_priority, TaskInput};
+use crate::storage::paths::caldav_state_path;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SubsecRound, Utc};
+use regex::Regex;
+use reqwest::header::{CONTENT_TYPE, ETAG, IF_MATCH, IF_NONE_MATCH};
+use reqwest::{Method, StatusCode, Url};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use uuid::Uuid;
+
+/// Attempts at pushing one task before it is left for the next sync
+const MAX_ATTEMPTS: usize = 3;
+
+/// VTODO properties eq writes; the rest, such as alarms set on a phone, are
+/// kept as the server has them
+const OWNED: &[&str] = &[
+    "SUMMARY",
+    "STATUS",
+    "PRIORITY",
+    "X-EQ-PRIORITY",
+    "DTSTART",
+    "DUE",
+    "COMPLETED",
+    "PERCENT-COMPLETE",
+    "DTSTAMP",
+    "LAST-MODIFIED",
+];
+
+const QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+  <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VTODO"/></c:comp-filter></c:filter>
+</c:calendar-query>"#;
+
+/// The parts of a task that are synced, as they are on one side
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Todo {
+    pub title: String,
+    pub status: TaskStatus,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Urgency and importance; `None` when the server has no priority
+    pub priority: Option<(u8, u8)>,
+    /// The day the task is planned for, kept in `DTSTART`
+    pub date: Option<NaiveDate>,
+    pub due: Option<NaiveDate>,
+}
+
+impl Todo {
+    pub fn from_task(task: &Task) -> Self {
+        Self {
+            title: task.title.clone(),
+            status: task.status,
+            completed_at: task.completed_at.map(|at| at.trunc_subsecs(0)),
+            priority: Some((task.urgency, task.importance)),
+            date: Some(task.date),
+            due: task.due,
+        }
+    }
+
+    /// The first VTODO in an iCalendar object
+    pub fn parse(ics: &str) -> Option<Self> {
+        let props = todo_properties(ics)?;
+        let get = |name: &str| props.get(name).map(String::as_str);
+        let status = match get("STATUS") {
+            Some("COMPLETED") => TaskStatus::Completed,
+            Some("CANCELLED") => TaskStatus::Dropped,
+            _ => TaskStatus::Pending,
+        };
+        let caldav = get("PRIORITY").and_then(|p| p.trim().parse::<u8>().ok());
+        // Our own priority survives unless the server's was changed since
+        let ours = get("X-EQ-PRIORITY")
+            .and_then(parse_priority)
+            .filter(|&(u, i)| caldav == Some(caldav_priority(u, i)));
+        Some(Self {
+            title: unescape(get("SUMMARY").unwrap_or_default()).replace('\n', " "),
+            status,
+            completed_at: get("COMPLETED")
+                .filter(|_| status == TaskStatus::Completed)
+                .and_then(parse_timestamp),
+            priority: ours.or_else(|| caldav.and_then(from_caldav_priority)),
+            date: get("DTSTART").and_then(parse_day),
+            due: get("DUE").and_then(parse_day),
+        })
+    }
+
+    /// A new task for a todo first seen on the server
+    fn new_task(&self, uid: &str, today: NaiveDate) -> Task {
+        let input = TaskInput {
+            title: self.title.clone(),
+            priority: self.priority,
+            date: self.date,
+            due: self.due,
+            ..TaskInput::default()
+        };
+        let mut task = input.into_task(today);
+        // Derived from the UID, so every machine pulls it as the same task
+        task.id = Uuid::parse_str(uid)
+            .unwrap_or_else(|_| Uuid::new_v5(&Uuid::NAMESPACE_URL, uid.as_bytes()));
+        task.status = self.status;
+        task.completed_at = self.completed_at;
+        task
+    }
+}
+
+/// CalDAV priority for a quadrant, 1 being the highest and 9 the lowest;
+/// phones show 1, 5, and 9 as high, medium, and low
+pub fn caldav_priority(urgency: u8, importance: u8) -> u8 {
+    match Quadrant::from_priority(urgency, importance) {
+        Quadrant::DoFirst => 1,
+        Quadrant::Schedule => 5,
+        Quadrant::Delegate => 7,
+        Quadrant::Drop => 9,
+    }
+}
+
+/// Urgency and importance for a CalDAV priority; `None` for 0, undefined
+pub fn from_caldav_priority(priority: u8) -> Option<(u8, u8)> {
+    match priority {
+        1..=4 => Some((3, 3)),
+        5 => Some((1, 3)),
+        6..=8 => Some((3, 1)),
+        9 => Some((1, 1)),
+        _ => None,
+    }
+}
+
+/// Which side's version of a task to keep
+pub fn merge(base: Option<&Todo>, ours: &Todo, theirs: &Todo) -> Todo {
+    if base == Some(theirs) {
+        ours.clone()
+    } else if base == Some(ours) {
+        theirs.clone()
+    } else if ours.status == TaskStatus::Pending && theirs.status != TaskStatus::Pending {
+        // Changed on both sides: finishing a task wins over editing it
+        theirs.clone()
+    } else {
+        ours.clone()
+    }
+}
+
+/// An iCalendar object holding `todo`. Given the server's copy, only the
+/// properties eq owns are replaced
+pub fn render(todo: &Todo, uid: &str, original: Option<&str>) -> String {
+    let now = stamp(clock::now());
+    let mut props = vec![
+        format!("SUMMARY:{}", escape(&todo.title)),
+        format!("DTSTAMP:{}", now),
+        format!("LAST-MODIFIED:{}", now),
+    ];
+    if let Some((urgency, importance)) = todo.priority {
+        props.push(format!("PRIORITY:{}", caldav_priority(urgency, importance)));
+        props.push(format!("X-EQ-PRIORITY:u{}i{}", urgency, importance));
+    }
+    if let Some(date) = todo.date {
+        props.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+    }
+    if let Some(due) = todo.due {
+        props.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+    }
+    match todo.status {
+        TaskStatus::Pending => props.push(String::from("STATUS:NEEDS-ACTION")),
+        TaskStatus::Completed => {
+            props.push(String::from("STATUS:COMPLETED"));
+            props.push(String::from("PERCENT-COMPLETE:100"));
+            if let Some(at) = todo.completed_at {
+                props.push(format!("COMPLETED:{}", stamp(at)));
+            }
+        }
+        TaskStatus::Dropped => props.push(String::from("STATUS:CANCELLED")),
+    }
+
+    let lines: Vec<String> = match original {
+        Some(ics) => {
+            let mut lines = Vec::new();
+            // Depth inside the VTODO, so an alarm's own SUMMARY is kept
+            let mut depth: Option<usize> = None;
+            for line in unfold(ics) {
+                match (depth, line.as_str()) {
+                    (None, "BEGIN:VTODO") => depth = Some(0),
+                    (Some(0), "END:VTODO") => {
+                        lines.append(&mut props);
+                        depth = None;
+                    }
+                    (Some(d), l) if l.starts_with("BEGIN:") => depth = Some(d + 1),
+                    (Some(d), l) if l.starts_with("END:") => depth = Some(d.saturating_sub(1)),
+                    (Some(0), l) if OWNED.contains(&property_name(l).as_str()) => continue,
+                    _ => {}
+                }
+                lines.push(line);
+            }
+            lines
+        }
+        None => {
+            let mut lines: Vec<String> = [
+                "BEGIN:VCALENDAR",
+                "VERSION:2.0",
+                "PRODID:-//eq//eq//EN",
+                "BEGIN:VTODO",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+            lines.push(format!("UID:{}", uid));
+            lines.append(&mut props);
+            lines.push(String::from("END:VTODO"));
+            lines.push(String::from("END:VCALENDAR"));
+            lines
+        }
+    };
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+/// Counts from one run of `sync`
+#[derive(Debug, Default)]
+pub struct Summary {
+    /// Tasks added or changed here from the server
+    pub pulled: usize,
+    /// Tasks written to the server
+    pub pushed: usize,
+    /// Tasks left for the next sync because the server's copy kept
+    /// changing while they were written
+    pub conflicts: usize,
+}
+
+/// What the server had for a task after the last sync
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Synced {
+    href: String,
+    etag: String,
+    uid: String,
+    todo: Todo,
+}
+
+/// `caldav.json`: the server's copy of each synced task by task ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    items: HashMap<Uuid, Synced>,
+}
+
+impl SyncState {
+    fn load() -> Self {
+        caldav_state_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = caldav_state_path()?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+
+    fn id_at(&self, href: &str) -> Option<Uuid> {
+        self.items
+            .iter()
+            .find(|(_, synced)| synced.href == href)
+            .map(|(id, _)| *id)
+    }
+}
+
+/// A VTODO resource on the server
+struct Remote {
+    href: String,
+    etag: String,
+    ics: String,
+}
+
+/// Outcome of a conditional write
+enum Put {
+    Stored(String),
+    Conflict,
+}
+
+struct Client {
+    http: reqwest::Client,
+    collection: Url,
+    username: String,
+    password: Option<String>,
+}
+
+impl Client {
+    fn new(account: &CalDavAccount) -> Result<Self, Box<dyn Error>> {
+        let mut url = account.url.clone();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        Ok(Self {
+            http: reqwest::Client::new(),
+            collection: Url::parse(&url)?,
+            username: account.username.clone(),
+            password: account
+                .password
+                .clone()
+                .or_else(|| std::env::var("EQ_CALDAV_PASSWORD").ok()),
+        })
+    }
+
+    fn request(
+        &self,
+        method: Method,
+        href: &str,
+    ) -> Result<reqwest::RequestBuilder, Box<dyn Error>> {
+        let url = self.collection.join(href)?;
+        Ok(self
+            .http
+            .request(method, url)
+            .basic_auth(&self.username, self.password.as_ref()))
+    }
+
+    /// Every VTODO in the collection
+    async fn fetch(&self) -> Result<Vec<Remote>, Box<dyn Error>> {
+        let report = Method::from_bytes(b"REPORT")?;
+        let body = self
+            .request(report, "")?
+            .header("Depth", "1")
+            .header(CONTENT_TYPE, "application/xml; charset=utf-8")
+            .body(QUERY)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(multistatus(&body))
+    }
+
+    async fn get(&self, href: &str) -> Result<Option<Remote>, Box<dyn Error>> {
+        let response = self.request(Method::GET, href)?.send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+        let etag = header_etag(&response).unwrap_or_default();
+        Ok(Some(Remote {
+            href: href.to_string(),
+            etag,
+            ics: response.text().await?,
+        }))
+    }
+
+    /// Write `ics` at `href`, only over version `etag`, or only where
+    /// nothing is yet when `etag` is `None`
+    async fn put(
+        &self,
+        href: &str,
+        ics: String,
+        etag: Option<&str>,
+    ) -> Result<Put, Box<dyn Error>> {
+        let request = self
+            .request(Method::PUT, href)?
+            .header(CONTENT_TYPE, "text/calendar; charset=utf-8");
+        let request = match etag {
+            Some(etag) => request.header(IF_MATCH, etag),
+            None => request.header(IF_NONE_MATCH, "*"),
+        };
+        let response = request.body(ics).send().await?;
+        if response.status() == StatusCode::PRECONDITION_FAILED {
+            return Ok(Put::Conflict);
+        }
+        let response = response.error_for_status()?;
+        match header_etag(&response) {
+            Some(etag) => Ok(Put::Stored(etag)),
+            // Servers that rewrite what they store don't say which version
+            // they kept; ask for it
+            None => match self.get(href).await? {
+                Some(remote) => Ok(Put::Stored(remote.etag)),
+                None => Err(format!("{} vanished after it was written", href).into()),
+            },
+        }
+    }
+
+    async fn delete(&self, href: &str, etag: &str) -> Result<(), Box<dyn Error>> {
+        let response = self
+            .request(Method::DELETE, href)?
+            .header(IF_MATCH, etag)
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::NOT_FOUND | StatusCode::PRECONDITION_FAILED => Ok(()),
+            _ => {
+                response.error_for_status()?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Sync `store` both ways with the account's task list. Tasks changed on
+/// one side take that side's version; a task changed on both keeps the
+/// finished version if either side completed or dropped it, and this
+/// machine's otherwise. The caller saves the store
+pub fn sync(store: &mut TaskStore, account: &CalDavAccount) -> Result<Summary, Box<dyn Error>> {
+    let client = Client::new(account)?;
+    let mut state = SyncState::load();
+    let mut summary = Summary::default();
+    let result = runtime()?.block_on(sync_with(store, &client, &mut state, &mut summary));
+    state.save()?;
+    result.map(|_| summary)
+}
+
+async fn sync_with(
+    store: &mut TaskStore,
+    client: &Client,
+    state: &mut SyncState,
+    summary: &mut Summary,
+) -> Result<(), Box<dyn Error>> {
+    let today = store.today();
+    let mut seen = HashSet::new();
+
+    for remote in client.fetch().await? {
+        let Some(theirs) = Todo::parse(&remote.ics) else {
+            continue;
+        };
+        let uid = todo_properties(&remote.ics)
+            .and_then(|props| props.get("UID").cloned())
+            .unwrap_or_else(|| remote.href.clone());
+        match state.id_at(&remote.href) {
+            // Deleted here since the last sync
+            Some(id) if !store.tasks.iter().any(|t| t.id == id) => {
+                client.delete(&remote.href, &remote.etag).await?;
+                state.items.remove(&id);
+                summary.pushed += 1;
+            }
+            Some(id) => {
+                seen.insert(id);
+                sync_task(store, client, state, summary, id, uid, remote).await?;
+            }
+            None => {
+                let task = theirs.new_task(&uid, today);
+                let id = task.id;
+                let known = store.tasks.iter().any(|t| t.id == id);
+                // Work finished elsewhere before eq knew of it isn't imported
+                if !known && theirs.status != TaskStatus::Pending {
+                    continue;
+                }
+                seen.insert(id);
+                if !known {
+                    store.add_task(task);
+                    summary.pulled += 1;
+                }
+                sync_task(store, client, state, summary, id, uid, remote).await?;
+            }
+        }
+    }
+
+    // Synced before but gone from the server: deleted there
+    let gone: Vec<Uuid> = state
+        .items
+        .keys()
+        .filter(|id| !seen.contains(id))
+        .copied()
+        .collect();
+    for id in gone {
+        let Some(synced) = state.items.remove(&id) else {
+            continue;
+        };
+        let unchanged =
+            store.tasks.iter().find(|t| t.id == id).is_some_and(|t| {
+                t.status == TaskStatus::Pending && Todo::from_task(t) == synced.todo
+            });
+        if unchanged {
+            store.drop_task_with_reason(id, Some(String::from("deleted on the CalDAV server")));
+            summary.pulled += 1;
+        }
+    }
+
+    // Pending tasks the server hasn't seen yet, including ones changed here
+    // after being deleted there
+    let new: Vec<Task> = store
+        .tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending && !state.items.contains_key(&t.id))
+        .cloned()
+        .collect();
+    for task in new {
+        let (uid, todo) = (task.id.to_string(), Todo::from_task(&task));
+        let href = format!("{}.ics", uid);
+        match client.put(&href, render(&todo, &uid, None), None).await? {
+            Put::Stored(etag) => {
+                let href = client.collection.join(&href)?.path().to_string();
+                state.items.insert(
+                    task.id,
+                    Synced {
+                        href,
+                        etag,
+                        uid,
+                        todo,
+                    },
+                );
+                summary.pushed += 1;
+            }
+            Put::Conflict => summary.conflicts += 1,
+        }
+    }
+    Ok(())
+}
+
+/// Merge one task that exists on both sides and write the result back to
+/// the server, fetching it again when it changed in the meantime
+async fn sync_task(
+    store: &mut TaskStore,
+    client: &Client,
+    state: &mut SyncState,
+    summary: &mut Summary,
+    id: Uuid,
+    uid: String,
+    mut remote: Remote,
+) -> Result<(), Box<dyn Error>> {
+    for _ in 0..MAX_ATTEMPTS {
+        let Some(theirs) = Todo::parse(&remote.ics) else {
+            return Ok(());
+        };
+        let Some(task) = store.tasks.iter().find(|t| t.id == id) else {
+            return Ok(());
+        };
+        let ours = Todo::from_task(task);
+        let base = state.items.get(&id).map(|synced| &synced.todo);
+        let merged = merge(base, &ours, &theirs);
+        if merged != ours {
+            apply(store, id, &merged);
+            summary.pulled += 1;
+        }
+
+        let etag = if merged == theirs {
+            remote.etag.clone()
+        } else {
+            let ics = render(&merged, &uid, Some(&remote.ics));
+            match client.put(&remote.href, ics, Some(&remote.etag)).await? {
+                Put::Stored(etag) => {
+                    summary.pushed += 1;
+                    etag
+                }
+                Put::Conflict => match client.get(&remote.href).await? {
+                    Some(fresh) => {
+                        remote = fresh;
+                        continue;
+                    }
+                    None => return Ok(()),
+                },
+            }
+        };
+        let synced = Synced {
+            href: remote.href,
+            etag,
+            uid,
+            todo: merged,
+        };
+        state.items.insert(id, synced);
+        return Ok(());
+    }
+    summary.conflicts += 1;
+    Ok(())
+}
+
+/// Bring the local task in line with `todo`, through the store so the
+/// changes are logged and can be undone
+fn apply(store: &mut TaskStore, id: Uuid, todo: &Todo) {
+    let Some(task) = store.tasks.iter().find(|t| t.id == id).cloned() else {
+        return;
+    };
+    let (urgency, importance) = todo.priority.unwrap_or((task.urgency, task.importance));
+    if todo.title != task.title || (urgency, importance) != (task.urgency, task.importance) {
+        store.update_task(id, todo.title.clone(), urgency, importance);
+    }
+    if let Some(date) = todo.date.filter(|&date| date != task.date) {
+        store.move_task_to_date(id, date);
+    }
+    match (task.status, todo.status) {
+        (from, to) if from == to => {}
+        (_, TaskStatus::Completed) => {
+            store.complete_task(id);
+        }
+        (_, TaskStatus::Dropped) => {
+            store.drop_task(id);
+        }
+        (TaskStatus::Completed, TaskStatus::Pending) => {
+            store.toggle_complete_task(id);
+        }
+        (_, TaskStatus::Pending) => {
+            if let Some(task) = store.tasks.iter_mut().find(|t| t.id == id) {
+                task.status = TaskStatus::Pending;
+                task.drop_reason = None;
+            }
+        }
+    }
+    if let Some(task) = store.tasks.iter_mut().find(|t| t.id == id) {
+        task.due = todo.due;
+        if todo.completed_at.is_some() {
+            task.completed_at = todo.completed_at;
+        }
+    }
+}
+
+/// Resources in a WebDAV multistatus reply that carry calendar data
+fn multistatus(xml: &str) -> Vec<Remote> {
+    let Ok(response) = Regex::new(r"(?s)<(?:[\w-]+:)?response\b.*?</(?:[\w-]+:)?response>") else {
+        return Vec::new();
+    };
+    response
+        .find_iter(xml)
+        .filter_map(|part| {
+            let part = part.as_str();
+            Some(Remote {
+                href: element(part, "href")?,
+                etag: element(part, "getetag")?,
+                ics: element(part, "calendar-data")?,
+            })
+        })
+        .filter(|remote| !remote.ics.is_empty())
+        .collect()
+}
+
+/// Text of the first `name` element, whatever its namespace prefix
+fn element(xml: &str, name: &str) -> Option<String> {
+    let pattern = format!(
+        r"(?s)<(?:[\w-]+:)?{0}\b[^>]*>(.*?)</(?:[\w-]+:)?{0}>",
+        regex::escape(name)
+    );
+    let text = Regex::new(&pattern)
+        .ok()?
+        .captures(xml)?
+        .get(1)?
+        .as_str()
+        .trim();
+    if let Some(cdata) = text
+        .strip_prefix("<![CDATA[")
+        .and_then(|t| t.strip_suffix("]]>"))
+    {
+        return Some(cdata.to_string());
+    }
+    Some(
+        text.replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&#13;", "\r")
+            .replace("&#xD;", "\r")
+            .replace("&#10;", "\n")
+            .replace("&amp;", "&"),
+    )
+}
+
+fn header_etag(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(ETAG)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+/// Properties of the first VTODO, by name, without those of its alarms
+fn todo_properties(ics: &str) -> Option<HashMap<String, String>> {
+    let mut props = HashMap::new();
+    let mut depth: Option<usize> = None;
+    for line in unfold(ics) {
+        match (depth, line.as_str()) {
+            (None, "BEGIN:VTODO") => depth = Some(0),
+            (Some(0), "END:VTODO") => return Some(props),
+            (Some(d), l) if l.starts_with("BEGIN:") => depth = Some(d + 1),
+            (Some(d), l) if l.starts_with("END:") => depth = Some(d.saturating_sub(1)),
+            (Some(0), l) => {
+                if let Some((_, value)) = split_property(l) {
+                    props.entry(property_name(l)).or_insert(value.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Logical lines, with folded continuations joined back on
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        let line = line.trim_end_matches('\r');
+        if let (Some(rest), Some(last)) = (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            last.push_str(rest);
+        } else if !line.is_empty() {
+            lines.push(line.to_string());
+        }
+    }
+    lines
+}
+
+/// A content line broken into lines of at most 75 bytes
+fn fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+/// `NAME;PARAM=…:value` split before the value; colons in quoted
+/// parameters don't count
+fn split_property(line: &str) -> Option<(&str, &str)> {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ':' if !quoted => return Some((&line[..i], &line[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn property_name(line: &str) -> String {
+    line.split([';', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_uppercase()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn unescape(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => {}
+        }
+    }
+    out
+}
+
+/// A date, or the date of a date-time; UTC times count in the local day
+fn parse_day(value: &str) -> Option<NaiveDate> {
+    if let Some(moment) = parse_timestamp(value) {
+        return Some(clock::date_of(moment));
+    }
+    NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    NaiveDateTime::parse_from_str(value.trim(), "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|moment| moment.and_utc())
+}
+
+fn stamp(moment: DateTime<Utc>) -> String {
+    moment.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo() -> Todo {
+        Todo {
+            title: String::from("Call the bank; ask about fees"),
+            status: TaskStatus::Pending,
+            completed_at: None,
+            priority: Some((2, 3)),
+            date: NaiveDate::from_ymd_opt(2024, 6, 12),
+            due: NaiveDate::from_ymd_opt(2024, 6, 14),
+        }
+    }
+
+    #[test]
+    fn test_render_and_parse_round_trip() {
+        let ics = render(&todo(), "abc", None);
+        assert!(ics.contains("SUMMARY:Call the bank\\; ask about fees\r\n"));
+        assert!(ics.contains("PRIORITY:1\r\n"));
+        assert_eq!(Todo::parse(&ics), Some(todo()));
+
+        let mut done = todo();
+        done.status = TaskStatus::Completed;
+        done.completed_at = parse_timestamp("20240612T093000Z");
+        assert_eq!(Todo::parse(&render(&done, "abc", None)), Some(done));
+    }
+
+    #[test]
+    fn test_priority_changed_on_a_phone_wins() {
+        let ics = render(&todo(), "abc", None).replace("PRIORITY:1\r\n", "PRIORITY:9\r\n");
+        assert_eq!(Todo::parse(&ics).unwrap().priority, Some((1, 1)));
+        assert_eq!(from_caldav_priority(0), None);
+        assert_eq!(caldav_priority(1, 3), 5);
+    }
+
+    #[test]
+    fn test_render_keeps_foreign_properties() {
+        let phone = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VTODO\r\nUID:phone-1\r\n\
+                     SUMMARY:Old\r\nPRIORITY:5\r\nBEGIN:VALARM\r\nSUMMARY:Alarm\r\n\
+                     TRIGGER:-PT15M\r\nEND:VALARM\r\nEND:VTODO\r\nEND:VCALENDAR\r\n";
+        let ics = render(&todo(), "ignored", Some(phone));
+        assert!(ics.contains("UID:phone-1\r\n"));
+        assert!(!ics.contains("SUMMARY:Old"));
+        assert!(ics.contains("SUMMARY:Alarm\r\n"));
+        assert_eq!(ics.matches("PRIORITY:").count(), 2);
+        assert_eq!(Todo::parse(&ics), Some(todo()));
+    }
+
+    #[test]
+    fn test_merge_prefers_the_changed_side() {
+        let base = todo();
+        let mut ours = todo();
+        ours.title = String::from("Call the bank");
+        let mut theirs = todo();
+        theirs.status = TaskStatus::Completed;
+
+        assert_eq!(merge(Some(&base), &ours, &base), ours);
+        assert_eq!(merge(Some(&base), &base, &theirs), theirs);
+        assert_eq!(merge(Some(&base), &ours, &theirs), theirs);
+        assert_eq!(merge(None, &ours, &base), ours);
+    }
+
+    #[test]
+    fn test_multistatus_reads_escaped_calendar_data() {
+        let xml = r#"<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav">
+            <d:response><d:href>/dav/tasks/a.ics</d:href><d:propstat><d:prop>
+            <d:getetag>"1"</d:getetag>
+            <cal:calendar-data>BEGIN:VTODO&#13;
+SUMMARY:Tom &amp; Jerry&#13;
+END:VTODO</cal:calendar-data></d:prop></d:propstat></d:response>
+            <d:response><d:href>/dav/tasks/</d:href><d:propstat><d:prop>
+            <d:getetag/></d:prop></d:propstat></d:response>
+        </d:multistatus>"#;
+        let remotes = multistatus(xml);
+        assert_eq!(remotes.len(), 1);
+        assert_eq!(remotes[0].href, "/dav/tasks/a.ics");
+        assert_eq!(remotes[0].etag, "\"1\"");
+        assert_eq!(Todo::parse(&remotes[0].ics).unwrap().title, "Tom & Jerry");
+    }
+
+    #[test]
+    fn test_fold_and_unfold() {
+        let line = format!("SUMMARY:{}", "é".repeat(60));
+        let folded = fold(&line);
+        assert!(folded.split("\r\n").all(|l| l.len() <= 75));
+        assert_eq!(unfold(&folded), vec![line]);
+    }
+}
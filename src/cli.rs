@@ -1,9 +1,24 @@
+use crate::stats::VELOCITY_WEEKS;
+use chrono::NaiveDate;
 use clap::{Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(name = "eq")]
 #[command(about = "Eisenhower Quadrants - A terminal-based task manager", long_about = None)]
 pub struct Cli {
+    /// Time zone for dates, e.g. Asia/Tokyo (overrides `timezone` in the config)
+    #[arg(long, global = true, value_name = "ZONE")]
+    pub tz: Option<String>,
+
+    /// Pretend it is this moment, e.g. "2024-06-13 09:00"
+    #[arg(long, global = true, hide = true, value_name = "TIME")]
+    pub now: Option<String>,
+
+    /// Report load, save, and TUI frame times on stderr
+    #[arg(long, global = true)]
+    pub timings: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -12,37 +27,55 @@ pub struct Cli {
 pub enum Commands {
     /// Add a new task
     Add {
-        /// Task title and priority notation (e.g., "Buy milk !!$$")
+        /// Task title, priority, and estimate notation (e.g., "Buy milk !!$$ ~15m")
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
 
         /// Schedule for tomorrow
         #[arg(long, short)]
         tomorrow: bool,
+
+        /// Ask the AI for a priority when none is given
+        #[arg(long)]
+        suggest: bool,
+
+        /// Add the tasks spoken in a voice memo (e.g., memo.m4a)
+        #[arg(long, value_name = "FILE")]
+        audio: Option<PathBuf>,
     },
 
     /// Mark a task as done
     Done {
-        /// Task ID or index
-        id: String,
+        /// Task index, ID prefix, or title; pick from today's tasks if omitted
+        id: Option<String>,
     },
 
     /// Drop (delete) a task
     Drop {
-        /// Task ID or index
-        id: String,
+        /// Task index, ID prefix, or title; pick from today's tasks if omitted
+        id: Option<String>,
+
+        /// Why the task is being dropped (shown in the journal)
+        #[arg(long, short)]
+        reason: Option<String>,
     },
 
     /// Edit a task's priority
     Edit {
-        /// Task ID or index
-        id: String,
+        /// Task index, ID prefix, or title; pick from today's tasks if omitted
+        id: Option<String>,
 
         /// New priority notation (e.g., u3i2)
         #[arg(trailing_var_arg = true)]
         args: Vec<String>,
     },
 
+    /// Show how a task's score and quadrant were worked out
+    Explain {
+        /// Task index, ID prefix, or title; pick from today's tasks if omitted
+        id: Option<String>,
+    },
+
     /// Show today's matrix (default)
     Today,
 
@@ -55,9 +88,270 @@ pub enum Commands {
     /// Show weekly overview
     Week,
 
+    /// Compare what was planned on a past day's morning with what got done
+    Day {
+        /// The day (e.g., 2024-06-03, yesterday, -3)
+        date: String,
+    },
+
     /// Launch interactive TUI
     Tui,
 
+    /// Have the assistant challenge today's DELEGATE and DROP tasks
+    Audit,
+
+    /// Print a quote from the local bank, no API key needed
+    Quote,
+
+    /// Ask the assistant for a short prioritized plan of today's work
+    Brief {
+        /// Apply the suggested re-prioritizations ([EDIT], [MOVE], [DROP])
+        #[arg(long)]
+        apply: bool,
+    },
+
+    /// Plan a day from carry-overs, overdue and upcoming tasks
+    Plan {
+        /// Goal for the assistant to break into tasks, added after confirmation
+        goal: Option<String>,
+
+        /// Plan tomorrow instead of today
+        #[arg(long, short)]
+        tomorrow: bool,
+
+        /// Candidate numbers to move onto the planned day
+        #[arg(long, num_args = 1..)]
+        pull: Vec<usize>,
+
+        /// Ask the assistant which candidates to take on
+        #[arg(long)]
+        ai: bool,
+    },
+
+    /// Run a pomodoro countdown without the full TUI
+    Focus {
+        /// Task index, ID prefix, or title (defaults to the top DO FIRST task today)
+        id: Option<String>,
+
+        /// Session length in minutes
+        #[arg(long, short, default_value_t = 25, value_parser = clap::value_parser!(u64).range(1..))]
+        minutes: u64,
+
+        /// Send a desktop notification when the session ends
+        #[arg(long)]
+        notify: bool,
+    },
+
     /// Show productivity statistics
-    Stats,
+    Stats {
+        /// Show pomodoro focus session statistics instead
+        #[arg(long)]
+        focus: bool,
+    },
+
+    /// Show planned vs completed, dropped, and carried-over tasks per day
+    Burndown {
+        /// Report on last week instead of the current one
+        #[arg(long)]
+        last: bool,
+    },
+
+    /// Review the week: outcomes per quadrant, then what to drop or delegate
+    Review {
+        /// Run the weekly review (the daily review lives in the TUI)
+        #[arg(long)]
+        week: bool,
+
+        /// Review last week instead of the current one
+        #[arg(long)]
+        last: bool,
+
+        /// Output folder for the review note
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Add a note to the review (repeatable)
+        #[arg(long = "note")]
+        notes: Vec<String>,
+
+        /// Print to stdout instead of writing a file
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Ask the assistant for patterns in the week and one improvement
+    Retro {
+        /// Look back on last week instead of the current one
+        #[arg(long)]
+        last: bool,
+
+        /// Output folder for the retrospective note
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Print to stdout instead of writing a file
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Write a Markdown journal of a day's work
+    Journal {
+        /// Day to summarise (YYYY-MM-DD, defaults to today)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+
+        /// Output folder, e.g. an Obsidian daily-notes folder
+        #[arg(long)]
+        dir: Option<PathBuf>,
+
+        /// Add a note to the journal (repeatable)
+        #[arg(long = "note")]
+        notes: Vec<String>,
+
+        /// Print to stdout instead of writing a file
+        #[arg(long)]
+        print: bool,
+    },
+
+    /// Show average completed work per weekday
+    Velocity {
+        /// Number of past weeks to average over
+        #[arg(long, default_value_t = VELOCITY_WEEKS)]
+        weeks: usize,
+    },
+
+    /// List pending tasks that keep being put off, to drop or reschedule
+    Stale {
+        /// Days a task must have been carried forward to be listed
+        #[arg(long, default_value_t = 14)]
+        days: i64,
+
+        /// Drop every listed task
+        #[arg(long)]
+        drop: bool,
+
+        /// Move every listed task to a date (e.g., monday, +7, 2024-07-01)
+        #[arg(long, value_name = "DATE", conflicts_with = "drop")]
+        reschedule: Option<String>,
+    },
+
+    /// Remind of tasks due today or overdue with a desktop notification
+    Notify {
+        /// Keep running: remind every morning and announce finished pomodoros
+        #[arg(long)]
+        daemon: bool,
+
+        /// Print a systemd user service that runs the daemon
+        #[arg(long, conflicts_with = "daemon")]
+        systemd: bool,
+
+        /// Post today's matrix to the configured Slack and Telegram sinks
+        #[arg(long, conflicts_with_all = ["daemon", "systemd"])]
+        summary: bool,
+    },
+
+    /// Commit the data directory to git and pull and push its remote
+    Sync {
+        /// Set the remote to sync with, e.g. git@github.com:me/eq-data.git
+        #[arg(long, value_name = "URL")]
+        remote: Option<String>,
+    },
+
+    /// Import tasks from another task manager
+    Import {
+        /// Where to import from
+        #[arg(long, value_name = "SOURCE", value_parser = ["todoist"])]
+        from: String,
+
+        /// Keep running and pull again every this many minutes
+        #[arg(long, value_name = "MINUTES", value_parser = clap::value_parser!(u64).range(1..))]
+        every: Option<u64>,
+    },
+
+    /// Push tasks to another service for others to see
+    Export {
+        /// Where to export to
+        #[arg(long, value_name = "TARGET", value_parser = ["notion"])]
+        to: String,
+
+        /// ID of the Notion database that gets a page per task
+        #[arg(long, value_name = "ID")]
+        database: String,
+    },
+
+    /// Work with GitHub issues as tasks
+    Gh {
+        #[command(subcommand)]
+        command: GhCommand,
+    },
+
+    /// Work with Jira issues as tasks
+    Jira {
+        #[command(subcommand)]
+        command: JiraCommand,
+    },
+
+    /// Complete tasks from git commit messages
+    Hook {
+        #[command(subcommand)]
+        command: HookCommand,
+    },
+
+    /// Sync tasks both ways with the CalDAV task list set in the config
+    Caldav,
+
+    /// Print today's progress for a status bar
+    Status {
+        /// `text` for one plain line (polybar), `waybar` for Waybar's JSON
+        #[arg(long, value_parser = ["text", "waybar"], default_value = "text")]
+        format: String,
+    },
+
+    /// Serve task CRUD, queries, and stats as JSON-RPC for editors and other frontends
+    Serve {
+        /// Read requests from stdin and write replies to stdout, one per line
+        #[arg(long)]
+        stdio: bool,
+
+        /// Speak the Model Context Protocol over stdio instead, for MCP clients
+        #[arg(long)]
+        mcp: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GhCommand {
+    /// Add a task for each open issue in a repository
+    Import {
+        /// Repository as owner/name
+        repo: String,
+
+        /// Only issues assigned to this user; `me` for the token's owner
+        #[arg(long)]
+        assignee: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum HookCommand {
+    /// Add a post-commit hook to this repository: a commit saying
+    /// `eq:done ab3f` or `closes eq#ab3f` completes that task
+    Install,
+
+    /// Complete the tasks named in the last commit; run by the hook
+    #[command(hide = true)]
+    PostCommit,
+}
+
+#[derive(Subcommand)]
+pub enum JiraCommand {
+    /// Add a task for each issue a JQL search finds
+    Pull {
+        /// The search, e.g. "assignee = currentUser() AND sprint in openSprints()"
+        #[arg(
+            long,
+            default_value = "assignee = currentUser() AND statusCategory != Done"
+        )]
+        jql: String,
+    },
 }
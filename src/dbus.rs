@@ -0,0 +1,174 @@
+use crate::models::log::LogEvent;
+use crate::serve::{self, Call, RpcResult};
+use crate::storage::paths::history_log_path;
+use serde_json::{json, Value};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
+use zbus::blocking::{connection, Connection};
+use zbus::{fdo, interface, SignalContext};
+
+/// Well-known name on the session bus, and the interface's name
+pub const BUS_NAME: &str = "dev.quadtasks.eq";
+const OBJECT_PATH: &str = "/dev/quadtasks/eq";
+
+/// How often the history log is checked for events to signal
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where calls from the bus are carried out
+pub enum Target {
+    /// `tasks.json` itself, under the store lock, as `eq serve` does
+    Disk,
+    /// A running TUI, which applies them to its copy of the store
+    Channel(UnboundedSender<Call>),
+}
+
+struct Service {
+    target: Target,
+}
+
+impl Service {
+    /// Run `method` and return the ID of the task it touched
+    fn dispatch(&self, method: &'static str, params: Value) -> fdo::Result<String> {
+        let result: RpcResult = match &self.target {
+            Target::Disk => serve::run(method, &params),
+            Target::Channel(sender) => {
+                let (reply, answer) = mpsc::channel();
+                let gone = || fdo::Error::Failed(String::from("eq is shutting down"));
+                sender
+                    .send(Call {
+                        method,
+                        params,
+                        reply,
+                    })
+                    .map_err(|_| gone())?;
+                answer.recv().map_err(|_| gone())?
+            }
+        };
+        match result {
+            Ok(task) => Ok(task["id"].as_str().unwrap_or_default().to_string()),
+            Err(err) => Err(fdo::Error::Failed(err.message)),
+        }
+    }
+}
+
+#[interface(name = "dev.quadtasks.eq")]
+impl Service {
+    /// Add a task from a line such as `Email Bob u3i2 ~15m @tomorrow`;
+    /// returns its ID
+    fn add_task(&self, line: String) -> fdo::Result<String> {
+        self.dispatch("tasks.add", json!({ "text": line }))
+    }
+
+    /// Complete the task an ID prefix, index among today's pending tasks,
+    /// or part of a title names; returns its ID
+    fn complete_task(&self, identifier: String) -> fdo::Result<String> {
+        self.dispatch("tasks.complete", json!({ "id": identifier }))
+    }
+
+    /// A task was created, completed, dropped, updated, or moved, by this
+    /// or any other eq
+    #[zbus(signal)]
+    async fn task_event(
+        ctxt: &SignalContext<'_>,
+        action: &str,
+        task_id: &str,
+        details: &str,
+    ) -> zbus::Result<()>;
+}
+
+/// Serve `dev.quadtasks.eq` on the session bus until the connection is
+/// dropped. `None` when there is no session bus or another eq has the name
+pub fn start(target: Target) -> Option<Connection> {
+    let connection = connection::Builder::session()
+        .ok()?
+        .name(BUS_NAME)
+        .ok()?
+        .serve_at(OBJECT_PATH, Service { target })
+        .ok()?
+        .build()
+        .ok()?;
+    let emitter = connection.clone();
+    thread::spawn(move || forward_events(&emitter));
+    Some(connection)
+}
+
+/// Signal each event appended to the history log from now on, whichever
+/// process wrote it
+fn forward_events(connection: &Connection) {
+    let Ok(path) = history_log_path() else {
+        return;
+    };
+    let mut offset = fs::metadata(&path).map_or(0, |m| m.len());
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let len = fs::metadata(&path).map_or(0, |m| m.len());
+        if len < offset {
+            // Replaced, as `eq sync` does when merging
+            offset = len;
+        }
+        if len == offset {
+            continue;
+        }
+        let mut appended = String::new();
+        let read = File::open(&path).and_then(|mut file| {
+            file.seek(SeekFrom::Start(offset))?;
+            file.take(len - offset).read_to_string(&mut appended)
+        });
+        if read.is_err() {
+            continue;
+        }
+        let (events, consumed) = complete_lines(&appended);
+        offset += consumed as u64;
+        for event in events {
+            let _ = emit(connection, &event);
+        }
+    }
+}
+
+/// Events in the whole lines of `appended`, and how many bytes those lines
+/// take; a line still being written is left for next time
+pub fn complete_lines(appended: &str) -> (Vec<LogEvent>, usize) {
+    let consumed = appended.rfind('\n').map_or(0, |end| end + 1);
+    let events = appended[..consumed]
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    (events, consumed)
+}
+
+fn emit(connection: &Connection, event: &LogEvent) -> zbus::Result<()> {
+    let service = connection
+        .object_server()
+        .interface::<_, Service>(OBJECT_PATH)?;
+    zbus::block_on(Service::task_event(
+        service.signal_context(),
+        &format!("{:?}", event.action),
+        &event.task_id.to_string(),
+        &event.details,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::log::EventAction;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_complete_lines_leaves_a_partial_line() {
+        let event = LogEvent::new(EventAction::Completed, Uuid::new_v4(), "Done".into());
+        let line = serde_json::to_string(&event).unwrap();
+        let appended = format!("{}\n{}", line, &line[..10]);
+        let (events, consumed) = complete_lines(&appended);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].task_id, event.task_id);
+        assert_eq!(consumed, line.len() + 1);
+        let (events, consumed) = complete_lines(&line[..10]);
+        assert!(events.is_empty());
+        assert_eq!(consumed, 0);
+    }
+}
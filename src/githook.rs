@@ -0,0 +1,174 @@
+use crate::models::store::{TaskLookup, TaskStore, MIN_ID_PREFIX};
+use regex::Regex;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+/// Marks the line eq adds to a hook, so installing twice is harmless
+const MARKER: &str = "# eq: complete tasks named in the commit message";
+
+/// Task ID prefixes a commit message names as done, in order: `eq:done ab3f`
+/// or `closes eq#ab3f` (also close, fixes, resolves, and their variants)
+pub fn referenced_ids(message: &str) -> Vec<String> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(
+            r"(?i)(?:\beq:done\s+|\b(?:close[sd]?|fix(?:e[sd])?|resolve[sd]?)\s+eq#)([0-9a-f-]+)\b",
+        )
+        .expect("valid regex")
+    });
+    let mut ids: Vec<String> = Vec::new();
+    for caps in pattern.captures_iter(message) {
+        let id = caps[1].to_lowercase();
+        if id.len() >= MIN_ID_PREFIX && !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Complete the tasks named in the last commit's message, recording its
+/// hash. Run by the hook from the repository's work tree; the caller holds
+/// the store lock
+pub fn complete_from_last_commit(store: &mut TaskStore) -> Result<(), Box<dyn Error>> {
+    let output = git(&["log", "-1", "--format=%H%n%B"])?;
+    let (hash, message) = output.split_once('\n').unwrap_or((&output, ""));
+    let mut completed: Vec<Uuid> = Vec::new();
+    for prefix in referenced_ids(message) {
+        let id = match store.find_by_id_prefix(&prefix) {
+            TaskLookup::Found(id) => id,
+            TaskLookup::NotFound => {
+                eprintln!("eq: no task with ID {}", prefix);
+                continue;
+            }
+            TaskLookup::Ambiguous(tasks) => {
+                eprintln!(
+                    "eq: {} tasks have IDs starting with {}; use more of the ID",
+                    tasks.len(),
+                    prefix
+                );
+                continue;
+            }
+        };
+        let title = store
+            .tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| t.title.clone());
+        if store.complete_task_in_commit(id, Some(hash)) {
+            println!("eq: completed \"{}\"", title.unwrap_or_default());
+            completed.push(id);
+        }
+    }
+    if !completed.is_empty() {
+        store.save()?;
+    }
+    Ok(())
+}
+
+/// Add a post-commit hook to the current repository that runs
+/// `eq hook post-commit`; an existing hook keeps its commands. Returns the
+/// hook's path
+pub fn install() -> Result<PathBuf, Box<dyn Error>> {
+    let dir = PathBuf::from(git(&["rev-parse", "--git-path", "hooks"])?.trim());
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("post-commit");
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    if existing.contains(MARKER) {
+        return Ok(path);
+    }
+    let exe = std::env::current_exe()?;
+    fs::write(&path, with_hook(&existing, &exe.display().to_string()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(path)
+}
+
+/// `existing` hook script with a call to `exe hook post-commit` added; it
+/// goes before a closing `exit`, which would otherwise skip it
+fn with_hook(existing: &str, exe: &str) -> String {
+    let call = format!("{}\n{} hook post-commit\n", MARKER, shell_quote(exe));
+    if existing.trim().is_empty() {
+        return format!("#!/bin/sh\n{}", call);
+    }
+    let mut lines: Vec<&str> = existing.trim_end().lines().collect();
+    let exit = lines.last().filter(|line| is_exit(line)).copied();
+    if exit.is_some() {
+        lines.pop();
+    }
+    let mut script = lines.join("\n");
+    script.push('\n');
+    script.push_str(&call);
+    if let Some(exit) = exit {
+        script.push_str(exit);
+        script.push('\n');
+    }
+    script
+}
+
+/// An `exit` or `exit <status>` line
+fn is_exit(line: &str) -> bool {
+    line.trim()
+        .strip_prefix("exit")
+        .is_some_and(|status| status.trim().chars().all(|c| c.is_ascii_digit()))
+}
+
+/// `text` as one single-quoted shell word
+fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+fn git(args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = Command::new("git").args(args).output()?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )
+        .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_referenced_ids() {
+        let message = "Fix login redirect\n\neq:done AB3F, closes eq#9c2e\nFixes eq#ab3f\nSee eq#7d1 and eq:done 12";
+        assert_eq!(referenced_ids(message), vec!["ab3f", "9c2e"]);
+        assert!(referenced_ids("Refactor parser").is_empty());
+    }
+
+    #[test]
+    fn test_with_hook_goes_before_exit() {
+        let existing = "#!/bin/sh\nnpm run lint\nexit 0\n";
+        assert_eq!(
+            with_hook(existing, "/opt/eq/bin/eq"),
+            format!(
+                "#!/bin/sh\nnpm run lint\n{}\n'/opt/eq/bin/eq' hook post-commit\nexit 0\n",
+                MARKER
+            )
+        );
+        assert_eq!(
+            with_hook("", "/usr/bin/eq"),
+            format!("#!/bin/sh\n{}\n'/usr/bin/eq' hook post-commit\n", MARKER)
+        );
+        // Only a closing exit moves the call up
+        assert!(with_hook("#!/bin/sh\nexit_code=1\n", "eq").ends_with("'eq' hook post-commit\n"));
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/home/o'brien/eq"), r"'/home/o'\''brien/eq'");
+        assert_eq!(shell_quote("/tmp/my eq/eq"), "'/tmp/my eq/eq'");
+    }
+}
@@ -0,0 +1,235 @@
+use crate::ai::runtime;
+use crate::config::GitHubSettings;
+use crate::models::store::TaskStore;
+use crate::parser::input::{parse_priority, TaskInput};
+use chrono::NaiveDate;
+use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::{Method, RequestBuilder};
+use serde_json::{json, Value};
+use std::error::Error;
+use uuid::Uuid;
+
+const API: &str = "https://api.github.com";
+
+/// Issues fetched per request; GitHub's maximum
+const PER_PAGE: usize = 100;
+
+/// Posted on the issue when `on_done` is `comment` and no comment is set
+const DEFAULT_COMMENT: &str = "Done.";
+
+/// An open issue as eq imports it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub title: String,
+    pub url: String,
+    /// From the first label that reads as a priority, such as `urgent`
+    pub priority: Option<(u8, u8)>,
+}
+
+impl Issue {
+    /// An issue from the API; `None` for pull requests, which the issues
+    /// endpoint lists too
+    pub fn from_json(issue: &Value) -> Option<Self> {
+        if issue.get("pull_request").is_some() {
+            return None;
+        }
+        let priority = issue["labels"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|label| label["name"].as_str())
+            .find_map(|name| parse_priority(&name.to_lowercase()));
+        Some(Self {
+            title: issue["title"].as_str()?.trim().to_string(),
+            url: issue["html_url"].as_str()?.to_string(),
+            priority,
+        })
+    }
+
+    /// The task ID for the issue, the same on every import
+    pub fn task_id(&self) -> Uuid {
+        Uuid::new_v5(&Uuid::NAMESPACE_URL, self.url.as_bytes())
+    }
+}
+
+/// Owner, repository, and number of a `github.com/owner/repo/issues/N` URL
+pub fn parse_issue_url(url: &str) -> Option<(String, String, u64)> {
+    let path = url
+        .strip_prefix("https://github.com/")
+        .or_else(|| url.strip_prefix("http://github.com/"))?;
+    match path.split('/').collect::<Vec<_>>().as_slice() {
+        [owner, repo, "issues", number] => {
+            Some((owner.to_string(), repo.to_string(), number.parse().ok()?))
+        }
+        _ => None,
+    }
+}
+
+fn token(settings: &GitHubSettings) -> Option<String> {
+    settings
+        .token
+        .clone()
+        .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+}
+
+fn request(method: Method, path: &str, token: Option<&str>) -> RequestBuilder {
+    let request = reqwest::Client::new()
+        .request(method, format!("{}{}", API, path))
+        .header(USER_AGENT, concat!("eq/", env!("CARGO_PKG_VERSION")))
+        .header(ACCEPT, "application/vnd.github+json");
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+/// Open issues in `repo` (`owner/name`), assigned to `assignee` when given;
+/// `me` is whoever the token belongs to
+pub fn fetch_issues(
+    settings: &GitHubSettings,
+    repo: &str,
+    assignee: Option<&str>,
+) -> Result<Vec<Issue>, Box<dyn Error>> {
+    if repo.split('/').count() != 2 {
+        return Err(format!("Expected owner/name, got '{}'", repo).into());
+    }
+    let token = token(settings);
+    let token = token.as_deref();
+    runtime()?.block_on(async {
+        let assignee = match assignee {
+            Some("me") => {
+                let token = token.ok_or("--assignee me needs a GitHub token")?;
+                let user: Value = request(Method::GET, "/user", Some(token))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+                user["login"].as_str().map(str::to_string)
+            }
+            other => other.map(str::to_string),
+        };
+
+        let mut issues = Vec::new();
+        for page in 1.. {
+            let mut query = vec![
+                ("state", String::from("open")),
+                ("per_page", PER_PAGE.to_string()),
+                ("page", page.to_string()),
+            ];
+            if let Some(assignee) = &assignee {
+                query.push(("assignee", assignee.clone()));
+            }
+            let listed: Vec<Value> =
+                request(Method::GET, &format!("/repos/{}/issues", repo), token)
+                    .query(&query)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json()
+                    .await?;
+            issues.extend(listed.iter().filter_map(Issue::from_json));
+            if listed.len() < PER_PAGE {
+                break;
+            }
+        }
+        Ok::<_, Box<dyn Error>>(issues)
+    })
+}
+
+/// Add a task for each issue not imported before; returns how many were added
+pub fn import(store: &mut TaskStore, issues: &[Issue], date: NaiveDate) -> usize {
+    let mut added = 0;
+    for issue in issues {
+        let id = issue.task_id();
+        if store.tasks.iter().any(|t| t.id == id) {
+            continue;
+        }
+        let input = TaskInput {
+            title: issue.title.clone(),
+            priority: issue.priority,
+            ..TaskInput::default()
+        };
+        let mut task = input.into_task(date);
+        task.id = id;
+        task.link = Some(issue.url.clone());
+        store.add_task(task);
+        added += 1;
+    }
+    added
+}
+
+/// Close or comment on the issue behind a completed task, as `on_done`
+/// says. Runs from the log listener, so it reads the task from disk
+pub fn on_done(settings: &GitHubSettings, task_id: Uuid) -> Result<(), Box<dyn Error>> {
+    let Some(action) = settings.on_done.as_deref() else {
+        return Ok(());
+    };
+    let store = TaskStore::load()?;
+    let Some((owner, repo, number)) = store
+        .tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .and_then(|t| t.link.as_deref())
+        .and_then(parse_issue_url)
+    else {
+        return Ok(());
+    };
+    let token = token(settings).ok_or("Updating GitHub issues needs a token")?;
+    let path = format!("/repos/{}/{}/issues/{}", owner, repo, number);
+    let call = match action {
+        "close" => request(Method::PATCH, &path, Some(&token))
+            .json(&json!({ "state": "closed", "state_reason": "completed" })),
+        "comment" => {
+            let body = settings.done_comment.as_deref().unwrap_or(DEFAULT_COMMENT);
+            request(Method::POST, &format!("{}/comments", path), Some(&token))
+                .json(&json!({ "body": body }))
+        }
+        other => return Err(format!("Unknown github.on_done '{}'", other).into()),
+    };
+    runtime()?.block_on(async {
+        call.send().await?.error_for_status()?;
+        Ok::<_, Box<dyn Error>>(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_from_json_skips_pull_requests() {
+        let issue = json!({
+            "title": "Crash on empty config ",
+            "html_url": "https://github.com/xiaolong-y/eq/issues/42",
+            "labels": [{ "name": "bug" }, { "name": "Urgent" }],
+        });
+        let parsed = Issue::from_json(&issue).unwrap();
+        assert_eq!(parsed.title, "Crash on empty config");
+        assert_eq!(parsed.priority, Some((3, 2)));
+        assert_eq!(
+            parsed.task_id(),
+            Issue::from_json(&issue).unwrap().task_id()
+        );
+
+        let pull = json!({
+            "title": "Fix crash",
+            "html_url": "https://github.com/xiaolong-y/eq/pull/43",
+            "pull_request": {},
+        });
+        assert_eq!(Issue::from_json(&pull), None);
+    }
+
+    #[test]
+    fn test_parse_issue_url() {
+        assert_eq!(
+            parse_issue_url("https://github.com/xiaolong-y/eq/issues/42"),
+            Some((String::from("xiaolong-y"), String::from("eq"), 42))
+        );
+        assert_eq!(
+            parse_issue_url("https://github.com/xiaolong-y/eq/pull/43"),
+            None
+        );
+        assert_eq!(parse_issue_url("https://example.com/a/b/issues/1"), None);
+    }
+}
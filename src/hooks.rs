@@ -0,0 +1,184 @@
+use crate::ai::runtime;
+use crate::clock;
+use crate::config::{Config, Hook};
+use crate::github;
+use crate::jira;
+use crate::models::log::{set_listener, EventAction, LogEvent};
+use crate::sinks;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// How long a webhook may take before it is abandoned
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Hooks started in the background and not yet waited for
+static RUNNING: Mutex<Vec<JoinHandle<()>>> = Mutex::new(Vec::new());
+
+/// Waits for running hooks when dropped, so a quick CLI command doesn't
+/// exit before its webhooks are sent
+pub struct HookRunner;
+
+impl Drop for HookRunner {
+    fn drop(&mut self) {
+        let running = std::mem::take(&mut *RUNNING.lock().unwrap_or_else(|e| e.into_inner()));
+        let Ok(runtime) = runtime() else {
+            return;
+        };
+        for handle in running {
+            let _ = runtime.block_on(handle);
+        }
+    }
+}
+
+/// Run the configured hooks on each event logged from now on, update the
+/// GitHub or Jira issue behind each completed task, and post completion
+/// milestones to the chat sinks. Hooks run in the background on the async
+/// runtime's blocking pool; their output and failures are ignored
+pub fn install(config: &Config) -> HookRunner {
+    let github = Some(config.github.clone()).filter(|github| github.on_done.is_some());
+    let jira = config.jira.clone();
+    let chats = config.sinks.clone();
+    if config.hooks.is_empty() && github.is_none() && jira.is_none() && chats.is_empty() {
+        return HookRunner;
+    }
+    let hooks = config.hooks.clone();
+    set_listener(move |event, tasks| {
+        let due: Vec<Hook> = hooks
+            .iter()
+            .filter(|hook| hook.fires_on(event.action))
+            .cloned()
+            .collect();
+        let (github, jira, chats) = if event.action == EventAction::Completed {
+            (github.clone(), jira.clone(), chats.clone())
+        } else {
+            (None, None, Vec::new())
+        };
+        if due.is_empty() && github.is_none() && jira.is_none() && chats.is_empty() {
+            return;
+        }
+        let Ok(runtime) = runtime() else {
+            return;
+        };
+        // Worked out here, from the store that logged the completion
+        let milestone = if chats.is_empty() {
+            None
+        } else {
+            sinks::milestone(tasks, event.task_id, clock::date_of(event.timestamp))
+        };
+        let payload = payload(event);
+        let task_id = event.task_id;
+        let handle = runtime.spawn_blocking(move || {
+            for hook in &due {
+                run(hook, &payload);
+            }
+            if let Some(github) = github {
+                let _ = github::on_done(&github, task_id);
+            }
+            if let Some(jira) = jira {
+                let _ = jira::on_done(&jira, task_id);
+            }
+            if let Some(text) = milestone {
+                let _ = sinks::post_all(&chats, &text);
+            }
+        });
+        let mut running = RUNNING.lock().unwrap_or_else(|e| e.into_inner());
+        running.retain(|handle| !handle.is_finished());
+        running.push(handle);
+    });
+    HookRunner
+}
+
+/// The event as hooks see it: the log line, plus `text` for chat webhooks
+/// such as Slack's
+pub fn payload(event: &LogEvent) -> Value {
+    let mut value = json!(event);
+    value["text"] = json!(event.details);
+    value
+}
+
+fn run(hook: &Hook, payload: &Value) {
+    if let Some(command) = &hook.command {
+        let _ = run_command(command, payload);
+    }
+    if let Some(url) = &hook.url {
+        let _ = post(url, payload);
+    }
+}
+
+/// Run `command` in the shell with `payload` on stdin and wait for it
+pub fn run_command(command: &str, payload: &Value) -> io::Result<()> {
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    };
+    let mut child = shell
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "{}", payload)?;
+    }
+    child.wait()?;
+    Ok(())
+}
+
+fn post(url: &str, payload: &Value) -> Result<(), Box<dyn Error>> {
+    runtime()?.block_on(async {
+        reqwest::Client::new()
+            .post(url)
+            .timeout(WEBHOOK_TIMEOUT)
+            .json(payload)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_payload_and_matching() {
+        let event = LogEvent::new(
+            EventAction::Completed,
+            Uuid::new_v4(),
+            "Completed task: Ship it".into(),
+        );
+        let payload = payload(&event);
+        assert_eq!(payload["action"], "Completed");
+        assert_eq!(payload["text"], "Completed task: Ship it");
+
+        let hook = Hook {
+            on: vec![EventAction::Completed, EventAction::Dropped],
+            ..Hook::default()
+        };
+        assert!(hook.fires_on(EventAction::Completed));
+        assert!(!hook.fires_on(EventAction::Moved));
+        assert!(Hook::default().fires_on(EventAction::Moved));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_command_passes_the_event_on_stdin() {
+        let out = std::env::temp_dir().join(format!("eq-hook-{}.json", std::process::id()));
+        let command = format!("cat > '{}'", out.display());
+        run_command(&command, &json!({ "action": "Created" })).unwrap();
+        let written = std::fs::read_to_string(&out).unwrap();
+        let _ = std::fs::remove_file(&out);
+        assert_eq!(written.trim(), r#"{"action":"Created"}"#);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::config::Config;
+use crate::models::task::Quadrant;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+/// Locale variables checked in order when `language` is not configured
+const LOCALE_VARS: &[&str] = &["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// Languages the interface is translated into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Chinese,
+}
+
+impl Language {
+    /// Language of a tag such as `zh`, `zh-CN`, or `zh_CN.UTF-8`; anything
+    /// without a translation is English
+    pub fn from_tag(tag: &str) -> Self {
+        let code = tag
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or("")
+            .to_lowercase();
+        match code.as_str() {
+            "zh" | "chinese" => Language::Chinese,
+            _ => Language::English,
+        }
+    }
+}
+
+/// One message of the interface, looked up by a stable ID so rewording the
+/// English doesn't lose its translations
+struct Message {
+    id: &'static str,
+    en: &'static str,
+    zh: &'static str,
+}
+
+impl Message {
+    fn text(&self, language: Language) -> &'static str {
+        match language {
+            Language::English => self.en,
+            Language::Chinese => self.zh,
+        }
+    }
+}
+
+const fn m(id: &'static str, en: &'static str, zh: &'static str) -> Message {
+    Message { id, en, zh }
+}
+
+/// Every translated message: ID, English, Simplified Chinese
+const MESSAGES: &[Message] = &[
+    m("quadrant.do_first", "DO FIRST", "立即做"),
+    m("quadrant.schedule", "SCHEDULE", "计划做"),
+    m("quadrant.delegate", "DELEGATE", "委派"),
+    m("quadrant.drop", "DROP", "舍弃"),
+    m(
+        "task.added",
+        "Added task: {} (U={}, I={}) -> {}",
+        "已添加任务：{}（紧急={}，重要={}）→ {}",
+    ),
+    m("task.done", "Marked task as done: {}", "已完成任务：{}"),
+    m("task.dropped", "Dropped task: {}", "已舍弃任务：{}"),
+    m("task.updated", "Updated task: {}", "已更新任务：{}"),
+    m("task.not_found", "Task not found: {}", "找不到任务：{}"),
+    m("task.none_pending", "No pending tasks.", "没有待办任务。"),
+    m("matrix.title", "Eisenhower Matrix for {}", "{} 的艾森豪威尔矩阵"),
+    m("pick.matches", "\"{}\" matches {} tasks:", "“{}”匹配到 {} 个任务："),
+    m(
+        "pick.which",
+        "Which one? [1-{}, Enter to cancel] ",
+        "选择哪一个？[1-{}，回车取消] ",
+    ),
+    m("pick.cancelled", "Cancelled.", "已取消。"),
+    m("pick.prompt", "Task:", "任务："),
+    m(
+        "pick.hint",
+        "{}/{}  ↑↓ move · Enter pick · Esc cancel",
+        "{}/{}  ↑↓ 移动 · 回车 选择 · Esc 取消",
+    ),
+    m("tui.title", "Xiaolong's Eisenhower Quadrants", "Xiaolong 的艾森豪威尔象限"),
+    m(
+        "tui.hint.compact",
+        "[a]dd [d]one [x]drop [←→]tab [:]cmd [?]help [q]uit",
+        "[a]添加 [d]完成 [x]舍弃 [←→]切换 [:]命令 [?]帮助 [q]退出",
+    ),
+    m(
+        "tui.hint",
+        "[a]dd  [d]one  [x]drop  [u]ndo  [↑↓]nav  [tab]quadrant  [L]ist  [:]cmd  [?]help  [q]uit",
+        "[a]添加  [d]完成  [x]舍弃  [u]撤销  [↑↓]移动  [tab]象限  [L]列表  [:]命令  [?]帮助  [q]退出",
+    ),
+    m(
+        "focus.header",
+        " FOCUS MODE: {}   [v] {}  [z] Zen Mode  [Esc] Exit ",
+        " 专注模式：{}   [v] {}  [z] 禅模式  [Esc] 退出 ",
+    ),
+    m("focus.all", "ALL TASKS - Ranked", "全部任务 - 按排名"),
+    m("focus.do_first", "DO NOW - Urgent & Important", "立即做 - 紧急且重要"),
+    m(
+        "focus.schedule",
+        "SCHEDULE - Important, Not Urgent",
+        "计划做 - 重要但不紧急",
+    ),
+    m(
+        "focus.delegate",
+        "DELEGATE - Urgent, Not Important",
+        "委派 - 紧急但不重要",
+    ),
+    m(
+        "focus.drop",
+        "ELIMINATE - Neither Urgent nor Important",
+        "舍弃 - 既不紧急也不重要",
+    ),
+    m("help.title", " Keyboard Shortcuts ", " 快捷键 "),
+    m(
+        "help.footer",
+        " j/k scroll  Tab next screen  Esc close ",
+        " j/k 滚动  Tab 下一屏  Esc 关闭 ",
+    ),
+    m("help.screen.main", "Main", "主界面"),
+    m("help.screen.focus", "Focus", "专注"),
+    m("help.screen.zen", "Zen", "禅模式"),
+    m("help.screen.review", "Review", "回顾"),
+    m("help.screen.triage", "Triage", "分诊"),
+    m("help.screen.day", "Day", "日视图"),
+    m("help.screen.weekly", "Weekly Review", "周回顾"),
+    m("help.screen.plan", "Plan", "计划"),
+    m("help.screen.chat", "Chat", "聊天"),
+    m("help.group.navigation", "Navigation", "导航"),
+    m("help.group.task", "Task Actions", "任务操作"),
+    m("help.group.view", "View Controls", "视图"),
+    m("help.group.screens", "Screens", "界面"),
+    m("help.group.quadrant", "Quadrant", "象限"),
+    m("help.group.single", "Single task", "单个任务"),
+    m("help.group.unfinished", "Each unfinished task", "每个未完成的任务"),
+    m("help.group.do_first", "Each DO FIRST task", "每个立即做的任务"),
+    m("help.group.planned", "Planned vs done", "计划与完成"),
+    m("help.group.open", "Each open task", "每个待办任务"),
+    m("help.group.candidates", "Candidates", "候选任务"),
+    m("help.group.conversation", "Conversation", "对话"),
+    m(
+        "help.group.input",
+        "Text Input (also add/edit and filter)",
+        "文本输入（也用于添加、编辑和筛选）",
+    ),
+];
+
+/// `language` from the config, else the first locale variable set; `env`
+/// looks up environment variables so this can be tested
+pub fn select_language(configured: Option<&str>, env: impl Fn(&str) -> Option<String>) -> Language {
+    configured
+        .map(str::to_string)
+        .or_else(|| LOCALE_VARS.iter().find_map(|var| env(var)))
+        .filter(|tag| !tag.trim().is_empty())
+        .map_or(Language::English, |tag| Language::from_tag(tag.trim()))
+}
+
+/// The interface language; English until `configure` is called
+static LANGUAGE: OnceLock<Language> = OnceLock::new();
+
+/// Pick the interface language from `config` and the locale. Call once at
+/// startup, before anything is printed
+pub fn configure(config: &Config) {
+    let _ = LANGUAGE.set(select_language(config.language.as_deref(), |var| {
+        std::env::var(var).ok()
+    }));
+}
+
+/// The interface language
+pub fn language() -> Language {
+    LANGUAGE.get().copied().unwrap_or(Language::English)
+}
+
+/// Message `id` in `language` with each `{}` replaced by the next of
+/// `args`; an unknown ID is shown as is
+pub fn translate(language: Language, id: &str, args: &[&dyn Display]) -> String {
+    let template = MESSAGES
+        .iter()
+        .find(|message| message.id == id)
+        .map_or(id, |message| message.text(language));
+    let mut args = args.iter();
+    let mut out = String::new();
+    let mut pieces = template.split("{}").peekable();
+    while let Some(piece) = pieces.next() {
+        out.push_str(piece);
+        if pieces.peek().is_some() {
+            if let Some(arg) = args.next() {
+                out.push_str(&arg.to_string());
+            }
+        }
+    }
+    out
+}
+
+/// Message `id` in the interface language; see `translate`
+pub fn tr(id: &str, args: &[&dyn Display]) -> String {
+    translate(language(), id, args)
+}
+
+/// A quadrant's name in the interface language
+pub fn quadrant_name(quadrant: Quadrant) -> String {
+    let id = match quadrant {
+        Quadrant::DoFirst => "quadrant.do_first",
+        Quadrant::Schedule => "quadrant.schedule",
+        Quadrant::Delegate => "quadrant.delegate",
+        Quadrant::Drop => "quadrant.drop",
+    };
+    tr(id, &[])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_language() {
+        let env = |var: &str| (var == "LANG").then(|| "zh_CN.UTF-8".to_string());
+        assert_eq!(select_language(None, env), Language::Chinese);
+        assert_eq!(select_language(Some("en"), env), Language::English);
+        assert_eq!(select_language(Some("zh-TW"), |_| None), Language::Chinese);
+        assert_eq!(select_language(None, |_| None), Language::English);
+    }
+
+    #[test]
+    fn test_translate_fills_placeholders() {
+        assert_eq!(
+            translate(Language::Chinese, "task.done", &[&"写周报"]),
+            "已完成任务：写周报"
+        );
+        assert_eq!(
+            translate(Language::English, "pick.which", &[&3]),
+            "Which one? [1-3, Enter to cancel] "
+        );
+        assert_eq!(
+            translate(Language::Chinese, "Not translated {}", &[&1]),
+            "Not translated 1"
+        );
+    }
+
+    #[test]
+    fn test_catalog_ids_and_placeholders() {
+        for (i, message) in MESSAGES.iter().enumerate() {
+            assert!(
+                MESSAGES[..i].iter().all(|m| m.id != message.id),
+                "duplicate ID {}",
+                message.id
+            );
+            assert_eq!(
+                message.en.matches("{}").count(),
+                message.zh.matches("{}").count(),
+                "{}",
+                message.id
+            );
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::ai::runtime;
+use crate::config::JiraSettings;
+use crate::models::store::TaskStore;
+use crate::parser::input::{parse_priority, TaskInput};
+use chrono::NaiveDate;
+use reqwest::{Method, RequestBuilder};
+use serde_json::{json, Value};
+use std::error::Error;
+use uuid::Uuid;
+
+/// Issues fetched per request
+const PAGE_SIZE: usize = 100;
+
+/// Jira's stock priorities, as urgency and importance
+const PRIORITIES: &[(&str, (u8, u8))] = &[
+    ("highest", (3, 3)),
+    ("high", (2, 3)),
+    ("medium", (2, 2)),
+    ("low", (1, 2)),
+    ("lowest", (1, 1)),
+];
+
+/// An issue as eq imports it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+    pub key: String,
+    pub summary: String,
+    pub priority: Option<(u8, u8)>,
+    pub due: Option<NaiveDate>,
+}
+
+impl Issue {
+    pub fn from_json(issue: &Value, settings: &JiraSettings) -> Option<Self> {
+        let fields = &issue["fields"];
+        Some(Self {
+            key: issue["key"].as_str()?.to_string(),
+            summary: fields["summary"].as_str()?.trim().to_string(),
+            priority: fields["priority"]["name"]
+                .as_str()
+                .and_then(|name| priority(name, settings)),
+            due: fields["duedate"].as_str().and_then(|d| d.parse().ok()),
+        })
+    }
+}
+
+/// Urgency and importance for a Jira priority name: `priorities` from the
+/// config first, then Jira's stock Highest to Lowest
+pub fn priority(name: &str, settings: &JiraSettings) -> Option<(u8, u8)> {
+    let name = name.trim().to_lowercase();
+    settings
+        .priorities
+        .iter()
+        .find(|(jira, _)| jira.to_lowercase() == name)
+        .and_then(|(_, ours)| parse_priority(ours))
+        .or_else(|| {
+            PRIORITIES
+                .iter()
+                .find(|(jira, _)| *jira == name)
+                .map(|(_, ours)| *ours)
+        })
+}
+
+/// The issue's page, stored as the task's link
+pub fn issue_url(settings: &JiraSettings, key: &str) -> String {
+    format!("{}/browse/{}", settings.url.trim_end_matches('/'), key)
+}
+
+/// The issue key in a link to an issue on the configured site
+pub fn key_of(settings: &JiraSettings, link: &str) -> Option<String> {
+    let prefix = format!("{}/browse/", settings.url.trim_end_matches('/'));
+    let key = link.strip_prefix(&prefix)?;
+    (!key.is_empty() && !key.contains('/')).then(|| key.to_string())
+}
+
+fn request(settings: &JiraSettings, method: Method, path: &str) -> RequestBuilder {
+    let token = settings
+        .token
+        .clone()
+        .or_else(|| std::env::var("JIRA_API_TOKEN").ok());
+    reqwest::Client::new()
+        .request(
+            method,
+            format!("{}{}", settings.url.trim_end_matches('/'), path),
+        )
+        .basic_auth(&settings.email, token)
+}
+
+/// Issues matching `jql`
+pub fn search(settings: &JiraSettings, jql: &str) -> Result<Vec<Issue>, Box<dyn Error>> {
+    runtime()?.block_on(async {
+        let mut issues = Vec::new();
+        let mut page_token: Option<String> = None;
+        loop {
+            let mut body = json!({
+                "jql": jql,
+                "fields": ["summary", "priority", "duedate"],
+                "maxResults": PAGE_SIZE,
+            });
+            if let Some(token) = &page_token {
+                body["nextPageToken"] = json!(token);
+            }
+            let page: Value = request(settings, Method::POST, "/rest/api/3/search/jql")
+                .json(&body)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            issues.extend(
+                page["issues"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|issue| Issue::from_json(issue, settings)),
+            );
+            match page["nextPageToken"].as_str() {
+                Some(next) if page["isLast"] != true => page_token = Some(next.to_string()),
+                _ => return Ok::<_, Box<dyn Error>>(issues),
+            }
+        }
+    })
+}
+
+/// Add a task for each issue not pulled before; returns how many were added
+pub fn import(
+    store: &mut TaskStore,
+    settings: &JiraSettings,
+    issues: &[Issue],
+    date: NaiveDate,
+) -> usize {
+    let mut added = 0;
+    for issue in issues {
+        let url = issue_url(settings, &issue.key);
+        let id = Uuid::new_v5(&Uuid::NAMESPACE_URL, url.as_bytes());
+        if store.tasks.iter().any(|t| t.id == id) {
+            continue;
+        }
+        let input = TaskInput {
+            title: format!("{} {}", issue.key, issue.summary),
+            priority: issue.priority,
+            due: issue.due,
+            ..TaskInput::default()
+        };
+        let mut task = input.into_task(date);
+        task.id = id;
+        task.link = Some(url);
+        store.add_task(task);
+        added += 1;
+    }
+    added
+}
+
+/// Move the issue behind a completed task to done: through the transition
+/// named `done_transition`, or else the first one into a done status. Runs
+/// from the log listener, so it reads the task from disk
+pub fn on_done(settings: &JiraSettings, task_id: Uuid) -> Result<(), Box<dyn Error>> {
+    let store = TaskStore::load()?;
+    let Some(key) = store
+        .tasks
+        .iter()
+        .find(|t| t.id == task_id)
+        .and_then(|t| t.link.as_deref())
+        .and_then(|link| key_of(settings, link))
+    else {
+        return Ok(());
+    };
+    let path = format!("/rest/api/3/issue/{}/transitions", key);
+    runtime()?.block_on(async {
+        let listed: Value = request(settings, Method::GET, &path)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let transitions = listed["transitions"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let Some(id) = done_transition(&transitions, settings.done_transition.as_deref()) else {
+            let message = format!("{} has no transition to done", key);
+            return Err(Box::<dyn Error>::from(message));
+        };
+        request(settings, Method::POST, &path)
+            .json(&json!({ "transition": { "id": id } }))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok::<_, Box<dyn Error>>(())
+    })
+}
+
+/// ID of the transition to take when a task is done
+fn done_transition(transitions: &[Value], name: Option<&str>) -> Option<String> {
+    transitions
+        .iter()
+        .find(|t| match name {
+            Some(name) => t["name"]
+                .as_str()
+                .is_some_and(|n| n.eq_ignore_ascii_case(name)),
+            None => t["to"]["statusCategory"]["key"] == "done",
+        })
+        .and_then(|t| t["id"].as_str())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings() -> JiraSettings {
+        JiraSettings {
+            url: String::from("https://acme.atlassian.net/"),
+            priorities: [(String::from("P0"), String::from("u3i3"))].into(),
+            ..JiraSettings::default()
+        }
+    }
+
+    #[test]
+    fn test_issue_from_json_maps_priority() {
+        let issue = json!({
+            "key": "OPS-12",
+            "fields": {
+                "summary": "Rotate certificates",
+                "priority": { "name": "High" },
+                "duedate": "2024-06-14",
+            },
+        });
+        let issue = Issue::from_json(&issue, &settings()).unwrap();
+        assert_eq!(issue.key, "OPS-12");
+        assert_eq!(issue.priority, Some((2, 3)));
+        assert_eq!(issue.due, NaiveDate::from_ymd_opt(2024, 6, 14));
+        assert_eq!(priority("p0", &settings()), Some((3, 3)));
+        assert_eq!(priority("Blocker", &settings()), None);
+    }
+
+    #[test]
+    fn test_issue_links_round_trip() {
+        let url = issue_url(&settings(), "OPS-12");
+        assert_eq!(url, "https://acme.atlassian.net/browse/OPS-12");
+        assert_eq!(key_of(&settings(), &url), Some(String::from("OPS-12")));
+        assert_eq!(key_of(&settings(), "https://github.com/a/b/issues/1"), None);
+    }
+
+    #[test]
+    fn test_done_transition() {
+        let transitions = vec![
+            json!({ "id": "11", "name": "Start", "to": { "statusCategory": { "key": "indeterminate" } } }),
+            json!({ "id": "31", "name": "Resolve", "to": { "statusCategory": { "key": "done" } } }),
+            json!({ "id": "41", "name": "Close", "to": { "statusCategory": { "key": "done" } } }),
+        ];
+        assert_eq!(
+            done_transition(&transitions, None),
+            Some(String::from("31"))
+        );
+        assert_eq!(
+            done_transition(&transitions, Some("close")),
+            Some(String::from("41"))
+        );
+        assert_eq!(done_transition(&transitions, Some("Ship")), None);
+    }
+}
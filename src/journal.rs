@@ -0,0 +1,236 @@
+use crate::models::focus::FocusSession;
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use crate::stats::format_duration;
+use crate::storage::atomic::write_atomic;
+use crate::storage::paths::journal_dir;
+use chrono::NaiveDate;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const ENV_JOURNAL_DIR: &str = "EQ_JOURNAL_DIR";
+const SECTION_START: &str = "<!-- eq:journal:start -->";
+const SECTION_END: &str = "<!-- eq:journal:end -->";
+
+/// Render the Markdown journal section for `date`.
+pub fn render_journal(
+    date: NaiveDate,
+    tasks: &[Task],
+    sessions: &[FocusSession],
+    notes: &[String],
+) -> String {
+    let day: Vec<&Task> = tasks.iter().filter(|t| t.date == date).collect();
+    let mut md = format!("# Journal — {}\n", date.format("%Y-%m-%d (%a)"));
+
+    md.push_str("\n## Completed\n");
+    let completed: Vec<&&Task> = day
+        .iter()
+        .filter(|t| t.status == TaskStatus::Completed)
+        .collect();
+    if completed.is_empty() {
+        md.push_str("- Nothing completed\n");
+    }
+    for task in completed {
+        md.push_str(&format!("- [x] {} ({})\n", task.title, task.quadrant()));
+    }
+
+    let dropped: Vec<&&Task> = day
+        .iter()
+        .filter(|t| t.status == TaskStatus::Dropped)
+        .collect();
+    if !dropped.is_empty() {
+        md.push_str("\n## Dropped\n");
+        for task in dropped {
+            match &task.drop_reason {
+                Some(reason) => md.push_str(&format!("- ~~{}~~ — {}\n", task.title, reason)),
+                None => md.push_str(&format!("- ~~{}~~\n", task.title)),
+            }
+        }
+    }
+
+    let open: Vec<&&Task> = day
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending)
+        .collect();
+    if !open.is_empty() {
+        md.push_str("\n## Still Open\n");
+        for task in open {
+            md.push_str(&format!("- [ ] {} ({})\n", task.title, task.quadrant()));
+        }
+    }
+
+    let day_sessions: Vec<&FocusSession> = sessions.iter().filter(|s| s.date == date).collect();
+    if !day_sessions.is_empty() {
+        md.push_str("\n## Focus\n");
+        for session in &day_sessions {
+            let title = session
+                .task_id
+                .and_then(|id| tasks.iter().find(|t| t.id == id))
+                .map(|t| t.title.as_str())
+                .unwrap_or("Unassigned");
+            md.push_str(&format!(
+                "- {} · {}\n",
+                format_duration(session.duration_secs),
+                title
+            ));
+        }
+        let total: u64 = day_sessions.iter().map(|s| s.duration_secs).sum();
+        md.push_str(&format!(
+            "\nTotal: {} across {} session(s)\n",
+            format_duration(total),
+            day_sessions.len()
+        ));
+    }
+
+    if !notes.is_empty() {
+        md.push_str("\n## Notes\n");
+        for note in notes {
+            md.push_str(&format!("- {}\n", note));
+        }
+    }
+
+    md
+}
+
+/// The tasks on `date` that pass `keep`, grouped under a `heading` per
+/// quadrant, most important first, one `item` line each. Quadrants with
+/// nothing in them are left out
+pub fn render_matrix(
+    date: NaiveDate,
+    tasks: &[Task],
+    keep: impl Fn(&Task) -> bool,
+    heading: impl Fn(Quadrant) -> String,
+    item: impl Fn(&Task) -> String,
+) -> String {
+    let mut md = String::new();
+    for quadrant in Quadrant::ALL {
+        let mut group: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.date == date && t.quadrant() == quadrant && keep(t))
+            .collect();
+        if group.is_empty() {
+            continue;
+        }
+        group.sort_by_key(|t| std::cmp::Reverse(t.score()));
+        md.push_str(&heading(quadrant));
+        for task in group {
+            md.push_str(&item(task));
+        }
+    }
+    md
+}
+
+/// Insert `section` into an existing note, replacing any earlier eq section.
+///
+/// Text outside the eq markers is left untouched so the journal can live
+/// inside a hand-written daily note.
+pub fn merge_into_note(existing: &str, section: &str) -> String {
+    merge_section(existing, section, (SECTION_START, SECTION_END))
+}
+
+/// `merge_into_note` for a section between other `markers`
+pub fn merge_section(existing: &str, section: &str, markers: (&str, &str)) -> String {
+    let (start_marker, end_marker) = markers;
+    let block = format!("{}\n{}{}\n", start_marker, section, end_marker);
+
+    if let (Some(start), Some(end)) = (existing.find(start_marker), existing.find(end_marker)) {
+        if start < end {
+            let after = &existing[end + end_marker.len()..];
+            let after = after.strip_prefix('\n').unwrap_or(after);
+            return format!("{}{}{}", &existing[..start], block, after);
+        }
+    }
+
+    if existing.trim().is_empty() {
+        return block;
+    }
+
+    let mut merged = existing.to_string();
+    if !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    merged.push('\n');
+    merged.push_str(&block);
+    merged
+}
+
+/// Directory journal files are written to: `EQ_JOURNAL_DIR` or `<data dir>/journal`.
+pub fn default_journal_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = std::env::var_os(ENV_JOURNAL_DIR) {
+        return Ok(PathBuf::from(dir));
+    }
+    journal_dir()
+}
+
+/// Write `section` into `<dir>/<date>.md`, preserving any surrounding note text.
+pub fn write_journal(dir: &Path, date: NaiveDate, section: &str) -> io::Result<PathBuf> {
+    write_note(dir, &date.format("%Y-%m-%d").to_string(), section)
+}
+
+/// Write `section` into `<dir>/<name>.md`, preserving any surrounding note text.
+pub fn write_note(dir: &Path, name: &str, section: &str) -> io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}.md", name));
+
+    let existing = if path.exists() {
+        fs::read_to_string(&path)?
+    } else {
+        String::new()
+    };
+
+    write_atomic(&path, &merge_into_note(&existing, section))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_render_journal_sections() {
+        let day = date("2024-06-03");
+        let mut done = Task::new("Ship release".into(), 3, 3, day);
+        done.complete();
+        let mut dropped = Task::new("Scroll feeds".into(), 1, 1, day);
+        dropped.drop_task();
+        dropped.drop_reason = Some("not worth it".into());
+        let other_day = Task::new("Tomorrow".into(), 1, 1, date("2024-06-04"));
+        let sessions = vec![FocusSession::new(Some(done.id), day, 1500)];
+
+        let md = render_journal(
+            day,
+            &[done, dropped, other_day],
+            &sessions,
+            &["Good day".to_string()],
+        );
+        assert!(md.starts_with("# Journal — 2024-06-03 (Mon)"));
+        assert!(md.contains("- [x] Ship release (DO FIRST)"));
+        assert!(md.contains("- ~~Scroll feeds~~ — not worth it"));
+        assert!(md.contains("- 25m · Ship release"));
+        assert!(md.contains("- Good day"));
+        assert!(!md.contains("Tomorrow"));
+    }
+
+    #[test]
+    fn test_merge_into_note_preserves_user_text() {
+        let first = merge_into_note("# Daily\nWoke up early", "eq v1\n");
+        assert!(first.starts_with("# Daily\nWoke up early\n\n"));
+        assert!(first.contains("eq v1"));
+
+        let second = merge_into_note(&format!("{}More thoughts\n", first), "eq v2\n");
+        assert!(second.contains("Woke up early"));
+        assert!(second.contains("More thoughts"));
+        assert!(second.contains("eq v2"));
+        assert!(!second.contains("eq v1"));
+    }
+
+    #[test]
+    fn test_merge_into_empty_note() {
+        let merged = merge_into_note("", "eq\n");
+        assert_eq!(merged, format!("{}\neq\n{}\n", SECTION_START, SECTION_END));
+    }
+}
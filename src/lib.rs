@@ -1,6 +1,30 @@
+pub use eq_core::{clock, config, models, parser, similar, stats, storage, timings};
+
 pub mod ai;
+pub mod audit;
+pub mod caldav;
 pub mod cli;
-pub mod models;
-pub mod parser;
-pub mod storage;
+#[cfg(target_os = "linux")]
+pub mod dbus;
+pub mod github;
+pub mod githook;
+pub mod hooks;
+pub mod i18n;
+pub mod jira;
+pub mod journal;
+pub mod logging;
+pub mod mcp;
+pub mod notify;
+pub mod notion;
+pub mod obsidian;
+pub mod plan;
+pub mod quotes;
+pub mod serve;
+pub mod sinks;
+pub mod status;
+pub mod sync;
+pub mod todoist;
 pub mod tui;
+pub mod voice;
+pub mod walkthrough;
+pub mod weekly;
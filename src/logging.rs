@@ -0,0 +1,35 @@
+use crate::storage::paths::log_dir;
+use std::env;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable holding the log filter, e.g. `debug` or
+/// `eq=trace,eq_core=debug`; nothing is logged when it is unset
+pub const ENV_FILTER: &str = "EQ_LOG";
+
+/// Days of logs kept before the oldest file is deleted
+const KEPT_FILES: usize = 7;
+
+/// Start writing `EQ_LOG`-filtered traces to a daily `debug.*.log` file in
+/// the data directory's `logs/`. Keep the guard until exit: dropping it
+/// flushes what is still buffered
+pub fn init() -> Option<WorkerGuard> {
+    let filter = EnvFilter::try_new(env::var(ENV_FILTER).ok()?).ok()?;
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("debug")
+        .filename_suffix("log")
+        .max_log_files(KEPT_FILES)
+        .build(log_dir().ok()?)
+        .ok()?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_thread_names(true)
+        .try_init()
+        .ok()?;
+    Some(guard)
+}
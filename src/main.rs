@@ -1,75 +1,235 @@
-use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use chrono::{Duration, NaiveDate, Weekday};
 use clap::Parser;
-use eq::cli::{Cli, Commands};
-use eq::models::store::TaskStore;
+use eq::ai::context::task_context;
+use eq::ai::priority::{parse_priority_suggestion, priority_prompt};
+use eq::ai::repeat::{parse_repeat_reply, repeat_prompt, REPEAT_SYSTEM};
+use eq::ai::{runtime, AIClient, AIResponse, ChatMessage};
+use eq::audit::{audit_prompt, audit_tasks};
+use eq::caldav;
+use eq::cli::{Cli, Commands, GhCommand, HookCommand, JiraCommand};
+use eq::clock;
+use eq::config::{Config, ModelSettings};
+use eq::githook;
+use eq::github;
+use eq::hooks;
+use eq::i18n::{self, quadrant_name, tr};
+use eq::jira;
+use eq::journal::{default_journal_dir, render_journal, write_journal, write_note};
+use eq::logging;
+use eq::mcp::serve_mcp;
+use eq::models::focus::{append_session, read_sessions, FocusSession};
+use eq::models::log::read_log;
+use eq::models::store::{TaskLookup, TaskStore};
 use eq::models::task::{Quadrant, Task, TaskStatus};
-use eq::parser::input::parse_priority;
+use eq::notify::{due_reminder, run_daemon, send_desktop, systemd_unit};
+use eq::notion;
+use eq::obsidian;
+use eq::parser::ai_commands::{AICommand, TaskIdentifier};
+use eq::parser::date::parse_date;
+use eq::parser::input::{self, parse_input, parse_priority};
+use eq::plan::{
+    breakdown_prompt, brief_tasks, briefing_prompt, capacity_line, plan_candidates,
+    planning_prompt, PLAN_HORIZON_DAYS,
+};
+use eq::quotes::random_quote;
+use eq::serve::serve_stdio;
+use eq::similar::{find_repeat, repeat_candidates, Repeat};
+use eq::sinks::{morning_summary, post_all};
+use eq::stats::{
+    balance_insights, balance_trend, compare_day, do_first_overload, focus_stats, forecast,
+    format_duration, record_postponements, stale_tasks, week_start, weekday_velocity,
+    weekly_burndown, wip_overflow, DayOutcome, DO_FIRST_SHARE, VELOCITY_WEEKS,
+};
+use eq::status;
+use eq::storage::lock::lock_store;
+use eq::sync::sync;
+use eq::timings;
+use eq::todoist::{self, PullState};
+use eq::tui::countdown::run_countdown;
+use eq::tui::picker::pick_task;
+use eq::voice::{memo_prompt, memo_tasks, transcribe};
+use eq::weekly::{
+    render_retro, render_weekly_review, retro_note_name, retro_prompt, week_log, week_outcomes,
+    weekly_note_name, WeeklyChoice, WeeklyReview,
+};
 use std::collections::HashMap;
 use std::error::Error;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use tokio::sync::mpsc::unbounded_channel;
+
+/// Prints the `--timings` counters to stderr however `main` returns
+struct TimingsReport;
+
+impl Drop for TimingsReport {
+    fn drop(&mut self) {
+        for line in timings::report() {
+            eprintln!("{}", line);
+        }
+    }
+}
 
 fn main() -> Result<(), Box<dyn Error>> {
     // Load .env file from current directory
     dotenv::dotenv().ok();
+    let _log = logging::init();
+    tracing::debug!(version = env!("CARGO_PKG_VERSION"), "eq started");
     let cli = Cli::parse();
+    if let Some(zone) = &cli.tz {
+        clock::set_time_zone(zone)?;
+    }
+    let config = Config::load().unwrap_or_else(|err| {
+        eprintln!("eq: {}; using the default settings until it is fixed", err);
+        Config::fallback(err)
+    });
+    if let Err(err) = clock::configure(&config) {
+        eprintln!("eq: config.json: {}", err);
+    }
+    input::configure(&config);
+    i18n::configure(&config);
+    if let Some(now) = &cli.now {
+        clock::set_now(now)?;
+    }
+    let _timings = cli.timings.then(|| {
+        timings::enable();
+        TimingsReport
+    });
+    // Status bars poll often, so this reads only the summary cache
+    if let Some(Commands::Status { format }) = &cli.command {
+        let summary = status::current(clock::today())?;
+        match format.as_str() {
+            "waybar" => println!("{}", status::waybar(&summary)),
+            _ => println!("{}", status::text(&summary)),
+        }
+        return Ok(());
+    }
+    let _hooks = hooks::install(&config);
+    obsidian::install(&config);
+    // Long-running commands leave the store unlocked so `eq serve` and
+    // other commands aren't kept waiting
+    let _lock = match &cli.command {
+        Some(
+            Commands::Tui
+            | Commands::Focus { .. }
+            | Commands::Serve { .. }
+            | Commands::Import { every: Some(_), .. }
+            | Commands::Notify { daemon: true, .. },
+        ) => None,
+        _ => Some(lock_store()?),
+    };
     let mut store = TaskStore::load()?;
+    if store.catch_up() {
+        store.save()?;
+    }
+    // Finished tasks from months back are only read by commands that look
+    // that far
+    if matches!(
+        &cli.command,
+        Some(
+            Commands::Stats { .. }
+                | Commands::Velocity { .. }
+                | Commands::Journal { .. }
+                | Commands::Export { .. }
+                | Commands::Day { .. }
+        )
+    ) {
+        store.load_history()?;
+    }
 
     match &cli.command {
-        Some(Commands::Add { args, tomorrow }) => {
-            let mut urgency = 1;
-            let mut importance = 1;
-            let mut title_parts = Vec::new();
-
-            for arg in args {
-                if let Some((u, i)) = parse_priority(arg) {
-                    urgency = u;
-                    importance = i;
-                } else {
-                    title_parts.push(arg.clone());
-                }
+        Some(Commands::Add {
+            args,
+            tomorrow,
+            suggest,
+            audio,
+        }) => {
+            let today = clock::today();
+            let date = if *tomorrow {
+                today + Duration::days(1)
+            } else {
+                today
+            };
+            if let Some(path) = audio {
+                return add_from_memo(&mut store, date, path);
             }
 
-            let title = title_parts.join(" ");
+            let mut parsed = parse_input(&args.join(" "), today);
+            let ask = *suggest || Config::load_or_default().ai_suggest_priority;
+            if parsed.priority.is_none() && parsed.due.is_none() && ask {
+                parsed.priority = suggest_priority(&store, &parsed.title)?;
+            }
+            let task = parsed.into_task(date);
+            let date = task.date;
+            let quadrant = task.quadrant();
 
-            let date = if *tomorrow {
-                Local::now().date_naive() + Duration::days(1)
+            // The same task on the same day is bumped; a similar one merged
+            let repeat = if config.ignore_duplicates {
+                None
             } else {
-                Local::now().date_naive()
+                find_repeat(&store.tasks, &task)
+                    .map(|(t, kind)| (t.id, t.title.clone(), kind.prompt(t, &task), kind))
+                    .or_else(|| repeat_by_ai(&store, &task, &config))
             };
+            if let Some((existing, existing_title, question, kind)) = repeat {
+                // Only ask when someone can answer; scripts get a warning
+                if io::stdin().is_terminal() {
+                    let answer = prompt(&format!("{} [y/N] ", question))?;
+                    if answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+                        kind.apply(&mut store, existing, &task);
+                        store.save()?;
+                        println!("{}", kind.done_message(&existing_title));
+                        return Ok(());
+                    }
+                } else {
+                    println!("Warning: {}", question);
+                }
+            }
 
-            let task = Task::new(title, urgency, importance, date);
             println!(
-                "Added task: {} (U={}, I={}) -> {}",
-                task.title,
-                task.urgency,
-                task.importance,
-                task.quadrant()
+                "{}",
+                tr(
+                    "task.added",
+                    &[
+                        &task.title,
+                        &task.urgency,
+                        &task.importance,
+                        &quadrant_name(task.quadrant())
+                    ]
+                )
             );
             store.add_task(task);
             store.save()?;
+
+            let limits = Config::load_or_default().wip_limits;
+            if let Some(over) = wip_overflow(store.tasks_on(date), quadrant, &limits) {
+                println!("{}", over.warning());
+            }
+            if let Some(warning) =
+                forecast(&store.tasks, date, VELOCITY_WEEKS).and_then(|f| f.warning())
+            {
+                println!("{}", warning);
+            }
+            print_triage_nudge(&store, date, &config);
         }
         Some(Commands::Done { id }) => {
-            let today = Local::now().date_naive();
-            if let Some(task_id) = store.find_task_id(id, Some(today)) {
+            let today = clock::today();
+            if let Some((task_id, title)) = choose_task(&store, id.as_deref(), today)? {
                 store.complete_task(task_id);
-                println!("Marked task as done: {}", id);
+                println!("{}", tr("task.done", &[&title]));
                 store.save()?;
-            } else {
-                println!("Task not found: {}", id);
             }
         }
-        Some(Commands::Drop { id }) => {
-            let today = Local::now().date_naive();
-            if let Some(task_id) = store.find_task_id(id, Some(today)) {
-                store.drop_task(task_id);
-                println!("Dropped task: {}", id);
+        Some(Commands::Drop { id, reason }) => {
+            let today = clock::today();
+            if let Some((task_id, title)) = choose_task(&store, id.as_deref(), today)? {
+                store.drop_task_with_reason(task_id, reason.clone());
+                println!("{}", tr("task.dropped", &[&title]));
                 store.save()?;
-            } else {
-                println!("Task not found: {}", id);
             }
         }
         Some(Commands::Edit { id, args }) => {
-            let today = Local::now().date_naive();
-            if let Some(task_id) = store.find_task_id(id, Some(today)) {
+            let today = clock::today();
+            if let Some((task_id, title)) = choose_task(&store, id.as_deref(), today)? {
                 // Get current task info
                 let (current_title, current_u, current_i) = {
                     let task = store.tasks.iter().find(|t| t.id == task_id).unwrap();
@@ -79,42 +239,512 @@ fn main() -> Result<(), Box<dyn Error>> {
                 let mut urgency = current_u;
                 let mut importance = current_i;
 
-                let input = args.join(" ");
+                let mut input = args.join(" ");
+                if input.is_empty() && io::stdin().is_terminal() {
+                    input = prompt(&format!("New priority for \"{}\" (e.g., u3i2): ", title))?;
+                }
                 if let Some((u, i)) = parse_priority(&input) {
                     urgency = u;
                     importance = i;
                 }
 
                 store.update_task(task_id, current_title, urgency, importance);
-                println!("Updated task: {}", id);
+                println!("{}", tr("task.updated", &[&title]));
                 store.save()?;
-            } else {
-                println!("Task not found: {}", id);
+            }
+        }
+        Some(Commands::Explain { id }) => {
+            let today = clock::today();
+            if let Some((task_id, _)) = choose_task(&store, id.as_deref(), today)? {
+                let task = store.tasks.iter().find(|t| t.id == task_id).unwrap();
+                print_explain(task, today);
             }
         }
         Some(Commands::Today) | None => {
-            print_matrix(&store, Local::now().date_naive());
+            print_matrix(&store, clock::today());
+            print_triage_nudge(&store, clock::today(), &config);
         }
         Some(Commands::Tomorrow) => {
-            print_matrix(&store, Local::now().date_naive() + Duration::days(1));
+            print_matrix(&store, clock::today() + Duration::days(1));
         }
         Some(Commands::Yesterday) => {
-            print_matrix(&store, Local::now().date_naive() - Duration::days(1));
+            print_matrix(&store, clock::today() - Duration::days(1));
         }
         Some(Commands::Week) => {
             print_week(&store);
         }
+        Some(Commands::Day { date }) => {
+            let day = parse_date(date, clock::today()).ok_or(format!("Unknown date: {}", date))?;
+            print_day(&store, day);
+        }
         Some(Commands::Tui) => {
             eq::tui::app::run(&mut store)?;
         }
-        Some(Commands::Stats) => {
-            print_stats(&store);
+        Some(Commands::Notify {
+            daemon,
+            systemd,
+            summary,
+        }) => {
+            if *systemd {
+                print!("{}", systemd_unit()?);
+            } else if *summary {
+                if config.sinks.is_empty() {
+                    return Err("No sinks configured; add one under \"sinks\" in the config".into());
+                }
+                post_all(&config.sinks, &morning_summary(store.today(), &store.tasks))?;
+                println!("Posted today's matrix to {} sink(s).", config.sinks.len());
+            } else if *daemon {
+                run_daemon()?;
+            } else {
+                match due_reminder(&store.tasks, clock::today()) {
+                    Some((summary, body)) => {
+                        println!("{}\n{}", summary, body);
+                        if !send_desktop(&summary, &body) {
+                            eprintln!("Could not send a desktop notification");
+                        }
+                    }
+                    None => println!("Nothing due today."),
+                }
+            }
+        }
+        Some(Commands::Sync { remote }) => {
+            sync(remote.as_deref())?;
+        }
+        Some(Commands::Import { every, .. }) => {
+            let token = Config::load_or_default()
+                .todoist_token
+                .or_else(|| std::env::var("TODOIST_API_TOKEN").ok())
+                .ok_or("Set \"todoist_token\" in config.json or TODOIST_API_TOKEN")?;
+            match every {
+                None => import_todoist(&mut store, &token)?,
+                Some(minutes) => loop {
+                    // Hold the store only while pulling, not while waiting
+                    let pulled = lock_store()
+                        .map_err(Box::<dyn Error>::from)
+                        .and_then(|_lock| {
+                            // Saved with the pull, so mirror edits are kept
+                            let mut store = TaskStore::load()?;
+                            store.catch_up();
+                            import_todoist(&mut store, &token)
+                        });
+                    if let Err(err) = pulled {
+                        eprintln!("Todoist import failed: {}", err);
+                    }
+                    std::thread::sleep(std::time::Duration::from_secs(minutes * 60));
+                },
+            }
         }
+        Some(Commands::Export { database, .. }) => {
+            let token = Config::load_or_default()
+                .notion_token
+                .or_else(|| std::env::var("NOTION_TOKEN").ok())
+                .ok_or("Set \"notion_token\" in config.json or NOTION_TOKEN")?;
+            let exported = notion::export(&store, &token, database)?;
+            println!(
+                "Notion: {} created, {} updated, {} archived",
+                exported.created, exported.updated, exported.archived
+            );
+        }
+        Some(Commands::Gh {
+            command: GhCommand::Import { repo, assignee },
+        }) => {
+            let issues = github::fetch_issues(&Config::load_or_default().github, repo, assignee.as_deref())?;
+            let added = github::import(&mut store, &issues, clock::today());
+            store.save()?;
+            println!(
+                "Added {} task(s) for {} open issue(s) in {}",
+                added,
+                issues.len(),
+                repo
+            );
+        }
+        Some(Commands::Jira {
+            command: JiraCommand::Pull { jql },
+        }) => {
+            let settings = Config::load_or_default()
+                .jira
+                .ok_or("Set \"jira\" in config.json to your site's URL, email, and API token")?;
+            let issues = jira::search(&settings, jql)?;
+            let added = jira::import(&mut store, &settings, &issues, clock::today());
+            store.save()?;
+            println!("Added {} task(s) for {} issue(s)", added, issues.len());
+        }
+        Some(Commands::Hook { command }) => match command {
+            HookCommand::Install => {
+                let path = githook::install()?;
+                println!("Installed {}", path.display());
+            }
+            HookCommand::PostCommit => githook::complete_from_last_commit(&mut store)?,
+        },
+        Some(Commands::Caldav) => {
+            let account = Config::load_or_default()
+                .caldav
+                .ok_or("Set \"caldav\" in config.json to the task list's URL and your username")?;
+            let summary = caldav::sync(&mut store, &account)?;
+            store.save()?;
+            println!(
+                "Pulled {} and pushed {} task(s)",
+                summary.pulled, summary.pushed
+            );
+            if summary.conflicts > 0 {
+                println!(
+                    "{} task(s) changed on the server during the sync; run `eq caldav` again",
+                    summary.conflicts
+                );
+            }
+        }
+        Some(Commands::Serve { stdio, mcp }) => {
+            if *mcp {
+                serve_mcp()?;
+            } else if *stdio {
+                serve_stdio()?;
+            } else {
+                return Err("eq serve needs --stdio or --mcp".into());
+            }
+        }
+        Some(Commands::Quote) => {
+            println!("{}", random_quote());
+        }
+        Some(Commands::Audit) => {
+            run_audit(&mut store, clock::today())?;
+        }
+        Some(Commands::Brief { apply }) => {
+            run_brief(&mut store, clock::today(), *apply)?;
+        }
+        Some(Commands::Plan {
+            goal,
+            tomorrow,
+            pull,
+            ai,
+        }) => {
+            let mut date = clock::today();
+            if *tomorrow {
+                date += Duration::days(1);
+            }
+
+            if let Some(goal) = goal {
+                plan_goal(&mut store, date, goal)?;
+                return Ok(());
+            }
+
+            if !pull.is_empty() {
+                let ids: Vec<uuid::Uuid> = plan_candidates(&store.tasks, date, PLAN_HORIZON_DAYS)
+                    .iter()
+                    .map(|c| c.task.id)
+                    .collect();
+                for n in pull {
+                    match n.checked_sub(1).and_then(|i| ids.get(i)) {
+                        Some(id) => {
+                            store.move_task_to_date(*id, date);
+                            println!("Pulled candidate {} onto {}", n, date);
+                        }
+                        None => println!("No candidate {}", n),
+                    }
+                }
+                store.save()?;
+            }
+
+            print_plan(&store, date);
+            if *ai {
+                print_plan_suggestions(&store, date);
+            }
+        }
+        Some(Commands::Focus {
+            id,
+            minutes,
+            notify,
+        }) => {
+            let today = clock::today();
+            let task_id = match id {
+                Some(id) => match resolve_task(&store, id, today)? {
+                    Some((task_id, _)) => Some(task_id),
+                    None => return Ok(()),
+                },
+                None => top_do_first(&store, today),
+            };
+            let title = task_id
+                .and_then(|id| store.tasks.iter().find(|t| t.id == id))
+                .map_or_else(|| String::from("Focus"), |t| t.title.clone());
+
+            if run_countdown(&title, *minutes)? {
+                append_session(&FocusSession::new(task_id, today, minutes * 60))?;
+                println!("Focus session done: {} ({}m)", title, minutes);
+                if *notify && !send_desktop("Focus session done", &title) {
+                    eprintln!("Could not send a desktop notification");
+                }
+            } else {
+                println!("Focus session stopped; nothing recorded.");
+            }
+        }
+        Some(Commands::Stats { focus }) => {
+            if *focus {
+                print_focus_stats(&store);
+            } else {
+                print_stats(&store);
+            }
+        }
+        Some(Commands::Burndown { last }) => {
+            let this_week = week_start(clock::today());
+            let start = if *last {
+                this_week - Duration::days(7)
+            } else {
+                this_week
+            };
+            print_burndown(&store, start);
+        }
+        Some(Commands::Review {
+            week,
+            last,
+            dir,
+            notes,
+            print,
+        }) => {
+            if !*week {
+                println!(
+                    "The daily review runs in the TUI (press R); use --week for the weekly review."
+                );
+                return Ok(());
+            }
+            let this_week = week_start(clock::today());
+            let start = if *last {
+                this_week - Duration::days(7)
+            } else {
+                this_week
+            };
+
+            let section = run_weekly_review(&mut store, start, notes)?;
+            if *print {
+                print!("{}", section);
+            } else {
+                let dir = match dir {
+                    Some(d) => d.clone(),
+                    None => default_journal_dir()?,
+                };
+                let path = write_note(&dir, &weekly_note_name(start), &section)?;
+                println!("Wrote weekly review: {}", path.display());
+            }
+        }
+        Some(Commands::Retro { last, dir, print }) => {
+            let this_week = week_start(clock::today());
+            let start = if *last {
+                this_week - Duration::days(7)
+            } else {
+                this_week
+            };
+
+            let events = read_log().unwrap_or_default();
+            let log = week_log(&store.tasks, &events, start);
+            if log.is_empty() {
+                println!("Nothing completed, dropped, or carried over that week yet.");
+                return Ok(());
+            }
+            let Some((reflection, _)) = ask_assistant(&store, retro_prompt(start, &log)) else {
+                return Ok(());
+            };
+
+            let section = render_retro(start, &log, &reflection);
+            if *print {
+                print!("{}", section);
+            } else {
+                let dir = match dir {
+                    Some(d) => d.clone(),
+                    None => default_journal_dir()?,
+                };
+                println!("{}\n", reflection.trim());
+                let path = write_note(&dir, &retro_note_name(start), &section)?;
+                println!("Wrote retrospective: {}", path.display());
+            }
+        }
+        Some(Commands::Journal {
+            date,
+            dir,
+            notes,
+            print,
+        }) => {
+            let date = date.unwrap_or_else(clock::today);
+            let sessions = read_sessions().unwrap_or_default();
+            let section = render_journal(date, &store.tasks, &sessions, notes);
+
+            if *print {
+                print!("{}", section);
+            } else {
+                let dir = match dir {
+                    Some(d) => d.clone(),
+                    None => default_journal_dir()?,
+                };
+                let path = write_journal(&dir, date, &section)?;
+                println!("Wrote journal: {}", path.display());
+            }
+        }
+        Some(Commands::Velocity { weeks }) => {
+            print_velocity(&store, *weeks);
+        }
+        Some(Commands::Stale {
+            days,
+            drop,
+            reschedule,
+        }) => {
+            run_stale(&mut store, *days, *drop, reschedule.as_deref())?;
+        }
+        // Printed before the store was loaded
+        Some(Commands::Status { .. }) => {}
     }
 
     Ok(())
 }
 
+fn prompt(question: &str) -> io::Result<String> {
+    print!("{}", question);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+/// The task `identifier` names, or one picked from `date`'s pending tasks
+/// when it is omitted
+fn choose_task(
+    store: &TaskStore,
+    identifier: Option<&str>,
+    date: NaiveDate,
+) -> Result<Option<(uuid::Uuid, String)>, Box<dyn Error>> {
+    if let Some(identifier) = identifier {
+        return resolve_task(store, identifier, date);
+    }
+    if !io::stdin().is_terminal() {
+        return Err("Name a task: an index, ID prefix, or part of its title".into());
+    }
+    let mut tasks: Vec<&Task> = store
+        .tasks
+        .iter()
+        .filter(|t| t.date == date && t.status == TaskStatus::Pending)
+        .collect();
+    if tasks.is_empty() {
+        println!("{}", tr("task.none_pending", &[]));
+        return Ok(None);
+    }
+    tasks.sort_by_key(|t| std::cmp::Reverse(t.score()));
+    let picked = pick_task(&tr("pick.prompt", &[]), &tasks)?;
+    Ok(picked.and_then(|id| {
+        tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| (id, t.title.clone()))
+    }))
+}
+
+/// The task `identifier` names among `date`'s pending tasks, with its
+/// title. When several match, they are listed with short IDs and the user
+/// picks one; without a terminal to ask on, that is an error
+fn resolve_task(
+    store: &TaskStore,
+    identifier: &str,
+    date: NaiveDate,
+) -> Result<Option<(uuid::Uuid, String)>, Box<dyn Error>> {
+    let title = |id: uuid::Uuid| {
+        store
+            .tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map_or_else(String::new, |t| t.title.clone())
+    };
+    let candidates = match store.find_task_id(identifier, Some(date)) {
+        TaskLookup::Found(id) => return Ok(Some((id, title(id)))),
+        TaskLookup::NotFound => {
+            println!("{}", tr("task.not_found", &[&identifier]));
+            return Ok(None);
+        }
+        TaskLookup::Ambiguous(candidates) => candidates,
+    };
+
+    println!("{}", tr("pick.matches", &[&identifier, &candidates.len()]));
+    for (i, task) in candidates.iter().enumerate() {
+        println!(
+            "  {}. {}  {} [{}]",
+            i + 1,
+            &task.id.to_string()[..8],
+            task.title,
+            task.quadrant()
+        );
+    }
+    if !io::stdin().is_terminal() {
+        return Err(format!("\"{}\" is ambiguous; pass one of the IDs above", identifier).into());
+    }
+    let answer = prompt(&tr("pick.which", &[&candidates.len()]))?;
+    match answer.parse::<usize>() {
+        Ok(n) if (1..=candidates.len()).contains(&n) => {
+            let task = candidates[n - 1];
+            Ok(Some((task.id, task.title.clone())))
+        }
+        _ => {
+            println!("{}", tr("pick.cancelled", &[]));
+            Ok(None)
+        }
+    }
+}
+
+/// Walk the week's outcomes, ask what to do with each open task, apply the
+/// answers, and return the Markdown review
+fn run_weekly_review(
+    store: &mut TaskStore,
+    start: NaiveDate,
+    notes: &[String],
+) -> Result<String, Box<dyn Error>> {
+    println!(
+        "\n🗓  Weekly review: {} – {}\n",
+        start.format("%b %d"),
+        (start + Duration::days(6)).format("%b %d")
+    );
+    for outcome in week_outcomes(&store.tasks, start) {
+        println!(
+            "{:<9} {:>3} done {:>3} dropped {:>3} open",
+            outcome.quadrant.to_string(),
+            outcome.completed.len(),
+            outcome.dropped.len(),
+            outcome.open.len()
+        );
+    }
+
+    let mut review = WeeklyReview::new(&store.tasks, start);
+    if !review.is_finished() {
+        println!("\nOpen work for next week: [k]eep, [d]elegate, [x] drop, [q] stop");
+    }
+    while let Some(id) = review.current() {
+        let (step, total) = review.progress();
+        let Some(task) = store.tasks.iter().find(|t| t.id == id) else {
+            review.decide(WeeklyChoice::Keep);
+            continue;
+        };
+        let question = format!(
+            "{}/{} [{}] {} ({}) > ",
+            step,
+            total,
+            task.quadrant(),
+            task.title,
+            task.date.format("%a %b %d")
+        );
+        match prompt(&question)?.as_str() {
+            "d" => review.decide(WeeklyChoice::Delegate),
+            "x" => review.decide(WeeklyChoice::Drop),
+            "q" => review.finish(),
+            _ => review.decide(WeeklyChoice::Keep),
+        }
+    }
+
+    let mut notes = notes.to_vec();
+    if notes.is_empty() {
+        let note = prompt("\nOne thing to change next week (Enter to skip): ")?;
+        if !note.is_empty() {
+            notes.push(note);
+        }
+    }
+
+    let section = render_weekly_review(start, &store.tasks, &review, &notes);
+    review.apply(store);
+    store.save()?;
+    Ok(section)
+}
+
 fn print_stats(store: &TaskStore) {
     let mut counts: HashMap<Quadrant, usize> = HashMap::new();
     let mut durations: HashMap<Quadrant, i64> = HashMap::new();
@@ -176,11 +806,742 @@ fn print_stats(store: &TaskStore) {
         let bar = "█".repeat(bar_len);
         println!("{:<10} | {:<5} {}", q.to_string(), avg, bar);
     }
+
+    print_balance(store);
+}
+
+/// Highest-scoring pending DO FIRST task on `date`
+fn top_do_first(store: &TaskStore, date: NaiveDate) -> Option<uuid::Uuid> {
+    store
+        .tasks
+        .iter()
+        .filter(|t| {
+            t.date == date && t.status == TaskStatus::Pending && t.quadrant() == Quadrant::DoFirst
+        })
+        .max_by_key(|t| t.score())
+        .map(|t| t.id)
+}
+
+fn print_focus_stats(store: &TaskStore) {
+    let sessions = read_sessions().unwrap_or_default();
+    let stats = focus_stats(&sessions, &store.tasks, clock::today(), 7);
+
+    println!("\n🍅 Focus Sessions\n");
+    if stats.session_count == 0 {
+        println!("No focus sessions recorded yet. Finish a pomodoro in Zen mode to start.\n");
+        return;
+    }
+
+    println!("Sessions:          {}", stats.session_count);
+    println!("Total focus:       {}", format_duration(stats.total_secs));
+    println!("Longest session:   {}", format_duration(stats.longest_secs));
+    if let Some(avg) = stats.avg_sessions_to_complete {
+        println!("Sessions per done: {:.1}", avg);
+    }
+
+    println!("\nLast 7 Days:");
+    let max_day = stats.per_day.iter().map(|(_, s)| *s).max().unwrap_or(0);
+    for (date, secs) in &stats.per_day {
+        let bar_len = if max_day > 0 {
+            (*secs as f64 / max_day as f64 * 20.0) as usize
+        } else {
+            0
+        };
+        println!(
+            "{:<10} | {:<8} {}",
+            date.format("%a %b %d").to_string(),
+            format_duration(*secs),
+            "█".repeat(bar_len)
+        );
+    }
+
+    println!("\nBy Quadrant:");
+    for q in &[
+        Quadrant::DoFirst,
+        Quadrant::Schedule,
+        Quadrant::Delegate,
+        Quadrant::Drop,
+    ] {
+        let secs = stats.per_quadrant.get(q).copied().unwrap_or(0);
+        println!("{:<10} | {}", q.to_string(), format_duration(secs));
+    }
+
+    println!("\nTop Tasks:");
+    for (task_id, secs) in stats.per_task.iter().take(5) {
+        let title = store
+            .tasks
+            .iter()
+            .find(|t| t.id == *task_id)
+            .map(|t| t.title.as_str())
+            .unwrap_or("(deleted task)");
+        println!("{:<8} {}", format_duration(*secs), title);
+    }
     println!();
 }
 
+/// Quadrant balance of completed work over recent weeks, with anti-pattern flags
+fn print_balance(store: &TaskStore) {
+    let trend = balance_trend(&store.tasks, clock::today(), 4);
+
+    println!("\n⚖️  Quadrant Balance (share of completed tasks per week)\n");
+    println!(
+        "{:<8} {:>8} {:>8} {:>8} {:>8} {:>6}",
+        "Week", "DO FIRST", "SCHEDULE", "DELEGATE", "DROP", "Done"
+    );
+    for week in &trend {
+        println!(
+            "{:<8} {:>7.0}% {:>7.0}% {:>7.0}% {:>7.0}% {:>6}",
+            week.week_start.format("%b %d").to_string(),
+            week.share(Quadrant::DoFirst),
+            week.share(Quadrant::Schedule),
+            week.share(Quadrant::Delegate),
+            week.share(Quadrant::Drop),
+            week.total()
+        );
+    }
+
+    println!("\n⏱  Estimated time per week (minutes of completed tasks)\n");
+    println!(
+        "{:<8} {:>8} {:>8} {:>8} {:>8} {:>6} {:>6}",
+        "Week", "DO FIRST", "SCHEDULE", "DELEGATE", "DROP", "Total", "Q3+Q4"
+    );
+    for week in &trend {
+        let low_value = week.minute_share(Quadrant::Delegate) + week.minute_share(Quadrant::Drop);
+        println!(
+            "{:<8} {:>8} {:>8} {:>8} {:>8} {:>6} {:>5.0}%",
+            week.week_start.format("%b %d").to_string(),
+            week.minutes_in(Quadrant::DoFirst),
+            week.minutes_in(Quadrant::Schedule),
+            week.minutes_in(Quadrant::Delegate),
+            week.minutes_in(Quadrant::Drop),
+            week.total_minutes(),
+            low_value
+        );
+    }
+
+    // Flag anti-patterns for the most recent week with completed work
+    if let Some(latest) = trend.iter().rev().find(|w| w.total() > 0) {
+        let insights = balance_insights(latest);
+        if insights.is_empty() {
+            println!(
+                "\n✓ Healthy balance for the week of {}.",
+                latest.week_start.format("%b %d")
+            );
+        } else {
+            println!("\nWeek of {}:", latest.week_start.format("%b %d"));
+            for insight in insights {
+                println!("  • {}", insight);
+            }
+        }
+    }
+    println!();
+}
+
+fn print_burndown(store: &TaskStore, start: NaiveDate) {
+    let events = read_log().unwrap_or_default();
+    let days = weekly_burndown(&store.tasks, &events, start);
+
+    println!(
+        "\n📉 Weekly Burndown ({} - {})\n",
+        start.format("%b %d"),
+        (start + Duration::days(6)).format("%b %d")
+    );
+    println!(
+        "{:<12} {:>7} {:>5} {:>7} {:>7} {:>5}",
+        "Day", "Planned", "Done", "Dropped", "Carried", "Open"
+    );
+
+    for day in &days {
+        println!(
+            "{:<12} {:>7} {:>5} {:>7} {:>7} {:>5}",
+            day.date.format("%a %b %d").to_string(),
+            day.planned,
+            day.completed,
+            day.dropped,
+            day.carried_over,
+            day.open()
+        );
+    }
+
+    let planned: usize = days.iter().map(|d| d.planned).sum();
+    let completed: usize = days.iter().map(|d| d.completed).sum();
+    let carried: usize = days.iter().map(|d| d.carried_over).sum();
+
+    if planned == 0 {
+        println!("\nNothing planned this week.\n");
+        return;
+    }
+
+    println!(
+        "\nCompleted {} of {} planned ({:.0}%), carried over {} ({:.0}%)\n",
+        completed,
+        planned,
+        completed as f64 / planned as f64 * 100.0,
+        carried,
+        carried as f64 / planned as f64 * 100.0
+    );
+}
+
+fn print_velocity(store: &TaskStore, weeks: usize) {
+    let today = clock::today();
+
+    println!("\n🏃 Velocity (avg completed, last {} weeks)\n", weeks);
+
+    let mut any = false;
+    for offset in 0..7 {
+        let date = today + Duration::days(offset);
+        if let Some(v) = weekday_velocity(&store.tasks, date, weeks) {
+            any = true;
+            let minutes = if v.avg_minutes > 0.0 {
+                format!("  ~{:.0}m", v.avg_minutes)
+            } else {
+                String::new()
+            };
+            println!(
+                "{:<4} | {:>4.1} tasks{}  ({} samples)",
+                v.weekday.to_string(),
+                v.avg_tasks,
+                minutes,
+                v.samples
+            );
+        }
+    }
+
+    if !any {
+        println!("Not enough history yet.");
+    }
+
+    if let Some(warning) = forecast(&store.tasks, today, weeks).and_then(|f| f.warning()) {
+        println!("\n{}", warning);
+    }
+    println!();
+}
+
+/// List tasks carried forward at least `days`, then drop or reschedule
+/// them: all at once with `drop` or `reschedule`, else one by one when
+/// run in a terminal
+fn run_stale(
+    store: &mut TaskStore,
+    days: i64,
+    drop: bool,
+    reschedule: Option<&str>,
+) -> Result<(), Box<dyn Error>> {
+    let today = clock::today();
+    let move_to = match reschedule {
+        Some(when) => Some(parse_date(when, today).ok_or(format!("Unknown date: {}", when))?),
+        None => None,
+    };
+    let mut postponements = HashMap::new();
+    record_postponements(&mut postponements, &read_log().unwrap_or_default());
+    let stale: Vec<(uuid::Uuid, String)> = {
+        let stale = stale_tasks(&store.tasks, &postponements, days);
+        if stale.is_empty() {
+            println!("Nothing has been put off for {} days or more.", days);
+            return Ok(());
+        }
+        println!("Put off for {}+ days:", days);
+        for (i, (task, carried)) in stale.iter().enumerate() {
+            let moves = postponements.get(&task.id).map_or(0, |p| p.moves);
+            println!(
+                "{:>3}. {}  ({} days, moved {}x, now {}, {})",
+                i + 1,
+                task.title,
+                carried,
+                moves,
+                task.date.format("%a %b %-d"),
+                task.quadrant()
+            );
+        }
+        stale.iter().map(|(t, _)| (t.id, t.title.clone())).collect()
+    };
+
+    if drop || move_to.is_some() {
+        for (id, _) in &stale {
+            match move_to {
+                Some(date) => store.move_task_to_date(*id, date),
+                None => store.drop_task(*id),
+            };
+        }
+        store.save()?;
+        match move_to {
+            Some(date) => println!("Moved {} task(s) to {}", stale.len(), date),
+            None => println!("Dropped {} task(s)", stale.len()),
+        }
+        return Ok(());
+    }
+    if !io::stdin().is_terminal() {
+        return Ok(());
+    }
+
+    println!("\nFor each: [k]eep, [d]rop, or a date to reschedule to (e.g., friday, +7)");
+    let mut changed = false;
+    for (id, title) in &stale {
+        loop {
+            let answer = prompt(&format!("{} > ", title))?;
+            if answer.is_empty() || answer.eq_ignore_ascii_case("k") {
+                break;
+            }
+            if answer.eq_ignore_ascii_case("d") {
+                store.drop_task(*id);
+                changed = true;
+                break;
+            }
+            match parse_date(&answer, today) {
+                Some(date) => {
+                    store.move_task_to_date(*id, date);
+                    println!("  Moved to {}", date.format("%a %b %-d"));
+                    changed = true;
+                    break;
+                }
+                None => println!("  Unknown date: {}", answer),
+            }
+        }
+    }
+    if changed {
+        store.save()?;
+    }
+    Ok(())
+}
+
+fn print_plan(store: &TaskStore, date: NaiveDate) {
+    println!("\n🌅 Plan for {}\n", date.format("%A %Y-%m-%d"));
+    println!("{}", capacity_line(&store.tasks, date));
+
+    let candidates = plan_candidates(&store.tasks, date, PLAN_HORIZON_DAYS);
+    if candidates.is_empty() {
+        println!("\nNothing carried over, overdue, or coming up.");
+    }
+
+    let mut source = None;
+    for (i, c) in candidates.iter().enumerate() {
+        if source != Some(c.source) {
+            source = Some(c.source);
+            println!("\n{}", c.source.label());
+        }
+        let estimate = c
+            .task
+            .estimate_minutes
+            .map(|m| format!(", ~{}m", m))
+            .unwrap_or_default();
+        println!(
+            "{:>3}. [{}] {} ({}{})",
+            i + 1,
+            c.task.quadrant(),
+            c.task.title,
+            c.task.date.format("%a %b %-d"),
+            estimate
+        );
+    }
+
+    if !candidates.is_empty() {
+        println!("\nPull candidates in with: eq plan --pull 1 3");
+    }
+    println!();
+}
+
+/// Send `prompt` with the task list as context and wait for the reply text
+/// and its suggested changes; failures are printed and give `None`
+fn ask_assistant(store: &TaskStore, prompt: String) -> Option<(String, Vec<AICommand>)> {
+    let client = match AIClient::from_config(&Config::load_or_default()) {
+        Ok(client) => client,
+        Err(reason) => {
+            println!("{}", reason);
+            return None;
+        }
+    };
+
+    let history = vec![ChatMessage {
+        role: "user".to_string(),
+        content: prompt,
+    }];
+    let (context, withheld) = task_context(&store.tasks, clock::today(), client.redactor());
+    if withheld > 0 {
+        println!("({} private task(s) withheld from the assistant)", withheld);
+    }
+
+    let (tx, mut rx) = unbounded_channel();
+    client.send_message(history, context, &ModelSettings::default(), true, tx);
+    // The whole reply follows the streamed pieces
+    let reply = std::iter::from_fn(|| rx.blocking_recv())
+        .find(|response| !matches!(response, AIResponse::Partial(_)));
+    match reply {
+        Some(AIResponse::Success { content, commands }) => Some((content, commands)),
+        Some(AIResponse::Error(err)) => {
+            println!("Error: {}\n", err);
+            None
+        }
+        Some(AIResponse::Partial(_)) | None => {
+            println!("No reply from the assistant.\n");
+            None
+        }
+    }
+}
+
+/// With `ai_duplicate_check` on, ask the AI whether `task` repeats a pending
+/// task in other words; the match comes back ready to offer as a merge
+fn repeat_by_ai(
+    store: &TaskStore,
+    task: &Task,
+    config: &Config,
+) -> Option<(uuid::Uuid, String, String, Repeat)> {
+    if !config.ai_duplicate_check {
+        return None;
+    }
+    let client = AIClient::from_config(config).ok()?;
+    let candidates: Vec<&Task> = repeat_candidates(&store.tasks, task.date)
+        .into_iter()
+        .filter(|t| !client.redactor().withholds(t))
+        .collect();
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let (tx, mut rx) = unbounded_channel();
+    client.ask_once(REPEAT_SYSTEM, repeat_prompt(&task.title, &candidates), tx);
+    let reply = rx.blocking_recv()?.ok()?;
+    let existing = candidates[parse_repeat_reply(&reply, candidates.len())?];
+    let question = Repeat::Similar.prompt(existing, task);
+    Some((
+        existing.id,
+        existing.title.clone(),
+        question,
+        Repeat::Similar,
+    ))
+}
+
+/// Ask the assistant to rate `title` and let the user accept the suggestion
+/// with Enter or type their own; `None` keeps the defaults
+fn suggest_priority(store: &TaskStore, title: &str) -> io::Result<Option<(u8, u8)>> {
+    let Some((reply, _)) = ask_assistant(store, priority_prompt(title)) else {
+        return Ok(None);
+    };
+    let Some((urgency, importance, reason)) = parse_priority_suggestion(&reply) else {
+        println!("No priority in the reply: {}", reply.trim());
+        return Ok(None);
+    };
+
+    println!(
+        "Suggested: u{}i{} ({}) — {}",
+        urgency,
+        importance,
+        Quadrant::from_priority(urgency, importance),
+        reason
+    );
+    if !io::stdin().is_terminal() {
+        return Ok(Some((urgency, importance)));
+    }
+    let answer = prompt("Enter to accept, or type a priority like u2i3: ")?;
+    if answer.is_empty() {
+        return Ok(Some((urgency, importance)));
+    }
+    Ok(parse_priority(&answer))
+}
+
+fn print_plan_suggestions(store: &TaskStore, date: NaiveDate) {
+    let candidates = plan_candidates(&store.tasks, date, PLAN_HORIZON_DAYS);
+    let prompt = planning_prompt(date, &capacity_line(&store.tasks, date), &candidates);
+    if let Some((reply, _)) = ask_assistant(store, prompt) {
+        println!("{}\n", reply);
+    }
+}
+
+/// Print the assistant's plan for the day's work and, with `apply`, carry
+/// out its [EDIT], [MOVE], and [DROP] suggestions
+fn run_brief(store: &mut TaskStore, date: NaiveDate, apply: bool) -> Result<(), Box<dyn Error>> {
+    let (prompt, due) = {
+        let tasks = brief_tasks(&store.tasks, date);
+        let due: Vec<(uuid::Uuid, String)> =
+            tasks.iter().map(|t| (t.id, t.title.clone())).collect();
+        (briefing_prompt(date, &tasks), due)
+    };
+    let Some((reply, commands)) = ask_assistant(store, prompt) else {
+        return Ok(());
+    };
+    println!("{}\n", reply.trim());
+
+    let changes: Vec<AICommand> = commands
+        .into_iter()
+        .filter(|cmd| {
+            matches!(
+                cmd,
+                AICommand::Edit { .. } | AICommand::Move { .. } | AICommand::Drop(_)
+            )
+        })
+        .collect();
+    if changes.is_empty() {
+        return Ok(());
+    }
+    if !apply {
+        println!(
+            "{} suggested change(s); run eq brief --apply to accept them.",
+            changes.len()
+        );
+        return Ok(());
+    }
+
+    apply_task_changes(store, changes, &due, date, "Morning brief", "brief")
+}
+
+/// Walk through `date`'s DELEGATE and DROP tasks with the assistant and
+/// apply its [DROP] and [EDIT] proposals once confirmed
+fn run_audit(store: &mut TaskStore, date: NaiveDate) -> Result<(), Box<dyn Error>> {
+    let (question, audited) = {
+        let tasks = audit_tasks(&store.tasks, date);
+        let audited: Vec<(uuid::Uuid, String)> =
+            tasks.iter().map(|t| (t.id, t.title.clone())).collect();
+        (audit_prompt(&tasks), audited)
+    };
+    if audited.is_empty() {
+        println!("No DELEGATE or DROP tasks to audit on {}.", date);
+        return Ok(());
+    }
+    let Some((reply, commands)) = ask_assistant(store, question) else {
+        return Ok(());
+    };
+    println!("{}\n", reply.trim());
+
+    let proposals: Vec<AICommand> = commands
+        .into_iter()
+        .filter(|cmd| matches!(cmd, AICommand::Drop(_) | AICommand::Edit { .. }))
+        .collect();
+    if proposals.is_empty() {
+        println!("No changes proposed.");
+        return Ok(());
+    }
+
+    println!("Proposed:");
+    for proposal in &proposals {
+        match proposal {
+            AICommand::Drop(target) => println!("  DROP {}", identifier_label(target)),
+            AICommand::Edit {
+                target,
+                new_title,
+                new_urgency,
+                new_importance,
+            } => {
+                let mut changes = Vec::new();
+                if let Some(title) = new_title {
+                    changes.push(format!("'{}'", title));
+                }
+                if let (Some(u), Some(i)) = (new_urgency, new_importance) {
+                    changes.push(format!("u{}i{}", u, i));
+                }
+                println!(
+                    "  EDIT {} → {}",
+                    identifier_label(target),
+                    changes.join(" ")
+                );
+            }
+            _ => {}
+        }
+    }
+
+    let answer = prompt(&format!("\nApply {} change(s)? [y/N] ", proposals.len()))?;
+    if !answer.eq_ignore_ascii_case("y") && !answer.eq_ignore_ascii_case("yes") {
+        println!("Nothing changed.");
+        return Ok(());
+    }
+    apply_task_changes(store, proposals, &audited, date, "Audit", "audit")
+}
+
+/// `#N` or the title fragment, as the assistant wrote it
+fn identifier_label(target: &TaskIdentifier) -> String {
+    match target {
+        TaskIdentifier::Index(n) => format!("#{}", n),
+        TaskIdentifier::Title(title) => title.clone(),
+    }
+}
+
+/// Carry out suggested [EDIT], [MOVE], and [DROP] changes as one undo step.
+/// `#N` refers to the Nth of `candidates`, titles match loosely; moves are
+/// relative to `date`
+fn apply_task_changes(
+    store: &mut TaskStore,
+    changes: Vec<AICommand>,
+    candidates: &[(uuid::Uuid, String)],
+    date: NaiveDate,
+    drop_reason: &str,
+    label: &str,
+) -> Result<(), Box<dyn Error>> {
+    let resolve = |target: &TaskIdentifier| -> Option<(uuid::Uuid, String)> {
+        match target {
+            TaskIdentifier::Index(n) => n.checked_sub(1).and_then(|i| candidates.get(i)).cloned(),
+            TaskIdentifier::Title(fragment) => {
+                let fragment = fragment.to_lowercase();
+                candidates
+                    .iter()
+                    .find(|(_, title)| title.to_lowercase().contains(&fragment))
+                    .cloned()
+            }
+        }
+    };
+
+    store.journal.begin_batch();
+    for change in changes {
+        let target = match &change {
+            AICommand::Edit { target, .. } | AICommand::Move { target, .. } => target,
+            AICommand::Drop(target) => target,
+            _ => continue,
+        };
+        let Some((id, title)) = resolve(target) else {
+            println!("  ? Could not find task: {}", identifier_label(target));
+            continue;
+        };
+        match change {
+            AICommand::Drop(_) => {
+                store.drop_task_with_reason(id, Some(drop_reason.to_string()));
+                println!("  ✗ Dropped: {}", title);
+            }
+            AICommand::Move { date: when, .. } => match parse_date(&when, date) {
+                Some(new_date) => {
+                    store.move_task_to_date(id, new_date);
+                    println!("  → Moved: {} to {}", title, new_date.format("%a %b %-d"));
+                }
+                None => println!("  ? Unknown date for {}: {}", title, when),
+            },
+            AICommand::Edit {
+                new_title,
+                new_urgency,
+                new_importance,
+                ..
+            } => {
+                let Some(task) = store.tasks.iter().find(|t| t.id == id) else {
+                    continue;
+                };
+                let new_title = new_title.unwrap_or_else(|| task.title.clone());
+                let urgency = new_urgency.unwrap_or(task.urgency);
+                let importance = new_importance.unwrap_or(task.importance);
+                store.update_task(id, new_title.clone(), urgency, importance);
+                println!(
+                    "  ✎ Edited: {} → {} (u{}i{})",
+                    title, new_title, urgency, importance
+                );
+            }
+            _ => {}
+        }
+    }
+    store.journal.end_batch(label);
+    store.save()?;
+    Ok(())
+}
+
+/// Ask the assistant to break `goal` into tasks and add them to `date`
+/// once confirmed
+fn plan_goal(store: &mut TaskStore, date: NaiveDate, goal: &str) -> Result<(), Box<dyn Error>> {
+    add_proposed_by_assistant(store, date, breakdown_prompt(date, goal), "plan")
+}
+
+/// Pull Todoist's open tasks into the store and report what changed
+fn import_todoist(store: &mut TaskStore, token: &str) -> Result<(), Box<dyn Error>> {
+    let open = todoist::fetch(token)?;
+    let mut state = PullState::load();
+    let pulled = todoist::pull(store, &mut state, open);
+    store.save()?;
+    state.save()?;
+    println!(
+        "Todoist: {} added, {} updated, {} closed",
+        pulled.added, pulled.updated, pulled.closed
+    );
+    Ok(())
+}
+
+/// Transcribe a voice memo and add the tasks in it to `date` once confirmed:
+/// the assistant picks them out when one is configured, otherwise every
+/// sentence becomes a task
+fn add_from_memo(
+    store: &mut TaskStore,
+    date: NaiveDate,
+    path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let config = Config::load_or_default();
+    println!("Transcribing {}...", path.display());
+    let transcript = runtime()?.block_on(transcribe(&config, path))?;
+    println!("Transcript: {}\n", transcript);
+
+    if AIClient::from_config(&config).is_ok() {
+        return add_proposed_by_assistant(store, date, memo_prompt(date, &transcript), "memo");
+    }
+    add_proposed(store, date, memo_tasks(&transcript, date), "memo")
+}
+
+/// Send `prompt` and add the tasks of its `[ADD]` suggestions once confirmed;
+/// `label` names the batch in the history log
+fn add_proposed_by_assistant(
+    store: &mut TaskStore,
+    date: NaiveDate,
+    prompt: String,
+    label: &str,
+) -> Result<(), Box<dyn Error>> {
+    let Some((reply, commands)) = ask_assistant(store, prompt) else {
+        return Ok(());
+    };
+    println!("{}\n", reply.trim());
+
+    let tasks = commands
+        .into_iter()
+        .filter_map(|cmd| match cmd {
+            AICommand::Add(task) => {
+                Some(Task::new(task.title, task.urgency, task.importance, date))
+            }
+            _ => None,
+        })
+        .collect();
+    add_proposed(store, date, tasks, label)
+}
+
+/// List `tasks` and add them to `date` if the user agrees
+fn add_proposed(
+    store: &mut TaskStore,
+    date: NaiveDate,
+    tasks: Vec<Task>,
+    label: &str,
+) -> Result<(), Box<dyn Error>> {
+    if tasks.is_empty() {
+        println!("No tasks proposed.");
+        return Ok(());
+    }
+
+    println!("Proposed for {}:", date.format("%a %b %-d"));
+    for task in &tasks {
+        println!(
+            "  [{}] {} (u{}i{})",
+            task.quadrant(),
+            task.title,
+            task.urgency,
+            task.importance
+        );
+    }
+
+    let answer = prompt(&format!("\nAdd {} task(s)? [y/N] ", tasks.len()))?;
+    if !answer.eq_ignore_ascii_case("y") && !answer.eq_ignore_ascii_case("yes") {
+        println!("Nothing added.");
+        return Ok(());
+    }
+
+    let count = tasks.len();
+    store.journal.begin_batch();
+    for task in tasks {
+        store.add_task(task);
+    }
+    store.journal.end_batch(label);
+    store.save()?;
+    println!("Added {} task(s) to {}", count, date);
+    Ok(())
+}
+
+/// Suggest re-triaging when too much of `date` is DO FIRST
+fn print_triage_nudge(store: &TaskStore, date: NaiveDate, config: &Config) {
+    let share = config.do_first_share.unwrap_or(DO_FIRST_SHARE);
+    if let Some(overload) = do_first_overload(store.tasks_on(date), share) {
+        println!(
+            "{} Press D in the TUI to walk through them.",
+            overload.nudge()
+        );
+    }
+}
+
 fn print_matrix(store: &TaskStore, date: NaiveDate) {
-    println!("Eisenhower Matrix for {}", date);
+    println!("{}", tr("matrix.title", &[&date]));
     let mut tasks: Vec<&Task> = store
         .tasks
         .iter()
@@ -189,28 +1550,70 @@ fn print_matrix(store: &TaskStore, date: NaiveDate) {
     tasks.sort_by_key(|b| std::cmp::Reverse(b.score()));
 
     if tasks.is_empty() {
-        println!("No pending tasks.");
+        println!("{}", tr("task.none_pending", &[]));
         return;
     }
 
     for (i, task) in tasks.iter().enumerate() {
+        let estimate = task
+            .estimate_minutes
+            .map(|m| format!(", ~{}m", m))
+            .unwrap_or_default();
         println!(
-            "{}. [{}] {} (Score: {})",
+            "{}. [{}] {} (Score: {}{})",
             i + 1,
-            task.quadrant(),
+            quadrant_name(task.quadrant()),
             task.title,
-            task.score()
+            task.score(),
+            estimate
         );
     }
 }
 
+/// Each term of `task`'s score and the rule behind its quadrant
+fn print_explain(task: &Task, today: NaiveDate) {
+    let breakdown = task.explain(today);
+    println!("\n{}\n", task.title);
+    println!("  Score     {}", breakdown.summary());
+    println!("  Urgency   {}", breakdown.urgency_source());
+    println!("  Aging     {}", breakdown.aging());
+    println!("  Quadrant  {}", breakdown.quadrant_rule());
+    println!();
+}
+
+/// The morning's plan for `date` against what came of it
+fn print_day(store: &TaskStore, date: NaiveDate) {
+    let events = read_log().unwrap_or_default();
+    let day = compare_day(&store.tasks, &events, date);
+    println!("\n{}: {}\n", date.format("%A, %b %-d %Y"), day.summary());
+
+    println!("Planned that morning");
+    if day.planned.is_empty() {
+        println!("  (nothing)");
+    }
+    for (task, outcome) in &day.planned {
+        let (mark, note) = match outcome {
+            DayOutcome::Completed => ("✓", String::new()),
+            DayOutcome::Dropped => ("✗", String::from(" (dropped)")),
+            DayOutcome::Moved(to) => ("→", format!(" (moved to {})", to.format("%a %b %-d"))),
+            DayOutcome::Unfinished => ("·", String::from(" (left open)")),
+        };
+        println!("  {} {} [{}]{}", mark, task.title, task.quadrant(), note);
+    }
+    if !day.unplanned.is_empty() {
+        println!("\nDone without being planned");
+        for task in &day.unplanned {
+            println!("  + {} [{}]", task.title, task.quadrant());
+        }
+    }
+}
+
 /// Fix #7: Week view implementation
 fn print_week(store: &TaskStore) {
-    let today = Local::now().date_naive();
+    let today = clock::today();
 
     // Find start of week (Monday)
-    let days_since_monday = today.weekday().num_days_from_monday();
-    let week_start = today - Duration::days(days_since_monday as i64);
+    let week_start = week_start(today);
 
     println!(
         "\n📅 Week Overview ({} - {})\n",
@@ -0,0 +1,222 @@
+use crate::clock;
+use crate::parser::ai_commands::{parse_task_identifier, TaskIdentifier};
+use crate::parser::ai_tools::tool_definitions;
+use crate::parser::input::escape_title;
+use crate::serve::{self, RpcError, RpcResult, INVALID_PARAMS, METHOD_NOT_FOUND};
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use std::error::Error;
+
+/// Protocol revision this server speaks
+const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Tools shared with the in-app assistant; their schemas come from
+/// `tool_definitions`
+const SHARED_TOOLS: &[&str] = &["add_task", "complete_task"];
+
+/// Serve the Model Context Protocol over stdio, so MCP clients can call
+/// eq's tools against the task store
+pub fn serve_mcp() -> Result<(), Box<dyn Error>> {
+    serve::serve_lines(dispatch)
+}
+
+fn dispatch(method: &str, params: &Value) -> RpcResult {
+    match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {} },
+            "serverInfo": { "name": "eq", "version": env!("CARGO_PKG_VERSION") },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tools() })),
+        "tools/call" => {
+            let name = params["name"]
+                .as_str()
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing tool name"))?;
+            let arguments = params.get("arguments").unwrap_or(&Value::Null);
+            // Failed tool calls are results the model can read, not
+            // protocol errors
+            let (text, is_error) = match call_tool(name, arguments) {
+                Ok(text) => (text, false),
+                Err(message) => (message, true),
+            };
+            Ok(json!({
+                "content": [{ "type": "text", "text": text }],
+                "isError": is_error,
+            }))
+        }
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method '{}'", other),
+        )),
+    }
+}
+
+/// MCP tool descriptions
+fn tools() -> Vec<Value> {
+    let mut tools: Vec<Value> = tool_definitions()
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|tool| &tool["function"])
+        .filter(|function| SHARED_TOOLS.iter().any(|name| function["name"] == *name))
+        .map(|function| {
+            json!({
+                "name": function["name"],
+                "description": function["description"],
+                "inputSchema": function["parameters"],
+            })
+        })
+        .collect();
+    tools.push(json!({
+        "name": "list_today",
+        "description": "List today's pending tasks, most important first, numbered as complete_task's #N",
+        "inputSchema": { "type": "object", "properties": {} },
+    }));
+    tools.push(json!({
+        "name": "get_stats",
+        "description": "Progress through a day's plan, the week's split across quadrants, and whether the day is overloaded",
+        "inputSchema": {
+            "type": "object",
+            "properties": {
+                "date": {
+                    "type": "string",
+                    "description": "YYYY-MM-DD, today, tomorrow, +N days, or a weekday name; today when omitted"
+                },
+            },
+        },
+    }));
+    tools
+}
+
+/// Run a tool against the store and describe the outcome
+fn call_tool(name: &str, arguments: &Value) -> Result<String, String> {
+    let (method, params) = tool_request(name, arguments, clock::today())?;
+    let result = serve::run(method, &params).map_err(|err| err.message)?;
+    Ok(match name {
+        "add_task" => format!(
+            "Added \"{}\" to {} ({})",
+            result["title"].as_str().unwrap_or_default(),
+            result["date"].as_str().unwrap_or_default(),
+            result["quadrant"].as_str().unwrap_or_default()
+        ),
+        "complete_task" => format!(
+            "Marked \"{}\" as done",
+            result["title"].as_str().unwrap_or_default()
+        ),
+        "list_today" => list_text(&result),
+        _ => serde_json::to_string_pretty(&result).unwrap_or_default(),
+    })
+}
+
+/// The `eq serve` method and params a tool call maps to
+fn tool_request(
+    name: &str,
+    arguments: &Value,
+    today: NaiveDate,
+) -> Result<(&'static str, Value), String> {
+    let string = |key: &str| {
+        arguments[key]
+            .as_str()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .ok_or_else(|| format!("{}: missing '{}'", name, key))
+    };
+    let level = |key: &str| arguments[key].as_u64().map_or(1, |n| n.clamp(1, 3));
+
+    match name {
+        "add_task" => {
+            let text = format!(
+                "{} u{}i{}",
+                escape_title(string("title")?, today),
+                level("urgency"),
+                level("importance")
+            );
+            Ok(("tasks.add", json!({ "text": text })))
+        }
+        "complete_task" => {
+            let id = match parse_task_identifier(string("task")?) {
+                Some(TaskIdentifier::Index(n)) => n.to_string(),
+                Some(TaskIdentifier::Title(title)) => title,
+                None => return Err(format!("{}: missing 'task'", name)),
+            };
+            Ok(("tasks.complete", json!({ "id": id })))
+        }
+        "list_today" => Ok(("tasks.list", json!({ "date": "today" }))),
+        "get_stats" => Ok(("stats", json!({ "date": arguments["date"] }))),
+        other => Err(format!("Unknown tool '{}'", other)),
+    }
+}
+
+/// Numbered lines for `list_today`
+fn list_text(tasks: &Value) -> String {
+    let tasks = tasks.as_array().map(Vec::as_slice).unwrap_or_default();
+    if tasks.is_empty() {
+        return String::from("No pending tasks today.");
+    }
+    tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            format!(
+                "{}. {} (u{}i{}, {})",
+                i + 1,
+                task["title"].as_str().unwrap_or_default(),
+                task["urgency"],
+                task["importance"],
+                task["quadrant"].as_str().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tools_cover_the_requested_set() {
+        let names: Vec<String> = tools()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            ["add_task", "complete_task", "list_today", "get_stats"]
+        );
+        assert!(tools().iter().all(|tool| tool["inputSchema"].is_object()));
+    }
+
+    #[test]
+    fn test_tool_request_maps_onto_serve_methods() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+        assert_eq!(
+            tool_request(
+                "add_task",
+                &json!({ "title": "Fix u2i3 parser", "urgency": 3, "importance": 2 }),
+                today
+            ),
+            Ok(("tasks.add", json!({ "text": "Fix \\u2i3 parser u3i2" })))
+        );
+        assert_eq!(
+            tool_request("complete_task", &json!({ "task": "#2" }), today),
+            Ok(("tasks.complete", json!({ "id": "2" })))
+        );
+        assert!(tool_request("complete_task", &json!({}), today).is_err());
+        assert!(tool_request("delete_everything", &json!({}), today).is_err());
+    }
+
+    #[test]
+    fn test_dispatch_handshake() {
+        let init = dispatch("initialize", &json!({})).unwrap();
+        assert_eq!(init["protocolVersion"], PROTOCOL_VERSION);
+        assert!(init["capabilities"]["tools"].is_object());
+        let unknown = dispatch("tools/call", &json!({ "name": "nope" })).unwrap();
+        assert_eq!(unknown["isError"], true);
+        assert_eq!(
+            dispatch("resources/list", &json!({})).unwrap_err().code,
+            METHOD_NOT_FOUND
+        );
+    }
+}
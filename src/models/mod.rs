@@ -1,3 +0,0 @@
-pub mod log;
-pub mod store;
-pub mod task;
@@ -0,0 +1,161 @@
+use crate::clock::{self, parse_day_start};
+use crate::config::Config;
+use crate::models::focus::read_sessions;
+use crate::models::store::TaskStore;
+use crate::models::task::{Task, TaskStatus};
+use crate::sinks::{morning_summary, post_all};
+use chrono::{NaiveDate, NaiveTime};
+use std::error::Error;
+use std::io::{self, Write};
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+/// How often the daemon looks at the store and the focus log
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Tasks named in a reminder before the rest are summed up
+const REMINDER_TASKS: usize = 5;
+
+/// Best-effort desktop notification, with a terminal bell as the fallback;
+/// returns whether it was shown
+pub fn send_desktop(summary: &str, body: &str) -> bool {
+    print!("\x07");
+    let _ = io::stdout().flush();
+
+    let sent = if cfg!(target_os = "macos") {
+        let script = format!("display notification {:?} with title {:?}", body, summary);
+        Command::new("osascript").args(["-e", &script]).status()
+    } else {
+        Command::new("notify-send").args([summary, body]).status()
+    };
+    sent.is_ok_and(|status| status.success())
+}
+
+/// Title and body of the reminder about pending tasks due on or before
+/// `today`, or `None` when nothing is due
+pub fn due_reminder(tasks: &[Task], today: NaiveDate) -> Option<(String, String)> {
+    let mut due: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending && t.due.is_some_and(|d| d <= today))
+        .collect();
+    if due.is_empty() {
+        return None;
+    }
+    due.sort_by_key(|t| (t.due, std::cmp::Reverse(t.score())));
+
+    let overdue = due.iter().filter(|t| t.due < Some(today)).count();
+    let summary = match (due.len() - overdue, overdue) {
+        (n, 0) => format!("{} task(s) due today", n),
+        (0, late) => format!("{} task(s) overdue", late),
+        (n, late) => format!("{} task(s) due today, {} overdue", n, late),
+    };
+    let mut lines: Vec<String> = due
+        .iter()
+        .take(REMINDER_TASKS)
+        .map(|t| match t.due {
+            Some(d) if d < today => format!("{} (due {})", t.title, d.format("%b %-d")),
+            _ => t.title.clone(),
+        })
+        .collect();
+    if due.len() > REMINDER_TASKS {
+        lines.push(format!("and {} more", due.len() - REMINDER_TASKS));
+    }
+    Some((summary, lines.join("\n")))
+}
+
+/// Remind of due and overdue tasks once a day at `morning_reminder_at`,
+/// posting the day's matrix to the chat sinks then too, and announce each
+/// pomodoro finished anywhere, until interrupted
+pub fn run_daemon() -> Result<(), Box<dyn Error>> {
+    let config = Config::load_or_default();
+    let morning = config
+        .morning_reminder_at
+        .as_deref()
+        .and_then(parse_day_start)
+        .unwrap_or(NaiveTime::from_hms_opt(9, 0, 0).unwrap_or_default());
+    // Scripts and extensions can add and complete tasks while it runs
+    #[cfg(target_os = "linux")]
+    let _bus = crate::dbus::start(crate::dbus::Target::Disk);
+    let mut reminded: Option<NaiveDate> = None;
+    let mut seen = clock::now();
+
+    loop {
+        // The CLI and TUI save by renaming a finished file into place, so
+        // reading without the store lock is safe
+        let store = TaskStore::load()?;
+        let today = store.today();
+        if reminded != Some(today) && clock::now_local().time() >= morning {
+            if let Some((summary, body)) = due_reminder(&store.tasks, today) {
+                send_desktop(&summary, &body);
+            }
+            if !config.sinks.is_empty() {
+                let _ = post_all(&config.sinks, &morning_summary(today, &store.tasks));
+            }
+            reminded = Some(today);
+        }
+
+        let sessions = read_sessions().unwrap_or_default();
+        for session in sessions.iter().filter(|s| s.ended_at > seen) {
+            let title = session
+                .task_id
+                .and_then(|id| store.tasks.iter().find(|t| t.id == id))
+                .map_or("Focus", |t| t.title.as_str());
+            send_desktop("Pomodoro done", title);
+        }
+        seen = sessions
+            .iter()
+            .map(|s| s.ended_at)
+            .fold(seen, |a, b| a.max(b));
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// A systemd user service that keeps `eq notify --daemon` running
+pub fn systemd_unit() -> Result<String, Box<dyn Error>> {
+    let exe = std::env::current_exe()?;
+    Ok(format!(
+        "[Unit]\n\
+         Description=eq reminders for due tasks and finished pomodoros\n\
+         \n\
+         [Service]\n\
+         ExecStart={} notify --daemon\n\
+         Restart=on-failure\n\
+         \n\
+         [Install]\n\
+         WantedBy=default.target\n",
+        exe.display()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due_reminder_lists_due_and_overdue() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+        let mut tasks = Vec::new();
+        for (title, due) in [
+            ("File taxes", "2024-06-10"),
+            ("Send invoice", "2024-06-12"),
+            ("Book flights", "2024-06-20"),
+        ] {
+            let mut task = Task::new(title.into(), 2, 2, today);
+            task.due = due.parse().ok();
+            tasks.push(task);
+        }
+        assert_eq!(
+            due_reminder(&tasks, today),
+            Some((
+                String::from("1 task(s) due today, 1 overdue"),
+                String::from("File taxes (due Jun 10)\nSend invoice")
+            ))
+        );
+
+        tasks[0].complete();
+        tasks[1].complete();
+        assert_eq!(due_reminder(&tasks, today), None);
+    }
+}
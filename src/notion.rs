@@ -0,0 +1,308 @@
+use crate::ai::runtime;
+use crate::models::store::TaskStore;
+use crate::models::task::{Task, TaskStatus};
+use chrono::{Duration, NaiveDate};
+use reqwest::{Method, Response, StatusCode};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use uuid::Uuid;
+
+const API: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+
+/// Past days whose tasks are exported; pending tasks go whatever their day
+const WINDOW_DAYS: i64 = 30;
+
+/// Requests retried after Notion's rate limit turns them away
+const MAX_ATTEMPTS: usize = 3;
+
+/// The column that ties a page to its task
+const ID_PROPERTY: &str = "eq ID";
+
+/// Columns eq fills in besides the title, with their Notion types
+const PROPERTIES: &[(&str, &str)] = &[
+    ("Quadrant", "select"),
+    ("Status", "select"),
+    ("Date", "date"),
+    (ID_PROPERTY, "rich_text"),
+];
+
+/// A task as a database row
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub title: String,
+    pub quadrant: String,
+    pub status: String,
+    pub date: NaiveDate,
+}
+
+impl Row {
+    pub fn from_task(task: &Task) -> Self {
+        let status = match task.status {
+            TaskStatus::Pending => "Pending",
+            TaskStatus::Completed => "Completed",
+            TaskStatus::Dropped => "Dropped",
+        };
+        Self {
+            title: task.title.replace('\n', " "),
+            quadrant: task.quadrant().to_string(),
+            status: status.to_string(),
+            date: task.date,
+        }
+    }
+
+    /// The row a page holds, with the task ID in its `eq ID` column
+    pub fn from_page(page: &Value, title_property: &str) -> Option<(Uuid, Self)> {
+        let properties = &page["properties"];
+        let id = plain_text(&properties[ID_PROPERTY]["rich_text"])
+            .parse()
+            .ok()?;
+        Some((
+            id,
+            Self {
+                title: plain_text(&properties[title_property]["title"]),
+                quadrant: properties["Quadrant"]["select"]["name"]
+                    .as_str()?
+                    .to_string(),
+                status: properties["Status"]["select"]["name"].as_str()?.to_string(),
+                date: properties["Date"]["date"]["start"].as_str()?.parse().ok()?,
+            },
+        ))
+    }
+
+    /// The page properties for this row
+    pub fn properties(&self, title_property: &str, id: Uuid) -> Value {
+        let text = |content: &str| json!([{ "type": "text", "text": { "content": content } }]);
+        json!({
+            title_property: { "title": text(&self.title) },
+            "Quadrant": { "select": { "name": self.quadrant } },
+            "Status": { "select": { "name": self.status } },
+            "Date": { "date": { "start": self.date.to_string() } },
+            ID_PROPERTY: { "rich_text": text(&id.to_string()) },
+        })
+    }
+}
+
+fn plain_text(rich_text: &Value) -> String {
+    rich_text
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|part| part["plain_text"].as_str())
+        .collect()
+}
+
+/// Tasks worth showing: the last month's, and every pending one
+pub fn exported_tasks(tasks: &[Task], today: NaiveDate) -> Vec<&Task> {
+    let since = today - Duration::days(WINDOW_DAYS);
+    tasks
+        .iter()
+        .filter(|t| t.date >= since || t.status == TaskStatus::Pending)
+        .collect()
+}
+
+/// Counts from one export
+#[derive(Debug, Default, PartialEq)]
+pub struct Exported {
+    pub created: usize,
+    pub updated: usize,
+    pub archived: usize,
+}
+
+struct Notion {
+    client: reqwest::Client,
+    token: String,
+}
+
+impl Notion {
+    /// Send a request, waiting out the rate limit as Notion asks
+    async fn call(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Value, Box<dyn Error>> {
+        for _ in 1..MAX_ATTEMPTS {
+            let response = self.send(method.clone(), path, body).await?;
+            if response.status() != StatusCode::TOO_MANY_REQUESTS {
+                return Ok(response.error_for_status()?.json().await?);
+            }
+            let wait = response
+                .headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1);
+            tokio::time::sleep(std::time::Duration::from_secs(wait)).await;
+        }
+        let response = self.send(method, path, body).await?;
+        Ok(response.error_for_status()?.json().await?)
+    }
+
+    async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<Response, reqwest::Error> {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", API, path))
+            .bearer_auth(&self.token)
+            .header("Notion-Version", NOTION_VERSION);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        request.send().await
+    }
+}
+
+/// Push the tasks to a Notion database as pages: new tasks are created,
+/// changed ones updated, and pages of deleted tasks archived. Columns eq
+/// needs are added to the database when it lacks them
+pub fn export(store: &TaskStore, token: &str, database: &str) -> Result<Exported, Box<dyn Error>> {
+    let notion = Notion {
+        client: reqwest::Client::new(),
+        token: token.to_string(),
+    };
+    let today = store.today();
+    runtime()?.block_on(async {
+        let path = format!("/databases/{}", database);
+        let schema = notion.call(Method::GET, &path, None).await?;
+        let columns = schema["properties"]
+            .as_object()
+            .cloned()
+            .unwrap_or_default();
+        let title_property = columns
+            .iter()
+            .find(|(_, column)| column["type"] == "title")
+            .map(|(name, _)| name.clone())
+            .ok_or("The database has no title column")?;
+        let mut missing = serde_json::Map::new();
+        for (name, kind) in PROPERTIES {
+            match columns.get(*name).and_then(|c| c["type"].as_str()) {
+                None => {
+                    missing.insert(name.to_string(), json!({ *kind: {} }));
+                }
+                Some(found) if found != *kind => {
+                    let message = format!("Column '{}' is a {}, expected {}", name, found, kind);
+                    return Err(Box::<dyn Error>::from(message));
+                }
+                Some(_) => {}
+            }
+        }
+        if !missing.is_empty() {
+            let body = json!({ "properties": missing });
+            notion.call(Method::PATCH, &path, Some(&body)).await?;
+        }
+
+        // Pages exported before, by task ID
+        let mut pages: HashMap<Uuid, (String, Row)> = HashMap::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut body = json!({
+                "page_size": 100,
+                "filter": { "property": ID_PROPERTY, "rich_text": { "is_not_empty": true } },
+            });
+            if let Some(cursor) = &cursor {
+                body["start_cursor"] = json!(cursor);
+            }
+            let listed = notion
+                .call(Method::POST, &format!("{}/query", path), Some(&body))
+                .await?;
+            for page in listed["results"].as_array().into_iter().flatten() {
+                if let (Some(page_id), Some((id, row))) =
+                    (page["id"].as_str(), Row::from_page(page, &title_property))
+                {
+                    pages.insert(id, (page_id.to_string(), row));
+                }
+            }
+            match listed["next_cursor"].as_str() {
+                Some(next) if listed["has_more"] == true => cursor = Some(next.to_string()),
+                _ => break,
+            }
+        }
+
+        let mut exported = Exported::default();
+        for task in exported_tasks(&store.tasks, today) {
+            let row = Row::from_task(task);
+            let properties = row.properties(&title_property, task.id);
+            match pages.get(&task.id) {
+                Some((_, old)) if *old == row => {}
+                Some((page_id, _)) => {
+                    let body = json!({ "properties": properties });
+                    let path = format!("/pages/{}", page_id);
+                    notion.call(Method::PATCH, &path, Some(&body)).await?;
+                    exported.updated += 1;
+                }
+                None => {
+                    let body = json!({
+                        "parent": { "database_id": database },
+                        "properties": properties,
+                    });
+                    notion.call(Method::POST, "/pages", Some(&body)).await?;
+                    exported.created += 1;
+                }
+            }
+        }
+
+        let kept: HashSet<Uuid> = store.tasks.iter().map(|t| t.id).collect();
+        for (id, (page_id, _)) in &pages {
+            if !kept.contains(id) {
+                let body = json!({ "archived": true });
+                let path = format!("/pages/{}", page_id);
+                notion.call(Method::PATCH, &path, Some(&body)).await?;
+                exported.archived += 1;
+            }
+        }
+        Ok::<_, Box<dyn Error>>(exported)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_round_trips_through_page_properties() {
+        let day = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+        let mut task = Task::new("Ship release".into(), 3, 3, day);
+        task.complete();
+        let row = Row::from_task(&task);
+        assert_eq!(row.quadrant, "DO FIRST");
+        assert_eq!(row.status, "Completed");
+
+        // Notion returns what was written, with `plain_text` filled in
+        let mut properties = row.properties("Name", task.id);
+        for column in ["Name", ID_PROPERTY] {
+            let kind = if column == "Name" {
+                "title"
+            } else {
+                "rich_text"
+            };
+            let part = &mut properties[column][kind][0];
+            part["plain_text"] = part["text"]["content"].clone();
+        }
+        let page = json!({ "id": "page-1", "properties": properties });
+        assert_eq!(Row::from_page(&page, "Name"), Some((task.id, row)));
+    }
+
+    #[test]
+    fn test_exported_tasks_skip_old_finished_work() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+        let old = today - Duration::days(WINDOW_DAYS + 1);
+        let mut finished = Task::new("Old release".into(), 3, 3, old);
+        finished.complete();
+        let tasks = vec![
+            finished,
+            Task::new("Old idea".into(), 1, 1, old),
+            Task::new("Ship".into(), 3, 3, today),
+        ];
+        let titles: Vec<&str> = exported_tasks(&tasks, today)
+            .iter()
+            .map(|t| t.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Old idea", "Ship"]);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::config::Config;
+use crate::journal::{merge_section, render_matrix};
+use crate::models::store::{set_mirror, Mirror, TaskStore};
+use crate::models::task::{Task, TaskStatus};
+use chrono::{Duration, NaiveDate};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+const SECTION_START: &str = "<!-- eq:matrix:start -->";
+const SECTION_END: &str = "<!-- eq:matrix:end -->";
+
+/// Past days whose notes are still written and read back
+const WINDOW_DAYS: i64 = 30;
+
+/// Daily notes in an Obsidian folder, one per day with tasks
+pub struct Vault {
+    pub dir: PathBuf,
+}
+
+/// Mirror the tasks into `obsidian_dir` when it is configured
+pub fn install(config: &Config) {
+    if let Some(dir) = &config.obsidian_dir {
+        set_mirror(Vault {
+            dir: PathBuf::from(dir),
+        });
+    }
+}
+
+/// The block ID that ties a checkbox line to its task; Obsidian hides it
+pub fn block_id(id: Uuid) -> String {
+    format!("eq-{}", &id.simple().to_string()[..8])
+}
+
+/// The matrix for `date` as Markdown checkboxes under quadrant headings;
+/// dropped tasks are `[-]`
+pub fn render_day(date: NaiveDate, tasks: &[Task]) -> String {
+    let matrix = render_matrix(
+        date,
+        tasks,
+        |_| true,
+        |quadrant| format!("\n### {} {}\n", quadrant.badge(), quadrant),
+        |task| {
+            let mark = match task.status {
+                TaskStatus::Pending => ' ',
+                TaskStatus::Completed => 'x',
+                TaskStatus::Dropped => '-',
+            };
+            format!(
+                "- [{}] {} ^{}\n",
+                mark,
+                task.title.replace('\n', " "),
+                block_id(task.id)
+            )
+        },
+    );
+    format!("## Matrix — {}\n{}", date.format("%Y-%m-%d (%a)"), matrix)
+}
+
+/// Block IDs and the state of their checkboxes in a note's eq section
+pub fn read_checkboxes(note: &str) -> Vec<(String, TaskStatus)> {
+    let section = match (note.find(SECTION_START), note.find(SECTION_END)) {
+        (Some(start), Some(end)) if start < end => &note[start..end],
+        _ => return Vec::new(),
+    };
+    section
+        .lines()
+        .filter_map(|line| {
+            let rest = line.trim_start().strip_prefix("- [")?;
+            let status = match rest.chars().next()? {
+                ' ' => TaskStatus::Pending,
+                'x' | 'X' => TaskStatus::Completed,
+                '-' => TaskStatus::Dropped,
+                _ => return None,
+            };
+            let (_, id) = line.trim_end().rsplit_once(" ^")?;
+            id.starts_with("eq-").then(|| (id.to_string(), status))
+        })
+        .collect()
+}
+
+impl Vault {
+    fn note_path(&self, date: NaiveDate) -> PathBuf {
+        self.dir.join(format!("{}.md", date.format("%Y-%m-%d")))
+    }
+
+    /// Today and every day with tasks since the window began
+    fn days(store: &TaskStore) -> BTreeSet<NaiveDate> {
+        let today = store.today();
+        let since = today - Duration::days(WINDOW_DAYS);
+        store
+            .tasks
+            .iter()
+            .map(|t| t.date)
+            .filter(|date| *date >= since)
+            .chain([today])
+            .collect()
+    }
+}
+
+impl Mirror for Vault {
+    fn read_back(&self, store: &mut TaskStore) -> bool {
+        let mut changed = false;
+        for date in Vault::days(store) {
+            let Ok(note) = fs::read_to_string(self.note_path(date)) else {
+                continue;
+            };
+            for (block, status) in read_checkboxes(&note) {
+                let task = store
+                    .tasks
+                    .iter()
+                    .find(|t| t.date == date && block_id(t.id) == block);
+                if let Some(id) = task.filter(|t| t.status != status).map(|t| t.id) {
+                    set_status(store, id, status);
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    fn write(&self, store: &TaskStore) {
+        if fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        for date in Vault::days(store) {
+            let path = self.note_path(date);
+            let existing = fs::read_to_string(&path).unwrap_or_default();
+            let note = merge_section(
+                &existing,
+                &render_day(date, &store.tasks),
+                (SECTION_START, SECTION_END),
+            );
+            if note != existing {
+                let _ = fs::write(&path, note);
+            }
+        }
+    }
+}
+
+/// Move a task to `status` through the store, so the change is logged
+fn set_status(store: &mut TaskStore, id: Uuid, status: TaskStatus) {
+    let Some(current) = store.tasks.iter().find(|t| t.id == id).map(|t| t.status) else {
+        return;
+    };
+    match (current, status) {
+        (_, TaskStatus::Completed) => {
+            store.complete_task(id);
+        }
+        (_, TaskStatus::Dropped) => {
+            store.drop_task(id);
+        }
+        (TaskStatus::Completed, TaskStatus::Pending) => {
+            store.toggle_complete_task(id);
+        }
+        (_, TaskStatus::Pending) => {
+            if let Some(task) = store.tasks.iter_mut().find(|t| t.id == id) {
+                task.status = TaskStatus::Pending;
+                task.drop_reason = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_and_read_back_checkboxes() {
+        let day: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut done = Task::new("Ship release".into(), 3, 3, day);
+        done.complete();
+        let open = Task::new("Plan Q3".into(), 1, 3, day);
+        let mut dropped = Task::new("Scroll feeds".into(), 1, 1, day);
+        dropped.drop_task();
+        let tasks = vec![done.clone(), open.clone(), dropped.clone()];
+
+        let md = render_day(day, &tasks);
+        assert!(md.contains("### Q1 DO FIRST\n"));
+        assert!(md.contains(&format!("- [x] Ship release ^{}\n", block_id(done.id))));
+        assert!(md.contains(&format!("- [ ] Plan Q3 ^{}\n", block_id(open.id))));
+        assert!(!md.contains("DELEGATE"));
+
+        // Ticked in Obsidian, next to the user's own checklist
+        let note = merge_section(
+            "- [ ] Water plants\n",
+            &md.replace("- [ ] Plan Q3", "- [x] Plan Q3"),
+            (SECTION_START, SECTION_END),
+        );
+        assert_eq!(
+            read_checkboxes(&note),
+            vec![
+                (block_id(done.id), TaskStatus::Completed),
+                (block_id(open.id), TaskStatus::Completed),
+                (block_id(dropped.id), TaskStatus::Dropped),
+            ]
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::models::task::{Task, TaskStatus};
+use crate::stats::{forecast, VELOCITY_WEEKS};
+use chrono::{Duration, NaiveDate};
+
+/// Days ahead whose scheduled tasks are offered when planning
+pub const PLAN_HORIZON_DAYS: i64 = 7;
+
+/// Why a task is offered for the day being planned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    CarriedOver, // Left pending the day before
+    Overdue,     // Left pending on an earlier day
+    Upcoming,    // Scheduled within the planning horizon
+}
+
+impl Source {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Source::CarriedOver => "Carried over from yesterday",
+            Source::Overdue => "Overdue",
+            Source::Upcoming => "Coming up",
+        }
+    }
+}
+
+/// A pending task that could be pulled onto the planned day
+#[derive(Debug, Clone, Copy)]
+pub struct Candidate<'a> {
+    pub task: &'a Task,
+    pub source: Source,
+}
+
+/// Pending tasks worth pulling onto `date`: yesterday's leftovers, older
+/// overdue work, then the next `horizon_days` of backlog, each by score
+pub fn plan_candidates(tasks: &[Task], date: NaiveDate, horizon_days: i64) -> Vec<Candidate<'_>> {
+    let yesterday = date - Duration::days(1);
+    let horizon = date + Duration::days(horizon_days);
+
+    let mut candidates: Vec<Candidate> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending && t.date != date)
+        .filter_map(|task| {
+            let source = if task.date == yesterday {
+                Source::CarriedOver
+            } else if task.date < yesterday {
+                Source::Overdue
+            } else if task.date <= horizon {
+                Source::Upcoming
+            } else {
+                return None;
+            };
+            Some(Candidate { task, source })
+        })
+        .collect();
+
+    candidates.sort_by_key(|c| {
+        let group = match c.source {
+            Source::CarriedOver => 0,
+            Source::Overdue => 1,
+            Source::Upcoming => 2,
+        };
+        (group, std::cmp::Reverse(c.task.score()), c.task.date)
+    });
+    candidates
+}
+
+/// Planned work on `date` next to what usually gets done on that weekday,
+/// e.g. `3 tasks, 90m planned · usually 4.0 tasks, 120m on Wednesdays`
+pub fn capacity_line(tasks: &[Task], date: NaiveDate) -> String {
+    let planned: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.date == date && t.status != TaskStatus::Dropped)
+        .collect();
+    let minutes: u32 = planned.iter().filter_map(|t| t.estimate_minutes).sum();
+    let mut line = format!("{} tasks, {}m planned", planned.len(), minutes);
+
+    if let Some(f) = forecast(tasks, date, VELOCITY_WEEKS) {
+        line.push_str(&format!(
+            " · usually {:.1} tasks, {:.0}m on {}s",
+            f.velocity.avg_tasks,
+            f.velocity.avg_minutes,
+            date.format("%A")
+        ));
+        if f.is_overloaded() {
+            line.push_str(" (full)");
+        }
+    }
+    line
+}
+
+/// Chat message asking the assistant to help compose the day
+pub fn planning_prompt(date: NaiveDate, capacity: &str, candidates: &[Candidate]) -> String {
+    let mut prompt = format!(
+        "Help me plan {}. Already planned: {}.\n",
+        date.format("%A %b %-d"),
+        capacity
+    );
+    if candidates.is_empty() {
+        prompt.push_str("There are no carry-overs, overdue, or upcoming tasks.\n");
+    } else {
+        prompt.push_str("Candidates I could pull in:\n");
+        for c in candidates {
+            prompt.push_str(&format!(
+                "- {} (u{}i{}, {})\n",
+                c.task.title,
+                c.task.urgency,
+                c.task.importance,
+                c.source.label().to_lowercase()
+            ));
+        }
+    }
+    prompt.push_str(
+        "Which should I take on today, which should wait or be dropped, and what is missing?",
+    );
+    prompt
+}
+
+/// Pending work due by `date`, overdue first, then by score
+pub fn brief_tasks(tasks: &[Task], date: NaiveDate) -> Vec<&Task> {
+    let mut due: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.status == TaskStatus::Pending && t.date <= date)
+        .collect();
+    due.sort_by_key(|t| (t.date == date, std::cmp::Reverse(t.score())));
+    due
+}
+
+/// Chat message asking for a short prioritized plan of `tasks`, numbered so
+/// suggestions can refer to them as `#N`
+pub fn briefing_prompt(date: NaiveDate, tasks: &[&Task]) -> String {
+    let mut prompt = format!("Morning briefing for {}.\n", date.format("%A %b %-d"));
+    if tasks.is_empty() {
+        prompt.push_str("Nothing is due today or overdue.\n");
+    } else {
+        prompt.push_str("Due today or overdue:\n");
+        for (i, task) in tasks.iter().enumerate() {
+            let overdue = if task.date < date {
+                format!(", overdue since {}", task.date.format("%b %-d"))
+            } else {
+                String::new()
+            };
+            let estimate = task
+                .estimate_minutes
+                .map(|m| format!(", ~{}m", m))
+                .unwrap_or_default();
+            prompt.push_str(&format!(
+                "#{} {} (u{}i{}{}{})\n",
+                i + 1,
+                task.title,
+                task.urgency,
+                task.importance,
+                estimate,
+                overdue
+            ));
+        }
+    }
+    prompt.push_str(
+        "In a few short lines, tell me what to start with, what to batch together, and what \
+         to consider dropping. Put any re-prioritization on its own [EDIT], [MOVE], or [DROP] \
+         line using the #N numbers above.",
+    );
+    prompt
+}
+
+/// Chat message asking the assistant to break `goal` into tasks for `date`
+pub fn breakdown_prompt(date: NaiveDate, goal: &str) -> String {
+    format!(
+        "Break this goal into next actions I can start on {}, one [ADD] line each: {}",
+        date.format("%A %b %-d"),
+        goal.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_candidates_grouped_by_source() {
+        let today = date("2024-06-12");
+        let tasks = vec![
+            Task::new("upcoming".into(), 3, 3, date("2024-06-14")),
+            Task::new("overdue".into(), 1, 1, date("2024-06-01")),
+            Task::new("yesterday".into(), 1, 1, date("2024-06-11")),
+            Task::new("today".into(), 3, 3, today),
+            Task::new("far future".into(), 3, 3, date("2024-07-30")),
+        ];
+
+        let titles: Vec<&str> = plan_candidates(&tasks, today, PLAN_HORIZON_DAYS)
+            .iter()
+            .map(|c| c.task.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["yesterday", "overdue", "upcoming"]);
+    }
+
+    #[test]
+    fn test_finished_tasks_are_not_candidates() {
+        let today = date("2024-06-12");
+        let mut done = Task::new("done".into(), 2, 2, date("2024-06-11"));
+        done.complete();
+        let mut dropped = Task::new("dropped".into(), 2, 2, date("2024-06-10"));
+        dropped.drop_task();
+        assert!(plan_candidates(&[done, dropped], today, PLAN_HORIZON_DAYS).is_empty());
+    }
+
+    #[test]
+    fn test_brief_lists_overdue_then_today() {
+        let today = date("2024-06-12");
+        let mut done = Task::new("done".into(), 3, 3, today);
+        done.complete();
+        let tasks = vec![
+            Task::new("today low".into(), 1, 1, today),
+            Task::new("today high".into(), 3, 3, today),
+            Task::new("overdue".into(), 1, 1, date("2024-06-10")),
+            Task::new("tomorrow".into(), 3, 3, date("2024-06-13")),
+            done,
+        ];
+
+        let due = brief_tasks(&tasks, today);
+        let titles: Vec<&str> = due.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["overdue", "today high", "today low"]);
+
+        let prompt = briefing_prompt(today, &due);
+        assert!(prompt.contains("#1 overdue (u1i1, overdue since Jun 10)"));
+        assert!(prompt.contains("#3 today low (u1i1)"));
+    }
+
+    #[test]
+    fn test_capacity_line_counts_planned_work() {
+        let today = date("2024-06-12");
+        let mut a = Task::new("a".into(), 2, 2, today);
+        a.estimate_minutes = Some(30);
+        let mut b = Task::new("b".into(), 2, 2, today);
+        b.estimate_minutes = Some(60);
+        let mut dropped = Task::new("c".into(), 2, 2, today);
+        dropped.drop_task();
+
+        assert_eq!(
+            capacity_line(&[a, b, dropped], today),
+            "2 tasks, 90m planned"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::ai::PAUL_GRAHAM_QUOTES;
+use crate::storage::paths::quotes_dir;
+use std::fmt;
+use std::fs;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One entry of the quote bank
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quote {
+    pub text: String,
+    pub author: String,
+    pub source: Option<String>,
+}
+
+impl fmt::Display for Quote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\" — {}", self.text, self.author)?;
+        if let Some(source) = &self.source {
+            write!(f, ", {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+/// The curated quotes that ship with eq
+pub fn builtin_quotes() -> Vec<Quote> {
+    PAUL_GRAHAM_QUOTES
+        .iter()
+        .map(|(text, source)| Quote {
+            text: text.to_string(),
+            author: "Paul Graham".to_string(),
+            source: Some(source.to_string()),
+        })
+        .collect()
+}
+
+/// Parse `"Text" — Author, Source`; `--` works for the dash, the source is
+/// optional, and blank or `#` lines are skipped
+pub fn parse_quote_line(line: &str) -> Option<Quote> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    let (text, attribution) = line
+        .rsplit_once(" — ")
+        .or_else(|| line.rsplit_once(" -- "))?;
+    let text = text
+        .trim()
+        .trim_matches(|c| c == '"' || c == '“' || c == '”');
+    let (author, source) = match attribution.split_once(',') {
+        Some((author, source)) => (author.trim(), Some(source.trim().to_string())),
+        None => (attribution.trim(), None),
+    };
+    if text.is_empty() || author.is_empty() {
+        return None;
+    }
+    Some(Quote {
+        text: text.to_string(),
+        author: author.to_string(),
+        source: source.filter(|s| !s.is_empty()),
+    })
+}
+
+/// Quotes from the `.txt` files in the data dir's `quotes` folder
+pub fn user_quotes() -> Vec<Quote> {
+    let Ok(entries) = quotes_dir().and_then(fs::read_dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .flat_map(|content| {
+            content
+                .lines()
+                .filter_map(parse_quote_line)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The built-in quotes followed by the user's own
+pub fn quote_bank() -> Vec<Quote> {
+    let mut bank = builtin_quotes();
+    bank.extend(user_quotes());
+    bank
+}
+
+/// A quote from the full bank, picked by the clock
+pub fn random_quote() -> Quote {
+    let bank = quote_bank();
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as usize);
+    bank[seed % bank.len()].clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_quote_line() {
+        assert_eq!(
+            parse_quote_line("\"Well begun is half done.\" — Aristotle, Politics"),
+            Some(Quote {
+                text: "Well begun is half done.".into(),
+                author: "Aristotle".into(),
+                source: Some("Politics".into()),
+            })
+        );
+        assert_eq!(
+            parse_quote_line("Ship it -- Anonymous").map(|q| q.to_string()),
+            Some("\"Ship it\" — Anonymous".to_string())
+        );
+        assert_eq!(parse_quote_line("# my favourites"), None);
+        assert_eq!(parse_quote_line("No attribution here"), None);
+    }
+
+    #[test]
+    fn test_builtin_quotes_are_attributed() {
+        let quote = &builtin_quotes()[0];
+        assert_eq!(quote.author, "Paul Graham");
+        assert!(quote.to_string().ends_with(", How to Do Great Work"));
+    }
+}
@@ -0,0 +1,426 @@
+use crate::models::store::{TaskLookup, TaskStore};
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use crate::parser::date::parse_date;
+use crate::parser::input::parse_input;
+use crate::stats::{day_progress, forecast, quadrant_balance, week_start, VELOCITY_WEEKS};
+use crate::storage::lock::lock_store;
+use chrono::NaiveDate;
+use serde_json::{json, Map, Value};
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc;
+use uuid::Uuid;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+pub(crate) const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// No task fits the identifier
+const TASK_NOT_FOUND: i64 = -32001;
+/// Several tasks fit the identifier; `data` lists them
+const TASK_AMBIGUOUS: i64 = -32002;
+
+/// Methods that change the store, which is saved after they succeed
+pub(crate) const WRITE_METHODS: &[&str] =
+    &["tasks.add", "tasks.update", "tasks.complete", "tasks.drop"];
+
+/// A failed call, sent back as a JSON-RPC error object
+#[derive(Debug, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn internal(err: impl Display) -> Self {
+        Self::new(INTERNAL_ERROR, err.to_string())
+    }
+}
+
+pub type RpcResult = Result<Value, RpcError>;
+
+/// A call carried to the thread that holds the store, such as the TUI's,
+/// and answered on `reply`
+pub struct Call {
+    pub method: &'static str,
+    pub params: Value,
+    pub reply: mpsc::Sender<RpcResult>,
+}
+
+/// Answer JSON-RPC 2.0 requests read from stdin, one JSON object per line,
+/// with one reply per line on stdout until stdin closes
+pub fn serve_stdio() -> Result<(), Box<dyn Error>> {
+    serve_lines(run)
+}
+
+/// Read requests from stdin and answer each with `dispatch`, which gets the
+/// method name and params
+pub(crate) fn serve_lines(
+    dispatch: impl Fn(&str, &Value) -> RpcResult,
+) -> Result<(), Box<dyn Error>> {
+    let mut stdout = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(reply) = handle_line(&line, &dispatch) {
+            writeln!(stdout, "{}", reply)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// The reply to one request line; `None` for a notification, which has no
+/// `id` and gets no reply
+fn handle_line(line: &str, dispatch: impl Fn(&str, &Value) -> RpcResult) -> Option<Value> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(err) => {
+            return Some(reply(
+                Value::Null,
+                Err(RpcError::new(PARSE_ERROR, err.to_string())),
+            ))
+        }
+    };
+    let Some(object) = request.as_object() else {
+        return Some(reply(
+            Value::Null,
+            Err(RpcError::new(INVALID_REQUEST, "Expected a JSON object")),
+        ));
+    };
+    let result = match object.get("method").and_then(Value::as_str) {
+        Some(method) => dispatch(method, object.get("params").unwrap_or(&Value::Null)),
+        None => Err(RpcError::new(INVALID_REQUEST, "Missing method")),
+    };
+    object.get("id").map(|id| reply(id.clone(), result))
+}
+
+fn reply(id: Value, result: RpcResult) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => {
+            let mut error = json!({ "code": err.code, "message": err.message });
+            if let Some(data) = err.data {
+                error["data"] = data;
+            }
+            json!({ "jsonrpc": "2.0", "id": id, "error": error })
+        }
+    }
+}
+
+/// Call `method` on the store as it is on disk now, holding the store lock
+/// throughout so a concurrent `eq` command can't interleave with it
+pub(crate) fn run(method: &str, params: &Value) -> RpcResult {
+    let _lock = lock_store().map_err(RpcError::internal)?;
+    let mut store = TaskStore::load().map_err(RpcError::internal)?;
+    let caught_up = store.catch_up();
+    let result = call(&mut store, method, params)?;
+    if caught_up || WRITE_METHODS.contains(&method) {
+        store.save().map_err(RpcError::internal)?;
+    }
+    Ok(result)
+}
+
+/// Call `method` on `store`; the caller saves after a write method
+pub(crate) fn call(store: &mut TaskStore, method: &str, params: &Value) -> RpcResult {
+    match method {
+        "tasks.list" => {
+            // Only finished tasks are ever archived
+            if params.get("status").is_some_and(|s| s != "pending") {
+                store.load_history().map_err(RpcError::internal)?;
+            }
+            list_tasks(store, params)
+        }
+        "tasks.get" => {
+            let id = resolve(store, params)?;
+            Ok(task_json(find(store, id)))
+        }
+        "tasks.add" => {
+            let text = string_param(params, "text")?
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing text"))?;
+            let date = date_param(store, params)?.unwrap_or(store.today());
+            let task = parse_input(&text, store.today()).into_task(date);
+            if task.title.is_empty() {
+                return Err(RpcError::new(INVALID_PARAMS, "Task title is empty"));
+            }
+            let value = task_json(&task);
+            store.add_task(task);
+            Ok(value)
+        }
+        "tasks.update" => update_task(store, params),
+        "tasks.complete" => {
+            let id = resolve(store, params)?;
+            store.complete_task(id);
+            Ok(task_json(find(store, id)))
+        }
+        "tasks.drop" => {
+            let id = resolve(store, params)?;
+            store.drop_task_with_reason(id, string_param(params, "reason")?);
+            Ok(task_json(find(store, id)))
+        }
+        "matrix" => {
+            let date = date_param(store, params)?.unwrap_or(store.today());
+            let mut matrix = Map::new();
+            for quadrant in Quadrant::ALL {
+                let mut tasks: Vec<&Task> = store
+                    .tasks
+                    .iter()
+                    .filter(|t| {
+                        t.date == date
+                            && t.status == TaskStatus::Pending
+                            && t.quadrant() == quadrant
+                    })
+                    .collect();
+                tasks.sort_by_key(|t| std::cmp::Reverse(t.score()));
+                matrix.insert(
+                    format!("{:?}", quadrant),
+                    tasks.into_iter().map(task_json).collect(),
+                );
+            }
+            Ok(json!({ "date": date, "quadrants": matrix }))
+        }
+        "stats" => {
+            let date = date_param(store, params)?.unwrap_or(store.today());
+            let progress = day_progress(&store.tasks, date);
+            let balance = quadrant_balance(&store.tasks, week_start(date));
+            let shares: Map<String, Value> = Quadrant::ALL
+                .iter()
+                .map(|q| (format!("{:?}", q), json!(balance.share(*q))))
+                .collect();
+            let forecast = forecast(&store.tasks, date, VELOCITY_WEEKS).map(|f| {
+                json!({
+                    "planned_tasks": f.planned_tasks,
+                    "planned_minutes": f.planned_minutes,
+                    "usual_tasks": f.velocity.avg_tasks,
+                    "usual_minutes": f.velocity.avg_minutes,
+                    "overloaded": f.is_overloaded(),
+                })
+            });
+            Ok(json!({
+                "date": date,
+                "progress": {
+                    "done": progress.done,
+                    "planned": progress.planned,
+                    "percent": progress.percent(),
+                    "remaining_minutes": progress.remaining_minutes,
+                },
+                "week": {
+                    "start": balance.week_start,
+                    "completed": balance.total(),
+                    "shares": shares,
+                },
+                "forecast": forecast,
+            }))
+        }
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method '{}'", other),
+        )),
+    }
+}
+
+/// Tasks on `date` (every day when omitted) with `status` (`pending` when
+/// omitted, or `all`), best first
+fn list_tasks(store: &TaskStore, params: &Value) -> RpcResult {
+    let date = date_param(store, params)?;
+    let status = match string_param(params, "status")?.as_deref() {
+        None | Some("pending") => Some(TaskStatus::Pending),
+        Some("completed") => Some(TaskStatus::Completed),
+        Some("dropped") => Some(TaskStatus::Dropped),
+        Some("all") => None,
+        Some(other) => {
+            return Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("Unknown status '{}'", other),
+            ))
+        }
+    };
+    let mut tasks: Vec<&Task> = store
+        .tasks
+        .iter()
+        .filter(|t| date.is_none_or(|d| t.date == d) && status.is_none_or(|s| t.status == s))
+        .collect();
+    tasks.sort_by_key(|t| (t.date, std::cmp::Reverse(t.score())));
+    Ok(tasks.into_iter().map(task_json).collect())
+}
+
+/// Change the title, priority, or date of a task; fields left out keep
+/// their value
+fn update_task(store: &mut TaskStore, params: &Value) -> RpcResult {
+    let id = resolve(store, params)?;
+    let task = find(store, id);
+    let title = string_param(params, "title")?.unwrap_or_else(|| task.title.clone());
+    let urgency = level_param(params, "urgency")?.unwrap_or(task.urgency);
+    let importance = level_param(params, "importance")?.unwrap_or(task.importance);
+    let date = date_param(store, params)?;
+    if title.trim().is_empty() {
+        return Err(RpcError::new(INVALID_PARAMS, "Task title is empty"));
+    }
+
+    store.journal.begin_batch();
+    store.update_task(id, title, urgency, importance);
+    if let Some(date) = date {
+        store.move_task_to_date(id, date);
+    }
+    store.journal.end_batch("update");
+    Ok(task_json(find(store, id)))
+}
+
+/// The task named by the `id` param: an ID or ID prefix, an index among
+/// today's pending tasks, or a title
+fn resolve(store: &TaskStore, params: &Value) -> Result<Uuid, RpcError> {
+    let identifier =
+        string_param(params, "id")?.ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing id"))?;
+    match store.find_task_id(&identifier, Some(store.today())) {
+        TaskLookup::Found(id) => Ok(id),
+        TaskLookup::Ambiguous(tasks) => Err(RpcError {
+            code: TASK_AMBIGUOUS,
+            message: format!("\"{}\" matches {} tasks", identifier, tasks.len()),
+            data: Some(tasks.into_iter().map(task_json).collect()),
+        }),
+        TaskLookup::NotFound => Err(RpcError::new(
+            TASK_NOT_FOUND,
+            format!("Task not found: {}", identifier),
+        )),
+    }
+}
+
+fn find(store: &TaskStore, id: Uuid) -> &Task {
+    store
+        .tasks
+        .iter()
+        .find(|t| t.id == id)
+        .expect("resolved task is in the store")
+}
+
+/// A task as JSON, with its quadrant spelled out
+fn task_json(task: &Task) -> Value {
+    let mut value = json!(task);
+    value["quadrant"] = json!(task.quadrant());
+    value
+}
+
+fn string_param(params: &Value, name: &str) -> Result<Option<String>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        // Indexes may come as numbers
+        Some(Value::Number(n)) if name == "id" => Ok(Some(n.to_string())),
+        Some(_) => Err(RpcError::new(
+            INVALID_PARAMS,
+            format!("{} must be a string", name),
+        )),
+    }
+}
+
+/// An urgency or importance from 1 to 3
+fn level_param(params: &Value, name: &str) -> Result<Option<u8>, RpcError> {
+    match params.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => match value.as_u64() {
+            Some(level @ 1..=3) => Ok(Some(level as u8)),
+            _ => Err(RpcError::new(
+                INVALID_PARAMS,
+                format!("{} must be 1, 2, or 3", name),
+            )),
+        },
+    }
+}
+
+/// The `date` param: `YYYY-MM-DD` or anything `parse_date` reads, such as
+/// `tomorrow` or `fri`
+fn date_param(store: &TaskStore, params: &Value) -> Result<Option<NaiveDate>, RpcError> {
+    let Some(date) = string_param(params, "date")? else {
+        return Ok(None);
+    };
+    parse_date(&date, store.today())
+        .map(Some)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Invalid date '{}'", date)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{self, FixedClock};
+    use std::sync::Arc;
+
+    fn store() -> TaskStore {
+        let _ = clock::set_time_zone("UTC");
+        let mut store = TaskStore::with_clock(Arc::new(FixedClock(
+            "2024-06-12T09:00:00Z".parse().unwrap(),
+        )));
+        let today = store.today();
+        for (title, urgency, importance) in [("Email Bob", 3, 3), ("Plan trip", 1, 3)] {
+            store
+                .tasks
+                .push(Task::new(title.into(), urgency, importance, today));
+        }
+        store
+    }
+
+    #[test]
+    fn test_handle_line_errors() {
+        let parse = handle_line("{not json", run).unwrap();
+        assert_eq!(parse["error"]["code"], PARSE_ERROR);
+        assert_eq!(parse["id"], Value::Null);
+
+        let missing = handle_line(r#"{"jsonrpc":"2.0","id":7}"#, run).unwrap();
+        assert_eq!(missing["error"]["code"], INVALID_REQUEST);
+        assert_eq!(missing["id"], 7);
+
+        assert_eq!(handle_line(r#"{"jsonrpc":"2.0"}"#, run), None);
+    }
+
+    #[test]
+    fn test_call_reads_the_store() {
+        let mut store = store();
+        let list = call(&mut store, "tasks.list", &json!({ "date": "today" })).unwrap();
+        assert_eq!(list[0]["title"], "Email Bob");
+        assert_eq!(list[1]["quadrant"], "Schedule");
+
+        let matrix = call(&mut store, "matrix", &Value::Null).unwrap();
+        assert_eq!(matrix["date"], "2024-06-12");
+        assert_eq!(matrix["quadrants"]["DoFirst"][0]["title"], "Email Bob");
+        assert_eq!(matrix["quadrants"]["Drop"], json!([]));
+
+        let task = call(&mut store, "tasks.get", &json!({ "id": "trip" })).unwrap();
+        assert_eq!(task["title"], "Plan trip");
+
+        let stats = call(&mut store, "stats", &Value::Null).unwrap();
+        assert_eq!(stats["progress"]["planned"], 2);
+    }
+
+    #[test]
+    fn test_call_rejects_bad_params() {
+        let mut store = store();
+        let mut code =
+            |method: &str, params: Value| call(&mut store, method, &params).unwrap_err().code;
+        assert_eq!(
+            code("tasks.get", json!({ "id": "groceries" })),
+            TASK_NOT_FOUND
+        );
+        assert_eq!(code("tasks.get", json!({})), INVALID_PARAMS);
+        assert_eq!(
+            code("tasks.update", json!({ "id": "1", "urgency": 5 })),
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            code("tasks.list", json!({ "date": "someday" })),
+            INVALID_PARAMS
+        );
+        assert_eq!(code("tasks.purge", Value::Null), METHOD_NOT_FOUND);
+    }
+}
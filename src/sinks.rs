@@ -0,0 +1,187 @@
+use crate::ai::runtime;
+use crate::clock;
+use crate::config::Sink;
+use crate::i18n::quadrant_name;
+use crate::journal::render_matrix;
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use chrono::NaiveDate;
+use serde_json::json;
+use std::error::Error;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// How long a chat may take to accept a message before it is abandoned
+const POST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Completions in a day between "N done today" messages
+const MILESTONE_EVERY: usize = 5;
+
+/// Send `text` to one Slack webhook or Telegram chat
+pub fn post(sink: &Sink, text: &str) -> Result<(), Box<dyn Error>> {
+    let (url, body) = match sink {
+        Sink::Slack { webhook } => (webhook.clone(), json!({ "text": text })),
+        Sink::Telegram { bot_token, chat_id } => (
+            format!("https://api.telegram.org/bot{}/sendMessage", bot_token),
+            json!({ "chat_id": chat_id, "text": text }),
+        ),
+    };
+    runtime()?.block_on(async {
+        reqwest::Client::new()
+            .post(url)
+            .timeout(POST_TIMEOUT)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    })
+}
+
+/// Send `text` to every sink; a failed sink doesn't stop the rest, and the
+/// last failure is returned
+pub fn post_all(sinks: &[Sink], text: &str) -> Result<(), Box<dyn Error>> {
+    let mut result = Ok(());
+    for sink in sinks {
+        if let Err(e) = post(sink, text) {
+            result = Err(e);
+        }
+    }
+    result
+}
+
+/// The morning message: `date`'s pending tasks, grouped by quadrant
+pub fn morning_summary(date: NaiveDate, tasks: &[Task]) -> String {
+    let header = date.format("%A, %b %-d");
+    let pending = tasks
+        .iter()
+        .filter(|t| t.date == date && t.status == TaskStatus::Pending)
+        .count();
+    if pending == 0 {
+        return format!("{}: nothing planned", header);
+    }
+    let matrix = render_matrix(
+        date,
+        tasks,
+        |t| t.status == TaskStatus::Pending,
+        |quadrant| format!("\n{} {}\n", quadrant.badge(), quadrant_name(quadrant)),
+        |task| format!("• {}\n", task.title.replace('\n', " ")),
+    );
+    format!("{}: {} task(s) planned\n{}", header, pending, matrix)
+}
+
+/// A message worth sending now that `completed` is done, if any: the day's
+/// plan cleared, DO FIRST cleared, or every fifth completion of the day
+pub fn milestone(tasks: &[Task], completed: Uuid, today: NaiveDate) -> Option<String> {
+    let task = tasks.iter().find(|t| t.id == completed)?;
+    // Count `completed` as done even in tasks from before it was
+    let done = |t: &Task| t.id == completed || t.status == TaskStatus::Completed;
+    let open_today = |t: &&Task| t.date == today && t.status != TaskStatus::Dropped && !done(t);
+
+    if task.date == today {
+        let planned = tasks
+            .iter()
+            .filter(|t| t.date == today && t.status != TaskStatus::Dropped)
+            .count();
+        if !tasks.iter().any(|t| open_today(&t)) {
+            return Some(format!(
+                "Cleared today's plan: all {} task(s) done",
+                planned
+            ));
+        }
+        if task.quadrant() == Quadrant::DoFirst
+            && !tasks
+                .iter()
+                .filter(open_today)
+                .any(|t| t.quadrant() == Quadrant::DoFirst)
+        {
+            return Some(format!(
+                "{} is clear for today",
+                quadrant_name(Quadrant::DoFirst)
+            ));
+        }
+    }
+
+    let done_today = tasks
+        .iter()
+        .filter(|t| {
+            t.id == completed
+                || (t.status == TaskStatus::Completed
+                    && t.completed_at.is_some_and(|at| clock::date_of(at) == today))
+        })
+        .count();
+    (done_today % MILESTONE_EVERY == 0).then(|| format!("{} tasks done today", done_today))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 12).unwrap()
+    }
+
+    #[test]
+    fn test_morning_summary_lists_pending_by_quadrant() {
+        let mut done = Task::new("Ship release".into(), 3, 3, day());
+        done.complete();
+        let tasks = vec![
+            done,
+            Task::new("Fix outage".into(), 3, 3, day()),
+            Task::new("Plan Q3".into(), 1, 3, day()),
+            Task::new("Tomorrow's thing".into(), 3, 3, day().succ_opt().unwrap()),
+        ];
+        let text = morning_summary(day(), &tasks);
+        assert!(text.starts_with("Wednesday, Jun 12: 2 task(s) planned\n"));
+        assert!(text.contains("• Fix outage\n"));
+        assert!(text.contains("• Plan Q3\n"));
+        assert!(!text.contains("Ship release"));
+        assert!(!text.contains("Tomorrow"));
+        assert_eq!(
+            morning_summary(day(), &[]),
+            "Wednesday, Jun 12: nothing planned"
+        );
+    }
+
+    #[test]
+    fn test_milestones() {
+        let urgent = Task::new("Fix outage".into(), 3, 3, day());
+        let later = Task::new("Plan Q3".into(), 1, 3, day());
+        let tasks = vec![urgent.clone(), later.clone()];
+        assert_eq!(
+            milestone(&tasks, urgent.id, day()),
+            Some(format!(
+                "{} is clear for today",
+                quadrant_name(Quadrant::DoFirst)
+            ))
+        );
+
+        let mut urgent_done = urgent.clone();
+        urgent_done.status = TaskStatus::Completed;
+        let tasks = vec![urgent_done, later.clone()];
+        assert_eq!(
+            milestone(&tasks, later.id, day()),
+            Some(String::from("Cleared today's plan: all 2 task(s) done"))
+        );
+
+        // Fifth completion of the day, all planned for earlier days
+        let at = Utc.with_ymd_and_hms(2024, 6, 12, 12, 0, 0).unwrap();
+        let today = clock::date_of(at);
+        let yesterday = today.pred_opt().unwrap();
+        let mut tasks: Vec<Task> = (0..4)
+            .map(|i| {
+                let mut task = Task::new(format!("Old {}", i), 1, 1, yesterday);
+                task.status = TaskStatus::Completed;
+                task.completed_at = Some(at);
+                task
+            })
+            .collect();
+        let fifth = Task::new("Reply to Sam".into(), 1, 1, yesterday);
+        tasks.push(fifth.clone());
+        assert_eq!(
+            milestone(&tasks, fifth.id, today),
+            Some(String::from("5 tasks done today"))
+        );
+        assert_eq!(milestone(&tasks[1..], fifth.id, today), None);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::models::store::TaskStore;
+use crate::models::summary::Summary;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use std::error::Error;
+
+/// Today's summary from the cache, or from the store when the cache is
+/// missing or from another day. Never takes the store lock
+pub fn current(today: NaiveDate) -> Result<Summary, Box<dyn Error>> {
+    if let Some(summary) = Summary::load(today) {
+        return Ok(summary);
+    }
+    let store = TaskStore::load()?;
+    let summary = Summary::of(&store.tasks, today);
+    let _ = summary.save();
+    Ok(summary)
+}
+
+/// One line for a bar that shows plain text, such as polybar
+pub fn text(summary: &Summary) -> String {
+    let mut line = format!("{}/{} done", summary.done, summary.done + summary.pending);
+    if summary.overdue > 0 {
+        line.push_str(&format!(", {} overdue", summary.overdue));
+    }
+    line
+}
+
+/// Waybar's custom module JSON; `class` is `overdue`, `pending`, or `clear`
+/// for styling
+pub fn waybar(summary: &Summary) -> Value {
+    let total = summary.done + summary.pending;
+    let class = if summary.overdue > 0 {
+        "overdue"
+    } else if summary.pending > 0 {
+        "pending"
+    } else {
+        "clear"
+    };
+    let tooltip = if summary.top.is_empty() {
+        String::from("Nothing left today")
+    } else {
+        summary.top.join("\n")
+    };
+    json!({
+        "text": text(summary),
+        "tooltip": tooltip,
+        "class": class,
+        "percentage": (summary.done * 100).checked_div(total).unwrap_or(100),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_waybar_output() {
+        let summary = Summary {
+            date: NaiveDate::from_ymd_opt(2024, 6, 12).unwrap(),
+            pending: 3,
+            done: 1,
+            overdue: 2,
+            top: vec!["Q1 Fix outage".into(), "Q2 Plan Q3".into()],
+        };
+        let value = waybar(&summary);
+        assert_eq!(value["text"], "1/4 done, 2 overdue");
+        assert_eq!(value["tooltip"], "Q1 Fix outage\nQ2 Plan Q3");
+        assert_eq!(value["class"], "overdue");
+        assert_eq!(value["percentage"], 25);
+
+        let clear = waybar(&Summary::default());
+        assert_eq!(clear["class"], "clear");
+        assert_eq!(clear["tooltip"], "Nothing left today");
+    }
+}
@@ -0,0 +1,364 @@
+use crate::clock;
+use crate::models::store::TaskStore;
+use crate::models::task::{Task, TaskStatus};
+use crate::storage::paths::{archive_file_path, data_dir, focus_sessions_path, history_log_path};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use uuid::Uuid;
+
+/// Kept out of the repository: caches, debug logs, the lock file,
+/// half-written saves, each machine's own CalDAV sync state, and the config
+/// and chat history, which hold API tokens and private conversations
+const IGNORED: &[&str] = &[
+    "cache/",
+    "logs/",
+    "tasks.lock",
+    "*.tmp",
+    "caldav.json",
+    "config.json",
+    "chat_history.json",
+];
+
+/// Logs merged line by line, with the field that orders their entries
+const LOGS: &[(&str, &str)] = &[
+    ("history.jsonl", "timestamp"),
+    ("focus_sessions.jsonl", "ended_at"),
+];
+
+/// Commit the data directory and exchange it with the `origin` remote,
+/// setting `origin` to `remote` first when given. When both machines
+/// changed things, tasks are merged by ID and the logs by entry, so no
+/// edit is lost. The caller holds the store lock
+pub fn sync(remote: Option<&str>) -> Result<(), Box<dyn Error>> {
+    let dir = data_dir()?;
+    if !dir.join(".git").exists() {
+        git(&dir, &["init", "--quiet"])?;
+        println!("Started a git repository in {}", dir.display());
+    }
+    if let Some(url) = remote {
+        let verb = if git(&dir, &["remote", "get-url", "origin"]).is_ok() {
+            "set-url"
+        } else {
+            "add"
+        };
+        git(&dir, &["remote", verb, "origin", url])?;
+    }
+
+    stage(&dir)?;
+    if !git(&dir, &["status", "--porcelain"])?.is_empty() {
+        let message = format!("eq sync {}", clock::now_local().format("%Y-%m-%d %H:%M"));
+        git(&dir, &["commit", "--quiet", "-m", &message])?;
+    }
+    if git(&dir, &["remote", "get-url", "origin"]).is_err() {
+        println!("Committed locally. Add a remote with `eq sync --remote <url>` to share it.");
+        return Ok(());
+    }
+
+    let branch = git(&dir, &["symbolic-ref", "--short", "HEAD"])?;
+    git(&dir, &["fetch", "--quiet", "origin"])?;
+    let upstream = format!("origin/{}", branch);
+    if git(&dir, &["rev-parse", "--verify", "--quiet", &upstream]).is_err() {
+        git(&dir, &["push", "--quiet", "-u", "origin", &branch])?;
+        println!("Pushed to origin/{}", branch);
+        return Ok(());
+    }
+
+    let head = git(&dir, &["rev-parse", "HEAD"])?;
+    let theirs = git(&dir, &["rev-parse", &upstream])?;
+    if head == theirs {
+        println!("Already in sync");
+    } else if is_ancestor(&dir, &theirs, &head) {
+        git(&dir, &["push", "--quiet", "origin", &branch])?;
+        println!("Pushed to origin/{}", branch);
+    } else if is_ancestor(&dir, &head, &theirs) {
+        git(&dir, &["merge", "--quiet", "--ff-only", &upstream])?;
+        println!("Pulled from origin/{}", branch);
+    } else {
+        merge(&dir, &head, &theirs)?;
+        git(&dir, &["push", "--quiet", "origin", &branch])?;
+        println!("Merged with origin/{} and pushed", branch);
+    }
+    Ok(())
+}
+
+/// Stage everything in `dir` but the `IGNORED` files, untracking any an
+/// older version committed
+fn stage(dir: &Path) -> Result<(), Box<dyn Error>> {
+    let path = dir.join(".gitignore");
+    let existing = fs::read_to_string(&path).unwrap_or_default();
+    if let Some(merged) = with_ignored(&existing) {
+        fs::write(&path, merged)?;
+    }
+    let mut untrack = vec!["rm", "--cached", "--quiet", "--ignore-unmatch", "--"];
+    untrack.extend(IGNORED.iter().filter(|p| !p.ends_with('/')));
+    git(dir, &untrack)?;
+    git(dir, &["add", "--all"])?;
+    Ok(())
+}
+
+/// `gitignore` with the missing `IGNORED` lines added after the user's own,
+/// or `None` when it has them all
+fn with_ignored(gitignore: &str) -> Option<String> {
+    let missing: Vec<&str> = IGNORED
+        .iter()
+        .copied()
+        .filter(|pattern| !gitignore.lines().any(|line| line.trim() == *pattern))
+        .collect();
+    if missing.is_empty() {
+        return None;
+    }
+    let mut merged = gitignore.to_string();
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+    for pattern in missing {
+        merged.push_str(pattern);
+        merged.push('\n');
+    }
+    Some(merged)
+}
+
+/// Merge `theirs` into the checked-out `head`. Git merges everything else,
+/// keeping this machine's side of a conflict; tasks and logs are merged by
+/// ID on top
+fn merge(dir: &Path, head: &str, theirs: &str) -> Result<(), Box<dyn Error>> {
+    let base = git(dir, &["merge-base", head, theirs])?;
+    let tasks = |rev: &str, file: &str| -> Result<Vec<Task>, Box<dyn Error>> {
+        match show(dir, rev, file) {
+            Some(json) => Ok(serde_json::from_str::<TaskStore>(&json)?.tasks),
+            None => Ok(Vec::new()),
+        }
+    };
+    let [merged, archived] = ["tasks.json", "archive.json"].map(|file| {
+        Ok::<_, Box<dyn Error>>(merge_tasks(
+            &tasks(&base, file)?,
+            &tasks(head, file)?,
+            &tasks(theirs, file)?,
+        ))
+    });
+    let (merged, archived) = (merged?, archived?);
+    let logs: Vec<String> = LOGS
+        .iter()
+        .map(|(file, time_key)| {
+            merge_lines(
+                &show(dir, head, file).unwrap_or_default(),
+                &show(dir, theirs, file).unwrap_or_default(),
+                time_key,
+            )
+        })
+        .collect();
+
+    if let Err(err) = git(
+        dir,
+        &[
+            "merge",
+            "--quiet",
+            "--no-commit",
+            "--no-ff",
+            "-X",
+            "ours",
+            theirs,
+        ],
+    ) {
+        let _ = git(dir, &["merge", "--abort"]);
+        return Err(err);
+    }
+    let mut archive = TaskStore::default();
+    archive.tasks = archived;
+    fs::write(
+        archive_file_path()?,
+        serde_json::to_string_pretty(&archive)?,
+    )?;
+    // Saved with history, so tasks archived on one machine and not yet on
+    // the other end up in one file
+    let mut store = TaskStore::default();
+    store.tasks = merged;
+    store.load_history()?;
+    store.save()?;
+    fs::write(history_log_path()?, &logs[0])?;
+    fs::write(focus_sessions_path()?, &logs[1])?;
+    stage(dir)?;
+    git(dir, &["commit", "--quiet", "-m", "eq sync: merge"])?;
+    Ok(())
+}
+
+/// Three-way merge of task lists by ID. A task changed on one side takes
+/// that side's version; changed on both, a finished version wins over a
+/// pending one, and otherwise this machine's. Tasks removed on one side
+/// and untouched on the other stay removed
+pub fn merge_tasks(base: &[Task], local: &[Task], remote: &[Task]) -> Vec<Task> {
+    let by_id = |tasks: &[Task]| -> HashMap<Uuid, Task> {
+        tasks.iter().map(|t| (t.id, t.clone())).collect()
+    };
+    let (base, theirs) = (by_id(base), by_id(remote));
+    let ours: HashSet<Uuid> = local.iter().map(|t| t.id).collect();
+
+    let mut merged = Vec::new();
+    for task in local {
+        match (base.get(&task.id), theirs.get(&task.id)) {
+            (base, Some(remote)) => merged.push(pick(base, task, remote).clone()),
+            (Some(base), None) if base == task => {}
+            (_, None) => merged.push(task.clone()),
+        }
+    }
+    for task in remote.iter().filter(|t| !ours.contains(&t.id)) {
+        if base.get(&task.id) != Some(task) {
+            merged.push(task.clone());
+        }
+    }
+    merged
+}
+
+fn pick<'a>(base: Option<&Task>, local: &'a Task, remote: &'a Task) -> &'a Task {
+    if base == Some(remote) {
+        local
+    } else if base == Some(local)
+        || (local.status == TaskStatus::Pending && remote.status != TaskStatus::Pending)
+    {
+        remote
+    } else {
+        local
+    }
+}
+
+/// Union of two JSON-lines logs, each entry once by its `id`, ordered by
+/// the `time_key` field
+pub fn merge_lines(local: &str, remote: &str, time_key: &str) -> String {
+    let mut seen = HashSet::new();
+    let mut entries: Vec<(Option<DateTime<Utc>>, &str)> = Vec::new();
+    for line in local.lines().chain(remote.lines()) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: Option<Value> = serde_json::from_str(line).ok();
+        let key = value
+            .as_ref()
+            .and_then(|v| v["id"].as_str())
+            .unwrap_or(line)
+            .to_string();
+        if !seen.insert(key) {
+            continue;
+        }
+        let time = value
+            .as_ref()
+            .and_then(|v| v[time_key].as_str())
+            .and_then(|t| t.parse().ok());
+        entries.push((time, line));
+    }
+    entries.sort_by_key(|(time, _)| *time);
+    entries
+        .iter()
+        .map(|(_, line)| format!("{}\n", line))
+        .collect()
+}
+
+fn is_ancestor(dir: &Path, ancestor: &str, of: &str) -> bool {
+    git(dir, &["merge-base", "--is-ancestor", ancestor, of]).is_ok()
+}
+
+/// `file` as of `rev`, or `None` when it did not exist then
+fn show(dir: &Path, rev: &str, file: &str) -> Option<String> {
+    git(dir, &["show", &format!("{}:{}", rev, file)]).ok()
+}
+
+/// Run git in `dir`, returning its output; failures carry git's message
+fn git(dir: &Path, args: &[&str]) -> Result<String, Box<dyn Error>> {
+    let output = Command::new("git").arg("-C").arg(dir).args(args).output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {}: {}", args[0], stderr.trim()).into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_merge_tasks_by_id() {
+        let day = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+        let base = vec![
+            Task::new("Edited here".into(), 1, 1, day),
+            Task::new("Done there".into(), 2, 2, day),
+            Task::new("Removed there".into(), 1, 2, day),
+        ];
+        let mut local = base.clone();
+        local[0].urgency = 3;
+        local.push(Task::new("Added here".into(), 1, 1, day));
+        let mut remote = base.clone();
+        remote[1].complete();
+        remote.remove(2);
+        remote.push(Task::new("Added there".into(), 1, 1, day));
+
+        let merged = merge_tasks(&base, &local, &remote);
+        let titles: Vec<&str> = merged.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            ["Edited here", "Done there", "Added here", "Added there"]
+        );
+        assert_eq!(merged[0].urgency, 3);
+        assert_eq!(merged[1].status, TaskStatus::Completed);
+    }
+
+    #[test]
+    fn test_merge_tasks_keeps_finished_work_on_conflict() {
+        let day = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+        let base = vec![Task::new("Report".into(), 2, 2, day)];
+        let mut local = base.clone();
+        local[0].title = "Write report".into();
+        let mut remote = base.clone();
+        remote[0].drop_task();
+
+        let merged = merge_tasks(&base, &local, &remote);
+        assert_eq!(merged[0].status, TaskStatus::Dropped);
+    }
+
+    #[test]
+    fn test_with_ignored_keeps_the_users_lines() {
+        let merged = with_ignored("notes/\ncache/").unwrap();
+        assert!(merged.starts_with("notes/\ncache/\nlogs/\n"));
+        assert_eq!(merged.matches("cache/").count(), 1);
+        assert_eq!(with_ignored(&merged), None);
+    }
+
+    #[test]
+    fn test_stage_leaves_out_config_and_chat() {
+        let dir = std::env::temp_dir().join(format!("eq-sync-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        git(&dir, &["init", "--quiet"]).unwrap();
+        for file in ["tasks.json", "config.json", "chat_history.json"] {
+            fs::write(dir.join(file), "{}").unwrap();
+        }
+        // Committed by a version that didn't ignore it
+        git(&dir, &["add", "config.json"]).unwrap();
+
+        stage(&dir).unwrap();
+        let staged = git(&dir, &["diff", "--cached", "--name-only"]).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert_eq!(
+            staged.lines().collect::<Vec<_>>(),
+            [".gitignore", "tasks.json"]
+        );
+    }
+
+    #[test]
+    fn test_merge_lines_unions_by_id_in_time_order() {
+        let local = "{\"id\":\"a\",\"timestamp\":\"2024-06-12T09:00:00Z\"}\n\
+                     {\"id\":\"c\",\"timestamp\":\"2024-06-12T11:00:00Z\"}\n";
+        let remote = "{\"id\":\"a\",\"timestamp\":\"2024-06-12T09:00:00Z\"}\n\
+                      {\"id\":\"b\",\"timestamp\":\"2024-06-12T10:00:00Z\"}\n";
+        let merged = merge_lines(local, remote, "timestamp");
+        let ids: Vec<&str> = merged.lines().map(|line| &line[7..8]).collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+    }
+}
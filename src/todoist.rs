@@ -0,0 +1,211 @@
+use crate::ai::runtime;
+use crate::models::store::TaskStore;
+use crate::models::task::TaskStatus;
+use crate::parser::input::TaskInput;
+use crate::storage::paths::todoist_state_path;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use uuid::Uuid;
+
+const TASKS_URL: &str = "https://api.todoist.com/api/v1/tasks";
+
+/// A Todoist task as eq imports it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Imported {
+    /// Content with the labels added as `#tags`
+    pub title: String,
+    pub priority: Option<(u8, u8)>,
+    pub due: Option<NaiveDate>,
+}
+
+impl Imported {
+    /// An open task from the API, with its ID
+    pub fn from_json(task: &Value) -> Option<(String, Self)> {
+        let id = match &task["id"] {
+            Value::String(id) => id.clone(),
+            Value::Number(id) => id.to_string(),
+            _ => return None,
+        };
+        let mut title = task["content"].as_str()?.trim().to_string();
+        for label in task["labels"].as_array().into_iter().flatten() {
+            if let Some(label) = label.as_str() {
+                let tag: String = label
+                    .chars()
+                    .map(|c| if c.is_whitespace() { '_' } else { c })
+                    .collect();
+                title.push_str(&format!(" #{}", tag));
+            }
+        }
+        let due = task["due"]["date"]
+            .as_str()
+            .and_then(|date| date.get(..10))
+            .and_then(|date| date.parse().ok());
+        let priority = task["priority"].as_u64().and_then(priority);
+        Some((
+            id,
+            Self {
+                title,
+                priority,
+                due,
+            },
+        ))
+    }
+}
+
+/// Urgency and importance for Todoist's API priority, where 4 is what the
+/// app shows as P1. P4, the default, leaves the priority to the deadline
+pub fn priority(api_priority: u64) -> Option<(u8, u8)> {
+    match api_priority {
+        4 => Some((3, 3)),
+        3 => Some((2, 3)),
+        2 => Some((1, 2)),
+        _ => None,
+    }
+}
+
+/// The task ID for a Todoist task, the same on every machine
+pub fn task_id(todoist_id: &str) -> Uuid {
+    let url = format!("https://app.todoist.com/app/task/{}", todoist_id);
+    Uuid::new_v5(&Uuid::NAMESPACE_URL, url.as_bytes())
+}
+
+/// Counts from one pull
+#[derive(Debug, Default, PartialEq)]
+pub struct Pulled {
+    pub added: usize,
+    pub updated: usize,
+    pub closed: usize,
+}
+
+/// `todoist.json`: Todoist's open tasks as of the last pull, by Todoist ID
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct PullState {
+    pub tasks: HashMap<String, Imported>,
+}
+
+impl PullState {
+    pub fn load() -> Self {
+        todoist_state_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        let path = todoist_state_path()?;
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// Fetch every open task from Todoist
+pub fn fetch(token: &str) -> Result<Vec<(String, Imported)>, Box<dyn Error>> {
+    runtime()?.block_on(async {
+        let client = reqwest::Client::new();
+        let mut tasks = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let mut request = client
+                .get(TASKS_URL)
+                .bearer_auth(token)
+                .query(&[("limit", "200")]);
+            if let Some(cursor) = &cursor {
+                request = request.query(&[("cursor", cursor)]);
+            }
+            let page: Value = request.send().await?.error_for_status()?.json().await?;
+            tasks.extend(
+                page["results"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(Imported::from_json),
+            );
+            match page["next_cursor"].as_str() {
+                Some(next) => cursor = Some(next.to_string()),
+                None => return Ok::<_, Box<dyn Error>>(tasks),
+            }
+        }
+    })
+}
+
+/// Bring Todoist's open tasks into `store`. New tasks are added; a task
+/// changed in Todoist since the last pull takes the change, unless it is
+/// finished here; one closed or deleted there is completed here
+pub fn pull(store: &mut TaskStore, state: &mut PullState, open: Vec<(String, Imported)>) -> Pulled {
+    let today = store.today();
+    let mut pulled = Pulled::default();
+    let mut still_open = HashMap::new();
+
+    for (todoist_id, imported) in open {
+        let id = task_id(&todoist_id);
+        match store.tasks.iter().find(|t| t.id == id).cloned() {
+            None => {
+                let input = TaskInput {
+                    title: imported.title.clone(),
+                    priority: imported.priority,
+                    // Planned for the deadline, or today once it has passed
+                    date: imported.due.map(|due| due.max(today)),
+                    due: imported.due,
+                    ..TaskInput::default()
+                };
+                let mut task = input.into_task(today);
+                task.id = id;
+                store.add_task(task);
+                pulled.added += 1;
+            }
+            Some(task) if task.status == TaskStatus::Pending => {
+                if state.tasks.get(&todoist_id) != Some(&imported) {
+                    let (urgency, importance) =
+                        imported.priority.unwrap_or((task.urgency, task.importance));
+                    store.update_task(id, imported.title.clone(), urgency, importance);
+                    if let Some(task) = store.tasks.iter_mut().find(|t| t.id == id) {
+                        task.due = imported.due;
+                    }
+                    pulled.updated += 1;
+                }
+            }
+            Some(_) => {}
+        }
+        still_open.insert(todoist_id, imported);
+    }
+
+    for todoist_id in state.tasks.keys() {
+        if !still_open.contains_key(todoist_id) && store.complete_task(task_id(todoist_id)) {
+            pulled.closed += 1;
+        }
+    }
+    state.tasks = still_open;
+    pulled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_from_json_maps_priority_labels_and_due() {
+        let task = json!({
+            "id": "6X7rM8997g3RQmvh",
+            "content": "Renew passport",
+            "priority": 4,
+            "labels": ["errands", "before trip"],
+            "due": { "date": "2024-06-14", "is_recurring": false },
+        });
+        let (id, imported) = Imported::from_json(&task).unwrap();
+        assert_eq!(id, "6X7rM8997g3RQmvh");
+        assert_eq!(imported.title, "Renew passport #errands #before_trip");
+        assert_eq!(imported.priority, Some((3, 3)));
+        assert_eq!(imported.due, NaiveDate::from_ymd_opt(2024, 6, 14));
+        assert_eq!(priority(1), None);
+        assert_eq!(task_id(&id), task_id("6X7rM8997g3RQmvh"));
+        assert_ne!(task_id(&id), task_id("6X7rM8997g3RQmvi"));
+    }
+}
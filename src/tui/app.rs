@@ -1,59 +1,293 @@
-use crate::models::store::TaskStore;
-use crate::models::task::{Quadrant, TaskStatus};
-use chrono::{Duration, Local, NaiveDate};
+use crate::models::focus::{append_session, FocusSession};
+use crate::models::store::{TaskStore, ARCHIVE_AFTER_DAYS};
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use chrono::{Datelike, Duration, NaiveDate};
 use crossterm::{
-    event::{self},
+    cursor::Show,
+    event::{
+        DisableBracketedPaste, EnableBracketedPaste, Event, EventStream, KeyCode, KeyModifiers,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
+use futures_core::Stream;
+use std::collections::{HashMap, HashSet};
+use std::future::{poll_fn, Future};
 use std::io;
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll};
+use std::time::{Duration as StdDuration, Instant};
 
-use super::zen::ZenState;
-use crate::ai::{AIClient, AIResponse, ChatMessage};
-use crate::parser::ai_commands::{
-    parse_commands, AICommand, CommandResults, TaskIdentifier,
+use super::kanban::{classify, KanbanColumn};
+use super::clipboard::{task_text, SystemClipboard};
+use super::command::{ChatCommand, CHAT_COMMANDS};
+use super::editor::LineEditor;
+use super::filter::TaskFilter;
+use super::keymap::{help_index, help_offset};
+use super::review::{DailyReview, ReviewChoice};
+use super::saver::Saver;
+use super::scroll::ScrollState;
+use super::theme::Theme;
+use super::toast::Toast;
+use super::triage::{Triage, TriageChoice};
+use super::widgets::calendar::DatePicker;
+use crate::config::{Config, ModelSettings};
+use super::zen::{Motion, Pomodoro, ZenState, BREAK_MINUTES};
+use crate::i18n::quadrant_name;
+use crate::journal::{default_journal_dir, write_note};
+use crate::models::focus::read_sessions;
+use crate::models::log::{read_log, read_log_since, LogEvent};
+use crate::plan::{capacity_line, plan_candidates, planning_prompt, Candidate, PLAN_HORIZON_DAYS};
+use crate::serve::{self, Call};
+use crate::similar::{find_repeat, match_title, repeat_candidates, Repeat, TitleMatch};
+use crate::stats::{
+    carried_days, day_progress, do_first_overload, focus_stats, forecast, record_postponements,
+    week_start, DayProgress, DoFirstOverload, FocusStats, Postponements, DO_FIRST_SHARE,
+    VELOCITY_WEEKS,
 };
-use std::sync::mpsc;
+use crate::timings;
+use crate::weekly::{
+    render_retro, render_weekly_review, retro_note_name, retro_prompt, week_log, weekly_note_name,
+    WeeklyReview,
+};
+use crate::ai::context::{summary_due, task_context, trimmed_history, with_summary};
+use crate::ai::repeat::{parse_repeat_reply, repeat_prompt, REPEAT_SYSTEM};
+use crate::ai::{runtime, AIClient, AIResponse, ChatMessage};
+use crate::audit::{audit_prompt, audit_tasks};
+use crate::parser::ai_commands::{AICommand, CommandResults, TaskIdentifier};
+use crate::parser::date::parse_date;
+use crate::parser::input::parse_input;
+use crate::quotes::random_quote;
+use crate::voice::{memo_prompt, memo_tasks, transcribe};
+use std::path::PathBuf;
+use tokio::runtime::Runtime;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::task::AbortHandle;
 
+#[derive(Debug)]
 pub enum CurrentScreen {
     Main,
     Editing,
     Chat,
-    Focus,   // Full-screen quadrant view
-    ZenMode, // Single task focus mode
+    Focus,        // Full-screen quadrant view
+    ZenMode,      // Single task focus mode
+    Stats,        // Focus session statistics
+    Week,         // Seven-day overview
+    Review,       // End-of-day review of unfinished tasks
+    Triage,       // Demoting DO FIRST tasks on an overloaded day
+    Day,          // A day's morning plan against what got done
+    Plan,         // Morning planning from carry-overs and backlog
+    WeeklyReview, // Week's outcomes and what to drop or delegate next
     Exiting,
 }
 
+/// Which task statuses the matrix and Focus screen show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Visibility {
+    Pending,
+    PendingAndCompleted,
+    All,
+}
+
+impl Visibility {
+    pub fn shows(&self, status: TaskStatus) -> bool {
+        match self {
+            Visibility::Pending => status == TaskStatus::Pending,
+            Visibility::PendingAndCompleted => status != TaskStatus::Dropped,
+            Visibility::All => true,
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Visibility::Pending => Visibility::PendingAndCompleted,
+            Visibility::PendingAndCompleted => Visibility::All,
+            Visibility::All => Visibility::Pending,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Visibility::Pending => "pending",
+            Visibility::PendingAndCompleted => "pending+done",
+            Visibility::All => "all",
+        }
+    }
+}
+
+/// How the main screen arranges the day's tasks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewLayout {
+    Matrix, // 2x2 Eisenhower grid
+    List,   // One ranked list with quadrant badges
+    Kanban, // Status columns: backlog, today, in focus, done
+}
+
+impl ViewLayout {
+    pub fn next(&self) -> Self {
+        match self {
+            ViewLayout::Matrix => ViewLayout::List,
+            ViewLayout::List => ViewLayout::Kanban,
+            ViewLayout::Kanban => ViewLayout::Matrix,
+        }
+    }
+}
+
+/// Destructive action held back until the user confirms it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingAction {
+    Drop(Vec<uuid::Uuid>),
+    Complete(Vec<uuid::Uuid>),
+    Move(Vec<uuid::Uuid>, NaiveDate),
+    ClearChat,
+    /// Fold `App::pending_add` into this task instead of adding it: a bump
+    /// for the same task on its day, a merge for a similar one
+    Fold(uuid::Uuid, Repeat),
+}
+
+/// New task held back while the AI looks for a reworded repeat of it
+pub struct RepeatCheck {
+    pub task: Task,
+    /// Tasks the AI was shown, in the order it numbers them
+    pub ids: Vec<uuid::Uuid>,
+    pub receiver: UnboundedReceiver<Result<String, String>>,
+}
+
+/// Ordering of tasks within a quadrant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+    Score,
+    Created,
+    Alpha,
+    Manual, // User-arranged with J/K
+}
+
+impl SortMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "score" | "" => Some(SortMode::Score),
+            "created" | "age" => Some(SortMode::Created),
+            "alpha" | "title" | "name" => Some(SortMode::Alpha),
+            "manual" | "custom" => Some(SortMode::Manual),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Score => "score",
+            SortMode::Created => "created",
+            SortMode::Alpha => "alpha",
+            SortMode::Manual => "manual",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            SortMode::Score => SortMode::Created,
+            SortMode::Created => SortMode::Alpha,
+            SortMode::Alpha => SortMode::Manual,
+            SortMode::Manual => SortMode::Score,
+        }
+    }
+
+    pub fn sort(&self, tasks: &mut [&Task]) {
+        match self {
+            SortMode::Score => tasks.sort_by_key(|t| std::cmp::Reverse(t.score())),
+            SortMode::Created => tasks.sort_by_key(|t| t.created_at),
+            SortMode::Alpha => tasks.sort_by_key(|t| t.title.to_lowercase()),
+            SortMode::Manual => tasks.sort_by_key(|t| (t.position.is_none(), t.position)),
+        }
+    }
+}
+
 pub struct App<'a> {
     pub store: &'a mut TaskStore,
     pub current_screen: CurrentScreen,
     pub selected_quadrant: Quadrant,
     pub selected_task_index: usize,
     pub view_date: NaiveDate,
-    pub input_buffer: String,
+    pub today: NaiveDate, // Calendar day last seen by the event loop
+    pub input_buffer: LineEditor,
     pub input_mode: bool,
     pub editing_task_id: Option<uuid::Uuid>,
+    pub detail_task: Option<uuid::Uuid>,
     pub show_help: bool,
+    pub help_scroll: u16, // First visible row of the help overlay
+    pub visibility: Visibility,
+    pub week_day_index: usize,  // Selected column in the week view (0 = Monday)
+    pub week_task_index: usize, // Selected task within that column
+    pub date_picker: Option<DatePicker>, // Calendar popup for goto/move
+    pub marked_tasks: HashSet<uuid::Uuid>, // Multi-selection for batch actions
+    pub visual_anchor: Option<usize>,      // Start of a pending `V` range
+    pub move_pending: bool,                // `m` pressed, awaiting a direction
+    pub confirm: Option<PendingAction>,    // Action awaiting a y/n answer
+    pub toast: Option<Toast>,              // Transient status message
+    pub command_input: Option<String>,     // `:` palette line while open
+    pub command_error: Option<String>,     // Last palette error, shown beside the line
+    pub filter: Option<TaskFilter>,        // Active `/` filter
+    pub filter_input: Option<LineEditor>,  // `/` prompt while open
+    pub tag_input: Option<LineEditor>,     // `#` retag prompt while open
+    pub sort_mode: SortMode,
+    pub motion: Motion,
+    pub layout: ViewLayout,
+    pub kanban_column: KanbanColumn,
+    pub focused_today: HashSet<uuid::Uuid>, // Tasks with a focus session on `focused_for`
+    pub focused_for: Option<NaiveDate>,
+    pub scroll: HashMap<Quadrant, ScrollState>, // Per-quadrant view offsets
+    pub compact: bool, // Terminal too small for the 2x2 grid; set while rendering
+    pub config: Config,
+    pub theme: Theme,
 
     // AI Chat State
     pub chat_history: Vec<ChatMessage>,
-    pub chat_input: String,
-    pub ai_client: Option<AIClient>,
-    pub chat_receiver: Option<mpsc::Receiver<AIResponse>>,
+    pub chat_input: LineEditor,
+    pub ai_client: Result<AIClient, String>, // Err says which key is missing
+    pub chat_overrides: ModelSettings,       // Set with /model, /temp, /tokens
+    pub chat_receiver: Option<UnboundedReceiver<AIResponse>>,
+    pub chat_task: Option<AbortHandle>,      // Aborted to cancel the request
+    pub chat_partial: String,                // Reply text streamed in so far
     pub is_loading: bool,
     pub chat_scroll: u16,
     pub chat_auto_scroll: bool,
-    pub show_chat_help: bool,        // Fix #5: Chat help toggle
-    pub spinner_state: u8,           // Spinner animation state
-    pub zen_state: Option<ZenState>, // Zen mode state with particles and pomodoro
+    pub chat_withheld: usize,                // Tasks kept out of the last request
+    pub chat_selected: Option<usize>,        // Message picked for copying
+    pub chat_summary: Option<String>,        // Rolling summary of older messages
+    pub chat_summarized: usize,              // Leading messages the summary covers
+    pub summary_receiver: Option<(usize, UnboundedReceiver<Result<String, String>>)>,
+    pub transcript_receiver: Option<UnboundedReceiver<Result<String, String>>>,
+    header_stats: Option<((u64, NaiveDate), DayProgress, Option<String>)>,
+    /// Set when reading the archive failed, so it isn't retried every frame
+    archive_failed: bool,
+    /// Moves read from the history log so far, for the age of each task
+    pub postponements: HashMap<uuid::Uuid, Postponements>,
+    /// Where the history log was read up to, and at which store revision
+    log_read: (u64, Option<u64>),
+    pub saver: Saver,
+    /// Why the last save failed, until one succeeds; shown as a banner
+    pub save_error: Option<String>,
+    pub bus_calls: Option<UnboundedReceiver<Call>>,
+    pub spinner_state: u8,                   // Spinner animation state
+    pub zen_state: Option<ZenState>,         // Zen mode particles and breathing
+    pub pomodoro: Option<Pomodoro>,          // Keeps running when Zen mode is left
+    pub focus_stats: Option<FocusStats>,     // Snapshot shown on the stats screen
+    pub review: Option<DailyReview>,         // End-of-day review in progress
+    pub triage: Option<Triage>,              // DO FIRST re-triage in progress
+    pub day_events: Vec<LogEvent>,           // History log behind the day screen
+    pub plan_index: usize,                   // Selected candidate on the plan screen
+    pub weekly_review: Option<WeeklyReview>, // Weekly review in progress
+    pub retro_week: Option<NaiveDate>,       // Week whose retrospective is awaited
+    pub pending_add: Option<Task>,           // New task held back by a merge or bump prompt
+    pub repeat_check: Option<RepeatCheck>,   // New task held back for the AI repeat check
+    pub clipboard: SystemClipboard,          // Opened on first copy
 
     // Pending AI commands
     pub pending_commands: Vec<AICommand>,
 }
 
 impl<'a> App<'a> {
-    pub fn new(store: &'a mut TaskStore) -> App<'a> {
+    pub fn new(store: &'a mut TaskStore, runtime: &Runtime) -> App<'a> {
         // Fix #8: Load persisted chat history
         let saved_history = TaskStore::load_chat_history();
         let chat_history: Vec<ChatMessage> = saved_history
@@ -64,33 +298,105 @@ impl<'a> App<'a> {
             })
             .collect();
 
+        let config = Config::load_or_default();
+        let sort_mode = config
+            .sort
+            .as_deref()
+            .and_then(SortMode::from_name)
+            .unwrap_or(SortMode::Score);
+        let motion = config
+            .motion
+            .as_deref()
+            .and_then(Motion::from_name)
+            .unwrap_or_default();
+        let theme = Theme::from_config(&config);
+        let ai_client = AIClient::from_config(&config);
+        let today = store.today();
+        let saver = Saver::new(store, runtime);
+        let toast = config
+            .load_error()
+            .map(|err| Toast::error(format!("Using default settings: {}", err)));
+
         App {
             store,
             current_screen: CurrentScreen::Main,
             selected_quadrant: Quadrant::DoFirst,
             selected_task_index: 0,
-            view_date: Local::now().date_naive(),
-            input_buffer: String::new(),
+            view_date: today,
+            today,
+            input_buffer: LineEditor::new(),
             input_mode: false,
             editing_task_id: None,
+            detail_task: None,
             show_help: false,
+            help_scroll: 0,
+            visibility: Visibility::PendingAndCompleted,
+            week_day_index: 0,
+            week_task_index: 0,
+            date_picker: None,
+            marked_tasks: HashSet::new(),
+            visual_anchor: None,
+            move_pending: false,
+            confirm: None,
+            toast,
+            command_input: None,
+            command_error: None,
+            filter: None,
+            filter_input: None,
+            tag_input: None,
+            sort_mode,
+            motion,
+            layout: ViewLayout::Matrix,
+            kanban_column: KanbanColumn::Today,
+            focused_today: HashSet::new(),
+            focused_for: None,
+            scroll: HashMap::new(),
+            compact: false,
+            config,
+            theme,
 
             chat_history,
-            chat_input: String::new(),
-            ai_client: AIClient::new(),
+            chat_input: LineEditor::new(),
+            ai_client,
+            chat_overrides: ModelSettings::default(),
             chat_receiver: None,
+            chat_task: None,
+            chat_partial: String::new(),
             is_loading: false,
             chat_scroll: 0,
             chat_auto_scroll: true,
-            show_chat_help: false,
+            chat_selected: None,
+            chat_withheld: 0,
+            chat_summary: None,
+            chat_summarized: 0,
+            summary_receiver: None,
+            transcript_receiver: None,
+            header_stats: None,
+            bus_calls: None,
+            archive_failed: false,
+            postponements: HashMap::new(),
+            log_read: (0, None),
+            saver,
+            save_error: None,
             spinner_state: 0,
             zen_state: None,
+            pomodoro: None,
+            focus_stats: None,
+            review: None,
+            triage: None,
+            day_events: Vec::new(),
+            plan_index: 0,
+            weekly_review: None,
+            retro_week: None,
+            pending_add: None,
+            repeat_check: None,
+            clipboard: SystemClipboard::default(),
             pending_commands: Vec::new(),
         }
     }
 
     pub fn toggle_view_date(&mut self) {
-        let today = Local::now().date_naive();
+        let today = self.store.today();
         if self.view_date == today {
             self.view_date = today + Duration::days(1);
         } else {
@@ -98,17 +404,151 @@ impl<'a> App<'a> {
         }
     }
 
-    /// Fix #4: Get task count for current quadrant and clamp index if needed
-    pub fn get_current_task_count(&self) -> usize {
-        self.store
+    /// Follow the calendar past midnight: a view of "today" moves on to the
+    /// new day, and unfinished work left on the old one is pointed out.
+    /// Returns whether the day changed
+    pub fn check_day_change(&mut self) -> bool {
+        let today = self.store.today();
+        if today == self.today {
+            return false;
+        }
+        let yesterday = std::mem::replace(&mut self.today, today);
+        if self.view_date == yesterday {
+            self.view_date = today;
+            self.clamp_selected_index();
+        }
+
+        let unfinished = self
+            .store
             .tasks
             .iter()
+            .filter(|t| t.date == yesterday && t.status == TaskStatus::Pending)
+            .count();
+        let mut message = format!("New day: {}", today.format("%a %b %d"));
+        if unfinished > 0 {
+            message.push_str(&format!(
+                "; {} unfinished on {} (y to review)",
+                unfinished,
+                yesterday.format("%a")
+            ));
+        }
+        let reprioritized = self.store.refresh_due_urgency(today);
+        if reprioritized > 0 {
+            message.push_str(&format!("; {} task(s) more urgent by deadline", reprioritized));
+            self.save_with(message);
+        } else {
+            self.notify(message);
+        }
+        true
+    }
+
+    /// How often the screen animates without input: Zen's particles and
+    /// timer, the chat spinner while a reply is on its way, or a running
+    /// pomodoro's countdown
+    pub fn tick_rate(&self) -> Option<StdDuration> {
+        if self.is_loading {
+            Some(ANIMATION_TICK)
+        } else if matches!(self.current_screen, CurrentScreen::ZenMode) {
+            Some(self.motion.tick())
+        } else if self.pomodoro.as_ref().is_some_and(|p| !p.is_complete()) {
+            // Header countdown
+            Some(StdDuration::from_secs(1))
+        } else {
+            None
+        }
+    }
+
+    /// Tasks on the view date as shown on screen, optionally limited to one
+    /// quadrant: visibility and filter applied, ordered by the current sort mode
+    fn visible_tasks(&self, quadrant: Option<Quadrant>) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .store
+            .tasks_on(self.view_date)
+            .into_iter()
             .filter(|t| {
-                t.date == self.view_date
-                    && t.status != TaskStatus::Dropped
-                    && t.quadrant() == self.selected_quadrant
+                self.visibility.shows(t.status)
+                    && quadrant.is_none_or(|q| t.quadrant() == q)
+                    && self.filter.as_ref().is_none_or(|f| f.matches(t))
             })
-            .count()
+            .collect();
+        self.sort_mode.sort(&mut tasks);
+        tasks
+    }
+
+    /// The view date's progress and overload warning for the header, worked
+    /// out again only when the store or the view date changes
+    pub fn header_stats(&mut self) -> (DayProgress, Option<String>) {
+        let key = (self.store.revision(), self.view_date);
+        match &self.header_stats {
+            Some((built_for, progress, warning)) if *built_for == key => {
+                (*progress, warning.clone())
+            }
+            _ => {
+                let progress = day_progress(&self.store.tasks, self.view_date);
+                let warning = forecast(&self.store.tasks, self.view_date, VELOCITY_WEEKS)
+                    .and_then(|f| f.warning());
+                self.header_stats = Some((key, progress, warning.clone()));
+                (progress, warning)
+            }
+        }
+    }
+
+    /// How many of the day's tasks pass visibility and the filter
+    pub fn shown_count(&self) -> usize {
+        self.visible_tasks(None).len()
+    }
+
+    /// Tasks of quadrant `q` as shown in the matrix
+    pub fn quadrant_tasks(&self, q: Quadrant) -> Vec<&Task> {
+        self.visible_tasks(Some(q))
+    }
+
+    /// Tasks the selection cursor moves through in the current layout
+    pub fn current_tasks(&self) -> Vec<&Task> {
+        match self.layout {
+            ViewLayout::Matrix => self.quadrant_tasks(self.selected_quadrant),
+            ViewLayout::List => self.visible_tasks(None),
+            ViewLayout::Kanban => self.kanban_tasks(self.kanban_column),
+        }
+    }
+
+    /// Tasks in one kanban column; the filter applies, visibility does not
+    /// since each column already implies a status
+    pub fn kanban_tasks(&self, column: KanbanColumn) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .store
+            .tasks
+            .iter()
+            .filter(|t| {
+                classify(t, self.view_date, &self.focused_today) == Some(column)
+                    && self.filter.as_ref().is_none_or(|f| f.matches(t))
+            })
+            .collect();
+        self.sort_mode.sort(&mut tasks);
+        if column == KanbanColumn::Backlog {
+            // Stable sort keeps the sort mode order within each day
+            tasks.sort_by_key(|t| t.date);
+        }
+        tasks
+    }
+
+    /// Reload which tasks have a focus session on the view date, once per date
+    pub fn refresh_focused(&mut self) {
+        if self.focused_for == Some(self.view_date) {
+            return;
+        }
+        self.focused_for = Some(self.view_date);
+        self.focused_today = read_sessions()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|s| s.date == self.view_date)
+            .filter_map(|s| s.task_id)
+            .collect();
+    }
+
+    /// Fix #4: Get task count for current quadrant and clamp index if needed
+    pub fn get_current_task_count(&self) -> usize {
+        self.current_tasks().len()
     }
 
     /// Fix #4: Clamp the selected index to valid range
@@ -121,6 +561,1022 @@ impl<'a> App<'a> {
         }
     }
 
+    /// Id of the task under the selection cursor
+    pub fn selected_task_id(&self) -> Option<uuid::Uuid> {
+        self.current_tasks()
+            .get(self.selected_task_index)
+            .map(|t| t.id)
+    }
+
+    /// Scroll the selected quadrant's view without moving the selection
+    pub fn scroll_view(&mut self, delta: isize) {
+        self.scroll
+            .entry(self.selected_quadrant)
+            .or_default()
+            .scroll_by(delta);
+    }
+
+    /// Jump the selection to the first or last task
+    pub fn select_edge(&mut self, last: bool) {
+        self.selected_task_index = if last {
+            self.get_current_task_count().saturating_sub(1)
+        } else {
+            0
+        };
+    }
+
+    /// Whether a multi-selection is active
+    pub fn has_marked(&self) -> bool {
+        !self.marked_tasks.is_empty()
+    }
+
+    /// The multi-selection as targets for a batch action; `perform` clears it
+    pub fn marked_ids(&self) -> Vec<uuid::Uuid> {
+        self.marked_tasks.iter().copied().collect()
+    }
+
+    pub fn clear_marked(&mut self) {
+        self.marked_tasks.clear();
+        self.visual_anchor = None;
+    }
+
+    /// Run `action`, or hold it for a y/n prompt when confirmations are on
+    pub fn request(&mut self, action: PendingAction) {
+        if self.config.confirm_destructive {
+            self.confirm = Some(action);
+        } else {
+            self.perform(action);
+        }
+    }
+
+    pub fn perform(&mut self, action: PendingAction) {
+        match action {
+            PendingAction::Drop(ids) => {
+                let message = match ids.as_slice() {
+                    [id] => format!("Dropped: {}", self.task_title(*id)),
+                    _ => format!("Dropped {} tasks", ids.len()),
+                };
+                let label = format!("drop {} tasks", ids.len());
+                self.store.journal.begin_batch();
+                for id in ids {
+                    self.store.drop_task(id);
+                }
+                self.store.journal.end_batch(label);
+                self.save_with(message);
+            }
+            PendingAction::Complete(ids) => {
+                let message = match ids.as_slice() {
+                    [id] => format!("Task completed: {}", self.task_title(*id)),
+                    _ => format!("Completed {} tasks", ids.len()),
+                };
+                let label = format!("complete {} tasks", ids.len());
+                self.store.journal.begin_batch();
+                for id in ids {
+                    self.store.complete_task(id);
+                }
+                self.store.journal.end_batch(label);
+                self.save_with(message);
+            }
+            PendingAction::Move(ids, date) => {
+                let message = match ids.len() {
+                    1 => format!("Moved to {}", date.format("%b %-d")),
+                    n => format!("Moved {} tasks to {}", n, date.format("%b %-d")),
+                };
+                let label = format!("move {} tasks", ids.len());
+                self.store.journal.begin_batch();
+                for id in ids {
+                    self.store.move_task_to_date(id, date);
+                }
+                self.store.journal.end_batch(label);
+                self.save_with(message);
+            }
+            PendingAction::Fold(id, kind) => {
+                if let Some(task) = self.pending_add.take() {
+                    kind.apply(self.store, id, &task);
+                    self.save_with(kind.done_message(&self.task_title(id)));
+                }
+            }
+            PendingAction::ClearChat => {
+                self.chat_history.clear();
+                self.chat_selected = None;
+                self.chat_summary = None;
+                self.chat_summarized = 0;
+                self.summary_receiver = None;
+                self.chat_overrides = ModelSettings::default();
+                self.chat_scroll = 0;
+                self.save_chat_history();
+                self.notify("Chat cleared");
+                return;
+            }
+        }
+        self.clear_marked();
+        self.clamp_selected_index();
+    }
+
+    /// Answer "no" to the pending confirmation; a declined merge or bump
+    /// adds the new task after all
+    pub fn decline(&mut self) {
+        if let Some(PendingAction::Fold(..)) = self.confirm.take() {
+            if let Some(task) = self.pending_add.take() {
+                self.add_task_now(task);
+            }
+        }
+    }
+
+    /// Add a new task, first offering to bump the same task already on its
+    /// day or to merge it into a similar pending one
+    pub fn add_new_task(&mut self, task: Task) {
+        if self.config.ignore_duplicates {
+            return self.add_task_now(task);
+        }
+        if let Some((existing, kind)) = find_repeat(&self.store.tasks, &task) {
+            self.confirm = Some(PendingAction::Fold(existing.id, kind));
+            self.pending_add = Some(task);
+            return;
+        }
+        // No shared words; the AI may still spot the same task reworded
+        match &self.ai_client {
+            Ok(client) if self.config.ai_duplicate_check && self.repeat_check.is_none() => {
+                let candidates: Vec<&Task> = repeat_candidates(&self.store.tasks, task.date)
+                    .into_iter()
+                    .filter(|t| !client.redactor().withholds(t))
+                    .collect();
+                if candidates.is_empty() {
+                    return self.add_task_now(task);
+                }
+                let (tx, receiver) = unbounded_channel();
+                client.ask_once(REPEAT_SYSTEM, repeat_prompt(&task.title, &candidates), tx);
+                let ids = candidates.iter().map(|t| t.id).collect();
+                self.repeat_check = Some(RepeatCheck { task, ids, receiver });
+                self.notify("Checking for a similar task…");
+            }
+            _ => self.add_task_now(task),
+        }
+    }
+
+    /// Offer to merge into the task the AI picked, or add the held-back
+    /// task when it found none or couldn't be asked
+    pub fn finish_repeat_check(&mut self, check: RepeatCheck, reply: Result<String, String>) {
+        let RepeatCheck { task, ids, .. } = check;
+        let picked = reply
+            .ok()
+            .and_then(|reply| parse_repeat_reply(&reply, ids.len()))
+            .map(|index| ids[index])
+            .filter(|id| {
+                let pending = |t: &Task| t.id == *id && t.status == TaskStatus::Pending;
+                self.store.tasks.iter().any(pending)
+            });
+        match picked {
+            Some(id) => {
+                self.confirm = Some(PendingAction::Fold(id, Repeat::Similar));
+                self.pending_add = Some(task);
+            }
+            None => self.add_task_now(task),
+        }
+    }
+
+    fn add_task_now(&mut self, task: Task) {
+        let message = format!("Added: {}", task.title);
+        self.store.add_task(task);
+        self.save_with(message);
+        self.clamp_selected_index();
+    }
+
+    /// Carry out an add or completion that came over DBus on this session's
+    /// copy of the store, and answer it
+    pub fn apply_bus_call(&mut self, call: Call) {
+        let result = serve::call(self.store, call.method, &call.params);
+        if let Ok(task) = &result {
+            if serve::WRITE_METHODS.contains(&call.method) {
+                let title = task["title"].as_str().unwrap_or_default();
+                let verb = if call.method == "tasks.add" { "Added" } else { "Done" };
+                self.save_with(format!("{}: {}", verb, title));
+                self.clamp_selected_index();
+            }
+        }
+        let _ = call.reply.send(result);
+    }
+
+    /// Revert the last task change of the session
+    pub fn undo(&mut self) {
+        match self.store.undo() {
+            Some(label) => {
+                self.save_with(format!("Undid: {}", label));
+                self.clamp_selected_index();
+            }
+            None => self.notify("Nothing to undo"),
+        }
+    }
+
+    /// Show a status message for a few seconds
+    /// Show the help overlay at the current screen's section
+    pub fn open_help(&mut self) {
+        self.show_help = true;
+        self.help_scroll = help_offset(help_index(&self.current_screen)) as u16;
+    }
+
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.toast = Some(Toast::info(message));
+    }
+
+    pub fn notify_error(&mut self, message: impl Into<String>) {
+        self.toast = Some(Toast::error(message));
+    }
+
+    /// Queue a save of the store and toast `message`; a failed write shows
+    /// as a banner once the saver reports it
+    pub fn save_with(&mut self, message: impl Into<String>) {
+        self.saver.changed();
+        self.notify(message);
+    }
+
+    /// Toast text after toggling a task's completion
+    pub fn completion_message(&self, id: uuid::Uuid) -> String {
+        let done = self
+            .store
+            .tasks
+            .iter()
+            .any(|t| t.id == id && t.status == TaskStatus::Completed);
+        let verb = if done { "completed" } else { "reopened" };
+        format!("Task {}: {}", verb, self.task_title(id))
+    }
+
+    fn task_title(&self, id: uuid::Uuid) -> String {
+        self.store
+            .tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map(|t| t.title.clone())
+            .unwrap_or_default()
+    }
+
+    /// Question for the pending confirmation, if any
+    pub fn confirm_prompt(&self) -> Option<String> {
+        let subject = |ids: &[uuid::Uuid]| match ids {
+            [id] => self
+                .store
+                .tasks
+                .iter()
+                .find(|t| t.id == *id)
+                .map(|t| format!("\"{}\"", t.title))
+                .unwrap_or_else(|| "1 task".to_string()),
+            _ => format!("{} tasks", ids.len()),
+        };
+        Some(match self.confirm.as_ref()? {
+            PendingAction::Drop(ids) => format!("Drop {}?", subject(ids)),
+            PendingAction::Complete(ids) => format!("Mark {} done?", subject(ids)),
+            PendingAction::Move(ids, date) => {
+                format!("Move {} to {}?", subject(ids), date.format("%b %-d"))
+            }
+            PendingAction::ClearChat => "Clear the chat history?".to_string(),
+            PendingAction::Fold(id, kind) => {
+                let existing = self.store.tasks.iter().find(|t| t.id == *id)?;
+                let incoming = self.pending_add.as_ref()?;
+                format!("{} (n adds it anyway)", kind.prompt(existing, incoming))
+            }
+        })
+    }
+
+    /// Open the week view with the current view date selected
+    pub fn open_week(&mut self) {
+        self.week_day_index = self.view_date.weekday().num_days_from_monday() as usize;
+        self.week_task_index = 0;
+        self.current_screen = CurrentScreen::Week;
+    }
+
+    /// Date of the column selected in the week view
+    pub fn week_selected_date(&self) -> NaiveDate {
+        week_start(self.view_date) + Duration::days(self.week_day_index as i64)
+    }
+
+    /// Non-dropped tasks scheduled on `date`, highest score first
+    pub fn tasks_for_date(&self, date: NaiveDate) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = self
+            .store
+            .tasks_on(date)
+            .into_iter()
+            .filter(|t| t.status != TaskStatus::Dropped)
+            .collect();
+        self.sort_mode.sort(&mut tasks);
+        tasks
+    }
+
+    /// Keep the week view's task cursor inside the selected day
+    pub fn clamp_week_task_index(&mut self) {
+        let count = self.tasks_for_date(self.week_selected_date()).len();
+        if count == 0 {
+            self.week_task_index = 0;
+        } else if self.week_task_index >= count {
+            self.week_task_index = count - 1;
+        }
+    }
+
+    /// Switch to a built-in theme preset and remember it in the config
+    pub fn set_theme(&mut self, name: &str) -> bool {
+        let Some(preset) = Theme::by_name(name) else {
+            return false;
+        };
+        self.config.theme = Some(preset.name.to_string());
+        self.theme = Theme::from_config(&self.config);
+        self.save_config(format!("Theme: {}", preset.name));
+        true
+    }
+
+    /// Tasks a retag applies to: the multi-selection, or else the task
+    /// under the cursor
+    fn retag_targets(&self) -> Vec<uuid::Uuid> {
+        if self.has_marked() {
+            self.marked_ids()
+        } else {
+            self.selected_task_id().into_iter().collect()
+        }
+    }
+
+    /// Open the `#` prompt with the tags its tasks all share
+    pub fn open_retag(&mut self) {
+        let ids = self.retag_targets();
+        let tasks: Vec<&Task> = self
+            .store
+            .tasks
+            .iter()
+            .filter(|t| ids.contains(&t.id))
+            .collect();
+        let Some((first, rest)) = tasks.split_first() else {
+            return;
+        };
+        let shared: Vec<&str> = first
+            .tags()
+            .into_iter()
+            .filter(|tag| rest.iter().all(|t| t.tags().contains(tag)))
+            .collect();
+        self.tag_input = Some(LineEditor::with_text(shared.join(" ")));
+    }
+
+    /// Give the targeted tasks exactly the tags in `line`, as one undo step;
+    /// the `#` may be left off
+    pub fn retag(&mut self, line: &str) {
+        let ids = self.retag_targets();
+        if ids.is_empty() {
+            return;
+        }
+        let tags: Vec<String> = line
+            .split_whitespace()
+            .map(|word| word.trim_start_matches('#'))
+            .filter(|tag| !tag.is_empty())
+            .map(|tag| format!("#{}", tag))
+            .collect();
+        self.store.retag(&ids, &tags);
+        let message = match (ids.len(), tags.is_empty()) {
+            (1, true) => "Tags cleared".to_string(),
+            (1, false) => format!("Tagged {}", tags.join(" ")),
+            (n, true) => format!("Cleared the tags of {} tasks", n),
+            (n, false) => format!("Tagged {} tasks {}", n, tags.join(" ")),
+        };
+        self.save_with(message);
+        self.clear_marked();
+        self.clamp_selected_index();
+    }
+
+    /// Replace the filter; a blank query clears it
+    pub fn set_filter(&mut self, query: &str) {
+        self.filter = TaskFilter::parse(query);
+        self.selected_task_index = 0;
+        self.visual_anchor = None;
+    }
+
+    /// Change the task order and remember it in the config
+    pub fn set_sort(&mut self, mode: SortMode) {
+        self.sort_mode = mode;
+        self.config.sort = Some(mode.label().to_string());
+        self.save_config(format!("Sort: {}", mode.label()));
+        self.clamp_selected_index();
+    }
+
+    /// Shift the selected task up or down, pinning the visible order as the
+    /// manual order first
+    pub fn move_in_order(&mut self, delta: isize) {
+        let mut ids: Vec<uuid::Uuid> = self.current_tasks().iter().map(|t| t.id).collect();
+        let from = self.selected_task_index;
+        let to = from as isize + delta;
+        if from >= ids.len() || to < 0 || to as usize >= ids.len() {
+            return;
+        }
+        ids.swap(from, to as usize);
+
+        self.store.set_manual_order(&ids);
+        self.selected_task_index = to as usize;
+        if self.sort_mode != SortMode::Manual {
+            self.set_sort(SortMode::Manual);
+        }
+        self.saver.changed();
+    }
+
+    /// Switch the Zen animation's motion and remember it in the config
+    pub fn set_motion(&mut self, motion: Motion) {
+        self.motion = motion;
+        if let Some(zen) = self.zen_state.as_mut() {
+            zen.motion = motion;
+        }
+        self.config.motion = Some(motion.label().to_string());
+        self.save_config(format!("Motion: {}", motion.label()));
+    }
+
+    /// Flip the quadrant glyphs/patterns setting and remember it in the config
+    pub fn toggle_accessible(&mut self) {
+        self.config.accessible = !self.config.accessible;
+        self.theme = Theme::from_config(&self.config);
+        let state = if self.config.accessible { "on" } else { "off" };
+        self.save_config(format!("Accessible markers {}", state));
+    }
+
+    fn save_config(&mut self, message: String) {
+        match self.config.save() {
+            Ok(()) => self.notify(message),
+            Err(err) => self.notify_error(format!("Could not save config: {}", err)),
+        }
+    }
+
+    /// Step through the view date's unfinished tasks one at a time
+    pub fn open_review(&mut self) {
+        self.review = Some(DailyReview::new(&self.store.tasks, self.view_date));
+        self.current_screen = CurrentScreen::Review;
+    }
+
+    /// Apply a review decision to the task under review and move on
+    pub fn review_decide(&mut self, choice: ReviewChoice) {
+        let Some(review) = self.review.as_mut() else {
+            return;
+        };
+        let Some(id) = review.current() else {
+            return;
+        };
+        let tomorrow = review.date + Duration::days(1);
+        review.decide(choice);
+
+        match choice {
+            ReviewChoice::Tomorrow => {
+                self.store.move_task_to_date(id, tomorrow);
+                self.save_with("Moved to tomorrow");
+            }
+            ReviewChoice::Rescheduled(date) => {
+                self.store.move_task_to_date(id, date);
+                self.save_with(format!("Moved to {}", date.format("%b %-d")));
+            }
+            ReviewChoice::Dropped => {
+                self.store.drop_task(id);
+                self.save_with("Dropped");
+            }
+            ReviewChoice::Kept => {}
+        }
+    }
+
+    /// Whether too much of the view date is DO FIRST, by the configured share
+    pub fn do_first_overload(&self) -> Option<DoFirstOverload> {
+        let share = self.config.do_first_share.unwrap_or(DO_FIRST_SHARE);
+        do_first_overload(self.store.tasks_on(self.view_date), share)
+    }
+
+    /// Compare the view date's morning plan with what got done
+    pub fn open_day(&mut self) {
+        self.day_events = read_log().unwrap_or_default();
+        self.current_screen = CurrentScreen::Day;
+    }
+
+    /// Step the day screen back or forward, no further than today
+    pub fn shift_day(&mut self, days: i64) {
+        self.view_date = (self.view_date + Duration::days(days)).min(self.store.today());
+    }
+
+    /// Step through the view date's DO FIRST tasks to demote some
+    pub fn open_triage(&mut self) {
+        self.triage = Some(Triage::new(&self.store.tasks, self.view_date));
+        self.current_screen = CurrentScreen::Triage;
+    }
+
+    /// Apply a triage decision to the task under triage and move on
+    pub fn triage_decide(&mut self, choice: TriageChoice) {
+        let Some(triage) = self.triage.as_mut() else {
+            return;
+        };
+        let Some(id) = triage.current() else {
+            return;
+        };
+        triage.decide(choice);
+        let Some(task) = self.store.tasks.iter().find(|t| t.id == id) else {
+            return;
+        };
+
+        if let Some((urgency, importance)) = choice.priority(task) {
+            let title = task.title.clone();
+            self.store.update_task(id, title, urgency, importance);
+            let quadrant = Quadrant::from_priority(urgency, importance);
+            self.save_with(format!("Moved to {}", quadrant_name(quadrant)));
+        } else if choice == TriageChoice::Dropped {
+            self.store.drop_task(id);
+            self.save_with("Dropped");
+        }
+    }
+
+    /// Review the view date's week and decide on next week's open work
+    pub fn open_weekly_review(&mut self) {
+        let start = week_start(self.view_date);
+        self.weekly_review = Some(WeeklyReview::new(&self.store.tasks, start));
+        self.current_screen = CurrentScreen::WeeklyReview;
+    }
+
+    /// Apply the weekly decisions and write the review note to the journal
+    pub fn finish_weekly_review(&mut self) {
+        let Some(review) = self.weekly_review.take() else {
+            return;
+        };
+        let section = render_weekly_review(review.date, &self.store.tasks, &review, &[]);
+        review.apply(self.store);
+        self.current_screen = CurrentScreen::Main;
+        self.clamp_selected_index();
+
+        let written = default_journal_dir()
+            .and_then(|dir| write_note(&dir, &weekly_note_name(review.date), &section));
+        match written {
+            Ok(path) => self.save_with(format!("Wrote weekly review: {}", path.display())),
+            Err(err) => {
+                self.save_with("Weekly review applied");
+                self.notify_error(format!("Could not write review: {}", err));
+            }
+        }
+    }
+
+    /// Compose the view date from carry-overs, overdue work and the backlog
+    pub fn open_plan(&mut self) {
+        self.plan_index = 0;
+        self.current_screen = CurrentScreen::Plan;
+    }
+
+    /// Candidates offered on the plan screen, recomputed as tasks move
+    pub fn plan_candidates(&self) -> Vec<Candidate<'_>> {
+        plan_candidates(&self.store.tasks, self.view_date, PLAN_HORIZON_DAYS)
+    }
+
+    fn plan_selected_id(&self) -> Option<uuid::Uuid> {
+        self.plan_candidates()
+            .get(self.plan_index)
+            .map(|c| c.task.id)
+    }
+
+    pub fn clamp_plan_index(&mut self) {
+        let count = self.plan_candidates().len();
+        self.plan_index = self.plan_index.min(count.saturating_sub(1));
+    }
+
+    /// Move the selected candidate onto the view date
+    pub fn plan_pull(&mut self) {
+        if let Some(id) = self.plan_selected_id() {
+            self.store.move_task_to_date(id, self.view_date);
+            self.save_with(format!(
+                "Planned for {}",
+                self.view_date.format("%a %b %-d")
+            ));
+            self.clamp_plan_index();
+        }
+    }
+
+    pub fn plan_drop(&mut self) {
+        if let Some(id) = self.plan_selected_id() {
+            self.store.drop_task(id);
+            self.save_with("Dropped");
+            self.clamp_plan_index();
+        }
+    }
+
+    /// Send the candidates and capacity to the assistant and open the chat
+    pub fn ask_plan_suggestions(&mut self) {
+        let prompt = planning_prompt(
+            self.view_date,
+            &capacity_line(&self.store.tasks, self.view_date),
+            &self.plan_candidates(),
+        );
+        self.send_chat(prompt);
+        self.current_screen = CurrentScreen::Chat;
+    }
+
+    /// Read the archived tasks in, once, when the view needs them: the stats
+    /// screen, or a week older than the archive cutoff
+    pub fn page_in_history(&mut self) {
+        let cutoff = self.store.today() - Duration::days(ARCHIVE_AFTER_DAYS);
+        let wanted = matches!(self.current_screen, CurrentScreen::Stats)
+            || week_start(self.view_date) < cutoff;
+        if wanted && !self.store.history_loaded() && !self.archive_failed {
+            if let Err(err) = self.store.load_history() {
+                self.archive_failed = true;
+                self.notify_error(format!("Couldn't read archive: {}", err));
+            }
+        }
+    }
+
+    /// Read moves appended to the history log since the store last changed,
+    /// keeping each task's age current
+    pub fn refresh_postponements(&mut self) {
+        let (offset, read_for) = self.log_read;
+        if read_for == Some(self.store.revision()) {
+            return;
+        }
+        // A failed read is tried again on the next change
+        if let Ok((events, next)) = read_log_since(offset) {
+            if next < offset {
+                self.postponements.clear();
+            }
+            record_postponements(&mut self.postponements, &events);
+            self.log_read = (next, Some(self.store.revision()));
+        }
+    }
+
+    /// Days each task has been carried forward
+    pub fn carried_days(&self, task: &Task) -> i64 {
+        carried_days(task, self.postponements.get(&task.id))
+    }
+
+    /// Load focus sessions from disk and open the stats screen
+    pub fn open_stats(&mut self) {
+        self.current_screen = CurrentScreen::Stats;
+        self.page_in_history();
+        let sessions = read_sessions().unwrap_or_default();
+        let today = self.store.today();
+        self.focus_stats = Some(focus_stats(&sessions, &self.store.tasks, today, 14));
+        self.current_screen = CurrentScreen::Stats;
+    }
+
+    /// Start a 25-minute pomodoro for the selected task
+    pub fn start_pomodoro(&mut self) {
+        self.pomodoro = Some(Pomodoro::new(25, self.selected_task_id()));
+    }
+
+    /// Leave a break for the next focus session; only on an explicit keypress
+    /// so breaks aren't skipped by accident
+    pub fn next_pomodoro(&mut self) {
+        if self.pomodoro.as_ref().is_some_and(|p| p.is_break) {
+            self.start_pomodoro();
+        }
+    }
+
+    /// Write a finished pomodoro to the focus log, bound to the task it was
+    /// started on, and start the break that follows it
+    pub fn record_finished_pomodoro(&mut self) {
+        let Some(pomo) = self.pomodoro.as_mut() else {
+            return;
+        };
+        if pomo.is_complete() && !pomo.is_break && !pomo.recorded {
+            pomo.recorded = true;
+            let session = FocusSession::new(pomo.task_id, self.store.today(), pomo.duration_secs);
+            let _ = append_session(&session);
+            if session.date == self.view_date {
+                self.focused_today.extend(pomo.task_id);
+            }
+            *pomo = Pomodoro::rest(BREAK_MINUTES, pomo.task_id);
+            self.notify(format!("Session logged · {}-minute break", BREAK_MINUTES));
+        }
+    }
+
+    /// Post a user message and ask the assistant for a reply
+    pub fn send_chat(&mut self, content: String) {
+        self.ask_assistant(content, true);
+    }
+
+    /// `send_chat`, skipping saved replies unless `use_cache` is set
+    fn ask_assistant(&mut self, content: String, use_cache: bool) {
+        let is_quote = content.trim().eq_ignore_ascii_case("quote");
+        self.chat_history.push(ChatMessage {
+            role: "user".to_string(),
+            content,
+        });
+
+        // Save after user message
+        self.save_chat_history();
+
+        match &self.ai_client {
+            Ok(client) => {
+                let (tx, rx) = unbounded_channel();
+                self.chat_receiver = Some(rx);
+                self.chat_partial.clear();
+                self.is_loading = true;
+                self.chat_auto_scroll = true;
+
+                let (tasks, withheld) =
+                    task_context(&self.store.tasks, self.view_date, client.redactor());
+                self.chat_withheld = withheld;
+                let context = with_summary(tasks, self.chat_summary.as_deref());
+                let history = trimmed_history(&self.chat_history, self.chat_summarized);
+                let overrides = &self.chat_overrides;
+                let task = client.send_message(history, context, overrides, use_cache, tx);
+                self.chat_task = Some(task);
+
+                // Fold older messages into the summary for the next turns
+                let due = summary_due(self.chat_history.len(), self.chat_summarized);
+                if let (Some(covered), None) = (due, &self.summary_receiver) {
+                    let (tx, rx) = unbounded_channel();
+                    let older = self.chat_history[self.chat_summarized..covered].to_vec();
+                    client.summarize(self.chat_summary.clone(), older, tx);
+                    self.summary_receiver = Some((covered, rx));
+                }
+            }
+            // Quotes need no model
+            Err(_) if is_quote => {
+                self.post_local(random_quote().to_string());
+            }
+            Err(reason) => {
+                self.chat_history.push(ChatMessage {
+                    role: "assistant".to_string(),
+                    content: reason.clone(),
+                });
+            }
+        }
+    }
+
+    /// Copy `text` to the system clipboard and say what was copied
+    pub fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        match self.clipboard.copy(text) {
+            Ok(()) => self.notify(format!("Copied {}", what)),
+            Err(err) => self.notify_error(err),
+        }
+    }
+
+    /// Copy the chat message picked in selection mode
+    pub fn copy_selected_message(&mut self) {
+        if let Some(message) = self.chat_selected.and_then(|i| self.chat_history.get(i)) {
+            let content = message.content.clone();
+            self.copy_to_clipboard(&content, "message");
+        }
+    }
+
+    /// Copy a task's title and details
+    pub fn copy_task(&mut self, id: uuid::Uuid) {
+        if let Some(task) = self.store.tasks.iter().find(|t| t.id == id) {
+            let text = task_text(task);
+            self.copy_to_clipboard(&text, "task");
+        }
+    }
+
+    /// Transcribe a voice memo in the background; `add_memo_tasks` takes
+    /// the transcript
+    fn transcribe_memo(&mut self, path: PathBuf) {
+        let runtime = match runtime() {
+            Ok(runtime) => runtime,
+            Err(err) => return self.post_local(format!("Error: {}", err)),
+        };
+        let (tx, rx) = unbounded_channel();
+        let config = self.config.clone();
+        let task = runtime.spawn(async move {
+            let _ = tx.send(transcribe(&config, &path).await);
+        });
+        self.transcript_receiver = Some(rx);
+        self.chat_task = Some(task.abort_handle());
+        self.is_loading = true;
+        self.chat_auto_scroll = true;
+    }
+
+    /// Turn a memo transcript into tasks for the viewed day: the assistant
+    /// proposes them for confirmation, or without one each sentence is added
+    pub fn add_memo_tasks(&mut self, transcript: String) {
+        if self.ai_client.is_ok() {
+            self.send_chat(memo_prompt(self.view_date, &transcript));
+            return;
+        }
+        let tasks = memo_tasks(&transcript, self.view_date);
+        let mut lines = vec![format!("Transcript: {}", transcript)];
+        self.store.journal.begin_batch();
+        for task in tasks {
+            lines.push(format!("Added: {}", task.title));
+            self.store.add_task(task);
+        }
+        self.store.journal.end_batch("memo");
+        self.save_with(format!("Added {} task(s) from the memo", lines.len() - 1));
+        self.post_local(lines.join("\n"));
+    }
+
+    /// Take in part of the assistant's reply; the text streams in before
+    /// the whole reply and its commands arrive
+    fn take_reply(&mut self, response: AIResponse) {
+        if let AIResponse::Partial(text) = &response {
+            self.chat_partial.push_str(text);
+            return;
+        }
+        self.is_loading = false;
+        self.chat_task = None;
+        self.chat_partial.clear();
+        match response {
+            AIResponse::Success { content, commands } => {
+                tracing::debug!(commands = commands.len(), "AI reply received");
+                if let Some(start) = self.retro_week.take() {
+                    self.save_retro(start, &content);
+                }
+                // Queue suggested changes for confirmation
+                let full_content = self.process_ai_response(content, commands);
+
+                self.chat_history.push(ChatMessage {
+                    role: "assistant".to_string(),
+                    content: full_content,
+                });
+                // Fix #8: Auto-save after AI response
+                self.save_chat_history();
+            }
+            AIResponse::Error(err) => {
+                tracing::debug!(%err, "AI request failed");
+                self.retro_week = None;
+                self.chat_history.push(ChatMessage {
+                    role: "assistant".to_string(),
+                    content: format!("Error: {}", err),
+                });
+            }
+            AIResponse::Partial(_) => {}
+        }
+    }
+
+    /// Abort the request in flight, including any retries it is waiting on
+    pub fn cancel_chat_request(&mut self) {
+        if let Some(task) = self.chat_task.take() {
+            task.abort();
+        }
+        self.chat_receiver = None;
+        self.transcript_receiver = None;
+        self.chat_partial.clear();
+        self.is_loading = false;
+        self.retro_week = None;
+        self.chat_history.push(ChatMessage {
+            role: "assistant".to_string(),
+            content: "(Cancelled)".to_string(),
+        });
+        self.save_chat_history();
+        self.notify("Request cancelled");
+    }
+
+    /// Run a `/` chat command; only `/audit` and `/retro` call the AI
+    pub fn run_chat_command(&mut self, command: ChatCommand) {
+        match command {
+            ChatCommand::Audit => {
+                self.ask_audit();
+                return;
+            }
+            ChatCommand::Retro { last } => {
+                self.ask_retro(last);
+                return;
+            }
+            ChatCommand::Add(input) => {
+                let task = parse_input(&input, self.today).into_task(self.view_date);
+                self.add_new_task(task);
+                return;
+            }
+            ChatCommand::Done(identifier) => {
+                match self.find_task_by_identifier(&identifier) {
+                    Ok((id, title)) => {
+                        self.store.toggle_complete_task(id);
+                        self.save_with(format!("Done: {}", title));
+                        self.clamp_selected_index();
+                    }
+                    Err(err) => self.notify_error(err),
+                }
+                return;
+            }
+            ChatCommand::List => {
+                let list = self.pending_task_list();
+                self.post_local(list);
+                return;
+            }
+            ChatCommand::Clear => {
+                self.request(PendingAction::ClearChat);
+                return;
+            }
+            ChatCommand::Quote => {
+                self.post_local(random_quote().to_string());
+                return;
+            }
+            ChatCommand::NoCache(message) => {
+                self.ask_assistant(message, false);
+                return;
+            }
+            ChatCommand::Audio(path) => {
+                self.transcribe_memo(path);
+                return;
+            }
+            ChatCommand::Help => {
+                let help = CHAT_COMMANDS
+                    .iter()
+                    .map(|(usage, action)| format!("{} — {}", usage, action))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                self.post_local(help);
+                return;
+            }
+            ChatCommand::Model(model) => self.chat_overrides.model = Some(model),
+            ChatCommand::Temperature(t) => self.chat_overrides.temperature = Some(t),
+            ChatCommand::MaxTokens(n) => self.chat_overrides.max_tokens = Some(n),
+            ChatCommand::Reset => self.chat_overrides = ModelSettings::default(),
+            ChatCommand::Settings => {}
+        }
+
+        let o = &self.chat_overrides;
+        let or_default = |value: Option<String>| value.unwrap_or_else(|| "default".to_string());
+        let message = format!(
+            "Chat model: {} · temp: {} · tokens: {}",
+            or_default(o.model.clone()),
+            or_default(o.temperature.map(|t| t.to_string())),
+            or_default(o.max_tokens.map(|n| n.to_string()))
+        );
+        self.notify(message);
+    }
+
+    /// Show a reply produced locally, without a round-trip to the AI
+    fn post_local(&mut self, content: String) {
+        self.chat_history.push(ChatMessage {
+            role: "assistant".to_string(),
+            content,
+        });
+        self.chat_auto_scroll = true;
+        self.save_chat_history();
+    }
+
+    /// The viewed day's pending tasks by quadrant, numbered as `#N` refers
+    /// to them when that quadrant is selected
+    fn pending_task_list(&self) -> String {
+        let mut lines = vec![format!(
+            "Pending on {}:",
+            self.view_date.format("%a %b %-d")
+        )];
+        for quadrant in Quadrant::ALL {
+            let mut tasks: Vec<&Task> = self
+                .store
+                .tasks
+                .iter()
+                .filter(|t| {
+                    t.date == self.view_date
+                        && t.status == TaskStatus::Pending
+                        && t.quadrant() == quadrant
+                })
+                .collect();
+            if tasks.is_empty() {
+                continue;
+            }
+            self.sort_mode.sort(&mut tasks);
+            lines.push(format!("{}:", quadrant));
+            for (i, task) in tasks.iter().enumerate() {
+                lines.push(format!(
+                    "  #{} {} (u{}i{})",
+                    i + 1,
+                    task.title,
+                    task.urgency,
+                    task.importance
+                ));
+            }
+        }
+        if lines.len() == 1 {
+            lines.push("Nothing pending.".to_string());
+        }
+        lines.join("\n")
+    }
+
+    /// Ask the assistant to challenge the viewed day's DELEGATE and DROP
+    /// tasks; its proposals arrive as pending commands to confirm
+    pub fn ask_audit(&mut self) {
+        let tasks = audit_tasks(&self.store.tasks, self.view_date);
+        if tasks.is_empty() {
+            self.notify("No DELEGATE or DROP tasks to audit");
+            return;
+        }
+        let prompt = audit_prompt(&tasks);
+        self.send_chat(prompt);
+    }
+
+    /// Send the week's log for a retrospective; the reply is saved to the
+    /// journal when it arrives
+    pub fn ask_retro(&mut self, last: bool) {
+        let mut start = week_start(self.today);
+        if last {
+            start -= Duration::days(7);
+        }
+        let events = read_log().unwrap_or_default();
+        let log = week_log(&self.store.tasks, &events, start);
+        if log.is_empty() {
+            self.notify("Nothing completed, dropped, or carried over that week yet");
+            return;
+        }
+        let prompt = retro_prompt(start, &log);
+        self.retro_week = self.ai_client.is_ok().then_some(start);
+        self.send_chat(prompt);
+    }
+
+    /// Write the retrospective for `start` with the assistant's `reflection`
+    pub fn save_retro(&mut self, start: NaiveDate, reflection: &str) {
+        let events = read_log().unwrap_or_default();
+        let section = render_retro(
+            start,
+            &week_log(&self.store.tasks, &events, start),
+            reflection,
+        );
+        let written = default_journal_dir()
+            .and_then(|dir| write_note(&dir, &retro_note_name(start), &section));
+        match written {
+            Ok(path) => self.notify(format!("Wrote retrospective: {}", path.display())),
+            Err(err) => self.notify_error(format!("Could not write retrospective: {}", err)),
+        }
+    }
+
     /// Fix #8: Save chat history to disk
     pub fn save_chat_history(&self) {
         let history: Vec<crate::models::store::ChatMessage> = self
@@ -134,9 +1590,8 @@ impl<'a> App<'a> {
         let _ = TaskStore::save_chat_history(&history);
     }
 
-    /// Process AI response and extract commands
-    pub fn process_ai_response(&mut self, content: String) -> String {
-        let commands = parse_commands(&content);
+    /// Queue the reply's suggested task changes for confirmation
+    pub fn process_ai_response(&mut self, content: String, commands: Vec<AICommand>) -> String {
         if commands.is_empty() {
             return content;
         }
@@ -167,6 +1622,9 @@ impl<'a> App<'a> {
                     
                     msg.push_str(&format!("  {}. EDIT: {} → {}\n", i + 1, target_str, changes.join(", ")));
                 }
+                AICommand::Move { target, date } => {
+                    msg.push_str(&format!("  {}. MOVE: {} → {}\n", i + 1, self.format_identifier(target), date));
+                }
             }
         }
         
@@ -183,10 +1641,11 @@ impl<'a> App<'a> {
         let commands = std::mem::take(&mut self.pending_commands);
         let mut results = CommandResults::default();
 
+        self.store.journal.begin_batch();
         for cmd in commands {
             match cmd {
                 AICommand::Add(parsed) => {
-                    let task = crate::models::task::Task::new(
+                    let task = Task::new(
                         parsed.title.clone(),
                         parsed.urgency,
                         parsed.importance,
@@ -196,37 +1655,29 @@ impl<'a> App<'a> {
                     results.tasks_added.push(parsed);
                 }
 
-                AICommand::Done(identifier) => {
-                    if let Some((task_id, title)) = self.find_task_by_identifier(&identifier) {
+                AICommand::Done(identifier) => match self.find_task_by_identifier(&identifier) {
+                    Ok((task_id, title)) => {
                         self.store.toggle_complete_task(task_id);
                         results.tasks_completed.push(title);
-                    } else {
-                        results.errors.push(format!(
-                            "Could not find task: {}",
-                            self.format_identifier(&identifier)
-                        ));
                     }
-                }
+                    Err(err) => results.errors.push(err),
+                },
 
-                AICommand::Drop(identifier) => {
-                    if let Some((task_id, title)) = self.find_task_by_identifier(&identifier) {
+                AICommand::Drop(identifier) => match self.find_task_by_identifier(&identifier) {
+                    Ok((task_id, title)) => {
                         self.store.drop_task(task_id);
                         results.tasks_dropped.push(title);
-                    } else {
-                        results.errors.push(format!(
-                            "Could not find task: {}",
-                            self.format_identifier(&identifier)
-                        ));
                     }
-                }
+                    Err(err) => results.errors.push(err),
+                },
 
                 AICommand::Edit {
                     target,
                     new_title,
                     new_urgency,
                     new_importance,
-                } => {
-                    if let Some((task_id, old_title)) = self.find_task_by_identifier(&target) {
+                } => match self.find_task_by_identifier(&target) {
+                    Ok((task_id, old_title)) => {
                         let (current_title, current_u, current_i) = {
                             let task = self.store.tasks.iter().find(|t| t.id == task_id).unwrap();
                             (task.title.clone(), task.urgency, task.importance)
@@ -236,28 +1687,46 @@ impl<'a> App<'a> {
                         let final_u = new_urgency.unwrap_or(current_u);
                         let final_i = new_importance.unwrap_or(current_i);
 
-                        self.store.update_task(task_id, final_title.clone(), final_u, final_i);
+                        self.store
+                            .update_task(task_id, final_title.clone(), final_u, final_i);
                         results.tasks_edited.push(format!(
                             "{} → {} (u{}i{})",
                             old_title, final_title, final_u, final_i
                         ));
-                    } else {
-                        results.errors.push(format!(
-                            "Could not find task: {}",
-                            self.format_identifier(&target)
-                        ));
+                    }
+                    Err(err) => results.errors.push(err),
+                },
+
+                AICommand::Move { target, date } => {
+                    let Some(new_date) = parse_date(&date, self.today) else {
+                        results.errors.push(format!("Unknown date: {}", date));
+                        continue;
+                    };
+                    match self.find_task_by_identifier(&target) {
+                        Ok((task_id, title)) => {
+                            self.store.move_task_to_date(task_id, new_date);
+                            results.tasks_moved.push(format!(
+                                "{} → {}",
+                                title,
+                                new_date.format("%a %b %-d")
+                            ));
+                        }
+                        Err(err) => results.errors.push(err),
                     }
                 }
             }
         }
 
+        self.store.journal.end_batch("AI commands");
+
         // Save the store if we made any changes
         if !results.tasks_added.is_empty()
             || !results.tasks_completed.is_empty()
             || !results.tasks_dropped.is_empty()
             || !results.tasks_edited.is_empty()
+            || !results.tasks_moved.is_empty()
         {
-            let _ = self.store.save();
+            self.save_with("Saved");
             self.clamp_selected_index();
         }
 
@@ -276,12 +1745,16 @@ impl<'a> App<'a> {
         !self.pending_commands.is_empty()
     }
 
-    /// Find a task by identifier (title fragment or index)
-    fn find_task_by_identifier(&self, identifier: &TaskIdentifier) -> Option<(uuid::Uuid, String)> {
+    /// Find a task by identifier (title fragment or index); the error says
+    /// why nothing, or more than one task, matched
+    fn find_task_by_identifier(
+        &self,
+        identifier: &TaskIdentifier,
+    ) -> Result<(uuid::Uuid, String), String> {
         match identifier {
             TaskIdentifier::Index(idx) => {
                 // Get tasks in current quadrant, sorted by score
-                let mut tasks: Vec<&crate::models::task::Task> = self
+                let mut tasks: Vec<&Task> = self
                     .store
                     .tasks
                     .iter()
@@ -291,25 +1764,41 @@ impl<'a> App<'a> {
                             && t.quadrant() == self.selected_quadrant
                     })
                     .collect();
-                tasks.sort_by_key(|t| std::cmp::Reverse(t.score()));
+                self.sort_mode.sort(&mut tasks);
 
                 // 1-based index
                 if *idx > 0 && *idx <= tasks.len() {
                     let task = tasks[*idx - 1];
-                    Some((task.id, task.title.clone()))
+                    Ok((task.id, task.title.clone()))
                 } else {
-                    None
+                    Err(format!(
+                        "Could not find task: {}",
+                        self.format_identifier(identifier)
+                    ))
                 }
             }
             TaskIdentifier::Title(title_fragment) => {
-                // Case-insensitive substring match on today's pending tasks
-                let fragment_lower = title_fragment.to_lowercase();
-                self.store
+                // Fuzzy match on today's pending tasks
+                let pending = self
+                    .store
                     .tasks
                     .iter()
-                    .filter(|t| t.date == self.view_date && t.status == TaskStatus::Pending)
-                    .find(|t| t.title.to_lowercase().contains(&fragment_lower))
-                    .map(|t| (t.id, t.title.clone()))
+                    .filter(|t| t.date == self.view_date && t.status == TaskStatus::Pending);
+                match match_title(pending, title_fragment) {
+                    TitleMatch::Found(task) => Ok((task.id, task.title.clone())),
+                    TitleMatch::Ambiguous(tasks) => {
+                        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+                        Err(format!(
+                            "{} matches several tasks: {}",
+                            self.format_identifier(identifier),
+                            titles.join(", ")
+                        ))
+                    }
+                    TitleMatch::NotFound => Err(format!(
+                        "Could not find task: {}",
+                        self.format_identifier(identifier)
+                    )),
+                }
             }
         }
     }
@@ -324,77 +1813,309 @@ impl<'a> App<'a> {
 }
 
 pub fn run(store: &mut TaskStore) -> Result<(), Box<dyn std::error::Error>> {
+    let runtime = runtime()?;
     // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
+    install_panic_hook();
+    enter_terminal()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
     // Create app
-    let mut app = App::new(store);
+    let mut app = App::new(store, runtime);
+
+    // Scripts and extensions can add and complete tasks through this session
+    #[cfg(target_os = "linux")]
+    let _bus = {
+        let (sender, receiver) = unbounded_channel();
+        app.bus_calls = Some(receiver);
+        crate::dbus::start(crate::dbus::Target::Channel(sender))
+    };
 
     // Run loop
-    let res = run_app(&mut terminal, &mut app);
+    let res = runtime.block_on(run_app(&mut terminal, &mut app));
+    let unsaved = app.saver.finish(app.store);
 
     // Fix #8: Save chat history on exit
     app.save_chat_history();
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    restore_terminal()?;
 
     if let Err(err) = res {
         println!("{:?}", err);
     }
+    if let Some(err) = unsaved {
+        offer_save_elsewhere(app.store, &err)?;
+    }
 
     Ok(())
 }
 
-fn run_app<B: ratatui::backend::Backend>(
+/// After a failed final save, ask for another file to write the tasks to
+/// so the session's changes aren't lost
+fn offer_save_elsewhere(store: &TaskStore, err: &str) -> io::Result<()> {
+    eprintln!("Your changes couldn't be saved: {}", err);
+    let content = serde_json::to_string_pretty(store)?;
+    loop {
+        eprint!("Save them to another file? Path (Enter to discard): ");
+        io::Write::flush(&mut io::stderr())?;
+        let mut line = String::new();
+        io::stdin().read_line(&mut line)?;
+        let path = PathBuf::from(line.trim());
+        if path.as_os_str().is_empty() {
+            eprintln!("Changes discarded.");
+            return Ok(());
+        }
+        match std::fs::write(&path, &content) {
+            Ok(()) => {
+                eprintln!("Saved {} tasks to {}.", store.tasks.len(), path.display());
+                eprintln!("Copy it over tasks.json in the data directory once that is writable.");
+                return Ok(());
+            }
+            Err(err) => eprintln!("Couldn't write {}: {}", path.display(), err),
+        }
+    }
+}
+
+pub(crate) fn enter_terminal() -> io::Result<()> {
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableBracketedPaste)
+}
+
+/// Hand the terminal back to the shell in the state it expects
+pub(crate) fn restore_terminal() -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableBracketedPaste,
+        Show
+    )
+}
+
+/// Restore the terminal before a panic message is printed, so a crash
+/// doesn't leave the shell in raw mode on the alternate screen
+pub(crate) fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        default_hook(info);
+    }));
+}
+
+/// Ctrl+Z: raw mode swallows the shell's suspend key, so stop ourselves
+/// and set the terminal up again once the shell resumes us with `fg`
+#[cfg(unix)]
+fn suspend<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+    restore_terminal()?;
+    // SAFETY: raise only delivers a signal to the current process
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+    enter_terminal()?;
+    terminal.clear()
+}
+
+#[cfg(not(unix))]
+fn suspend<B: ratatui::backend::Backend>(_terminal: &mut Terminal<B>) -> io::Result<()> {
+    Ok(())
+}
+
+/// Frame interval while something on screen is animating
+const ANIMATION_TICK: StdDuration = StdDuration::from_millis(100);
+/// Longest wait for input when idle; still notices midnight, AI replies
+/// and expiring toasts
+const IDLE_POLL: StdDuration = StdDuration::from_secs(1);
+
+/// What woke the event loop
+enum Wake {
+    /// A key, paste, or resize; `None` once the terminal is gone
+    Input(Option<io::Result<Event>>),
+    /// Streamed text or the whole reply; `None` once the request is over
+    Reply(Option<AIResponse>),
+    Transcript(Option<Result<String, String>>),
+    Bus(Option<Call>),
+    Repeat(Option<Result<String, String>>),
+    Summary(Option<Result<String, String>>),
+    Saved(Option<Result<(), String>>),
+    /// The timeout ran out: an animation frame, a save coming due, or the
+    /// idle check for midnight and expiring toasts
+    Tick,
+}
+
+/// Poll an optional receiver; one that is unset is never ready
+fn poll_slot<T>(slot: &mut Option<UnboundedReceiver<T>>, cx: &mut Context) -> Poll<Option<T>> {
+    slot.as_mut()
+        .map_or(Poll::Pending, |receiver| receiver.poll_recv(cx))
+}
+
+/// Wait for whichever comes first: input, a message from a request or
+/// the bus, the saver reporting back, or the end of `timeout`
+async fn next_wake(app: &mut App<'_>, input: &mut EventStream, timeout: StdDuration) -> Wake {
+    let mut sleep = pin!(tokio::time::sleep(timeout));
+    poll_fn(|cx| {
+        if let Poll::Ready(event) = Pin::new(&mut *input).poll_next(cx) {
+            return Poll::Ready(Wake::Input(event));
+        }
+        if let Poll::Ready(response) = poll_slot(&mut app.chat_receiver, cx) {
+            return Poll::Ready(Wake::Reply(response));
+        }
+        if let Poll::Ready(result) = poll_slot(&mut app.transcript_receiver, cx) {
+            return Poll::Ready(Wake::Transcript(result));
+        }
+        if let Poll::Ready(call) = poll_slot(&mut app.bus_calls, cx) {
+            return Poll::Ready(Wake::Bus(call));
+        }
+        if let Some(check) = &mut app.repeat_check {
+            if let Poll::Ready(reply) = check.receiver.poll_recv(cx) {
+                return Poll::Ready(Wake::Repeat(reply));
+            }
+        }
+        if let Some((_, receiver)) = &mut app.summary_receiver {
+            if let Poll::Ready(result) = receiver.poll_recv(cx) {
+                return Poll::Ready(Wake::Summary(result));
+            }
+        }
+        if let Poll::Ready(outcome) = app.saver.poll_outcome(cx) {
+            return Poll::Ready(Wake::Saved(outcome));
+        }
+        sleep.as_mut().poll(cx).map(|()| Wake::Tick)
+    })
+    .await
+}
+
+/// Draw and handle input until the user quits. Runs on the async runtime:
+/// each pass waits on terminal input, the AI and transcription requests,
+/// bus calls, and the saver at once, and handles whichever is ready
+async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
-    app: &mut App,
+    app: &mut App<'_>,
 ) -> io::Result<()> {
+    let mut input = EventStream::new();
+    // Only draw when something changed: input, a tick, an AI reply, a new day
+    let mut redraw = true;
+    let mut last_tick = Instant::now();
+    let mut screen = std::mem::discriminant(&app.current_screen);
     loop {
-        // Increment spinner state for animation
-        app.spinner_state = app.spinner_state.wrapping_add(1);
+        // Write changes once they settle, or as soon as the screen changes
+        if std::mem::discriminant(&app.current_screen) != screen {
+            screen = std::mem::discriminant(&app.current_screen);
+            tracing::debug!(screen = ?app.current_screen, "switched screen");
+            app.saver.flush(app.store);
+        }
+        // Take in what other eq processes saved, before it is saved over
+        match app.saver.reload_if_changed(app.store) {
+            Ok(true) => {
+                app.clamp_selected_index();
+                redraw = true;
+            }
+            Ok(false) => {}
+            Err(err) => tracing::warn!(%err, "could not read the tasks saved elsewhere"),
+        }
+        app.saver.save_if_due(app.store);
+        app.page_in_history();
+        app.refresh_postponements();
+        if redraw {
+            let started = Instant::now();
+            terminal.draw(|f| crate::tui::ui::ui(f, app))?;
+            timings::FRAMES.record(started.elapsed());
+            redraw = false;
+        }
 
-        terminal.draw(|f| crate::tui::ui::ui(f, app))?;
+        // Persist the pomodoro as soon as it runs out
+        app.record_finished_pomodoro();
 
-        // Poll for AI responses
-        if let Some(receiver) = &app.chat_receiver {
-            if let Ok(response) = receiver.try_recv() {
-                app.is_loading = false;
-                match response {
-                    AIResponse::Success(content) => {
-                        // Process response and auto-add any [ADD] tasks
-                        let full_content = app.process_ai_response(content);
-
-                        app.chat_history.push(ChatMessage {
-                            role: "assistant".to_string(),
-                            content: full_content,
-                        });
-                        // Fix #8: Auto-save after AI response
-                        app.save_chat_history();
+        // Keep "today" current when left open past midnight
+        redraw |= app.check_day_change();
+
+        let tick = app.tick_rate();
+        let timeout = tick.map_or(IDLE_POLL, |rate| rate.saturating_sub(last_tick.elapsed()));
+        let timeout = app.saver.due_in().map_or(timeout, |due| timeout.min(due));
+        match next_wake(app, &mut input, timeout).await {
+            Wake::Input(None) => return Ok(()),
+            Wake::Input(Some(event)) => {
+                let event = event?;
+                redraw = true;
+                if let Event::Key(key) = &event {
+                    if key.code == KeyCode::Char('z')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                    {
+                        // The shell may never resume us
+                        app.saver.flush(app.store);
+                        suspend(terminal)?;
+                        continue;
                     }
-                    AIResponse::Error(err) => {
-                        app.chat_history.push(ChatMessage {
-                            role: "assistant".to_string(),
-                            content: format!("Error: {}", err),
-                        });
+                }
+                if crate::tui::handlers::handle_key_events(event, app) == Some(true) {
+                    return Ok(());
+                }
+            }
+            Wake::Reply(Some(response)) => {
+                app.take_reply(response);
+                redraw = true;
+            }
+            Wake::Reply(None) => {
+                // The request ended without a reply
+                app.chat_receiver = None;
+                app.chat_task = None;
+                app.is_loading = false;
+                redraw = true;
+            }
+            Wake::Transcript(result) => {
+                app.transcript_receiver = None;
+                app.chat_task = None;
+                app.is_loading = false;
+                redraw = true;
+                match result {
+                    Some(Ok(transcript)) => app.add_memo_tasks(transcript),
+                    Some(Err(err)) => app.post_local(format!("Error: {}", err)),
+                    None => {}
+                }
+            }
+            Wake::Bus(Some(call)) => {
+                app.apply_bus_call(call);
+                redraw = true;
+            }
+            Wake::Bus(None) => app.bus_calls = None,
+            Wake::Repeat(reply) => {
+                if let Some(check) = app.repeat_check.take() {
+                    let reply = reply.unwrap_or_else(|| Err("no reply".to_string()));
+                    app.finish_repeat_check(check, reply);
+                }
+                redraw = true;
+            }
+            Wake::Summary(result) => {
+                if let Some((covered, _)) = app.summary_receiver.take() {
+                    // On failure the next message simply tries again
+                    if let Some(Ok(summary)) = result {
+                        app.chat_summary = Some(summary);
+                        app.chat_summarized = covered;
                     }
                 }
             }
-        }
-
-        if event::poll(std::time::Duration::from_millis(100))? {
-            let event = event::read()?;
-            if let Some(res) = crate::tui::handlers::handle_key_events(event, app) {
-                if res {
-                    return Ok(());
+            Wake::Saved(Some(Ok(()))) => {
+                if app.save_error.take().is_some() {
+                    app.notify("Saved");
+                    redraw = true;
                 }
             }
+            Wake::Saved(Some(Err(err))) => {
+                app.save_error = Some(err);
+                redraw = true;
+            }
+            // Turned down because the file changed; merged and saved next pass
+            Wake::Saved(None) => {}
+            Wake::Tick => {}
+        }
+
+        if tick.is_some_and(|rate| last_tick.elapsed() >= rate) {
+            app.spinner_state = app.spinner_state.wrapping_add(1);
+            last_tick = Instant::now();
+            redraw = true;
+        }
+        // Clear a toast once it has had its time
+        if app.toast.as_ref().is_some_and(Toast::is_expired) {
+            redraw = true;
         }
     }
 }
@@ -0,0 +1,58 @@
+use crate::models::task::{Task, TaskStatus};
+use arboard::Clipboard;
+
+/// The system clipboard, opened on first use and kept open: on X11 and
+/// Wayland copied text is only served while its owner is alive
+#[derive(Default)]
+pub struct SystemClipboard {
+    inner: Option<Clipboard>,
+}
+
+impl SystemClipboard {
+    pub fn copy(&mut self, text: &str) -> Result<(), String> {
+        if self.inner.is_none() {
+            let clipboard =
+                Clipboard::new().map_err(|e| format!("Clipboard unavailable: {}", e))?;
+            self.inner = Some(clipboard);
+        }
+        let clipboard = self.inner.as_mut().expect("opened above");
+        clipboard
+            .set_text(text)
+            .map_err(|e| format!("Copy failed: {}", e))
+    }
+}
+
+/// A task as plain text for pasting: the title, then its priority and day
+pub fn task_text(task: &Task) -> String {
+    let mut details = vec![
+        task.quadrant().to_string(),
+        format!("u{}i{}", task.urgency, task.importance),
+        task.date.format("%a %b %-d").to_string(),
+    ];
+    if let Some(minutes) = task.estimate_minutes {
+        details.push(format!("~{}m", minutes));
+    }
+    match task.status {
+        TaskStatus::Pending => {}
+        TaskStatus::Completed => details.push("done".to_string()),
+        TaskStatus::Dropped => details.push("dropped".to_string()),
+    }
+    format!("{}\n{}", task.title, details.join(" · "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_text() {
+        let mut task = Task::new("Email Imai".into(), 3, 2, "2024-06-12".parse().unwrap());
+        task.estimate_minutes = Some(15);
+        assert_eq!(
+            task_text(&task),
+            "Email Imai\nDO FIRST · u3i2 · Wed Jun 12 · ~15m"
+        );
+        task.complete();
+        assert!(task_text(&task).ends_with("· done"));
+    }
+}
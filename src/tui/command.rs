@@ -0,0 +1,348 @@
+use crate::parser::ai_commands::{parse_task_identifier, TaskIdentifier};
+use crate::parser::date::parse_date;
+use crate::tui::app::SortMode;
+use crate::tui::theme::{Theme, THEME_NAMES};
+use crate::tui::zen::Motion;
+use chrono::{Duration, NaiveDate};
+use std::path::PathBuf;
+
+/// Command names offered by the `:` palette, in completion order
+pub const COMMANDS: &[&str] = &[
+    "a11y", "add", "chat", "filter", "goto", "help", "motion", "quit", "sort", "stats", "theme",
+    "today", "tomorrow", "week",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaletteCommand {
+    Add(String),
+    Goto(NaiveDate),
+    Filter(Option<String>),
+    Sort(SortMode),
+    /// Toggle glyphs and border patterns for quadrants
+    Accessible,
+    /// Set Zen mode animation, or cycle to the next level when `None`
+    Motion(Option<Motion>),
+    /// Switch to a preset, or cycle to the next one when `None`
+    Theme(Option<&'static str>),
+    Chat,
+    Help,
+    Quit,
+    Stats,
+    Week,
+}
+
+/// Parse a palette line (without the leading `:`).
+///
+/// `today` is used to resolve relative dates such as `tomorrow` or `+3`.
+pub fn parse_command(input: &str, today: NaiveDate) -> Result<PaletteCommand, String> {
+    let input = input.trim();
+    let (name, rest) = match input.split_once(char::is_whitespace) {
+        Some((name, rest)) => (name, rest.trim()),
+        None => (input, ""),
+    };
+
+    match name {
+        "add" | "a" => {
+            if rest.is_empty() {
+                Err(String::from("Usage: :add <title> [u2i3] [~30m]"))
+            } else {
+                Ok(PaletteCommand::Add(rest.to_string()))
+            }
+        }
+        "goto" | "g" => parse_date(rest, today)
+            .map(PaletteCommand::Goto)
+            .ok_or_else(|| format!("Invalid date: '{}' (try 2024-07-01, tomorrow, +3)", rest)),
+        "today" => Ok(PaletteCommand::Goto(today)),
+        "tomorrow" => Ok(PaletteCommand::Goto(today + Duration::days(1))),
+        "filter" | "f" => Ok(PaletteCommand::Filter(if rest.is_empty() {
+            None
+        } else {
+            Some(rest.to_string())
+        })),
+        "sort" => SortMode::from_name(rest)
+            .map(PaletteCommand::Sort)
+            .ok_or_else(|| format!("Unknown sort '{}' (score, created, alpha, manual)", rest)),
+        "theme" if rest.is_empty() => Ok(PaletteCommand::Theme(None)),
+        "theme" => Theme::by_name(rest)
+            .map(|theme| PaletteCommand::Theme(Some(theme.name)))
+            .ok_or_else(|| format!("Unknown theme '{}' ({})", rest, THEME_NAMES.join(", "))),
+        "a11y" | "accessible" => Ok(PaletteCommand::Accessible),
+        "motion" if rest.is_empty() => Ok(PaletteCommand::Motion(None)),
+        "motion" => Motion::from_name(rest)
+            .map(|motion| PaletteCommand::Motion(Some(motion)))
+            .ok_or_else(|| format!("Unknown motion '{}' (full, reduced, still)", rest)),
+        "chat" => Ok(PaletteCommand::Chat),
+        "help" | "h" => Ok(PaletteCommand::Help),
+        "q" | "quit" => Ok(PaletteCommand::Quit),
+        "stats" => Ok(PaletteCommand::Stats),
+        "week" | "w" => Ok(PaletteCommand::Week),
+        "" => Err(String::from("Type a command, Tab to complete")),
+        other => Err(format!("Unknown command: {}", other)),
+    }
+}
+
+/// A `/` command typed in the chat instead of a message
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatCommand {
+    Model(String),
+    Temperature(f32),
+    MaxTokens(u32),
+    /// Show the conversation's model settings
+    Settings,
+    /// Drop this conversation's overrides
+    Reset,
+    /// Challenge the DELEGATE and DROP tasks of the viewed day
+    Audit,
+    /// Ask for a retrospective of this week, or last week with `/retro last`
+    Retro {
+        last: bool,
+    },
+    /// Add a task to the viewed day, with the same notation as `a`
+    Add(String),
+    /// Mark a task of the viewed day done by title fragment or `#N`
+    Done(TaskIdentifier),
+    /// Post the viewed day's pending tasks
+    List,
+    /// Clear the chat history
+    Clear,
+    /// Post a quote from the built-in bank
+    Quote,
+    /// Send a message without reusing a saved reply
+    NoCache(String),
+    /// Transcribe a voice memo and turn it into tasks
+    Audio(PathBuf),
+    /// Post the chat commands
+    Help,
+}
+
+/// Chat commands and what they do, as posted by `/help`
+pub const CHAT_COMMANDS: &[(&str, &str)] = &[
+    ("/add <title> [u2i3] [~30m]", "Add a task to the viewed day"),
+    ("/done <title or #N>", "Mark a task done"),
+    ("/list", "List the day's pending tasks"),
+    ("/clear", "Clear the chat history"),
+    ("/quote", "Show a quote without asking the AI"),
+    ("/nocache <message>", "Ask without reusing a saved reply"),
+    ("/audio <file>", "Turn a voice memo into tasks"),
+    ("/model, /temp, /tokens", "Override the chat model settings"),
+    ("/reset", "Drop the overrides"),
+    ("/audit", "Challenge DELEGATE and DROP tasks"),
+    ("/retro [last]", "Weekly retrospective to the journal"),
+    ("/help", "This list"),
+];
+
+/// Parse a chat line starting with `/`; `None` means it is a plain message
+pub fn parse_chat_command(input: &str) -> Option<Result<ChatCommand, String>> {
+    let rest = input.trim().strip_prefix('/')?;
+    let (name, arg) = match rest.split_once(char::is_whitespace) {
+        Some((name, arg)) => (name, arg.trim()),
+        None => (rest, ""),
+    };
+
+    Some(match (name, arg) {
+        ("model" | "temp" | "temperature" | "tokens", "") => Ok(ChatCommand::Settings),
+        ("model", model) => Ok(ChatCommand::Model(model.to_string())),
+        ("temp" | "temperature", value) => match value.parse::<f32>() {
+            Ok(t) if (0.0..=2.0).contains(&t) => Ok(ChatCommand::Temperature(t)),
+            _ => Err(format!("Temperature must be 0.0-2.0, not '{}'", value)),
+        },
+        ("tokens", value) => match value.parse::<u32>() {
+            Ok(n) if n > 0 => Ok(ChatCommand::MaxTokens(n)),
+            _ => Err(format!(
+                "Token limit must be a positive number, not '{}'",
+                value
+            )),
+        },
+        ("reset", _) => Ok(ChatCommand::Reset),
+        ("audit", _) => Ok(ChatCommand::Audit),
+        ("retro", "") => Ok(ChatCommand::Retro { last: false }),
+        ("retro", "last") => Ok(ChatCommand::Retro { last: true }),
+        ("retro", other) => Err(format!("Usage: /retro [last], not '{}'", other)),
+        ("add", "") => Err(String::from("Usage: /add <title> [u2i3] [~30m]")),
+        ("add", task) => Ok(ChatCommand::Add(task.to_string())),
+        ("done", target) => parse_task_identifier(target)
+            .map(ChatCommand::Done)
+            .ok_or_else(|| String::from("Usage: /done <title or #N>")),
+        ("list", _) => Ok(ChatCommand::List),
+        ("clear", _) => Ok(ChatCommand::Clear),
+        ("quote", _) => Ok(ChatCommand::Quote),
+        ("help", _) => Ok(ChatCommand::Help),
+        ("nocache", "") => Err(String::from("Usage: /nocache <message>")),
+        ("nocache", message) => Ok(ChatCommand::NoCache(message.to_string())),
+        ("audio", "") => Err(String::from("Usage: /audio <file>")),
+        ("audio", path) => Ok(ChatCommand::Audio(PathBuf::from(path))),
+        (other, _) => Err(format!("Unknown chat command /{} (try /help)", other)),
+    })
+}
+
+/// Whether the characters of `query` appear in order within `candidate`
+fn is_subsequence(query: &str, candidate: &str) -> bool {
+    let mut chars = candidate.chars();
+    query.chars().all(|q| chars.any(|c| c == q))
+}
+
+/// Command names matching the first word of `input`, prefix matches first
+pub fn complete(input: &str) -> Vec<&'static str> {
+    let query = input.trim_start().to_lowercase();
+    if query.contains(char::is_whitespace) {
+        return Vec::new();
+    }
+
+    let mut prefix: Vec<&'static str> = Vec::new();
+    let mut fuzzy: Vec<&'static str> = Vec::new();
+    for name in COMMANDS {
+        if name.starts_with(&query) {
+            prefix.push(name);
+        } else if is_subsequence(&query, name) {
+            fuzzy.push(name);
+        }
+    }
+    prefix.extend(fuzzy);
+    prefix
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 3).unwrap()
+    }
+
+    #[test]
+    fn test_parse_add_and_quit() {
+        assert_eq!(
+            parse_command("add buy milk u2i1", today()),
+            Ok(PaletteCommand::Add("buy milk u2i1".into()))
+        );
+        assert!(parse_command("add", today()).is_err());
+        assert_eq!(parse_command("q", today()), Ok(PaletteCommand::Quit));
+    }
+
+    #[test]
+    fn test_parse_goto() {
+        assert_eq!(
+            parse_command("goto 2024-07-01", today()),
+            Ok(PaletteCommand::Goto(
+                NaiveDate::from_ymd_opt(2024, 7, 1).unwrap()
+            ))
+        );
+        assert_eq!(
+            parse_command("goto +2", today()),
+            Ok(PaletteCommand::Goto(
+                NaiveDate::from_ymd_opt(2024, 6, 5).unwrap()
+            ))
+        );
+        assert!(parse_command("goto someday", today()).is_err());
+    }
+
+    #[test]
+    fn test_parse_filter_and_sort() {
+        assert_eq!(
+            parse_command("filter #work", today()),
+            Ok(PaletteCommand::Filter(Some("#work".into())))
+        );
+        assert_eq!(
+            parse_command("filter", today()),
+            Ok(PaletteCommand::Filter(None))
+        );
+        assert_eq!(
+            parse_command("sort created", today()),
+            Ok(PaletteCommand::Sort(SortMode::Created))
+        );
+        assert!(parse_command("sort sideways", today()).is_err());
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        assert_eq!(
+            parse_command("theme mono", today()),
+            Ok(PaletteCommand::Theme(Some("monochrome")))
+        );
+        assert_eq!(
+            parse_command("theme", today()),
+            Ok(PaletteCommand::Theme(None))
+        );
+        assert!(parse_command("theme neon", today()).is_err());
+    }
+
+    #[test]
+    fn test_parse_motion() {
+        assert_eq!(
+            parse_command("motion off", today()),
+            Ok(PaletteCommand::Motion(Some(Motion::Still)))
+        );
+        assert_eq!(
+            parse_command("motion", today()),
+            Ok(PaletteCommand::Motion(None))
+        );
+        assert!(parse_command("motion wild", today()).is_err());
+    }
+
+    #[test]
+    fn test_parse_chat_command() {
+        assert_eq!(parse_chat_command("plan my week"), None);
+        assert_eq!(
+            parse_chat_command("/model gpt-4o-mini"),
+            Some(Ok(ChatCommand::Model("gpt-4o-mini".into())))
+        );
+        assert_eq!(
+            parse_chat_command("/temp 0.2"),
+            Some(Ok(ChatCommand::Temperature(0.2)))
+        );
+        assert_eq!(
+            parse_chat_command("/tokens 1200"),
+            Some(Ok(ChatCommand::MaxTokens(1200)))
+        );
+        assert_eq!(
+            parse_chat_command("/model"),
+            Some(Ok(ChatCommand::Settings))
+        );
+        assert!(matches!(parse_chat_command("/temp 5"), Some(Err(_))));
+        assert!(matches!(parse_chat_command("/tokens 0"), Some(Err(_))));
+        assert_eq!(
+            parse_chat_command("/retro last"),
+            Some(Ok(ChatCommand::Retro { last: true }))
+        );
+        assert!(matches!(parse_chat_command("/dance"), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_parse_local_chat_commands() {
+        assert_eq!(
+            parse_chat_command("/add Call the bank u3i2"),
+            Some(Ok(ChatCommand::Add("Call the bank u3i2".into())))
+        );
+        assert!(matches!(parse_chat_command("/add"), Some(Err(_))));
+        assert_eq!(
+            parse_chat_command("/done #2"),
+            Some(Ok(ChatCommand::Done(TaskIdentifier::Index(2))))
+        );
+        assert_eq!(
+            parse_chat_command("/done report"),
+            Some(Ok(ChatCommand::Done(TaskIdentifier::Title(
+                "report".into()
+            ))))
+        );
+        assert!(matches!(parse_chat_command("/done"), Some(Err(_))));
+        assert_eq!(parse_chat_command("/list"), Some(Ok(ChatCommand::List)));
+        assert_eq!(parse_chat_command("/help"), Some(Ok(ChatCommand::Help)));
+        assert_eq!(
+            parse_chat_command("/nocache plan my day"),
+            Some(Ok(ChatCommand::NoCache("plan my day".into())))
+        );
+        assert!(matches!(parse_chat_command("/nocache"), Some(Err(_))));
+        assert_eq!(
+            parse_chat_command("/audio memo.m4a"),
+            Some(Ok(ChatCommand::Audio("memo.m4a".into())))
+        );
+    }
+
+    #[test]
+    fn test_complete_prefers_prefix() {
+        assert_eq!(complete("st"), vec!["stats", "sort"]);
+        assert_eq!(complete("to")[..2], ["today", "tomorrow"]);
+        assert!(complete("wk").contains(&"week"));
+        assert!(complete("add milk").is_empty());
+    }
+}
@@ -0,0 +1,98 @@
+use crate::config::Config;
+use crate::tui::app::{enter_terminal, install_panic_hook, restore_terminal};
+use crate::tui::theme::Theme;
+use crate::tui::zen::Pomodoro;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame, Terminal,
+};
+use std::error::Error;
+use std::io;
+use std::time::Duration;
+
+/// Dots in the progress row
+const PROGRESS_DOTS: u64 = 30;
+
+/// Full-screen countdown for `eq focus`, without the rest of the TUI.
+/// Returns whether it ran to the end rather than being stopped
+pub fn run_countdown(title: &str, minutes: u64) -> Result<bool, Box<dyn Error>> {
+    install_panic_hook();
+    enter_terminal()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let theme = Theme::from_config(&Config::load_or_default());
+    let pomo = Pomodoro::new(minutes, None);
+
+    let res = countdown_loop(&mut terminal, title, &pomo, &theme);
+    restore_terminal()?;
+    Ok(res?)
+}
+
+fn countdown_loop<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    title: &str,
+    pomo: &Pomodoro,
+    theme: &Theme,
+) -> io::Result<bool> {
+    loop {
+        terminal.draw(|f| render(f, title, pomo, theme))?;
+        if pomo.is_complete() {
+            return Ok(true);
+        }
+
+        if event::poll(Duration::from_millis(250))? {
+            if let Event::Key(key) = event::read()? {
+                let ctrl_c =
+                    key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                if ctrl_c || matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+                    return Ok(false);
+                }
+            }
+        }
+    }
+}
+
+fn render(f: &mut Frame, title: &str, pomo: &Pomodoro, theme: &Theme) {
+    let area = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+        ])
+        .split(f.area())[1];
+
+    let filled = (pomo.progress() * PROGRESS_DOTS as f64) as usize;
+    let lines = vec![
+        Line::from(Span::styled(
+            title,
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(Span::styled(
+            pomo.format_remaining(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("•".repeat(filled), Style::default().fg(theme.zen_progress)),
+            Span::styled(
+                "·".repeat(PROGRESS_DOTS as usize - filled),
+                Style::default().fg(theme.zen_track),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "q to stop (nothing is recorded)",
+            Style::default().fg(theme.muted),
+        )),
+    ];
+
+    f.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+}
@@ -0,0 +1,294 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::tui::text::{display_width, head_to_width, tail_to_width};
+
+/// Single-line text input with a cursor
+///
+/// The cursor is a byte offset that always sits on a grapheme boundary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineEditor {
+    text: String,
+    cursor: usize,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Editor holding `text` with the cursor at the end
+    pub fn with_text(text: impl Into<String>) -> Self {
+        let text = text.into();
+        let cursor = text.len();
+        Self { text, cursor }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.cursor = 0;
+    }
+
+    pub fn insert_char(&mut self, c: char) {
+        self.text.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+    }
+
+    pub fn insert_str(&mut self, s: &str) {
+        self.text.insert_str(self.cursor, s);
+        self.cursor += s.len();
+    }
+
+    /// Insert pasted text at the cursor as a single line
+    pub fn paste(&mut self, text: &str) {
+        self.insert_str(&single_line(text));
+    }
+
+    /// Delete the grapheme before the cursor
+    pub fn backspace(&mut self) -> bool {
+        let start = self.prev_boundary();
+        if start == self.cursor {
+            return false;
+        }
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+        true
+    }
+
+    /// Delete the grapheme under the cursor
+    pub fn delete(&mut self) -> bool {
+        let end = self.next_boundary();
+        if end == self.cursor {
+            return false;
+        }
+        self.text.replace_range(self.cursor..end, "");
+        true
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.prev_boundary();
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = self.next_boundary();
+    }
+
+    pub fn move_home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_end(&mut self) {
+        self.cursor = self.text.len();
+    }
+
+    pub fn word_left(&mut self) {
+        self.cursor = self.word_start();
+    }
+
+    pub fn word_right(&mut self) {
+        let after = &self.text[self.cursor..];
+        let skip = after.len() - after.trim_start().len();
+        let end = after[skip..]
+            .find(char::is_whitespace)
+            .map(|i| skip + i)
+            .unwrap_or(after.len());
+        self.cursor += end;
+    }
+
+    /// Delete from the start of the previous word up to the cursor
+    pub fn delete_word_back(&mut self) {
+        let start = self.word_start();
+        self.text.replace_range(start..self.cursor, "");
+        self.cursor = start;
+    }
+
+    pub fn delete_to_start(&mut self) {
+        self.text.replace_range(..self.cursor, "");
+        self.cursor = 0;
+    }
+
+    pub fn delete_to_end(&mut self) {
+        self.text.truncate(self.cursor);
+    }
+
+    /// Apply an editing key. Returns false when the key is not an editing key
+    /// so the caller can handle it.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Left if ctrl || alt => self.word_left(),
+            KeyCode::Right if ctrl || alt => self.word_right(),
+            KeyCode::Left => self.move_left(),
+            KeyCode::Right => self.move_right(),
+            KeyCode::Home => self.move_home(),
+            KeyCode::End => self.move_end(),
+            KeyCode::Backspace if ctrl || alt => self.delete_word_back(),
+            KeyCode::Backspace => {
+                self.backspace();
+            }
+            KeyCode::Delete => {
+                self.delete();
+            }
+            KeyCode::Char('a') if ctrl => self.move_home(),
+            KeyCode::Char('e') if ctrl => self.move_end(),
+            KeyCode::Char('b') if alt => self.word_left(),
+            KeyCode::Char('f') if alt => self.word_right(),
+            KeyCode::Char('w') if ctrl => self.delete_word_back(),
+            KeyCode::Char('u') if ctrl => self.delete_to_start(),
+            KeyCode::Char('k') if ctrl => self.delete_to_end(),
+            KeyCode::Char(c) if !ctrl && !alt => self.insert_char(c),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Visible part of the text and the cursor column for a field `width`
+    /// columns wide, scrolled horizontally so the cursor stays in view
+    pub fn view(&self, width: usize) -> (&str, usize) {
+        if width == 0 {
+            return ("", 0);
+        }
+        let before = &self.text[..self.cursor];
+        // Leave one column for the cursor itself
+        let shown_before = tail_to_width(before, width - 1);
+        let start = self.cursor - shown_before.len();
+        let visible = head_to_width(&self.text[start..], width);
+        (visible, display_width(shown_before))
+    }
+
+    fn prev_boundary(&self) -> usize {
+        self.text[..self.cursor]
+            .grapheme_indices(true)
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or(0)
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.cursor
+            + self.text[self.cursor..]
+                .graphemes(true)
+                .next()
+                .map(str::len)
+                .unwrap_or(0)
+    }
+
+    fn word_start(&self) -> usize {
+        let trimmed = self.text[..self.cursor].trim_end();
+        match trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+        {
+            Some((i, c)) => i + c.len_utf8(),
+            None => 0,
+        }
+    }
+}
+
+/// Join pasted lines with spaces, dropping blank ones, for single-line inputs
+pub fn single_line(text: &str) -> String {
+    text.split(['\r', '\n'])
+        .map(|l| l.replace('\t', " "))
+        .filter(|l| !l.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn test_insert_at_cursor() {
+        let mut ed = LineEditor::with_text("buy milk");
+        ed.move_home();
+        ed.word_right();
+        ed.insert_str(" oat");
+        assert_eq!(ed.as_str(), "buy oat milk");
+        ed.move_left();
+        ed.insert_char('s');
+        assert_eq!(ed.as_str(), "buy oast milk");
+    }
+
+    #[test]
+    fn test_paste_joins_lines() {
+        let mut ed = LineEditor::with_text("notes: ");
+        ed.paste("first line\r\n\nsecond\tline\n");
+        assert_eq!(ed.as_str(), "notes: first line second line");
+        assert_eq!(ed.cursor(), ed.as_str().len());
+    }
+
+    #[test]
+    fn test_grapheme_movement_and_delete() {
+        let mut ed = LineEditor::with_text("a👨‍👩‍👧b");
+        ed.move_left();
+        ed.move_left();
+        assert_eq!(ed.cursor(), 1);
+        assert!(ed.delete());
+        assert_eq!(ed.as_str(), "ab");
+        assert!(ed.backspace());
+        assert_eq!(ed.as_str(), "b");
+        assert!(!ed.backspace());
+    }
+
+    #[test]
+    fn test_word_motions() {
+        let mut ed = LineEditor::with_text("fix  the bug");
+        ed.word_left();
+        assert_eq!(ed.cursor(), 9);
+        ed.word_left();
+        assert_eq!(ed.cursor(), 5);
+        ed.word_right();
+        assert_eq!(ed.cursor(), 8);
+        ed.move_end();
+        ed.delete_word_back();
+        assert_eq!(ed.as_str(), "fix  the ");
+    }
+
+    #[test]
+    fn test_handle_key() {
+        let mut ed = LineEditor::with_text("hello");
+        assert!(ed.handle_key(&key(KeyCode::Char('a'), KeyModifiers::CONTROL)));
+        assert_eq!(ed.cursor(), 0);
+        ed.handle_key(&key(KeyCode::Char('X'), KeyModifiers::SHIFT));
+        assert_eq!(ed.as_str(), "Xhello");
+        ed.handle_key(&key(KeyCode::Char('e'), KeyModifiers::CONTROL));
+        assert_eq!(ed.cursor(), ed.as_str().len());
+        assert!(!ed.handle_key(&key(KeyCode::Enter, KeyModifiers::NONE)));
+        assert!(!ed.handle_key(&key(KeyCode::Char('l'), KeyModifiers::CONTROL)));
+    }
+
+    #[test]
+    fn test_view_keeps_cursor_visible() {
+        let mut ed = LineEditor::with_text("abcdefghij");
+        assert_eq!(ed.view(5), ("ghij", 4));
+        ed.move_home();
+        assert_eq!(ed.view(5), ("abcde", 0));
+        ed.move_right();
+        ed.move_right();
+        assert_eq!(ed.view(5), ("abcde", 2));
+
+        let wide = LineEditor::with_text("世界世界");
+        let (visible, col) = wide.view(5);
+        assert_eq!(visible, "世界");
+        assert_eq!(col, 4);
+    }
+}
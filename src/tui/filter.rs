@@ -0,0 +1,101 @@
+use crate::models::task::Task;
+
+/// Parsed `/` filter; a task must match every term
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskFilter {
+    query: String,
+    words: Vec<String>,    // Lowercase substrings of the title
+    tags: Vec<String>,     // Lowercase `#tag` words the title must contain
+    min_score: Option<u8>, // From `>=N` or `>N`
+}
+
+impl TaskFilter {
+    /// Parse a query such as `report #work >=10`; `None` when it is blank
+    pub fn parse(query: &str) -> Option<Self> {
+        let query = query.trim();
+        if query.is_empty() {
+            return None;
+        }
+
+        let mut filter = TaskFilter {
+            query: query.to_string(),
+            words: Vec::new(),
+            tags: Vec::new(),
+            min_score: None,
+        };
+        for term in query.split_whitespace() {
+            let term = term.to_lowercase();
+            let min = if let Some(n) = term.strip_prefix(">=") {
+                n.parse::<u8>().ok()
+            } else if let Some(n) = term.strip_prefix('>') {
+                n.parse::<u8>().ok().map(|n| n.saturating_add(1))
+            } else {
+                None
+            };
+
+            if let Some(min) = min {
+                filter.min_score = Some(filter.min_score.map_or(min, |m| m.max(min)));
+            } else if term.len() > 1 && term.starts_with('#') {
+                filter.tags.push(term);
+            } else {
+                filter.words.push(term);
+            }
+        }
+        Some(filter)
+    }
+
+    /// The query as typed, for the header
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn matches(&self, task: &Task) -> bool {
+        let title = task.title.to_lowercase();
+        let has_tag = |tag: &String| {
+            title
+                .split_whitespace()
+                .any(|w| w.trim_end_matches(|c: char| c.is_ascii_punctuation()) == tag)
+        };
+
+        self.min_score.is_none_or(|min| task.score() >= min)
+            && self.tags.iter().all(has_tag)
+            && self.words.iter().all(|w| title.contains(w.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn task(title: &str, urgency: u8, importance: u8) -> Task {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 12).unwrap();
+        Task::new(title.to_string(), urgency, importance, date)
+    }
+
+    #[test]
+    fn test_blank_query_is_no_filter() {
+        assert!(TaskFilter::parse("   ").is_none());
+    }
+
+    #[test]
+    fn test_words_and_tags() {
+        let filter = TaskFilter::parse("Report #work").unwrap();
+        assert!(filter.matches(&task("Write quarterly report #work", 2, 2)));
+        assert!(filter.matches(&task("report draft #work, then lunch", 2, 2)));
+        assert!(!filter.matches(&task("Write report #workshop", 2, 2)));
+        assert!(!filter.matches(&task("Plan #work offsite", 2, 2)));
+    }
+
+    #[test]
+    fn test_min_score() {
+        // u3i3 scores 15, u1i1 scores 5
+        let filter = TaskFilter::parse(">=10").unwrap();
+        assert!(filter.matches(&task("a", 3, 3)));
+        assert!(!filter.matches(&task("b", 1, 1)));
+
+        let strict = TaskFilter::parse(">15").unwrap();
+        assert!(!strict.matches(&task("a", 3, 3)));
+        assert_eq!(strict.query(), ">15");
+    }
+}
@@ -1,50 +1,120 @@
-use crate::ai::{AIResponse, ChatMessage};
-use crate::models::task::{Quadrant, Task, TaskStatus};
-use crate::parser::input::parse_priority;
-use crate::tui::app::{App, CurrentScreen};
-use crate::tui::zen::Pomodoro;
+use crate::models::task::{nudge_priority, MatrixDirection, Quadrant};
+use crate::parser::input::{escape_title, parse_input};
+use crate::tui::app::{App, CurrentScreen, PendingAction, ViewLayout};
+use crate::tui::command::{complete, parse_chat_command, parse_command, PaletteCommand};
+use crate::tui::editor::{single_line, LineEditor};
+use crate::tui::keymap::{help_offset, HELP};
+use crate::tui::review::ReviewChoice;
+use crate::tui::text::pop_grapheme;
+use crate::tui::triage::TriageChoice;
+use crate::tui::widgets::calendar::{DatePicker, DatePickerAction};
+use crate::weekly::WeeklyChoice;
 use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
-use std::sync::mpsc;
 
 pub fn handle_key_events(event: Event, app: &mut App) -> Option<bool> {
-    // Poll for AI responses
-    if let Some(receiver) = &app.chat_receiver {
-        if let Ok(response) = receiver.try_recv() {
-            app.is_loading = false;
-            match response {
-                AIResponse::Success(content) => {
-                    app.chat_history.push(ChatMessage {
-                        role: "assistant".to_string(),
-                        content,
-                    });
-                    app.save_chat_history();
-                }
-                AIResponse::Error(err) => {
-                    app.chat_history.push(ChatMessage {
-                        role: "assistant".to_string(),
-                        content: format!("Error: {}", err),
-                    });
-                }
-            }
-        }
-    }
-
     match event {
+        Event::Paste(text) => {
+            handle_paste(&text, app);
+            None
+        }
+        Event::Key(key) if app.confirm.is_some() => handle_confirm(key, app),
+        Event::Key(key) if app.show_help => handle_help(key, app),
+        Event::Key(key) if app.detail_task.is_some() => handle_detail(key, app),
+        Event::Key(key) if app.date_picker.is_some() => handle_date_picker(key, app),
+        Event::Key(key) if app.command_input.is_some() => handle_command_palette(key, app),
+        Event::Key(key) if app.filter_input.is_some() => handle_filter_prompt(key, app),
+        Event::Key(key) if app.tag_input.is_some() => handle_tag_prompt(key, app),
         Event::Key(key) => match app.current_screen {
             CurrentScreen::Main => handle_main_screen(key, app),
             CurrentScreen::Editing => handle_editing_screen(key, app),
             CurrentScreen::Chat => handle_chat_screen(key, app),
             CurrentScreen::Focus => handle_focus_screen(key, app),
             CurrentScreen::ZenMode => handle_zen_screen(key, app),
+            CurrentScreen::Stats => handle_stats_screen(key, app),
+            CurrentScreen::Week => handle_week_screen(key, app),
+            CurrentScreen::Review => handle_review_screen(key, app),
+            CurrentScreen::Triage => handle_triage_screen(key, app),
+            CurrentScreen::Day => handle_day_screen(key, app),
+            CurrentScreen::Plan => handle_plan_screen(key, app),
+            CurrentScreen::WeeklyReview => handle_weekly_review_screen(key, app),
             CurrentScreen::Exiting => Some(true),
         },
         _ => Some(false),
     }
 }
 
+/// Insert a bracketed paste into whichever text field has focus
+fn handle_paste(text: &str, app: &mut App) {
+    if app.confirm.is_some() || app.date_picker.is_some() {
+        return;
+    }
+    if let Some(line) = &mut app.command_input {
+        line.push_str(&single_line(text));
+        app.command_error = None;
+        return;
+    }
+    if let Some(editor) = &mut app.filter_input {
+        editor.paste(text);
+        let query = editor.as_str().to_string();
+        app.set_filter(&query);
+        return;
+    }
+    if let Some(editor) = &mut app.tag_input {
+        editor.paste(text);
+        return;
+    }
+    match app.current_screen {
+        CurrentScreen::Editing => app.input_buffer.paste(text),
+        CurrentScreen::Chat => app.chat_input.paste(text),
+        _ => {}
+    }
+}
+
 fn handle_main_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    if let Some(direction) = quadrant_move_direction(&key, app) {
+        move_selected_to_adjacent(app, direction);
+        return None;
+    }
+
+    // Outside the matrix, column keys step through the layout's own columns
+    let column_step = match key.code {
+        KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => Some(true),
+        KeyCode::Left | KeyCode::Char('h') => Some(false),
+        _ => None,
+    };
+    if let Some(forward) = column_step {
+        match app.layout {
+            ViewLayout::Matrix => {}
+            ViewLayout::List => return None,
+            ViewLayout::Kanban => {
+                app.kanban_column = if forward {
+                    app.kanban_column.next()
+                } else {
+                    app.kanban_column.prev()
+                };
+                app.selected_task_index = 0;
+                app.visual_anchor = None;
+                app.clamp_selected_index();
+                return None;
+            }
+        }
+    }
+
     match key.code {
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.scroll_view(1);
+        }
+        KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.scroll_view(-1);
+        }
+        KeyCode::Home => app.select_edge(false),
+        KeyCode::End => app.select_edge(true),
         KeyCode::Char('q') => return Some(true),
+        KeyCode::Char('u') => app.undo(),
+        KeyCode::Char(c @ '1'..='9') => jump_to_task(app, c, key.modifiers),
+        KeyCode::Char('o') => app.set_sort(app.sort_mode.next()),
+        KeyCode::Char('J') => app.move_in_order(1),
+        KeyCode::Char('K') => app.move_in_order(-1),
         KeyCode::Char('z') => {
             // Enter Focus mode (full-screen quadrant)
             app.current_screen = CurrentScreen::Focus;
@@ -52,8 +122,93 @@ fn handle_main_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
         KeyCode::Char('c') => {
             app.current_screen = CurrentScreen::Chat;
         }
-        KeyCode::Char('?') => {
-            app.show_help = !app.show_help;
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Char('S') => {
+            app.open_stats();
+        }
+        KeyCode::Char('P') => app.open_plan(),
+        KeyCode::Char('R') => app.open_review(),
+        KeyCode::Char('D') => app.open_triage(),
+        KeyCode::Char('H') => app.open_day(),
+        KeyCode::Char('i') => app.detail_task = app.selected_task_id(),
+        KeyCode::Char('W') => app.open_weekly_review(),
+        KeyCode::Char('w') => {
+            app.open_week();
+        }
+        KeyCode::Char('v') => {
+            app.visibility = app.visibility.next();
+            app.clamp_selected_index();
+        }
+        KeyCode::Char('L') => {
+            app.layout = app.layout.next();
+            app.selected_task_index = 0;
+            app.visual_anchor = None;
+        }
+        KeyCode::Char('T') => {
+            let name = app.theme.next_name();
+            app.set_theme(name);
+        }
+        KeyCode::Char(':') => {
+            app.command_input = Some(String::new());
+            app.command_error = None;
+        }
+        KeyCode::Char('/') => open_filter_prompt(app),
+        KeyCode::Char('#') => app.open_retag(),
+        KeyCode::Char('g') => {
+            app.date_picker = Some(DatePicker::new(app.view_date, DatePickerAction::Goto));
+        }
+        KeyCode::Char('M') => {
+            if app.has_marked() {
+                app.date_picker = Some(DatePicker::new(
+                    app.view_date,
+                    DatePickerAction::MoveSelection,
+                ));
+            } else if let Some(task_id) = app.selected_task_id() {
+                app.date_picker = Some(DatePicker::new(
+                    app.view_date,
+                    DatePickerAction::MoveTask(task_id),
+                ));
+            }
+        }
+        KeyCode::Char('m') => {
+            // Next direction key moves the task to the adjacent quadrant
+            app.move_pending = true;
+        }
+        KeyCode::Char(' ') => {
+            // Toggle the task under the cursor in the multi-selection
+            if let Some(task_id) = app.selected_task_id() {
+                if !app.marked_tasks.remove(&task_id) {
+                    app.marked_tasks.insert(task_id);
+                }
+            }
+        }
+        KeyCode::Char('V') => {
+            // First press drops an anchor, second press selects the range
+            match app.visual_anchor.take() {
+                None => app.visual_anchor = Some(app.selected_task_index),
+                Some(anchor) => {
+                    let (lo, hi) = if anchor <= app.selected_task_index {
+                        (anchor, app.selected_task_index)
+                    } else {
+                        (app.selected_task_index, anchor)
+                    };
+                    let ids: Vec<uuid::Uuid> = app.current_tasks()
+                        .iter()
+                        .skip(lo)
+                        .take(hi - lo + 1)
+                        .map(|t| t.id)
+                        .collect();
+                    app.marked_tasks.extend(ids);
+                }
+            }
+        }
+        KeyCode::Esc => {
+            // Clear the selection first, then the filter
+            if app.has_marked() || app.visual_anchor.is_some() {
+                app.clear_marked();
+            } else if app.filter.is_some() {
+                app.set_filter("");
+            }
         }
         KeyCode::Char('a') => {
             app.current_screen = CurrentScreen::Editing;
@@ -62,10 +217,22 @@ fn handle_main_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             app.editing_task_id = None;
         }
         KeyCode::Char('e') => {
-            if let Some(task_id) = get_selected_task_id(app) {
+            if let Some(task_id) = app.selected_task_id() {
                 if let Some(task) = app.store.tasks.iter().find(|t| t.id == task_id) {
-                    app.input_buffer =
-                        format!("{} u{}i{}", task.title, task.urgency, task.importance);
+                    let mut text = format!(
+                        "{} u{}i{}",
+                        escape_title(&task.title, app.today),
+                        task.urgency,
+                        task.importance
+                    );
+                    if let Some(minutes) = task.estimate_minutes {
+                        text.push_str(&format!(" ~{}m", minutes));
+                    }
+                    text.push_str(&format!(" @{}", task.date));
+                    if let Some(due) = task.due {
+                        text.push_str(&format!(" due:{}", due));
+                    }
+                    app.input_buffer = LineEditor::with_text(text);
                     app.editing_task_id = Some(task_id);
                     app.current_screen = CurrentScreen::Editing;
                     app.input_mode = true;
@@ -74,63 +241,68 @@ fn handle_main_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
         }
 
         KeyCode::Char('d') | KeyCode::Enter => {
-            if let Some(task_id) = get_selected_task_id(app) {
+            if app.has_marked() {
+                app.request(PendingAction::Complete(app.marked_ids()));
+            } else if let Some(task_id) = app.selected_task_id() {
                 app.store.toggle_complete_task(task_id);
-                let _ = app.store.save();
+                app.save_with(app.completion_message(task_id));
                 // Fix #4: Clamp index after mutation
                 app.clamp_selected_index();
             }
         }
         KeyCode::Char('x') => {
-            if let Some(task_id) = get_selected_task_id(app) {
-                app.store.drop_task(task_id);
-                let _ = app.store.save();
-                // Fix #4: Clamp index after mutation
-                app.clamp_selected_index();
+            if app.has_marked() {
+                app.request(PendingAction::Drop(app.marked_ids()));
+            } else if let Some(task_id) = app.selected_task_id() {
+                app.request(PendingAction::Drop(vec![task_id]));
             }
         }
         KeyCode::Char('t') => {
-            app.view_date = if app.view_date == chrono::Local::now().date_naive() {
-                chrono::Local::now().date_naive() + chrono::Duration::days(1)
+            app.view_date = if app.view_date == app.store.today() {
+                app.store.today() + chrono::Duration::days(1)
             } else {
-                chrono::Local::now().date_naive()
+                app.store.today()
             };
             // Fix #4: Clamp index when switching views
             app.clamp_selected_index();
         }
+        KeyCode::Char('Y') => {
+            if let Some(task_id) = app.selected_task_id() {
+                app.copy_task(task_id);
+            }
+        }
         KeyCode::Char('y') => {
-            app.view_date = chrono::Local::now().date_naive() - chrono::Duration::days(1);
+            app.view_date = app.store.today() - chrono::Duration::days(1);
             // Clamp index when switching views
             app.clamp_selected_index();
         }
         KeyCode::Char('>') | KeyCode::Char('.') => {
-            if let Some(task_id) = get_selected_task_id(app) {
-                app.store
-                    .move_task_to_date(task_id, app.view_date + chrono::Duration::days(1));
-                let _ = app.store.save();
+            if app.has_marked() {
+                let tomorrow = app.view_date + chrono::Duration::days(1);
+                app.request(PendingAction::Move(app.marked_ids(), tomorrow));
+            } else if let Some(task_id) = app.selected_task_id() {
+                let tomorrow = app.view_date + chrono::Duration::days(1);
+                app.store.move_task_to_date(task_id, tomorrow);
+                app.save_with(format!("Moved to {}", tomorrow.format("%b %-d")));
                 // Fix #4: Clamp index after mutation
                 app.clamp_selected_index();
             }
         }
         KeyCode::Tab => {
-            app.selected_quadrant = match app.selected_quadrant {
-                Quadrant::DoFirst => Quadrant::Schedule,
-                Quadrant::Schedule => Quadrant::Delegate,
-                Quadrant::Delegate => Quadrant::Drop,
-                Quadrant::Drop => Quadrant::DoFirst,
-            };
+            app.selected_quadrant = app.selected_quadrant.next();
             // Fix #4: Reset and clamp index when switching quadrants
             app.selected_task_index = 0;
+            app.visual_anchor = None;
             app.clamp_selected_index();
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 app.selected_task_index = (app.selected_task_index + 1) % count;
             }
         }
         KeyCode::Up | KeyCode::Char('k') => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 if app.selected_task_index == 0 {
                     app.selected_task_index = count - 1;
@@ -140,14 +312,14 @@ fn handle_main_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             }
         }
         KeyCode::PageDown => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 // Jump down by 5 items or to the end
                 app.selected_task_index = (app.selected_task_index + 5).min(count - 1);
             }
         }
         KeyCode::PageUp => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 // Jump up by 5 items or to the start
                 if app.selected_task_index >= 5 {
@@ -159,23 +331,28 @@ fn handle_main_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
         }
 
         KeyCode::Left | KeyCode::Char('h') => {
+            // The compact layout shows one quadrant, so step through the tabs
             app.selected_quadrant = match app.selected_quadrant {
+                q if app.compact => q.prev(),
                 Quadrant::Schedule => Quadrant::DoFirst,
                 Quadrant::Drop => Quadrant::Delegate,
                 _ => app.selected_quadrant,
             };
             // Fix #4: Reset and clamp index
             app.selected_task_index = 0;
+            app.visual_anchor = None;
             app.clamp_selected_index();
         }
         KeyCode::Right | KeyCode::Char('l') => {
             app.selected_quadrant = match app.selected_quadrant {
+                q if app.compact => q.next(),
                 Quadrant::DoFirst => Quadrant::Schedule,
                 Quadrant::Delegate => Quadrant::Drop,
                 _ => app.selected_quadrant,
             };
             // Fix #4: Reset and clamp index
             app.selected_task_index = 0;
+            app.visual_anchor = None;
             app.clamp_selected_index();
         }
         _ => {}
@@ -183,33 +360,108 @@ fn handle_main_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
     None
 }
 
+/// Direction for Shift+Arrow, or for an arrow/hjkl key following `m`
+fn quadrant_move_direction(key: &KeyEvent, app: &mut App) -> Option<MatrixDirection> {
+    let armed = std::mem::take(&mut app.move_pending);
+    let shifted = key.modifiers.contains(KeyModifiers::SHIFT);
+
+    let direction = match key.code {
+        KeyCode::Left => MatrixDirection::Left,
+        KeyCode::Right => MatrixDirection::Right,
+        KeyCode::Up => MatrixDirection::Up,
+        KeyCode::Down => MatrixDirection::Down,
+        KeyCode::Char('h') if armed => MatrixDirection::Left,
+        KeyCode::Char('l') if armed => MatrixDirection::Right,
+        KeyCode::Char('k') if armed => MatrixDirection::Up,
+        KeyCode::Char('j') if armed => MatrixDirection::Down,
+        _ => return None,
+    };
+
+    if armed || shifted {
+        Some(direction)
+    } else {
+        None
+    }
+}
+
+/// Select the task labelled `digit`; with Alt held, also toggle it done
+fn jump_to_task(app: &mut App, digit: char, modifiers: KeyModifiers) {
+    let index = digit as usize - '1' as usize;
+    if index >= app.get_current_task_count() {
+        return;
+    }
+    app.selected_task_index = index;
+
+    if modifiers.contains(KeyModifiers::ALT) {
+        if let Some(task_id) = app.selected_task_id() {
+            app.store.toggle_complete_task(task_id);
+            app.save_with(app.completion_message(task_id));
+            app.clamp_selected_index();
+        }
+    }
+}
+
+/// Re-prioritize the selected task into the neighbouring quadrant and follow it
+fn move_selected_to_adjacent(app: &mut App, direction: MatrixDirection) {
+    let Some(task_id) = app.selected_task_id() else {
+        return;
+    };
+    let Some(task) = app.store.tasks.iter().find(|t| t.id == task_id) else {
+        return;
+    };
+
+    let (urgency, importance) = nudge_priority(task.urgency, task.importance, direction);
+    if (urgency, importance) == (task.urgency, task.importance) {
+        return;
+    }
+
+    let title = task.title.clone();
+    app.store.update_task(task_id, title, urgency, importance);
+    let quadrant = Quadrant::from_priority(urgency, importance);
+    app.save_with(format!("Moved to {}", quadrant));
+
+    app.selected_quadrant = Quadrant::from_priority(urgency, importance);
+    app.selected_task_index = app.current_tasks()
+        .iter()
+        .position(|t| t.id == task_id)
+        .unwrap_or(0);
+    app.visual_anchor = None;
+}
+
 fn handle_editing_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
     match key.code {
         KeyCode::Enter => {
-            let input = app.input_buffer.trim().to_string();
+            let input = app.input_buffer.as_str().trim().to_string();
             if !input.is_empty() {
-                let mut urgency = 1;
-                let mut importance = 1;
-                let mut title_parts = Vec::new();
-
-                for part in input.split_whitespace() {
-                    if let Some((u, i)) = parse_priority(part) {
-                        urgency = u;
-                        importance = i;
-                    } else {
-                        title_parts.push(part);
-                    }
-                }
-                let title = title_parts.join(" ");
+                let parsed = parse_input(&input, app.today);
 
                 if let Some(edit_id) = app.editing_task_id {
-                    app.store.update_task(edit_id, title, urgency, importance);
+                    // The line holds the whole task, so a removed `~` or
+                    // `due:` token clears it; the day changes as a move
+                    let (urgency, importance) = (parsed.urgency(), parsed.importance());
+                    app.store.journal.begin_batch();
+                    app.store.update_task(edit_id, parsed.title, urgency, importance);
+                    let mut moved_to = None;
+                    if let Some(task) = app.store.tasks.iter_mut().find(|t| t.id == edit_id) {
+                        moved_to = parsed.date.filter(|&date| date != task.date);
+                        task.estimate_minutes = parsed.estimate;
+                        task.due = parsed.due;
+                        task.urgency_from_due &= task.due.is_some();
+                        if parsed.priority.is_none() && task.due.is_some() {
+                            task.urgency_from_due = true;
+                            task.refresh_due_urgency(app.today);
+                        }
+                    }
+                    if let Some(date) = moved_to {
+                        app.store.move_task_to_date(edit_id, date);
+                    }
+                    app.store.journal.end_batch("edit");
+                    app.store.touch();
                     app.editing_task_id = None;
+                    app.save_with("Saved");
                 } else {
-                    let task = Task::new(title, urgency, importance, app.view_date);
-                    app.store.add_task(task);
+                    app.add_new_task(parsed.into_task(app.view_date));
                 }
-                let _ = app.store.save();
             }
             app.input_buffer.clear();
             app.input_mode = false;
@@ -223,18 +475,32 @@ fn handle_editing_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             app.editing_task_id = None;
             app.current_screen = CurrentScreen::Main;
         }
-        KeyCode::Backspace => {
-            app.input_buffer.pop();
-        }
-        KeyCode::Char(c) => {
-            app.input_buffer.push(c);
+        _ => {
+            app.input_buffer.handle_key(&key);
         }
-        _ => {}
     }
     None
 }
 
 fn handle_chat_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    // Message selection: pick a message with the arrows and copy it
+    if let Some(selected) = app.chat_selected {
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                app.chat_selected = Some(selected.saturating_sub(1));
+            }
+            KeyCode::Down | KeyCode::Char('j') if selected + 1 < app.chat_history.len() => {
+                app.chat_selected = Some(selected + 1);
+            }
+            KeyCode::Char('Y') | KeyCode::Char('y') => app.copy_selected_message(),
+            _ => {
+                app.chat_selected = None;
+                app.chat_auto_scroll = true;
+            }
+        }
+        return None;
+    }
+
     match key.code {
         // Handle pending command confirmation
         KeyCode::Char('y') | KeyCode::Char('Y') if app.chat_input.is_empty() && app.has_pending_commands() => {
@@ -260,6 +526,7 @@ fn handle_chat_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             return Some(false);
         }
 
+        KeyCode::Esc if app.is_loading => app.cancel_chat_request(),
         KeyCode::Esc => {
             // Cancel pending commands on exit
             if app.has_pending_commands() {
@@ -270,9 +537,10 @@ fn handle_chat_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             app.save_chat_history();
         }
 
-        // Fix #5: Toggle chat help
-        KeyCode::Char('?') if app.chat_input.is_empty() => {
-            app.show_chat_help = !app.show_chat_help;
+        KeyCode::Char('?') if app.chat_input.is_empty() => app.open_help(),
+        KeyCode::Up if app.chat_input.is_empty() && !app.chat_history.is_empty() => {
+            app.chat_selected = Some(app.chat_history.len() - 1);
+            app.chat_auto_scroll = false;
         }
 
         // Fix #1: Scroll up in chat history
@@ -315,22 +583,6 @@ fn handle_chat_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             app.chat_scroll = 0;
         }
 
-        KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            // Delete word
-            if let Some(last_space) = app.chat_input.trim_end().rfind(' ') {
-                app.chat_input.truncate(last_space + 1);
-            } else {
-                app.chat_input.clear();
-            }
-        }
-        KeyCode::Backspace if key.modifiers.contains(KeyModifiers::ALT) => {
-            // Delete word (Alt+Backspace)
-            if let Some(last_space) = app.chat_input.trim_end().rfind(' ') {
-                app.chat_input.truncate(last_space + 1);
-            } else {
-                app.chat_input.clear();
-            }
-        }
         KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             // Delete line
             app.chat_input.clear();
@@ -338,83 +590,38 @@ fn handle_chat_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
 
         // Clear chat history
         KeyCode::Char('l') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            app.chat_history.clear();
-            app.chat_scroll = 0;
-            app.save_chat_history();
+            app.request(PendingAction::ClearChat);
         }
 
         KeyCode::Enter => {
-            if !app.chat_input.trim().is_empty() {
-                let content = app.chat_input.trim().to_string();
-                app.chat_history.push(ChatMessage {
-                    role: "user".to_string(),
-                    content: content.clone(),
-                });
-
-                // Save after user message
-                app.save_chat_history();
-
-                // Send to AI
-                if let Some(client) = &app.ai_client {
-                    let (tx, rx) = mpsc::channel();
-                    app.chat_receiver = Some(rx);
-                    app.is_loading = true;
-                    app.chat_auto_scroll = true;
-
-                    let context =
-                        serde_json::to_string_pretty(&app.store.tasks).unwrap_or_default();
-                    client.send_message(app.chat_history.clone(), context, tx);
-                } else {
-                    app.chat_history.push(ChatMessage {
-                        role: "assistant".to_string(),
-                        content: "API Key not found. Please set OPENAI_API_KEY.".to_string(),
-                    });
+            if !app.chat_input.as_str().trim().is_empty() {
+                let content = app.chat_input.as_str().trim().to_string();
+                match parse_chat_command(&content) {
+                    Some(Ok(command)) => app.run_chat_command(command),
+                    Some(Err(err)) => app.notify_error(err),
+                    None => app.send_chat(content),
                 }
-
                 app.chat_input.clear();
             }
         }
-        KeyCode::Backspace => {
-            app.chat_input.pop();
-        }
-        KeyCode::Char(c) => {
-            app.chat_input.push(c);
+        // Remaining keys edit the message (Ctrl+W / Alt+Backspace delete a word)
+        _ => {
+            app.chat_input.handle_key(&key);
         }
-        _ => {}
     }
     None
 }
 
-fn get_filtered_tasks<'a>(app: &'a App) -> Vec<&'a Task> {
-    let mut tasks: Vec<&Task> = app
-        .store
-        .tasks
-        .iter()
-        .filter(|t| {
-            t.date == app.view_date
-                && t.status != TaskStatus::Dropped
-                && t.quadrant() == app.selected_quadrant
-        })
-        .collect();
-    tasks.sort_by_key(|b| std::cmp::Reverse(b.score()));
-    tasks
-}
-
-fn get_task_count(app: &App) -> usize {
-    get_filtered_tasks(app).len()
-}
-
-fn get_selected_task_id(app: &App) -> Option<uuid::Uuid> {
-    let tasks = get_filtered_tasks(app);
-    if app.selected_task_index < tasks.len() {
-        Some(tasks[app.selected_task_index].id)
-    } else {
-        None
-    }
-}
-
 fn handle_focus_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
     match key.code {
+        KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.scroll_view(1);
+        }
+        KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.scroll_view(-1);
+        }
+        KeyCode::Home => app.select_edge(false),
+        KeyCode::End => app.select_edge(true),
         KeyCode::Esc => {
             // Exit to main screen
             app.current_screen = CurrentScreen::Main;
@@ -423,30 +630,38 @@ fn handle_focus_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             // Enter Zen mode (single task focus)
             app.current_screen = CurrentScreen::ZenMode;
         }
+        KeyCode::Char('u') => app.undo(),
+        KeyCode::Char(c @ '1'..='9') => jump_to_task(app, c, key.modifiers),
+        KeyCode::Char('o') => app.set_sort(app.sort_mode.next()),
+        KeyCode::Char('J') => app.move_in_order(1),
+        KeyCode::Char('K') => app.move_in_order(-1),
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Char('v') => {
+            app.visibility = app.visibility.next();
+            app.clamp_selected_index();
+        }
         KeyCode::Char('d') | KeyCode::Enter => {
             // Toggle task completion
-            if let Some(task_id) = get_selected_task_id(app) {
+            if let Some(task_id) = app.selected_task_id() {
                 app.store.toggle_complete_task(task_id);
-                let _ = app.store.save();
+                app.save_with(app.completion_message(task_id));
                 app.clamp_selected_index();
             }
         }
         KeyCode::Char('x') => {
             // Drop task
-            if let Some(task_id) = get_selected_task_id(app) {
-                app.store.drop_task(task_id);
-                let _ = app.store.save();
-                app.clamp_selected_index();
+            if let Some(task_id) = app.selected_task_id() {
+                app.request(PendingAction::Drop(vec![task_id]));
             }
         }
         KeyCode::Down | KeyCode::Char('j') => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 app.selected_task_index = (app.selected_task_index + 1) % count;
             }
         }
         KeyCode::Up | KeyCode::Char('k') => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 if app.selected_task_index == 0 {
                     app.selected_task_index = count - 1;
@@ -456,13 +671,13 @@ fn handle_focus_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
             }
         }
         KeyCode::PageDown => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 app.selected_task_index = (app.selected_task_index + 5).min(count - 1);
             }
         }
         KeyCode::PageUp => {
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 if app.selected_task_index >= 5 {
                     app.selected_task_index -= 5;
@@ -484,13 +699,13 @@ fn handle_zen_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
         }
         KeyCode::Char('d') | KeyCode::Enter | KeyCode::Char(' ') => {
             // Mark done and move to next task
-            if let Some(task_id) = get_selected_task_id(app) {
+            if let Some(task_id) = app.selected_task_id() {
                 app.store.toggle_complete_task(task_id);
-                let _ = app.store.save();
+                app.save_with(app.completion_message(task_id));
                 app.clamp_selected_index();
 
                 // Auto-advance to next task if available
-                if get_task_count(app) == 0 {
+                if app.get_current_task_count() == 0 {
                     // No more tasks, exit to focus view
                     app.current_screen = CurrentScreen::Focus;
                 }
@@ -498,32 +713,456 @@ fn handle_zen_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
         }
         KeyCode::Char('s') => {
             // Skip to next task without completing
-            let count = get_task_count(app);
+            let count = app.get_current_task_count();
             if count > 0 {
                 app.selected_task_index = (app.selected_task_index + 1) % count;
             }
         }
         KeyCode::Char('x') => {
             // Drop task and move to next
-            if let Some(task_id) = get_selected_task_id(app) {
-                app.store.drop_task(task_id);
-                let _ = app.store.save();
-                app.clamp_selected_index();
+            if let Some(task_id) = app.selected_task_id() {
+                app.request(PendingAction::Drop(vec![task_id]));
 
                 // Auto-exit if no more tasks
-                if get_task_count(app) == 0 {
+                if app.get_current_task_count() == 0 {
                     app.current_screen = CurrentScreen::Focus;
                 }
             }
         }
+        KeyCode::Char('r') => app.start_pomodoro(), // Reset the pomodoro timer
+        KeyCode::Char('n') => app.next_pomodoro(),
+        KeyCode::Char('m') => app.set_motion(app.motion.next()),
+        KeyCode::Char('?') => app.open_help(),
+        _ => {}
+    }
+    None
+}
+
+fn handle_review_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    let Some(review) = app.review.as_mut() else {
+        app.current_screen = CurrentScreen::Main;
+        return None;
+    };
+
+    if review.is_finished() {
+        // Summary: any of these returns to the matrix
+        if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+            app.review = None;
+            app.current_screen = CurrentScreen::Main;
+            app.clamp_selected_index();
+        }
+        return None;
+    }
+
+    match key.code {
+        KeyCode::Char('t') | KeyCode::Char('>') => app.review_decide(ReviewChoice::Tomorrow),
         KeyCode::Char('r') => {
-            // Reset pomodoro timer
-            if let Some(ref mut zen_state) = app.zen_state {
-                zen_state.pomodoro = Some(Pomodoro::new(25)); // Reset to 25 minutes
-                zen_state.message = String::from("Focus on what matters");
+            if let Some(id) = review.current() {
+                let tomorrow = review.date + chrono::Duration::days(1);
+                app.date_picker = Some(DatePicker::new(tomorrow, DatePickerAction::Reschedule(id)));
+            }
+        }
+        KeyCode::Char('x') => app.review_decide(ReviewChoice::Dropped),
+        KeyCode::Char('k') | KeyCode::Char(' ') | KeyCode::Enter => {
+            app.review_decide(ReviewChoice::Kept)
+        }
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Esc => review.finish(),
+        _ => {}
+    }
+    None
+}
+
+fn handle_day_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    match key.code {
+        KeyCode::Left | KeyCode::Char('h') => app.shift_day(-1),
+        KeyCode::Right | KeyCode::Char('l') => app.shift_day(1),
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+            app.day_events.clear();
+            app.current_screen = CurrentScreen::Main;
+            app.clamp_selected_index();
+        }
+        _ => {}
+    }
+    None
+}
+
+fn handle_triage_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    let Some(triage) = app.triage.as_mut() else {
+        app.current_screen = CurrentScreen::Main;
+        return None;
+    };
+
+    if triage.is_finished() {
+        if matches!(key.code, KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q')) {
+            app.triage = None;
+            app.current_screen = CurrentScreen::Main;
+            app.clamp_selected_index();
+        }
+        return None;
+    }
+
+    match key.code {
+        KeyCode::Char('s') => app.triage_decide(TriageChoice::Schedule),
+        KeyCode::Char('d') => app.triage_decide(TriageChoice::Delegate),
+        KeyCode::Char('x') => app.triage_decide(TriageChoice::Dropped),
+        KeyCode::Char('k') | KeyCode::Char(' ') | KeyCode::Enter => {
+            app.triage_decide(TriageChoice::Kept)
+        }
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Esc => triage.finish(),
+        _ => {}
+    }
+    None
+}
+
+fn handle_weekly_review_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    let Some(review) = app.weekly_review.as_mut() else {
+        app.current_screen = CurrentScreen::Main;
+        return None;
+    };
+
+    if review.is_finished() {
+        match key.code {
+            KeyCode::Enter => app.finish_weekly_review(),
+            KeyCode::Esc | KeyCode::Char('q') => {
+                // Leave without changing anything
+                app.weekly_review = None;
+                app.current_screen = CurrentScreen::Main;
+            }
+            _ => {}
+        }
+        return None;
+    }
+
+    match key.code {
+        KeyCode::Char('k') | KeyCode::Char(' ') | KeyCode::Enter => {
+            review.decide(WeeklyChoice::Keep)
+        }
+        KeyCode::Char('d') => review.decide(WeeklyChoice::Delegate),
+        KeyCode::Char('x') => review.decide(WeeklyChoice::Drop),
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Esc => review.finish(),
+        _ => {}
+    }
+    None
+}
+
+fn handle_plan_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            app.plan_index += 1;
+            app.clamp_plan_index();
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            app.plan_index = app.plan_index.saturating_sub(1);
+        }
+        KeyCode::Enter | KeyCode::Char(' ') => app.plan_pull(),
+        KeyCode::Char('x') => app.plan_drop(),
+        KeyCode::Char('t') => {
+            app.toggle_view_date();
+            app.clamp_plan_index();
+        }
+        KeyCode::Char('c') => app.ask_plan_suggestions(),
+        KeyCode::Char('?') => app.open_help(),
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.current_screen = CurrentScreen::Main;
+            app.clamp_selected_index();
+        }
+        _ => {}
+    }
+    None
+}
+
+fn handle_stats_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => {
+            app.current_screen = CurrentScreen::Main;
+            app.focus_stats = None;
+        }
+        _ => {}
+    }
+    None
+}
+
+fn handle_week_screen(key: KeyEvent, app: &mut App) -> Option<bool> {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('w') => {
+            app.current_screen = CurrentScreen::Main;
+            app.clamp_selected_index();
+        }
+        KeyCode::Enter => {
+            // Open the selected day in the matrix
+            app.view_date = app.week_selected_date();
+            app.current_screen = CurrentScreen::Main;
+            app.clamp_selected_index();
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            if app.week_day_index > 0 {
+                app.week_day_index -= 1;
+            } else {
+                app.view_date -= chrono::Duration::days(7);
+                app.week_day_index = 6;
+            }
+            app.week_task_index = 0;
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            if app.week_day_index < 6 {
+                app.week_day_index += 1;
+            } else {
+                app.view_date += chrono::Duration::days(7);
+                app.week_day_index = 0;
+            }
+            app.week_task_index = 0;
+        }
+        KeyCode::Char('[') => {
+            app.view_date -= chrono::Duration::days(7);
+            app.clamp_week_task_index();
+        }
+        KeyCode::Char(']') => {
+            app.view_date += chrono::Duration::days(7);
+            app.clamp_week_task_index();
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            let count = app.tasks_for_date(app.week_selected_date()).len();
+            if count > 0 {
+                app.week_task_index = (app.week_task_index + 1) % count;
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            let count = app.tasks_for_date(app.week_selected_date()).len();
+            if count > 0 {
+                if app.week_task_index == 0 {
+                    app.week_task_index = count - 1;
+                } else {
+                    app.week_task_index -= 1;
+                }
+            }
+        }
+        KeyCode::Char('d') => {
+            let date = app.week_selected_date();
+            let task_id = app.tasks_for_date(date).get(app.week_task_index).map(|t| t.id);
+            if let Some(task_id) = task_id {
+                app.store.toggle_complete_task(task_id);
+                app.save_with(app.completion_message(task_id));
+            }
+        }
+        KeyCode::Char('>') | KeyCode::Char('.') | KeyCode::Char('<') | KeyCode::Char(',') => {
+            let forward = matches!(key.code, KeyCode::Char('>') | KeyCode::Char('.'));
+            let date = app.week_selected_date();
+            let task_id = app.tasks_for_date(date).get(app.week_task_index).map(|t| t.id);
+            if let Some(task_id) = task_id {
+                let offset = if forward { 1 } else { -1 };
+                let target = date + chrono::Duration::days(offset);
+                app.store.move_task_to_date(task_id, target);
+                app.save_with(format!("Moved to {}", target.format("%b %-d")));
+                app.clamp_week_task_index();
             }
         }
         _ => {}
     }
     None
 }
+
+fn handle_confirm(key: KeyEvent, app: &mut App) -> Option<bool> {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+            if let Some(action) = app.confirm.take() {
+                app.perform(action);
+            }
+            // Zen mode leaves once its last task is gone
+            if matches!(app.current_screen, CurrentScreen::ZenMode)
+                && app.get_current_task_count() == 0
+            {
+                app.current_screen = CurrentScreen::Focus;
+            }
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => app.decline(),
+        KeyCode::Esc => {
+            app.confirm = None;
+            app.pending_add = None;
+        }
+        _ => {}
+    }
+    None
+}
+
+fn handle_detail(key: KeyEvent, app: &mut App) -> Option<bool> {
+    if matches!(
+        key.code,
+        KeyCode::Esc | KeyCode::Enter | KeyCode::Char('i') | KeyCode::Char('q')
+    ) {
+        app.detail_task = None;
+    }
+    None
+}
+
+fn handle_help(key: KeyEvent, app: &mut App) -> Option<bool> {
+    // Scrolling past the end is clamped when the overlay is drawn
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') => app.show_help = false,
+        KeyCode::Char('j') | KeyCode::Down => app.help_scroll = app.help_scroll.saturating_add(1),
+        KeyCode::Char('k') | KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+        KeyCode::PageDown => app.help_scroll = app.help_scroll.saturating_add(10),
+        KeyCode::PageUp => app.help_scroll = app.help_scroll.saturating_sub(10),
+        KeyCode::Home => app.help_scroll = 0,
+        KeyCode::End => app.help_scroll = u16::MAX,
+        KeyCode::Tab => {
+            // Jump to the next screen's section, wrapping to the top
+            let next = (0..HELP.len())
+                .map(help_offset)
+                .find(|&row| row > app.help_scroll as usize)
+                .unwrap_or(0);
+            app.help_scroll = next as u16;
+        }
+        _ => {}
+    }
+    None
+}
+
+fn handle_date_picker(key: KeyEvent, app: &mut App) -> Option<bool> {
+    let picker = app.date_picker.as_mut()?;
+
+    match key.code {
+        KeyCode::Left | KeyCode::Char('h') => picker.move_days(-1),
+        KeyCode::Right | KeyCode::Char('l') => picker.move_days(1),
+        KeyCode::Up | KeyCode::Char('k') => picker.move_days(-7),
+        KeyCode::Down | KeyCode::Char('j') => picker.move_days(7),
+        KeyCode::PageUp => picker.move_months(-1),
+        KeyCode::PageDown => picker.move_months(1),
+        KeyCode::Char('t') => picker.selected = app.store.today(),
+        KeyCode::Esc => app.date_picker = None,
+        KeyCode::Enter => {
+            if let Some(picker) = app.date_picker.take() {
+                match picker.action {
+                    DatePickerAction::Goto => {
+                        app.view_date = picker.selected;
+                    }
+                    DatePickerAction::MoveTask(task_id) => {
+                        app.store.move_task_to_date(task_id, picker.selected);
+                        app.save_with(format!("Moved to {}", picker.selected.format("%b %-d")));
+                    }
+                    DatePickerAction::MoveSelection => {
+                        app.request(PendingAction::Move(app.marked_ids(), picker.selected));
+                    }
+                    DatePickerAction::Reschedule(_) => {
+                        app.review_decide(ReviewChoice::Rescheduled(picker.selected));
+                    }
+                }
+                app.clamp_selected_index();
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Open the `/` prompt, starting from the active filter
+fn open_filter_prompt(app: &mut App) {
+    let query = app.filter.as_ref().map_or("", |f| f.query());
+    app.filter_input = Some(LineEditor::with_text(query));
+}
+
+/// `/` prompt: the filter follows every keystroke; Enter keeps it, Esc clears it
+fn handle_filter_prompt(key: KeyEvent, app: &mut App) -> Option<bool> {
+    let editor = app.filter_input.as_mut()?;
+
+    match key.code {
+        KeyCode::Enter => app.filter_input = None,
+        KeyCode::Esc => {
+            app.filter_input = None;
+            app.set_filter("");
+        }
+        _ => {
+            if editor.handle_key(&key) {
+                let query = editor.as_str().to_string();
+                app.set_filter(&query);
+            }
+        }
+    }
+    None
+}
+
+/// `#` prompt: Enter gives the tasks these tags in place of their own
+fn handle_tag_prompt(key: KeyEvent, app: &mut App) -> Option<bool> {
+    let editor = app.tag_input.as_mut()?;
+
+    match key.code {
+        KeyCode::Enter => {
+            let line = editor.as_str().to_string();
+            app.tag_input = None;
+            app.retag(&line);
+        }
+        KeyCode::Esc => app.tag_input = None,
+        _ => {
+            editor.handle_key(&key);
+        }
+    }
+    None
+}
+
+fn handle_command_palette(key: KeyEvent, app: &mut App) -> Option<bool> {
+    let line = app.command_input.as_mut()?;
+
+    match key.code {
+        KeyCode::Esc => {
+            app.command_input = None;
+            app.command_error = None;
+        }
+        KeyCode::Backspace => {
+            if pop_grapheme(line).is_none() {
+                app.command_input = None;
+            }
+            app.command_error = None;
+        }
+        KeyCode::Tab => {
+            // Complete the command name to the best fuzzy match
+            if let Some(name) = complete(line).first() {
+                *line = format!("{} ", name);
+            }
+        }
+        KeyCode::Char(c) => {
+            line.push(c);
+            app.command_error = None;
+        }
+        KeyCode::Enter => {
+            let today = app.store.today();
+            match parse_command(line, today) {
+                Ok(command) => {
+                    app.command_input = None;
+                    app.command_error = None;
+                    return run_palette_command(command, app);
+                }
+                Err(err) => app.command_error = Some(err),
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+fn run_palette_command(command: PaletteCommand, app: &mut App) -> Option<bool> {
+    match command {
+        PaletteCommand::Add(input) => {
+            let task = parse_input(&input, app.today).into_task(app.view_date);
+            app.add_new_task(task);
+        }
+        PaletteCommand::Goto(date) => app.view_date = date,
+        PaletteCommand::Filter(filter) => app.set_filter(filter.as_deref().unwrap_or("")),
+        PaletteCommand::Sort(mode) => app.set_sort(mode),
+        PaletteCommand::Accessible => app.toggle_accessible(),
+        PaletteCommand::Motion(motion) => app.set_motion(motion.unwrap_or(app.motion.next())),
+        PaletteCommand::Theme(name) => {
+            let name = name.unwrap_or_else(|| app.theme.next_name());
+            app.set_theme(name);
+        }
+        PaletteCommand::Chat => app.current_screen = CurrentScreen::Chat,
+        PaletteCommand::Help => app.open_help(),
+        PaletteCommand::Quit => return Some(true),
+        PaletteCommand::Stats => app.open_stats(),
+        PaletteCommand::Week => app.open_week(),
+    }
+    app.clamp_selected_index();
+    None
+}
@@ -0,0 +1,98 @@
+use crate::models::task::{Task, TaskStatus};
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Status columns of the kanban layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KanbanColumn {
+    Backlog, // Pending, scheduled after the view date
+    Today,   // Pending on the view date, not started
+    InFocus, // Pending on the view date with a pomodoro logged that day
+    Done,    // Completed on the view date
+}
+
+impl KanbanColumn {
+    pub const ALL: [KanbanColumn; 4] = [
+        KanbanColumn::Backlog,
+        KanbanColumn::Today,
+        KanbanColumn::InFocus,
+        KanbanColumn::Done,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            KanbanColumn::Backlog => "BACKLOG",
+            KanbanColumn::Today => "TODAY",
+            KanbanColumn::InFocus => "IN FOCUS",
+            KanbanColumn::Done => "DONE",
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        Self::ALL.iter().position(|c| c == self).unwrap_or(0)
+    }
+
+    pub fn next(&self) -> Self {
+        Self::ALL[(self.index() + 1) % Self::ALL.len()]
+    }
+
+    pub fn prev(&self) -> Self {
+        Self::ALL[(self.index() + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+/// Column a task belongs in when viewing `date`, or `None` if it is hidden
+/// (dropped, or from an earlier day)
+pub fn classify(task: &Task, date: NaiveDate, focused: &HashSet<Uuid>) -> Option<KanbanColumn> {
+    match task.status {
+        TaskStatus::Dropped => None,
+        TaskStatus::Completed if task.date == date => Some(KanbanColumn::Done),
+        TaskStatus::Completed => None,
+        TaskStatus::Pending if task.date > date => Some(KanbanColumn::Backlog),
+        TaskStatus::Pending if task.date < date => None,
+        TaskStatus::Pending if focused.contains(&task.id) => Some(KanbanColumn::InFocus),
+        TaskStatus::Pending => Some(KanbanColumn::Today),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let date = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let later = NaiveDate::from_ymd_opt(2024, 6, 5).unwrap();
+        let earlier = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+
+        let today = Task::new("today".into(), 2, 2, date);
+        let focused_task = Task::new("focused".into(), 2, 2, date);
+        let upcoming = Task::new("upcoming".into(), 1, 1, later);
+        let old = Task::new("old".into(), 1, 1, earlier);
+        let mut done = Task::new("done".into(), 1, 2, date);
+        done.status = TaskStatus::Completed;
+        let mut dropped = Task::new("dropped".into(), 1, 2, date);
+        dropped.status = TaskStatus::Dropped;
+
+        let focused: HashSet<Uuid> = [focused_task.id].into_iter().collect();
+        assert_eq!(classify(&today, date, &focused), Some(KanbanColumn::Today));
+        assert_eq!(
+            classify(&focused_task, date, &focused),
+            Some(KanbanColumn::InFocus)
+        );
+        assert_eq!(
+            classify(&upcoming, date, &focused),
+            Some(KanbanColumn::Backlog)
+        );
+        assert_eq!(classify(&done, date, &focused), Some(KanbanColumn::Done));
+        assert_eq!(classify(&old, date, &focused), None);
+        assert_eq!(classify(&dropped, date, &focused), None);
+    }
+
+    #[test]
+    fn test_column_cycle() {
+        assert_eq!(KanbanColumn::Done.next(), KanbanColumn::Backlog);
+        assert_eq!(KanbanColumn::Backlog.prev(), KanbanColumn::Done);
+    }
+}
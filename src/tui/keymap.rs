@@ -0,0 +1,338 @@
+use crate::tui::app::CurrentScreen;
+
+/// One row of the help overlay
+pub struct Binding {
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+/// Bindings of one screen, grouped under headings; the screen and group
+/// names are `i18n` message IDs
+pub struct ScreenHelp {
+    pub screen: &'static str,
+    pub groups: &'static [(&'static str, &'static [Binding])],
+}
+
+const fn b(keys: &'static str, action: &'static str) -> Binding {
+    Binding { keys, action }
+}
+
+/// Every keybinding, in the order the help overlay lists them
+pub const HELP: &[ScreenHelp] = &[
+    ScreenHelp {
+        screen: "help.screen.main",
+        groups: &[
+            (
+                "help.group.navigation",
+                &[
+                    b("↑↓ j k", "Navigate tasks"),
+                    b("← → h l", "Switch quadrant / column (tabs when compact)"),
+                    b("Tab", "Cycle quadrants"),
+                    b("Shift+Arrow", "Move task to adjacent quadrant"),
+                    b("m + direction", "Move task to adjacent quadrant"),
+                    b("PgUp/PgDn", "Jump 5 tasks"),
+                    b("Home/End", "First / last task"),
+                    b("1-9", "Jump to numbered task (Alt: toggle done)"),
+                    b("Ctrl+E / Ctrl+Y", "Scroll without moving selection"),
+                ],
+            ),
+            (
+                "help.group.task",
+                &[
+                    b("a", "Add new task"),
+                    b("e", "Edit selected task"),
+                    b("i", "Task details and how its score adds up"),
+                    b("d / Enter", "Toggle task done"),
+                    b("x", "Drop (delete) task"),
+                    b("u", "Undo last change"),
+                    b("Y", "Copy task to the clipboard"),
+                    b("> .", "Move task to tomorrow"),
+                    b("M", "Move task to a date"),
+                    b("#", "Set the tags of the task or selection"),
+                    b("J / K", "Move task down / up (manual order)"),
+                    b("Space", "Select task for batch action"),
+                    b("V", "Start / finish range selection"),
+                    b("Esc", "Clear selection, then filter"),
+                ],
+            ),
+            (
+                "help.group.view",
+                &[
+                    b("t", "Toggle tomorrow view"),
+                    b("y", "View yesterday"),
+                    b("v", "Show pending / +done / all"),
+                    b("g", "Go to date"),
+                    b("L", "Cycle matrix / ranked list / kanban"),
+                    b("T", "Cycle color theme"),
+                    b("o", "Sort by score / created / alpha / manual"),
+                    b("/", "Filter by text, #tag, or >=score"),
+                    b(":", "Command line (:add, :goto, :filter, :sort, :q)"),
+                ],
+            ),
+            (
+                "help.group.screens",
+                &[
+                    b("z", "Focus on the selected quadrant"),
+                    b("c", "Open AI chat"),
+                    b("w", "Week view"),
+                    b("S", "Focus statistics"),
+                    b("P", "Plan the day"),
+                    b("R", "End-of-day review"),
+                    b("D", "Re-triage DO FIRST"),
+                    b("H", "Planned vs done for the day"),
+                    b("W", "Weekly review"),
+                    b("?", "This help"),
+                    b("Ctrl+Z", "Suspend to the shell (fg resumes)"),
+                    b("q", "Quit"),
+                ],
+            ),
+        ],
+    },
+    ScreenHelp {
+        screen: "help.screen.focus",
+        groups: &[(
+            "help.group.quadrant",
+            &[
+                b("↑↓ j k", "Navigate tasks"),
+                b("PgUp/PgDn", "Jump 5 tasks"),
+                b("Home/End", "First / last task"),
+                b("1-9", "Jump to numbered task (Alt: toggle done)"),
+                b("Ctrl+E / Ctrl+Y", "Scroll without moving selection"),
+                b("d / Enter", "Toggle task done"),
+                b("x", "Drop (delete) task"),
+                b("u", "Undo last change"),
+                b("o", "Cycle sort order"),
+                b("J / K", "Move task down / up (manual order)"),
+                b("v", "Show pending / +done / all"),
+                b("z", "Enter zen mode"),
+                b("Esc", "Back to the matrix"),
+            ],
+        )],
+    },
+    ScreenHelp {
+        screen: "help.screen.zen",
+        groups: &[(
+            "help.group.single",
+            &[
+                b("d / Enter / Space", "Mark done and go to the next task"),
+                b("s", "Skip to the next task"),
+                b("x", "Drop and go to the next task"),
+                b("r", "Restart the 25-minute pomodoro"),
+                b("n", "End the break and start the next session"),
+                b("m", "Motion: full / reduced / still"),
+                b("Esc / z", "Back to focus view"),
+            ],
+        )],
+    },
+    ScreenHelp {
+        screen: "help.screen.review",
+        groups: &[(
+            "help.group.unfinished",
+            &[
+                b("t / >", "Move to tomorrow"),
+                b("r", "Reschedule to a date"),
+                b("x", "Drop"),
+                b("k / Space / Enter", "Keep it where it is"),
+                b("Esc", "Finish early and show the summary"),
+            ],
+        )],
+    },
+    ScreenHelp {
+        screen: "help.screen.triage",
+        groups: &[(
+            "help.group.do_first",
+            &[
+                b("s", "Not urgent: move to SCHEDULE"),
+                b("d", "Not important: move to DELEGATE"),
+                b("x", "Drop"),
+                b("k / Space / Enter", "Keep it in DO FIRST"),
+                b("Esc", "Finish early and show the summary"),
+            ],
+        )],
+    },
+    ScreenHelp {
+        screen: "help.screen.day",
+        groups: &[(
+            "help.group.planned",
+            &[
+                b("← → h l", "Previous / next day"),
+                b("Esc / q", "Back to the matrix"),
+            ],
+        )],
+    },
+    ScreenHelp {
+        screen: "help.screen.weekly",
+        groups: &[(
+            "help.group.open",
+            &[
+                b("k / Space", "Keep for next week"),
+                b("d", "Delegate"),
+                b("x", "Drop"),
+                b("Esc", "Finish early and show the summary"),
+                b("Enter", "Apply and write the journal note"),
+            ],
+        )],
+    },
+    ScreenHelp {
+        screen: "help.screen.plan",
+        groups: &[(
+            "help.group.candidates",
+            &[
+                b("↑↓ j k", "Navigate candidates"),
+                b("Enter / Space", "Pull onto the planned day"),
+                b("x", "Drop"),
+                b("t", "Plan today / tomorrow"),
+                b("c", "Ask AI for suggestions"),
+                b("Esc / q", "Back to the matrix"),
+            ],
+        )],
+    },
+    ScreenHelp {
+        screen: "help.screen.chat",
+        groups: &[
+            (
+                "help.group.conversation",
+                &[
+                    b("Enter", "Send message"),
+                    b("y / n", "Apply / cancel suggested changes"),
+                    b("PgUp/PgDn", "Scroll history"),
+                    b("Ctrl+K / Ctrl+J", "Scroll one line"),
+                    b("Home", "Jump to top"),
+                    b("End", "Resume auto-scroll"),
+                    b("/model <name>", "Chat model (also /temp, /tokens, /reset)"),
+                    b("/add /done /list", "Quick actions without the AI (/help)"),
+                    b("/audit", "Challenge DELEGATE and DROP tasks"),
+                    b("/retro [last]", "Weekly retrospective to the journal"),
+                    b("↑ then Y", "Pick a message and copy it"),
+                    b("Ctrl+L", "Clear chat history"),
+                    b("?", "This help (empty input)"),
+                    b("Esc", "Cancel a pending reply, or close chat"),
+                ],
+            ),
+            (
+                "help.group.input",
+                &[
+                    b("← →", "Move cursor"),
+                    b("Ctrl/Alt+← →", "Move by word (also Alt+b / Alt+f)"),
+                    b("Ctrl+A / Ctrl+E", "Start / end of input"),
+                    b("Backspace / Del", "Delete backward / forward"),
+                    b("Ctrl+W", "Delete previous word"),
+                    b("Ctrl+U", "Clear input"),
+                ],
+            ),
+        ],
+    },
+];
+
+/// Message ID of the `HELP` section that describes `screen`
+fn help_screen(screen: &CurrentScreen) -> &'static str {
+    match screen {
+        CurrentScreen::Focus => "help.screen.focus",
+        CurrentScreen::ZenMode => "help.screen.zen",
+        CurrentScreen::Review => "help.screen.review",
+        CurrentScreen::Triage => "help.screen.triage",
+        CurrentScreen::Day => "help.screen.day",
+        CurrentScreen::WeeklyReview => "help.screen.weekly",
+        CurrentScreen::Plan => "help.screen.plan",
+        CurrentScreen::Chat => "help.screen.chat",
+        _ => "help.screen.main",
+    }
+}
+
+/// Which `HELP` entry describes `screen`
+pub fn help_index(screen: &CurrentScreen) -> usize {
+    let name = help_screen(screen);
+    HELP.iter()
+        .position(|help| help.screen == name)
+        .unwrap_or(0)
+}
+
+/// A rendered row of the help overlay
+pub enum HelpLine {
+    Screen(&'static str),
+    Group(&'static str),
+    Binding(&'static Binding),
+    Blank,
+}
+
+/// The whole overlay, one entry per row
+pub fn help_lines() -> Vec<HelpLine> {
+    let mut lines = Vec::new();
+    for help in HELP {
+        if !lines.is_empty() {
+            lines.push(HelpLine::Blank);
+        }
+        lines.push(HelpLine::Screen(help.screen));
+        for (group, bindings) in help.groups {
+            lines.push(HelpLine::Group(group));
+            lines.extend(bindings.iter().map(HelpLine::Binding));
+        }
+    }
+    lines
+}
+
+/// Row where the `index`th screen's section starts
+pub fn help_offset(index: usize) -> usize {
+    help_lines()
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| matches!(line, HelpLine::Screen(_)))
+        .nth(index)
+        .map_or(0, |(row, _)| row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::{translate, Language};
+
+    #[test]
+    fn test_offsets_point_at_screen_headings() {
+        let lines = help_lines();
+        for (i, help) in HELP.iter().enumerate() {
+            match lines[help_offset(i)] {
+                HelpLine::Screen(name) => assert_eq!(name, help.screen),
+                _ => panic!("offset {} is not a heading", i),
+            }
+        }
+        assert_eq!(help_offset(help_index(&CurrentScreen::Main)), 0);
+    }
+
+    #[test]
+    fn test_each_screen_has_a_section() {
+        for screen in [
+            CurrentScreen::Main,
+            CurrentScreen::Focus,
+            CurrentScreen::ZenMode,
+            CurrentScreen::Review,
+            CurrentScreen::Triage,
+            CurrentScreen::Day,
+            CurrentScreen::WeeklyReview,
+            CurrentScreen::Plan,
+            CurrentScreen::Chat,
+        ] {
+            let name = help_screen(&screen);
+            assert_eq!(HELP[help_index(&screen)].screen, name, "{:?}", screen);
+        }
+    }
+
+    #[test]
+    fn test_headings_are_message_ids() {
+        for line in help_lines() {
+            if let HelpLine::Screen(id) | HelpLine::Group(id) = line {
+                assert_ne!(translate(Language::English, id, &[]), id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_column_fits() {
+        for help in HELP {
+            for (_, bindings) in help.groups {
+                for binding in bindings.iter() {
+                    assert!(binding.keys.chars().count() < 18, "{}", binding.keys);
+                }
+            }
+        }
+    }
+}
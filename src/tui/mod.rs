@@ -1,5 +1,20 @@
 pub mod app;
+pub mod clipboard;
+pub mod command;
+pub mod countdown;
+pub mod editor;
+pub mod filter;
 pub mod handlers;
+pub mod kanban;
+pub mod picker;
+pub mod keymap;
+pub mod review;
+pub mod saver;
+pub mod scroll;
+pub mod text;
+pub mod theme;
+pub mod toast;
+pub mod triage;
 pub mod ui;
 pub mod widgets;
 pub mod zen;
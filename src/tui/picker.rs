@@ -0,0 +1,198 @@
+use crate::config::Config;
+use crate::i18n::tr;
+use crate::models::task::Task;
+use crate::tui::app::install_panic_hook;
+use crate::tui::editor::LineEditor;
+use crate::tui::text::{display_width, truncate_to_width};
+use crate::tui::theme::Theme;
+use crossterm::event::{self, Event, KeyCode, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use ratatui::{
+    backend::CrosstermBackend,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame, Terminal, TerminalOptions, Viewport,
+};
+use std::error::Error;
+use std::io;
+use uuid::Uuid;
+
+/// Matches shown at once below the query line
+const PICKER_ROWS: usize = 8;
+
+/// Skim-style score of `query` against `text`, or `None` when the query's
+/// characters don't all appear in order. Case and spaces in the query are
+/// ignored; runs and word starts score higher, gaps lower
+pub fn fuzzy_score(text: &str, query: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut from = 0;
+    let mut last: Option<usize> = None;
+    for wanted in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let at = from + text[from..].iter().position(|&c| c == wanted)?;
+        score += 1;
+        if at == 0 || !text[at - 1].is_alphanumeric() {
+            score += 3;
+        }
+        match last {
+            Some(prev) if prev + 1 == at => score += 4,
+            Some(prev) => score -= (at - prev - 1).min(3) as i32,
+            None => {}
+        }
+        last = Some(at);
+        from = at + 1;
+    }
+    Some(score)
+}
+
+/// Indexes of the `titles` that match `query`, best first; equal scores keep
+/// their order
+pub fn rank(titles: &[&str], query: &str) -> Vec<usize> {
+    let mut scored: Vec<(usize, i32)> = titles
+        .iter()
+        .enumerate()
+        .filter_map(|(i, title)| fuzzy_score(title, query).map(|score| (i, score)))
+        .collect();
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+/// Pick one of `tasks` by typing a few letters of its title. The picker
+/// takes a few lines under the prompt rather than the whole screen and
+/// clears them when done. `None` when cancelled
+pub fn pick_task(prompt: &str, tasks: &[&Task]) -> Result<Option<Uuid>, Box<dyn Error>> {
+    install_panic_hook();
+    enable_raw_mode()?;
+    let height = tasks.len().min(PICKER_ROWS) as u16 + 2;
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(io::stdout()),
+        TerminalOptions {
+            viewport: Viewport::Inline(height),
+        },
+    )?;
+    let theme = Theme::from_config(&Config::load_or_default());
+
+    let res = picker_loop(&mut terminal, prompt, tasks, &theme);
+    terminal.clear()?;
+    terminal.show_cursor()?;
+    disable_raw_mode()?;
+    Ok(res?)
+}
+
+fn picker_loop<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    prompt: &str,
+    tasks: &[&Task],
+    theme: &Theme,
+) -> io::Result<Option<Uuid>> {
+    let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+    let mut query = LineEditor::new();
+    let mut matches = rank(&titles, "");
+    let mut selected = 0;
+
+    loop {
+        terminal.draw(|f| render(f, prompt, &query, tasks, &matches, selected, theme))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if ctrl => return Ok(None),
+            KeyCode::Enter => return Ok(matches.get(selected).map(|&i| tasks[i].id)),
+            KeyCode::Up | KeyCode::BackTab => selected = selected.saturating_sub(1),
+            KeyCode::Char('p') if ctrl => selected = selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Tab => {
+                selected = (selected + 1).min(matches.len().saturating_sub(1))
+            }
+            KeyCode::Char('n') if ctrl => {
+                selected = (selected + 1).min(matches.len().saturating_sub(1))
+            }
+            _ => {
+                if query.handle_key(&key) {
+                    matches = rank(&titles, query.as_str());
+                    selected = 0;
+                }
+            }
+        }
+    }
+}
+
+fn render(
+    f: &mut Frame,
+    prompt: &str,
+    query: &LineEditor,
+    tasks: &[&Task],
+    matches: &[usize],
+    selected: usize,
+    theme: &Theme,
+) {
+    let area = f.area();
+    let prompt = format!("{} ", prompt);
+    let (visible, cursor) =
+        query.view((area.width as usize).saturating_sub(display_width(&prompt) + 1));
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            prompt.clone(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(visible.to_string(), Style::default().fg(theme.text)),
+    ])];
+
+    // Scroll so the selected match stays in view
+    let offset = selected.saturating_sub(PICKER_ROWS - 1);
+    for (row, &i) in matches.iter().enumerate().skip(offset).take(PICKER_ROWS) {
+        let task = tasks[i];
+        let quadrant = task.quadrant();
+        let mut title_style = Style::default().fg(theme.text);
+        if row == selected {
+            title_style = title_style.add_modifier(Modifier::BOLD);
+        }
+        let width = (area.width as usize).saturating_sub(8);
+        lines.push(Line::from(vec![
+            Span::styled(
+                if row == selected { "› " } else { "  " },
+                Style::default().fg(theme.accent),
+            ),
+            Span::styled(
+                format!("{}{} ", theme.quadrant_marker(quadrant), quadrant.badge()),
+                Style::default().fg(theme.quadrant(quadrant)),
+            ),
+            Span::styled(truncate_to_width(&task.title, width), title_style),
+        ]));
+    }
+    lines.resize(PICKER_ROWS.min(tasks.len()) + 1, Line::from(""));
+    lines.push(Line::from(Span::styled(
+        tr("pick.hint", &[&matches.len(), &tasks.len()]),
+        Style::default().fg(theme.muted),
+    )));
+
+    f.render_widget(Paragraph::new(lines), area);
+    let x = area.x + (display_width(&prompt) + cursor) as u16;
+    f.set_cursor_position((x.min(area.right().saturating_sub(1)), area.y));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score_needs_letters_in_order() {
+        assert!(fuzzy_score("Email Bob", "emb").is_some());
+        assert!(fuzzy_score("Email Bob", "EMAIL bob").is_some());
+        assert_eq!(fuzzy_score("Email Bob", "bme"), None);
+        assert_eq!(fuzzy_score("anything", ""), Some(0));
+    }
+
+    #[test]
+    fn test_rank_prefers_runs_and_word_starts() {
+        let titles = ["Pay rent", "Prepare report", "Write report"];
+        assert_eq!(rank(&titles, "rep"), vec![1, 2]);
+        assert_eq!(rank(&titles, "pr"), vec![1, 0, 2]);
+        assert_eq!(rank(&titles, ""), vec![0, 1, 2]);
+        assert!(rank(&titles, "xyz").is_empty());
+    }
+}
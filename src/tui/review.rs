@@ -0,0 +1,94 @@
+use crate::models::task::{Task, TaskStatus};
+use crate::walkthrough::Walkthrough;
+use chrono::NaiveDate;
+
+/// What the user decided for one unfinished task
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewChoice {
+    Tomorrow,
+    Rescheduled(NaiveDate),
+    Dropped,
+    Kept,
+}
+
+/// How many tasks got each choice
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReviewTally {
+    pub tomorrow: usize,
+    pub rescheduled: usize,
+    pub dropped: usize,
+    pub kept: usize,
+}
+
+/// Guided end-of-day pass over a day's unfinished tasks, one at a time
+pub type DailyReview = Walkthrough<ReviewChoice>;
+
+impl DailyReview {
+    /// Queue the tasks still pending on `date`, highest score first
+    pub fn new(tasks: &[Task], date: NaiveDate) -> Self {
+        let mut pending: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.date == date && t.status == TaskStatus::Pending)
+            .collect();
+        pending.sort_by_key(|t| std::cmp::Reverse(t.score()));
+        Self::from_queue(date, pending.iter().map(|t| t.id).collect())
+    }
+
+    pub fn tally(&self) -> ReviewTally {
+        let mut tally = ReviewTally::default();
+        for choice in self.choices() {
+            match choice {
+                ReviewChoice::Tomorrow => tally.tomorrow += 1,
+                ReviewChoice::Rescheduled(_) => tally.rescheduled += 1,
+                ReviewChoice::Dropped => tally.dropped += 1,
+                ReviewChoice::Kept => tally.kept += 1,
+            }
+        }
+        tally
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_queues_pending_tasks_by_score() {
+        let day = date("2024-06-12");
+        let low = Task::new("low".into(), 1, 1, day);
+        let high = Task::new("high".into(), 3, 3, day);
+        let mut done = Task::new("done".into(), 3, 3, day);
+        done.complete();
+        let elsewhere = Task::new("tomorrow".into(), 3, 3, date("2024-06-13"));
+
+        let high_id = high.id;
+
+        let review = DailyReview::new(&[low, high, done, elsewhere], day);
+        assert_eq!(review.current(), Some(high_id));
+        assert_eq!(review.progress(), (1, 2));
+    }
+
+    #[test]
+    fn test_decisions_advance_and_tally() {
+        let day = date("2024-06-12");
+        let tasks: Vec<Task> = (0..3)
+            .map(|i| Task::new(format!("t{}", i), 2, 2, day))
+            .collect();
+        let mut review = DailyReview::new(&tasks, day);
+
+        review.decide(ReviewChoice::Tomorrow);
+        review.decide(ReviewChoice::Rescheduled(date("2024-06-20")));
+        assert_eq!(review.progress(), (3, 3));
+        review.decide(ReviewChoice::Dropped);
+        assert!(review.is_finished());
+        review.decide(ReviewChoice::Kept); // ignored once finished
+
+        let tally = review.tally();
+        assert_eq!((tally.tomorrow, tally.rescheduled), (1, 1));
+        assert_eq!((tally.dropped, tally.kept), (1, 0));
+    }
+}
@@ -0,0 +1,282 @@
+use crate::models::store::TaskStore;
+use crate::models::task::Task;
+use crate::storage::lock::lock_store;
+use crate::storage::paths::tasks_file_path;
+use crate::sync::merge_tasks;
+use std::error::Error;
+use std::fs;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::runtime::{Handle, Runtime};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::task::{spawn_blocking, JoinHandle};
+
+/// Quiet time after the last change before the store is written
+pub const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+/// Wait before trying again after a failed save, such as on a read-only
+/// or full disk
+pub const SAVE_RETRY: Duration = Duration::from_secs(15);
+
+/// How a queued save went
+enum Outcome {
+    /// Written; `tasks` as saved, and the file's new modification time
+    Saved {
+        tasks: Vec<Task>,
+        modified: Option<SystemTime>,
+    },
+    /// Not written, because another process changed `tasks.json` since the
+    /// snapshot's changes were merged with it
+    Stale,
+    Failed(String),
+}
+
+/// Writes the store on the async runtime, so a slow disk never holds up
+/// a keypress. Changes are batched until they settle for `SAVE_DEBOUNCE`
+/// or the caller flushes; each change is still appended to the history
+/// log as it is made, so a crash between saves loses no record of it.
+/// When a save fails the changes stay in memory and the save is retried.
+///
+/// Other eq processes write `tasks.json` too, so each save holds the store
+/// lock and only goes ahead if the file is as last seen; changes made
+/// elsewhere are merged in by `reload_if_changed` first
+pub struct Saver {
+    snapshots: Option<UnboundedSender<(TaskStore, Option<SystemTime>)>>,
+    outcomes: UnboundedReceiver<Outcome>,
+    worker: Option<JoinHandle<()>>,
+    runtime: Handle,
+    /// When the next save is due; `None` when nothing is waiting
+    due_at: Option<Instant>,
+    /// The tasks as last read from or written to `tasks.json`, which both
+    /// this session and any other writer started from
+    base: Vec<Task>,
+    /// Modification time of `tasks.json` when `base` was read or written
+    seen: Option<SystemTime>,
+    /// Whether a queued save hasn't reported back yet
+    awaiting: bool,
+}
+
+impl Saver {
+    /// A saver for `store`, which has just been loaded, writing on `runtime`
+    pub fn new(store: &TaskStore, runtime: &Runtime) -> Self {
+        let (snapshots, mut queued) = unbounded_channel::<(TaskStore, Option<SystemTime>)>();
+        let (report, outcomes) = unbounded_channel();
+        let worker = runtime.spawn(async move {
+            let mut written = None;
+            while let Some(mut queued_save) = queued.recv().await {
+                // Only the newest snapshot needs writing
+                while let Ok(newer) = queued.try_recv() {
+                    queued_save = newer;
+                }
+                let (store, expected) = queued_save;
+                // Taking the lock and writing block, so keep them off the
+                // runtime's own threads
+                let write = spawn_blocking(move || {
+                    let outcome = write_unless_changed(store, expected, &mut written);
+                    (outcome, written)
+                });
+                let outcome = match write.await {
+                    Ok((outcome, now)) => {
+                        written = now;
+                        outcome
+                    }
+                    Err(err) => Outcome::Failed(err.to_string()),
+                };
+                if let Outcome::Failed(err) = &outcome {
+                    tracing::warn!(%err, "background save failed");
+                }
+                let _ = report.send(outcome);
+            }
+        });
+        Self {
+            snapshots: Some(snapshots),
+            outcomes,
+            worker: Some(worker),
+            runtime: runtime.handle().clone(),
+            due_at: None,
+            base: store.tasks.clone(),
+            seen: modified(),
+            awaiting: false,
+        }
+    }
+
+    /// Note a change to the store, putting off the save until it settles
+    pub fn changed(&mut self) {
+        self.due_at = Some(Instant::now() + SAVE_DEBOUNCE);
+    }
+
+    /// Time left until the pending save is due; `None` when nothing is
+    /// waiting to be saved
+    pub fn due_in(&self) -> Option<Duration> {
+        self.due_at
+            .map(|at| at.saturating_duration_since(Instant::now()))
+    }
+
+    /// Save now if the last change has settled
+    pub fn save_if_due(&mut self, store: &TaskStore) {
+        if self.due_in() == Some(Duration::ZERO) {
+            self.flush(store);
+        }
+    }
+
+    /// Hand the store to the writer at once if anything is unsaved
+    pub fn flush(&mut self, store: &TaskStore) {
+        if self.due_at.take().is_none() {
+            return;
+        }
+        if let Some(snapshots) = &self.snapshots {
+            tracing::trace!("queued a save");
+            self.awaiting = true;
+            let _ = snapshots.send((store.snapshot(), self.seen));
+        }
+    }
+
+    /// Merge changes another process saved to `tasks.json` into `store`,
+    /// keeping the ones made here; returns whether there were any. Waits
+    /// while a save is on its way, since that changes the file too
+    pub fn reload_if_changed(&mut self, store: &mut TaskStore) -> Result<bool, Box<dyn Error>> {
+        let modified = modified();
+        if self.awaiting || modified == self.seen {
+            return Ok(false);
+        }
+        let mut disk = TaskStore::load()?;
+        if store.history_loaded() {
+            disk.load_history()?;
+        }
+        store.tasks = merge_tasks(&self.base, &store.tasks, &disk.tasks);
+        store.touch();
+        self.base = disk.tasks;
+        self.seen = modified;
+        tracing::debug!("took in changes saved elsewhere");
+        Ok(true)
+    }
+
+    /// How the latest save since the last call went, if one finished. A
+    /// failure schedules another try, and so does a save turned down
+    /// because the file changed meanwhile
+    pub fn poll(&mut self) -> Option<Result<(), String>> {
+        let mut latest = None;
+        while let Ok(outcome) = self.outcomes.try_recv() {
+            latest = self.take_outcome(outcome).or(latest);
+        }
+        latest
+    }
+
+    /// Wait for the next save to report back, for the event loop. Ready
+    /// with `None` for a save turned down because the file changed, which
+    /// is due again at once
+    pub fn poll_outcome(&mut self, cx: &mut Context) -> Poll<Option<Result<(), String>>> {
+        match self.outcomes.poll_recv(cx) {
+            Poll::Ready(Some(outcome)) => Poll::Ready(self.take_outcome(outcome)),
+            // The writer only stops in `finish`
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn take_outcome(&mut self, outcome: Outcome) -> Option<Result<(), String>> {
+        self.awaiting = false;
+        match outcome {
+            Outcome::Saved { tasks, modified } => {
+                self.base = tasks;
+                self.seen = modified;
+                Some(Ok(()))
+            }
+            Outcome::Stale => {
+                self.due_at.get_or_insert_with(Instant::now);
+                None
+            }
+            Outcome::Failed(err) => {
+                self.retry_later();
+                Some(Err(err))
+            }
+        }
+    }
+
+    fn retry_later(&mut self) {
+        self.due_at
+            .get_or_insert_with(|| Instant::now() + SAVE_RETRY);
+    }
+
+    /// Flush, then wait for the writer to finish; for exit. Anything still
+    /// unsaved is merged and written here. Returns the error when the
+    /// changes could not be saved after all
+    pub fn finish(&mut self, store: &mut TaskStore) -> Option<String> {
+        // A failure not yet seen schedules a retry, which is flushed too
+        self.poll();
+        self.flush(store);
+        self.snapshots = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = self.runtime.block_on(worker);
+        }
+        self.poll();
+        self.due_at.take()?;
+        self.save_now(store).err().map(|err| err.to_string())
+    }
+
+    /// Merge in changes made elsewhere and write `store`, all while holding
+    /// the store lock
+    fn save_now(&mut self, store: &mut TaskStore) -> Result<(), Box<dyn Error>> {
+        let _lock = lock_store()?;
+        self.awaiting = false;
+        self.reload_if_changed(store)?;
+        store.save()?;
+        self.base = store.tasks.clone();
+        self.seen = modified();
+        Ok(())
+    }
+}
+
+/// Modification time of `tasks.json`; `None` before it is first written
+fn modified() -> Option<SystemTime> {
+    let path = tasks_file_path().ok()?;
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Save `store` under the store lock, unless `tasks.json` was changed by
+/// someone else since it was `expected`. `written` is when this writer last
+/// saved, which a snapshot queued before that finished doesn't know about
+fn write_unless_changed(
+    store: TaskStore,
+    expected: Option<SystemTime>,
+    written: &mut Option<SystemTime>,
+) -> Outcome {
+    let _lock = match lock_store() {
+        Ok(lock) => lock,
+        Err(err) => return Outcome::Failed(err.to_string()),
+    };
+    let now = modified();
+    if now != expected && (written.is_none() || now != *written) {
+        tracing::debug!("tasks.json changed underneath; merging before saving");
+        return Outcome::Stale;
+    }
+    if let Err(err) = store.save() {
+        return Outcome::Failed(err.to_string());
+    }
+    *written = modified();
+    Outcome::Saved {
+        tasks: store.tasks,
+        modified: *written,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_waits_for_changes_to_settle() {
+        let mut saver = Saver::new(&TaskStore::default(), crate::ai::runtime().unwrap());
+        assert_eq!(saver.due_in(), None);
+        saver.changed();
+        assert!(saver.due_in().is_some_and(|wait| wait <= SAVE_DEBOUNCE));
+    }
+
+    #[test]
+    fn test_failed_save_is_retried() {
+        let mut saver = Saver::new(&TaskStore::default(), crate::ai::runtime().unwrap());
+        saver.retry_later();
+        assert!(saver.due_in().is_some_and(|wait| wait > SAVE_DEBOUNCE));
+        // A change made meanwhile is saved on the usual schedule
+        saver.changed();
+        assert!(saver.due_in().is_some_and(|wait| wait <= SAVE_DEBOUNCE));
+    }
+}
@@ -0,0 +1,67 @@
+/// Scroll position of a list, kept separately from its selection
+///
+/// The view follows the selection only when the selection moves, so the list
+/// can be scrolled away from the cursor with Ctrl+E / Ctrl+Y.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollState {
+    pub offset: usize,
+    last_selected: Option<usize>,
+}
+
+impl ScrollState {
+    /// Offset to render `len` rows in a `viewport` rows tall, scrolling to the
+    /// selection if it changed since the last frame
+    pub fn sync(&mut self, selected: Option<usize>, len: usize, viewport: usize) -> usize {
+        if viewport == 0 {
+            return 0;
+        }
+
+        if selected != self.last_selected {
+            if let Some(sel) = selected {
+                if sel < self.offset {
+                    self.offset = sel;
+                } else if sel >= self.offset + viewport {
+                    self.offset = sel + 1 - viewport;
+                }
+            }
+            self.last_selected = selected;
+        }
+
+        self.offset = self.offset.min(len.saturating_sub(viewport));
+        self.offset
+    }
+
+    /// Move the view without touching the selection; clamped on the next `sync`
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.offset = self.offset.saturating_add_signed(delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follows_selection_when_it_moves() {
+        let mut state = ScrollState::default();
+        assert_eq!(state.sync(Some(0), 20, 5), 0);
+        assert_eq!(state.sync(Some(7), 20, 5), 3);
+        assert_eq!(state.sync(Some(2), 20, 5), 2);
+    }
+
+    #[test]
+    fn test_manual_scroll_is_independent() {
+        let mut state = ScrollState::default();
+        state.sync(Some(0), 20, 5);
+
+        // Scrolling leaves the selection off-screen until it moves again
+        state.scroll_by(10);
+        assert_eq!(state.sync(Some(0), 20, 5), 10);
+        state.scroll_by(100);
+        assert_eq!(state.sync(Some(0), 20, 5), 15);
+        assert_eq!(state.sync(Some(1), 20, 5), 1);
+
+        state.scroll_by(-5);
+        assert_eq!(state.sync(Some(1), 20, 5), 0);
+    }
+}
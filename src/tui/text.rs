@@ -0,0 +1,95 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Terminal columns taken by `s` (CJK and emoji count as two)
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// Cut `s` to at most `max` columns on grapheme boundaries, ending with `…`
+/// when anything was removed
+pub fn truncate_to_width(s: &str, max: usize) -> String {
+    if display_width(s) <= max {
+        return s.to_string();
+    }
+    if max == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut width = 0;
+    for g in s.graphemes(true) {
+        let w = display_width(g);
+        if width + w > max - 1 {
+            break;
+        }
+        out.push_str(g);
+        width += w;
+    }
+    out.push('…');
+    out
+}
+
+/// Last `max` columns of `s`, for inputs that scroll to keep the end visible
+pub fn tail_to_width(s: &str, max: usize) -> &str {
+    let mut width = 0;
+    let mut start = s.len();
+    for (idx, g) in s.grapheme_indices(true).rev() {
+        let w = display_width(g);
+        if width + w > max {
+            break;
+        }
+        width += w;
+        start = idx;
+    }
+    &s[start..]
+}
+
+/// First `max` columns of `s`, cut on a grapheme boundary without an ellipsis
+pub fn head_to_width(s: &str, max: usize) -> &str {
+    let mut width = 0;
+    for (idx, g) in s.grapheme_indices(true) {
+        let w = display_width(g);
+        if width + w > max {
+            return &s[..idx];
+        }
+        width += w;
+    }
+    s
+}
+
+/// Remove the last user-perceived character
+pub fn pop_grapheme(s: &mut String) -> Option<String> {
+    let (idx, g) = s.grapheme_indices(true).next_back()?;
+    let g = g.to_string();
+    s.truncate(idx);
+    Some(g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_wide_characters() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("買牛奶和麵包", 7), "買牛奶…");
+        assert_eq!(display_width(&truncate_to_width("買牛奶和麵包", 7)), 7);
+        assert_eq!(truncate_to_width("🎉🎉🎉", 4), "🎉…");
+        assert_eq!(truncate_to_width("abc", 0), "");
+    }
+
+    #[test]
+    fn test_tail_and_pop() {
+        assert_eq!(tail_to_width("hello 世界", 5), " 世界");
+        assert_eq!(tail_to_width("abc", 10), "abc");
+        assert_eq!(head_to_width("世界 hello", 3), "世");
+        assert_eq!(head_to_width("abc", 10), "abc");
+
+        let mut s = String::from("family 👨‍👩‍👧");
+        assert_eq!(pop_grapheme(&mut s).as_deref(), Some("👨‍👩‍👧"));
+        assert_eq!(s, "family ");
+        let mut empty = String::new();
+        assert_eq!(pop_grapheme(&mut empty), None);
+    }
+}
@@ -0,0 +1,388 @@
+use crate::config::Config;
+use crate::models::task::Quadrant;
+use ratatui::style::Color;
+use ratatui::widgets::BorderType;
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
+
+/// Names of the built-in presets, in `T` cycling order
+pub const THEME_NAMES: &[&str] = &[
+    "default",
+    "solarized",
+    "gruvbox",
+    "monochrome",
+    "high-contrast",
+];
+
+/// Every color the TUI draws with, plus whether quadrants also get
+/// distinct glyphs and border patterns so color is never the only cue
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub name: &'static str,
+    pub do_first: Color,
+    pub schedule: Color,
+    pub delegate: Color,
+    pub drop: Color,
+    pub accent: Color,    // Active borders, headers, prompts
+    pub highlight: Color, // Secondary accent: today, dialog borders
+    pub muted: Color,     // Hints, inactive borders, finished tasks
+    pub text: Color,
+    pub success: Color,
+    pub danger: Color,
+    pub zen_progress: Color,
+    pub zen_track: Color,
+    pub zen_text: Color,
+    pub particles: [Color; 5],
+    pub glyphs: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: "default",
+            do_first: Color::Red,
+            schedule: Color::Blue,
+            delegate: Color::Yellow,
+            drop: Color::Gray,
+            accent: Color::Yellow,
+            highlight: Color::Cyan,
+            muted: Color::DarkGray,
+            text: Color::White,
+            success: Color::Green,
+            danger: Color::Red,
+            zen_progress: Color::Rgb(100, 180, 100),
+            zen_track: Color::Rgb(60, 60, 70),
+            zen_text: Color::Rgb(150, 150, 170),
+            particles: [
+                Color::Rgb(100, 120, 140),
+                Color::Rgb(80, 100, 120),
+                Color::Rgb(120, 140, 160),
+                Color::Rgb(90, 110, 130),
+                Color::Rgb(70, 90, 110),
+            ],
+            glyphs: false,
+        }
+    }
+}
+
+impl Theme {
+    pub fn solarized() -> Self {
+        Theme {
+            name: "solarized",
+            do_first: Color::Rgb(220, 50, 47),
+            schedule: Color::Rgb(38, 139, 210),
+            delegate: Color::Rgb(181, 137, 0),
+            drop: Color::Rgb(88, 110, 117),
+            accent: Color::Rgb(181, 137, 0),
+            highlight: Color::Rgb(42, 161, 152),
+            muted: Color::Rgb(88, 110, 117),
+            text: Color::Rgb(147, 161, 161),
+            success: Color::Rgb(133, 153, 0),
+            danger: Color::Rgb(220, 50, 47),
+            zen_progress: Color::Rgb(133, 153, 0),
+            zen_track: Color::Rgb(7, 54, 66),
+            zen_text: Color::Rgb(131, 148, 150),
+            particles: [
+                Color::Rgb(38, 139, 210),
+                Color::Rgb(42, 161, 152),
+                Color::Rgb(108, 113, 196),
+                Color::Rgb(88, 110, 117),
+                Color::Rgb(131, 148, 150),
+            ],
+            glyphs: false,
+        }
+    }
+
+    pub fn gruvbox() -> Self {
+        Theme {
+            name: "gruvbox",
+            do_first: Color::Rgb(251, 73, 52),
+            schedule: Color::Rgb(131, 165, 152),
+            delegate: Color::Rgb(250, 189, 47),
+            drop: Color::Rgb(146, 131, 116),
+            accent: Color::Rgb(254, 128, 25),
+            highlight: Color::Rgb(142, 192, 124),
+            muted: Color::Rgb(146, 131, 116),
+            text: Color::Rgb(235, 219, 178),
+            success: Color::Rgb(184, 187, 38),
+            danger: Color::Rgb(251, 73, 52),
+            zen_progress: Color::Rgb(184, 187, 38),
+            zen_track: Color::Rgb(80, 73, 69),
+            zen_text: Color::Rgb(168, 153, 132),
+            particles: [
+                Color::Rgb(142, 192, 124),
+                Color::Rgb(131, 165, 152),
+                Color::Rgb(211, 134, 155),
+                Color::Rgb(146, 131, 116),
+                Color::Rgb(102, 92, 84),
+            ],
+            glyphs: false,
+        }
+    }
+
+    pub fn monochrome() -> Self {
+        Theme {
+            name: "monochrome",
+            do_first: Color::White,
+            schedule: Color::Gray,
+            delegate: Color::Gray,
+            drop: Color::DarkGray,
+            accent: Color::White,
+            highlight: Color::Gray,
+            muted: Color::DarkGray,
+            text: Color::Reset,
+            success: Color::White,
+            danger: Color::White,
+            zen_progress: Color::White,
+            zen_track: Color::DarkGray,
+            zen_text: Color::Gray,
+            particles: [
+                Color::Gray,
+                Color::DarkGray,
+                Color::Gray,
+                Color::DarkGray,
+                Color::DarkGray,
+            ],
+            glyphs: true,
+        }
+    }
+
+    /// Blue/orange/purple palette that stays distinguishable with the common
+    /// color-vision deficiencies, with bright text and glyphs on
+    pub fn high_contrast() -> Self {
+        Theme {
+            name: "high-contrast",
+            do_first: Color::Rgb(230, 159, 0),
+            schedule: Color::Rgb(86, 180, 233),
+            delegate: Color::Rgb(204, 121, 167),
+            drop: Color::Gray,
+            accent: Color::LightYellow,
+            highlight: Color::LightCyan,
+            muted: Color::Gray,
+            text: Color::White,
+            success: Color::Rgb(86, 180, 233),
+            danger: Color::Rgb(230, 159, 0),
+            zen_progress: Color::Rgb(86, 180, 233),
+            zen_track: Color::Gray,
+            zen_text: Color::White,
+            particles: [
+                Color::White,
+                Color::Gray,
+                Color::Rgb(86, 180, 233),
+                Color::Gray,
+                Color::White,
+            ],
+            glyphs: true,
+        }
+    }
+
+    /// Terminal default colors everywhere, used when `NO_COLOR` is set
+    pub fn no_color() -> Self {
+        Theme {
+            name: "no-color",
+            do_first: Color::Reset,
+            schedule: Color::Reset,
+            delegate: Color::Reset,
+            drop: Color::Reset,
+            accent: Color::Reset,
+            highlight: Color::Reset,
+            muted: Color::Reset,
+            text: Color::Reset,
+            success: Color::Reset,
+            danger: Color::Reset,
+            zen_progress: Color::Reset,
+            zen_track: Color::Reset,
+            zen_text: Color::Reset,
+            particles: [Color::Reset; 5],
+            glyphs: true,
+        }
+    }
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "default" => Some(Theme::default()),
+            "solarized" => Some(Theme::solarized()),
+            "gruvbox" => Some(Theme::gruvbox()),
+            "monochrome" | "mono" => Some(Theme::monochrome()),
+            "high-contrast" | "contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Preset named in the config (default if unknown) with color overrides applied
+    pub fn from_config(config: &Config) -> Self {
+        Self::resolve(config, no_color_requested())
+    }
+
+    fn resolve(config: &Config, no_color: bool) -> Self {
+        if no_color {
+            return Theme::no_color();
+        }
+        let mut theme = config
+            .theme
+            .as_deref()
+            .and_then(Theme::by_name)
+            .unwrap_or_default();
+        theme.apply_overrides(&config.colors);
+        theme.glyphs |= config.accessible;
+        theme
+    }
+
+    /// The preset after this one in `THEME_NAMES`
+    pub fn next_name(&self) -> &'static str {
+        let idx = THEME_NAMES
+            .iter()
+            .position(|n| *n == self.name)
+            .unwrap_or(0);
+        THEME_NAMES[(idx + 1) % THEME_NAMES.len()]
+    }
+
+    /// Replace slots by name (`"do_first": "#ff5555"`); unknown slots or colors are ignored.
+    /// `particles` takes a comma-separated list, repeated to fill the five
+    /// particle colors
+    pub fn apply_overrides(&mut self, colors: &HashMap<String, String>) {
+        for (slot, value) in colors {
+            if slot == "particles" {
+                let list: Result<Vec<Color>, _> = value
+                    .split(',')
+                    .map(|c| Color::from_str(c.trim()))
+                    .collect();
+                if let Ok(list) = list {
+                    for (particle, color) in self.particles.iter_mut().zip(list.iter().cycle()) {
+                        *particle = *color;
+                    }
+                }
+                continue;
+            }
+            let Ok(color) = Color::from_str(value) else {
+                continue;
+            };
+            match slot.as_str() {
+                "do_first" => self.do_first = color,
+                "schedule" => self.schedule = color,
+                "delegate" => self.delegate = color,
+                "drop" => self.drop = color,
+                "accent" => self.accent = color,
+                "highlight" => self.highlight = color,
+                "muted" => self.muted = color,
+                "text" => self.text = color,
+                "success" => self.success = color,
+                "danger" => self.danger = color,
+                "zen_progress" => self.zen_progress = color,
+                "zen_track" => self.zen_track = color,
+                "zen_text" => self.zen_text = color,
+                _ => {}
+            }
+        }
+    }
+
+    /// Glyph and trailing space marking a quadrant, empty unless glyphs are on
+    pub fn quadrant_marker(&self, quadrant: Quadrant) -> &'static str {
+        if !self.glyphs {
+            return "";
+        }
+        match quadrant {
+            Quadrant::DoFirst => "▲ ",
+            Quadrant::Schedule => "◆ ",
+            Quadrant::Delegate => "● ",
+            Quadrant::Drop => "○ ",
+        }
+    }
+
+    /// Border pattern of a quadrant's panel
+    pub fn quadrant_border(&self, quadrant: Quadrant) -> BorderType {
+        if !self.glyphs {
+            return BorderType::Plain;
+        }
+        match quadrant {
+            Quadrant::DoFirst => BorderType::Thick,
+            Quadrant::Schedule => BorderType::Double,
+            Quadrant::Delegate => BorderType::Rounded,
+            Quadrant::Drop => BorderType::Plain,
+        }
+    }
+
+    /// Accent color used for a quadrant's tasks
+    pub fn quadrant(&self, quadrant: Quadrant) -> Color {
+        match quadrant {
+            Quadrant::DoFirst => self.do_first,
+            Quadrant::Schedule => self.schedule,
+            Quadrant::Delegate => self.delegate,
+            Quadrant::Drop => self.drop,
+        }
+    }
+}
+
+/// `NO_COLOR` is honored when present and non-empty (see no-color.org)
+pub fn no_color_requested() -> bool {
+    env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config_presets_and_overrides() {
+        let mut config = Config::default();
+        config.theme = Some(String::from("gruvbox"));
+        assert_eq!(Theme::from_config(&config).name, "gruvbox");
+
+        config.theme = Some(String::from("neon"));
+        assert_eq!(Theme::from_config(&config), Theme::default());
+
+        config
+            .colors
+            .insert(String::from("do_first"), String::from("#ff5555"));
+        config
+            .colors
+            .insert(String::from("schedule"), String::from("not-a-color"));
+        let theme = Theme::from_config(&config);
+        assert_eq!(theme.do_first, Color::Rgb(255, 85, 85));
+        assert_eq!(theme.schedule, Theme::default().schedule);
+
+        config
+            .colors
+            .insert(String::from("particles"), String::from("#ff5555, blue"));
+        let theme = Theme::from_config(&config);
+        assert_eq!(theme.particles[0], Color::Rgb(255, 85, 85));
+        assert_eq!(theme.particles[3], Color::Blue);
+    }
+
+    #[test]
+    fn test_next_name_cycles() {
+        let mut theme = Theme::default();
+        for expected in [
+            "solarized",
+            "gruvbox",
+            "monochrome",
+            "high-contrast",
+            "default",
+        ] {
+            theme = Theme::by_name(theme.next_name()).unwrap();
+            assert_eq!(theme.name, expected);
+        }
+    }
+
+    #[test]
+    fn test_accessibility_and_no_color() {
+        let mut config = Config::default();
+        assert!(!Theme::resolve(&config, false).glyphs);
+
+        config.accessible = true;
+        let theme = Theme::resolve(&config, false);
+        assert!(theme.glyphs);
+        assert_eq!(theme.quadrant_marker(Quadrant::DoFirst), "▲ ");
+        assert_eq!(
+            theme.quadrant_border(Quadrant::Schedule),
+            BorderType::Double
+        );
+
+        config.theme = Some(String::from("gruvbox"));
+        let theme = Theme::resolve(&config, true);
+        assert_eq!(theme.name, "no-color");
+        assert_eq!(theme.do_first, Color::Reset);
+        assert!(theme.glyphs);
+    }
+}
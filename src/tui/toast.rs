@@ -0,0 +1,41 @@
+use std::time::{Duration, Instant};
+
+/// How long a confirmation stays on screen
+const INFO_DURATION: Duration = Duration::from_secs(3);
+/// Errors linger a little longer so they can be read
+const ERROR_DURATION: Duration = Duration::from_secs(6);
+
+/// Short-lived status message shown in the corner of the screen
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub is_error: bool,
+    shown_at: Instant,
+}
+
+impl Toast {
+    pub fn info(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_error: false,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            is_error: true,
+            shown_at: Instant::now(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        let ttl = if self.is_error {
+            ERROR_DURATION
+        } else {
+            INFO_DURATION
+        };
+        self.shown_at.elapsed() >= ttl
+    }
+}
@@ -0,0 +1,90 @@
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use crate::walkthrough::Walkthrough;
+use chrono::NaiveDate;
+
+/// Where a DO FIRST task ends up after re-triage
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriageChoice {
+    /// Not urgent after all: SCHEDULE
+    Schedule,
+    /// Not important after all: DELEGATE
+    Delegate,
+    Dropped,
+    Kept,
+}
+
+impl TriageChoice {
+    /// Urgency and importance a task takes for this choice; `None` when it
+    /// keeps its own
+    pub fn priority(self, task: &Task) -> Option<(u8, u8)> {
+        match self {
+            TriageChoice::Schedule => Some((1, task.importance)),
+            TriageChoice::Delegate => Some((task.urgency, 1)),
+            TriageChoice::Dropped | TriageChoice::Kept => None,
+        }
+    }
+}
+
+/// One-at-a-time pass over a day's DO FIRST tasks, to demote those that are
+/// not really urgent and important
+pub type Triage = Walkthrough<TriageChoice>;
+
+impl Triage {
+    /// Queue the pending DO FIRST tasks on `date`, lowest score first, as
+    /// those are the likeliest to be demoted
+    pub fn new(tasks: &[Task], date: NaiveDate) -> Self {
+        let mut do_first: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| {
+                t.date == date
+                    && t.status == TaskStatus::Pending
+                    && t.quadrant() == Quadrant::DoFirst
+            })
+            .collect();
+        do_first.sort_by_key(|t| t.score());
+        Self::from_queue(date, do_first.iter().map(|t| t.id).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_queues_do_first_tasks_lowest_score_first() {
+        let day = date("2024-06-12");
+        let top = Task::new("top".into(), 3, 3, day);
+        let borderline = Task::new("borderline".into(), 2, 2, day);
+        let schedule = Task::new("schedule".into(), 1, 3, day);
+        let mut done = Task::new("done".into(), 2, 2, day);
+        done.complete();
+        let ids = (borderline.id, top.id);
+
+        let mut triage = Triage::new(&[top, borderline, schedule, done], day);
+        assert_eq!(triage.progress(), (1, 2));
+        assert_eq!(triage.current(), Some(ids.0));
+        triage.decide(TriageChoice::Schedule);
+        assert_eq!(triage.current(), Some(ids.1));
+        triage.decide(TriageChoice::Kept);
+        assert!(triage.is_finished());
+        assert_eq!(triage.count(TriageChoice::Schedule), 1);
+        assert_eq!(triage.count(TriageChoice::Delegate), 0);
+    }
+
+    #[test]
+    fn test_choices_leave_do_first() {
+        let task = Task::new("Reply to recruiter".into(), 3, 2, date("2024-06-12"));
+        for choice in [TriageChoice::Schedule, TriageChoice::Delegate] {
+            let (urgency, importance) = choice.priority(&task).unwrap();
+            assert_ne!(
+                Quadrant::from_priority(urgency, importance),
+                Quadrant::DoFirst
+            );
+        }
+        assert_eq!(TriageChoice::Kept.priority(&task), None);
+    }
+}
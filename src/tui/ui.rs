@@ -1,16 +1,92 @@
-use crate::models::task::{Quadrant, TaskStatus};
-use crate::tui::app::{App, CurrentScreen};
+use crate::i18n::tr;
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use crate::plan::capacity_line;
+use crate::stats::{
+    compare_day, format_duration, week_start, wip_overflow, DayOutcome, DayProgress,
+};
+use crate::timings;
+use crate::tui::app::{App, CurrentScreen, SortMode, ViewLayout};
+use crate::tui::command::complete;
+use crate::tui::editor::LineEditor;
+use crate::tui::kanban::KanbanColumn;
+use crate::tui::keymap::{help_lines, HelpLine};
+use crate::tui::text::{display_width, truncate_to_width};
+use crate::tui::theme::Theme;
+use crate::tui::toast::Toast;
+use crate::tui::triage::TriageChoice;
+use crate::tui::widgets::calendar::{CalendarWidget, DatePicker, CALENDAR_HEIGHT, CALENDAR_WIDTH};
 use crate::tui::widgets::quadrant::QuadrantWidget;
+use crate::tui::widgets::task_list::TaskListWidget;
 use crate::tui::zen::ZenState;
+use crate::walkthrough::Walkthrough;
+use crate::weekly::{week_outcomes, weekly_note_name, WeeklyChoice};
+use chrono::NaiveDate;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Sparkline, Tabs},
     Frame,
 };
+use std::collections::HashMap;
+
+/// Below this size the 2x2 grid truncates every title, so the matrix
+/// switches to one quadrant at a time with a tab bar
+const COMPACT_WIDTH: u16 = 100;
+const COMPACT_HEIGHT: u16 = 20;
+
+/// Below this size nothing useful fits
+const MIN_WIDTH: u16 = 30;
+const MIN_HEIGHT: u16 = 8;
 
 pub fn ui(f: &mut Frame, app: &mut App) {
+    render_screen(f, app);
+    if let Some(err) = &app.save_error {
+        render_save_banner(f, err, &app.theme);
+    }
+    if timings::enabled() {
+        render_timings(f, app);
+    }
+
+    if app.toast.as_ref().is_some_and(Toast::is_expired) {
+        app.toast = None;
+    }
+    if let Some(toast) = &app.toast {
+        render_toast(f, toast, &app.theme);
+    }
+
+    if let Some(task) = app
+        .detail_task
+        .and_then(|id| app.store.tasks.iter().find(|t| t.id == id))
+    {
+        render_detail(f, task, app);
+    }
+
+    if app.show_help {
+        render_help(f, app);
+    }
+
+    // Confirmations sit above every screen
+    if let Some(prompt) = app.confirm_prompt() {
+        render_confirm(f, &prompt, &app.theme);
+    }
+}
+
+fn render_screen(f: &mut Frame, app: &mut App) {
+    let area = f.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        let msg = Paragraph::new(format!(
+            "Terminal too small ({}x{})\nNeed at least {}x{}",
+            area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+        ))
+        .style(Style::default().fg(app.theme.muted))
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+        f.render_widget(msg, area);
+        return;
+    }
+    app.compact = area.width < COMPACT_WIDTH || area.height < COMPACT_HEIGHT;
+
     // Handle special screen modes
     match app.current_screen {
         CurrentScreen::Chat => {
@@ -25,6 +101,34 @@ pub fn ui(f: &mut Frame, app: &mut App) {
             render_zen(f, app);
             return;
         }
+        CurrentScreen::Stats => {
+            render_stats(f, app);
+            return;
+        }
+        CurrentScreen::Week => {
+            render_week(f, app);
+            return;
+        }
+        CurrentScreen::Review => {
+            render_review(f, app);
+            return;
+        }
+        CurrentScreen::Triage => {
+            render_triage(f, app);
+            return;
+        }
+        CurrentScreen::Day => {
+            render_day(f, app);
+            return;
+        }
+        CurrentScreen::Plan => {
+            render_plan(f, app);
+            return;
+        }
+        CurrentScreen::WeeklyReview => {
+            render_weekly(f, app);
+            return;
+        }
         _ => {}
     }
 
@@ -42,18 +146,380 @@ pub fn ui(f: &mut Frame, app: &mut App) {
 
     // Header
     let date_str = app.view_date.format("%a %b %d").to_string();
-    let header_text = format!(" Xiaolong's Eisenhower Quadrants   {}    [?] ", date_str);
+    let mut header_text = if app.compact {
+        format!(" eq  {}  [v] {} ", date_str, app.visibility.label())
+    } else {
+        format!(
+            " {}   {}   [v] {}   [?] ",
+            tr("tui.title", &[]),
+            date_str,
+            app.visibility.label()
+        )
+    };
+    if let Some(timer) = pomodoro_label(app) {
+        header_text.push_str(&format!("  {} ", timer));
+    }
+    let (progress, warning) = app.header_stats();
+    if progress.planned > 0 {
+        header_text.push_str(&format!("  {} ", progress_label(&progress, app.compact)));
+    }
+    if let Some(filter) = &app.filter {
+        header_text.push_str(&format!("  [/] {} ", truncate_to_width(filter.query(), 24)));
+    }
+    if app.sort_mode != SortMode::Score {
+        header_text.push_str(&format!("  [o] by {} ", app.sort_mode.label()));
+    }
+    let mut header_style = Style::default();
+
+    // Warn when the day holds more than is usually finished on this weekday
+    if let Some(warning) = warning {
+        header_text.push_str(&format!("  {} ", warning));
+        header_style = header_style.fg(app.theme.danger);
+    }
 
     let header = Paragraph::new(header_text)
+        .style(header_style)
         .block(Block::default().borders(Borders::ALL))
         .alignment(Alignment::Center);
     f.render_widget(header, chunks[0]);
 
-    // Main Matrix (2x2)
+    if app.layout == ViewLayout::List {
+        render_task_list(f, chunks[1], app);
+    } else if app.layout == ViewLayout::Kanban {
+        render_kanban(f, chunks[1], app);
+    } else if app.compact {
+        render_compact_matrix(f, chunks[1], app);
+    } else {
+        render_matrix(f, chunks[1], app);
+    }
+
+    // Footer / Input
+    render_footer(f, chunks[2], app);
+
+    if let Some(picker) = &app.date_picker {
+        render_date_picker(f, picker, app);
+    }
+}
+
+fn render_command_palette(
+    f: &mut Frame,
+    line: &str,
+    error: Option<&str>,
+    theme: &Theme,
+    area: Rect,
+) {
+    // Errors replace the completion hints until the next keystroke
+    let (hint, hint_color) = match error {
+        Some(err) => (err.to_string(), theme.danger),
+        None => (complete(line).join("  "), theme.muted),
+    };
+
+    let palette = Paragraph::new(Line::from(vec![
+        Span::styled(format!(":{}", line), Style::default().fg(theme.accent)),
+        Span::styled(format!("   {}", hint), Style::default().fg(hint_color)),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(" Command "));
+    f.render_widget(palette, area);
+
+    let x = area.x + 2 + display_width(line) as u16;
+    f.set_cursor_position((x.min(area.right() - 2), area.y + 1));
+}
+
+/// Status message in the bottom-right corner, above the footer
+/// Scrollable list of every keybinding, drawn over any screen
+fn render_help(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(70, 80, f.area());
+    let heading = Style::default()
+        .fg(app.theme.highlight)
+        .add_modifier(Modifier::BOLD);
+    let group = Style::default()
+        .fg(app.theme.accent)
+        .add_modifier(Modifier::BOLD);
+
+    let lines: Vec<Line> = help_lines()
+        .into_iter()
+        .map(|line| match line {
+            HelpLine::Screen(id) => {
+                Line::from(Span::styled(format!("── {} ──", tr(id, &[])), heading))
+            }
+            HelpLine::Group(id) => Line::from(Span::styled(format!("{}:", tr(id, &[])), group)),
+            HelpLine::Binding(b) => Line::from(format!("  {:<17}{}", b.keys, b.action)),
+            HelpLine::Blank => Line::from(""),
+        })
+        .collect();
+
+    // Keep the last page full instead of scrolling into blank space
+    let max_scroll = lines
+        .len()
+        .saturating_sub(area.height.saturating_sub(2) as usize);
+    app.help_scroll = app.help_scroll.min(max_scroll as u16);
+
+    let popup = Paragraph::new(lines)
+        .style(Style::default().fg(app.theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(tr("help.title", &[]))
+                .title_style(heading)
+                .title_bottom(Line::from(tr("help.footer", &[])).right_aligned())
+                .border_style(Style::default().fg(app.theme.muted)),
+        )
+        .scroll((app.help_scroll, 0));
+
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// The selected task's fields and how its score and quadrant come out
+fn render_detail(f: &mut Frame, task: &Task, app: &App) {
+    let area = centered_rect(70, 50, f.area());
+    let label = Style::default().fg(app.theme.muted);
+    let row = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<10}", name), label),
+            Span::raw(value),
+        ])
+    };
+
+    let breakdown = task.explain(app.today);
+    let mut lines = vec![
+        row("Quadrant", breakdown.quadrant_rule()),
+        row("Score", breakdown.summary()),
+        row("Urgency", breakdown.urgency_source()),
+        row("Aging", breakdown.aging().to_string()),
+    ];
+    let carried = app.carried_days(task);
+    if carried > 0 {
+        lines.push(row("Put off", format!("{} days", carried)));
+    }
+    if let Some(minutes) = task.estimate_minutes {
+        lines.push(row("Estimate", format!("{} min", minutes)));
+    }
+    if let Some(link) = &task.link {
+        lines.push(row("Link", link.clone()));
+    }
+
+    let popup = Paragraph::new(lines)
+        .style(Style::default().fg(app.theme.text))
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", task.title))
+                .title_style(
+                    Style::default()
+                        .fg(app.theme.highlight)
+                        .add_modifier(Modifier::BOLD),
+                )
+                .title_bottom(Line::from(" Esc close ").right_aligned())
+                .border_style(Style::default().fg(app.theme.muted)),
+        );
+
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// Warning across the top row while changes can't be written, until a
+/// save succeeds
+fn render_save_banner(f: &mut Frame, err: &str, theme: &Theme) {
+    let screen = f.area();
+    let area = Rect {
+        height: 1.min(screen.height),
+        ..screen
+    };
+    let text = format!(
+        " Not saved: {} | changes are kept and retried; on exit you can save them elsewhere ",
+        err
+    );
+    let banner = Paragraph::new(truncate_to_width(&text, area.width as usize))
+        .style(
+            Style::default()
+                .fg(theme.danger)
+                .add_modifier(Modifier::REVERSED | Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+    f.render_widget(Clear, area);
+    f.render_widget(banner, area);
+}
+
+/// `--timings` debug footer on the bottom row: the last frame, load, and
+/// save times
+fn render_timings(f: &mut Frame, app: &App) {
+    let screen = f.area();
+    let mut parts = vec![format!("{} tasks", app.store.tasks.len())];
+    for (name, counter) in [
+        ("frame", &timings::FRAMES),
+        ("load", &timings::LOADS),
+        ("save", &timings::SAVES),
+    ] {
+        if let Some(reading) = counter.read() {
+            parts.push(format!("{} {}", name, timings::millis(reading.last)));
+        }
+    }
+    let text = format!(" {} ", parts.join(" · "));
+    let width = (display_width(&text) as u16).min(screen.width);
+    let area = Rect {
+        x: screen.x,
+        y: screen.bottom().saturating_sub(1),
+        width,
+        height: 1.min(screen.height),
+    };
+    let widget = Paragraph::new(text).style(Style::default().fg(app.theme.muted));
+    f.render_widget(Clear, area);
+    f.render_widget(widget, area);
+}
+
+fn render_toast(f: &mut Frame, toast: &Toast, theme: &Theme) {
+    let screen = f.area();
+    let width = (display_width(&toast.message).min(60) as u16 + 4).min(screen.width);
+    let height = 3.min(screen.height);
+    let area = Rect {
+        x: screen.right().saturating_sub(width + 1).max(screen.x),
+        y: screen.bottom().saturating_sub(height + 3).max(screen.y),
+        width,
+        height,
+    };
+
+    let color = if toast.is_error {
+        theme.danger
+    } else {
+        theme.success
+    };
+    let text = truncate_to_width(&toast.message, width.saturating_sub(4) as usize);
+    let widget = Paragraph::new(text)
+        .style(Style::default().fg(theme.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(widget, area);
+}
+
+/// Yes/no popup for a destructive action
+fn render_confirm(f: &mut Frame, prompt: &str, theme: &Theme) {
+    let screen = f.area();
+    let width = (display_width(prompt).min(80) as u16 + 6)
+        .max(30)
+        .min(screen.width);
+    let height = 5.min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    let text = vec![
+        Line::from(Span::styled(
+            truncate_to_width(prompt, width.saturating_sub(4) as usize),
+            Style::default().fg(theme.text).add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y]", Style::default().fg(theme.danger)),
+            Span::styled(" yes   ", Style::default().fg(theme.text)),
+            Span::styled("[n]", Style::default().fg(theme.success)),
+            Span::styled(" no", Style::default().fg(theme.text)),
+        ]),
+    ];
+    let popup = Paragraph::new(text).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.danger))
+            .title(" Confirm "),
+    );
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// `/` prompt with the number of matching tasks
+fn render_filter_prompt(f: &mut Frame, editor: &LineEditor, app: &App, area: Rect) {
+    let hint = format!(
+        "   {} shown · #tag  >=score  Enter keep  Esc clear",
+        app.shown_count()
+    );
+    let room = (area.width as usize)
+        .saturating_sub(3 + display_width(&hint))
+        .max(10);
+    let (visible, cursor) = editor.view(room);
+
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("/{}", visible),
+            Style::default().fg(app.theme.accent),
+        ),
+        Span::styled(hint, Style::default().fg(app.theme.muted)),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(" Filter "));
+    f.render_widget(prompt, area);
+
+    let x = area.x + 2 + cursor as u16;
+    f.set_cursor_position((x.min(area.right() - 2), area.y + 1));
+}
+
+fn render_tag_prompt(f: &mut Frame, editor: &LineEditor, app: &App, area: Rect) {
+    let count = app.marked_tasks.len().max(1);
+    let hint = format!(
+        "   {} task(s) · replaces their tags  Enter apply  Esc cancel",
+        count
+    );
+    let room = (area.width as usize)
+        .saturating_sub(3 + display_width(&hint))
+        .max(10);
+    let (visible, cursor) = editor.view(room);
+
+    let prompt = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("#{}", visible),
+            Style::default().fg(app.theme.accent),
+        ),
+        Span::styled(hint, Style::default().fg(app.theme.muted)),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(" Tags "));
+    f.render_widget(prompt, area);
+
+    let x = area.x + 2 + cursor as u16;
+    f.set_cursor_position((x.min(area.right() - 2), area.y + 1));
+}
+
+fn render_date_picker(f: &mut Frame, picker: &DatePicker, app: &App) {
+    let screen = f.area();
+    let width = CALENDAR_WIDTH.min(screen.width);
+    let height = CALENDAR_HEIGHT.min(screen.height);
+    let area = Rect {
+        x: screen.x + (screen.width - width) / 2,
+        y: screen.y + (screen.height - height) / 2,
+        width,
+        height,
+    };
+
+    // Pending tasks per day for the count markers
+    let mut counts: HashMap<NaiveDate, usize> = HashMap::new();
+    for task in &app.store.tasks {
+        if task.status == TaskStatus::Pending {
+            *counts.entry(task.date).or_default() += 1;
+        }
+    }
+
+    let widget = CalendarWidget {
+        selected: picker.selected,
+        today: app.store.today(),
+        counts: &counts,
+        title: picker.title(),
+        theme: &app.theme,
+    };
+    f.render_widget(Clear, area);
+    f.render_widget(widget, area);
+}
+
+/// The 2x2 quadrant grid
+fn render_matrix(f: &mut Frame, area: Rect, app: &mut App) {
     let matrix_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-        .split(chunks[1]);
+        .split(area);
 
     let top_row = Layout::default()
         .direction(Direction::Horizontal)
@@ -65,96 +531,187 @@ pub fn ui(f: &mut Frame, app: &mut App) {
         .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
         .split(matrix_chunks[1]);
 
-    // Filter tasks for current view
-    let tasks: Vec<_> = app
-        .store
-        .tasks
+    // Fix #3: Use QuadrantWidget for rendering
+    render_quadrant(f, Quadrant::DoFirst, top_row[0], app);
+    render_quadrant(f, Quadrant::Schedule, top_row[1], app);
+    render_quadrant(f, Quadrant::Delegate, bottom_row[0], app);
+    render_quadrant(f, Quadrant::Drop, bottom_row[1], app);
+}
+
+/// One quadrant at a time under a tab bar, for narrow or short terminals
+fn render_compact_matrix(f: &mut Frame, area: Rect, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+        .split(area);
+
+    let titles: Vec<String> = Quadrant::ALL
         .iter()
-        .filter(|t| t.date == app.view_date && t.status != TaskStatus::Dropped)
+        .map(|q| {
+            format!(
+                "{}{} {}",
+                app.theme.quadrant_marker(*q),
+                q,
+                app.quadrant_tasks(*q).len()
+            )
+        })
         .collect();
+    let selected = Quadrant::ALL
+        .iter()
+        .position(|q| *q == app.selected_quadrant)
+        .unwrap_or(0);
+    let tabs = Tabs::new(titles)
+        .select(selected)
+        .style(Style::default().fg(app.theme.muted))
+        .highlight_style(
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .divider("│");
+    f.render_widget(tabs, chunks[0]);
 
-    // Fix #3: Use QuadrantWidget for rendering
-    render_quadrant(f, Quadrant::DoFirst, top_row[0], &tasks, app);
-    render_quadrant(f, Quadrant::Schedule, top_row[1], &tasks, app);
-    render_quadrant(f, Quadrant::Delegate, bottom_row[0], &tasks, app);
-    render_quadrant(f, Quadrant::Drop, bottom_row[1], &tasks, app);
+    render_quadrant(f, app.selected_quadrant, chunks[1], app);
+}
 
-    // Footer / Input
-    if app.input_mode {
-        let input = Paragraph::new(format!("Add Task: {}", app.input_buffer))
-            .style(Style::default().fg(Color::Yellow))
+/// Input bar, prompts, or the key hint line under the matrix
+fn render_footer(f: &mut Frame, area: Rect, app: &App) {
+    if let Some(line) = &app.command_input {
+        render_command_palette(f, line, app.command_error.as_deref(), &app.theme, area);
+    } else if let Some(editor) = &app.filter_input {
+        render_filter_prompt(f, editor, app, area);
+    } else if let Some(editor) = &app.tag_input {
+        render_tag_prompt(f, editor, app, area);
+    } else if app.input_mode {
+        // Scroll long input sideways so the cursor stays in view
+        let room = (area.width as usize).saturating_sub(13);
+        let (visible, cursor) = app.input_buffer.view(room);
+        let input = Paragraph::new(format!("Add Task: {}", visible))
+            .style(Style::default().fg(app.theme.accent))
             .block(Block::default().borders(Borders::ALL).title(" Input "));
-        f.render_widget(input, chunks[2]);
+        f.render_widget(input, area);
 
         // Show cursor for input
-        let x = chunks[2].x + 11 + app.input_buffer.len() as u16;
-        let y = chunks[2].y + 1;
-        f.set_cursor_position((x.min(chunks[2].right() - 2), y));
+        let x = area.x + 11 + cursor as u16;
+        let y = area.y + 1;
+        f.set_cursor_position((x.min(area.right() - 2), y));
+    } else if app.move_pending {
+        let prompt = Paragraph::new(
+            "MOVE TASK  [←/h] urgent  [→/l] not urgent  [↑/k] important  [↓/j] not important",
+        )
+        .style(Style::default().fg(app.theme.accent))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+        f.render_widget(prompt, area);
+    } else if app.has_marked() || app.visual_anchor.is_some() {
+        // Multi-selection count and batch actions
+        let mode = if app.visual_anchor.is_some() {
+            "VISUAL  [V]select range  "
+        } else {
+            ""
+        };
+        let status = Paragraph::new(format!(
+            "{}{} selected  [d]one  [x]drop  [#]tags  [>]tomorrow  [M]move to date  [Space]toggle  [Esc]clear",
+            mode,
+            app.marked_tasks.len()
+        ))
+        .style(Style::default().fg(app.theme.accent))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+        f.render_widget(status, area);
+    } else if let Some(overload) = app.do_first_overload() {
+        let nudge = Paragraph::new(format!("⚠ {}  [D] walk through them", overload.nudge()))
+            .style(Style::default().fg(app.theme.danger))
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::TOP));
+        f.render_widget(nudge, area);
     } else {
-        let help = Paragraph::new("[a]dd  [d]one  [x]drop  [↑↓]nav  [tab]quadrant  [?]help  [q]uit")
-            .style(Style::default().fg(Color::DarkGray))
+        let hint = tr(
+            if app.compact {
+                "tui.hint.compact"
+            } else {
+                "tui.hint"
+            },
+            &[],
+        );
+        let help = Paragraph::new(hint)
+            .style(Style::default().fg(app.theme.muted))
             .alignment(Alignment::Center)
             .block(Block::default().borders(Borders::TOP));
-        f.render_widget(help, chunks[2]);
+        f.render_widget(help, area);
     }
+}
 
-    // Keyboard Shortcuts Help
-    if app.show_help {
-        let area = centered_rect(70, 80, f.area());
-
-        let help_text = vec![
-            Line::from(Span::styled("Navigation:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
-            Line::from("  ↑↓ j k          Navigate tasks"),
-            Line::from("  ← → h l          Switch columns"),
-            Line::from("  Tab              Cycle quadrants"),
-            Line::from("  PgUp/PgDn        Jump 5 items"),
-            Line::from(""),
-            Line::from(Span::styled("Task Actions:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
-            Line::from("  a                Add new task"),
-            Line::from("  e                Edit selected task"),
-            Line::from("  d / Enter        Toggle task done"),
-            Line::from("  x                Drop (delete) task"),
-            Line::from("  >  .             Move task to tomorrow"),
-            Line::from(""),
-            Line::from(Span::styled("View Controls:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
-            Line::from("  t                Toggle tomorrow view"),
-            Line::from("  y                View yesterday"),
-            Line::from(""),
-            Line::from(Span::styled("Special Modes:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
-            Line::from("  z                Enter zen/focus mode"),
-            Line::from("  c                Open AI chat"),
-            Line::from(""),
-            Line::from(Span::styled("Other:", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD))),
-            Line::from("  ?                Toggle this help"),
-            Line::from("  q                Quit"),
-        ];
+/// All of the day's tasks in one ranked list
+fn render_task_list(f: &mut Frame, area: Rect, app: &App) {
+    let selected_idx = if app.input_mode {
+        None
+    } else {
+        Some(app.selected_task_index)
+    };
+    let title = match app.layout {
+        ViewLayout::Kanban => app.kanban_column.title().to_string(),
+        _ => format!("PRIORITY QUEUE · by {}", app.sort_mode.label()),
+    };
+    let widget = TaskListWidget::new(app.current_tasks(), &title, selected_idx)
+        .marked(&app.marked_tasks)
+        .numbered(!app.input_mode)
+        .theme(&app.theme);
+    f.render_widget(widget, area);
+}
 
-        let popup = Paragraph::new(help_text)
-            .style(Style::default().fg(Color::White))
-            .block(Block::default().borders(Borders::ALL).title(" Keyboard Shortcuts ")
-                .title_style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)))
-            .alignment(Alignment::Left);
+/// Status columns side by side, or the selected one under tabs when compact
+fn render_kanban(f: &mut Frame, area: Rect, app: &mut App) {
+    app.refresh_focused();
+
+    if app.compact {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)].as_ref())
+            .split(area);
+        let titles: Vec<String> = KanbanColumn::ALL
+            .iter()
+            .map(|c| format!("{} {}", c.title(), app.kanban_tasks(*c).len()))
+            .collect();
+        let tabs = Tabs::new(titles)
+            .select(app.kanban_column.index())
+            .style(Style::default().fg(app.theme.muted))
+            .highlight_style(
+                Style::default()
+                    .fg(app.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .divider("│");
+        f.render_widget(tabs, chunks[0]);
+        render_task_list(f, chunks[1], app);
+        return;
+    }
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 4); 4].as_ref())
+        .split(area);
 
-        f.render_widget(Clear, area);
-        f.render_widget(popup, area);
+    for (column, col_area) in KanbanColumn::ALL.iter().zip(columns.iter()) {
+        let active = *column == app.kanban_column;
+        let selected_idx = if active && !app.input_mode {
+            Some(app.selected_task_index)
+        } else {
+            None
+        };
+        let tasks = app.kanban_tasks(*column);
+        let title = format!("{} ({})", column.title(), tasks.len());
+        let widget = TaskListWidget::new(tasks, &title, selected_idx)
+            .active(active)
+            .marked(&app.marked_tasks)
+            .numbered(selected_idx.is_some())
+            .theme(&app.theme);
+        f.render_widget(widget, *col_area);
     }
 }
 
 /// Fix #3: Refactored to use QuadrantWidget
-fn render_quadrant(
-    f: &mut Frame,
-    q: Quadrant,
-    area: Rect,
-    all_tasks: &[&crate::models::task::Task],
-    app: &App,
-) {
-    let mut q_tasks: Vec<_> = all_tasks
-        .iter()
-        .filter(|t| t.quadrant() == q)
-        .cloned()
-        .collect();
-    q_tasks.sort_by_key(|t| std::cmp::Reverse(t.score()));
-
+fn render_quadrant(f: &mut Frame, q: Quadrant, area: Rect, app: &mut App) {
     let is_active = app.selected_quadrant == q && !app.input_mode;
     let selected_idx = if is_active {
         Some(app.selected_task_index)
@@ -162,7 +719,27 @@ fn render_quadrant(
         None
     };
 
-    let widget = QuadrantWidget::new(q_tasks, is_active, q, selected_idx);
+    let len = app.quadrant_tasks(q).len();
+    let viewport = area.height.saturating_sub(2) as usize;
+    let offset = app
+        .scroll
+        .entry(q)
+        .or_default()
+        .sync(selected_idx, len, viewport);
+
+    let over_limit = wip_overflow(app.store.tasks_on(app.view_date), q, &app.config.wip_limits);
+    let q_tasks = app.quadrant_tasks(q);
+    let carried = q_tasks
+        .iter()
+        .map(|t| (t.id, app.carried_days(t)))
+        .collect();
+    let widget = QuadrantWidget::new(q_tasks, is_active, q, selected_idx)
+        .marked(&app.marked_tasks)
+        .over_limit(over_limit)
+        .carried(carried)
+        .scroll(offset)
+        .numbered(is_active)
+        .theme(&app.theme);
     f.render_widget(widget, area);
 }
 
@@ -175,9 +752,20 @@ fn render_chat(f: &mut Frame, app: &mut App) {
         .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
         .split(area);
 
-    let block = Block::default()
-        .borders(Borders::ALL)
-        .title("Chat with eq (Esc to close) ");
+    let withheld = match app.chat_withheld {
+        0 => String::new(),
+        1 => String::from(" · 1 task withheld"),
+        n => format!(" · {} tasks withheld", n),
+    };
+    let title = match &app.ai_client {
+        Ok(client) => format!(
+            "Chat with eq · {}{} (Esc to close) ",
+            client.provider_name(),
+            withheld
+        ),
+        Err(_) => String::from("Chat with eq (Esc to close) "),
+    };
+    let block = Block::default().borders(Borders::ALL).title(title);
     f.render_widget(block, area);
 
     // Messages area
@@ -190,18 +778,23 @@ fn render_chat(f: &mut Frame, app: &mut App) {
     let width = messages_area.width as usize;
     let mut lines: Vec<Line> = Vec::new();
 
-    for msg in &app.chat_history {
+    let mut selected_line = None;
+    for (i, msg) in app.chat_history.iter().enumerate() {
         let (role, color) = if msg.role == "user" {
-            ("You", Color::Yellow)
+            ("You", app.theme.accent)
         } else {
-            ("eq", Color::Cyan)
+            ("eq", app.theme.highlight)
         };
 
-        // Role header
-        lines.push(Line::from(Span::styled(
-            format!("{}:", role),
-            Style::default().fg(color).add_modifier(Modifier::BOLD),
-        )));
+        // Role header, highlighted while the message is picked for copying
+        let mut header_style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+        let mut header = format!("{}:", role);
+        if app.chat_selected == Some(i) {
+            selected_line = Some(lines.len() as u16);
+            header_style = header_style.add_modifier(Modifier::REVERSED);
+            header.push_str(" (Y to copy)");
+        }
+        lines.push(Line::from(Span::styled(header, header_style)));
 
         // Wrap content
         let wrapped = textwrap::wrap(&msg.content, width.saturating_sub(2));
@@ -211,12 +804,23 @@ fn render_chat(f: &mut Frame, app: &mut App) {
         lines.push(Line::from("")); // Spacing
     }
 
+    // The reply so far, while it streams in
+    if app.is_loading && !app.chat_partial.is_empty() {
+        let style = Style::default()
+            .fg(app.theme.highlight)
+            .add_modifier(Modifier::BOLD);
+        lines.push(Line::from(Span::styled("eq:", style)));
+        for line in textwrap::wrap(&app.chat_partial, width.saturating_sub(2)) {
+            lines.push(Line::from(Span::raw(format!("  {}", line))));
+        }
+    }
+
     if app.is_loading {
         let frames = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
         let frame = frames[app.spinner_state as usize % frames.len()];
         lines.push(Line::from(Span::styled(
-            format!("{} Gently minding your business...", frame),
-            Style::default().fg(Color::Green),
+            format!("{} Gently minding your business... (Esc to cancel)", frame),
+            Style::default().fg(app.theme.success),
         )));
     }
 
@@ -229,12 +833,17 @@ fn render_chat(f: &mut Frame, app: &mut App) {
         0
     };
 
-    if app.chat_auto_scroll {
-        app.chat_scroll = max_scroll;
-    } else if app.chat_scroll > max_scroll {
+    if app.chat_auto_scroll || app.chat_scroll > max_scroll {
         app.chat_scroll = max_scroll;
     }
 
+    // Keep the picked message's header in view
+    if let Some(line) = selected_line {
+        if line < app.chat_scroll || line >= app.chat_scroll + height as u16 {
+            app.chat_scroll = line.min(max_scroll);
+        }
+    }
+
     let messages = Paragraph::new(lines).scroll((app.chat_scroll, 0));
     f.render_widget(messages, messages_area);
 
@@ -250,7 +859,7 @@ fn render_chat(f: &mut Frame, app: &mut App) {
         } else {
             format!("{}%", scroll_pct)
         };
-        let indicator_span = Span::styled(indicator, Style::default().fg(Color::DarkGray));
+        let indicator_span = Span::styled(indicator, Style::default().fg(app.theme.muted));
         let x = messages_area.right().saturating_sub(6);
         let y = messages_area.top();
         f.buffer_mut().set_span(x, y, &indicator_span, 6);
@@ -261,63 +870,26 @@ fn render_chat(f: &mut Frame, app: &mut App) {
         vertical: 0,
         horizontal: 1,
     });
-    let input_block = Block::default()
-        .borders(Borders::TOP)
-        .title(" Message (PgUp/PgDn to scroll, Ctrl+L clear) ");
-
-    // Calculate scroll for input to keep cursor visible
-    let width = input_area.width as usize;
-    let len = app.chat_input.len();
-    let scroll_h = if len >= width {
-        (len - width + 1) as u16
+    let hint = if app.chat_selected.is_some() {
+        " ↑↓ pick a message, Y to copy, Esc to go back "
     } else {
-        0
+        " Message (PgUp/PgDn to scroll, ↑ to copy, Ctrl+L clear) "
     };
+    let input_block = Block::default().borders(Borders::TOP).title(hint);
 
-    let input = Paragraph::new(app.chat_input.as_str())
-        .style(Style::default().fg(Color::White))
-        .block(input_block)
-        .scroll((0, scroll_h));
+    // Scroll long input sideways so the cursor stays visible
+    let width = input_area.width as usize;
+    let (visible, cursor) = app.chat_input.view(width.saturating_sub(1));
+
+    let input = Paragraph::new(visible)
+        .style(Style::default().fg(app.theme.text))
+        .block(input_block);
     f.render_widget(input, input_area);
 
     // Show cursor in chat input
-    // Adjust cursor position based on scroll
-    let cursor_visual_x = (len as u16).saturating_sub(scroll_h);
-    let cursor_x = input_area.x + cursor_visual_x;
+    let cursor_x = input_area.x + cursor as u16;
     let cursor_y = input_area.y + 1;
     f.set_cursor_position((cursor_x.min(input_area.right() - 1), cursor_y));
-
-    // Chat help overlay
-    if app.show_chat_help {
-        let help_area = centered_rect(50, 40, f.area());
-        f.render_widget(Clear, help_area);
-
-        let help_text = vec![
-            Line::from(Span::styled(
-                "Chat Keyboard Shortcuts",
-                Style::default().add_modifier(Modifier::BOLD),
-            )),
-            Line::from(""),
-            Line::from("PgUp/PgDn    Scroll history"),
-            Line::from("Ctrl+K/J     Scroll one line"),
-            Line::from("Home         Jump to top"),
-            Line::from("End          Resume auto-scroll"),
-            Line::from("Ctrl+L       Clear chat history"),
-            Line::from("Ctrl+W       Delete word"),
-            Line::from("Ctrl+U       Clear input"),
-            Line::from("Esc          Close chat"),
-            Line::from(""),
-            Line::from(Span::styled(
-                "Press ? to close",
-                Style::default().fg(Color::DarkGray),
-            )),
-        ];
-
-        let help = Paragraph::new(help_text)
-            .block(Block::default().borders(Borders::ALL).title(" Help "))
-            .alignment(Alignment::Left);
-        f.render_widget(help, help_area);
-    }
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -361,51 +933,38 @@ fn render_focus(f: &mut Frame, app: &mut App) {
 
     // Header
     let quadrant_name = match app.selected_quadrant {
-        Quadrant::DoFirst => "DO NOW - Urgent & Important",
-        Quadrant::Schedule => "SCHEDULE - Important, Not Urgent",
-        Quadrant::Delegate => "DELEGATE - Urgent, Not Important",
-        Quadrant::Drop => "ELIMINATE - Neither Urgent nor Important",
+        _ if app.layout == ViewLayout::List => tr("focus.all", &[]),
+        _ if app.layout == ViewLayout::Kanban => app.kanban_column.title().to_string(),
+        Quadrant::DoFirst => tr("focus.do_first", &[]),
+        Quadrant::Schedule => tr("focus.schedule", &[]),
+        Quadrant::Delegate => tr("focus.delegate", &[]),
+        Quadrant::Drop => tr("focus.drop", &[]),
     };
 
-    let header = Paragraph::new(format!(
-        " FOCUS MODE: {}   [z] Zen Mode  [Esc] Exit ",
-        quadrant_name
-    ))
-    .style(
-        Style::default()
-            .fg(Color::Yellow)
-            .add_modifier(Modifier::BOLD),
-    )
-    .block(Block::default().borders(Borders::ALL))
-    .alignment(Alignment::Center);
+    let mut header_text = tr("focus.header", &[&quadrant_name, &app.visibility.label()]);
+    if let Some(timer) = pomodoro_label(app) {
+        header_text.push_str(&format!("  {} ", timer));
+    }
+    let header = Paragraph::new(header_text)
+        .style(
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center);
     f.render_widget(header, chunks[0]);
 
     // Quadrant content (full screen)
-    let tasks: Vec<_> = app
-        .store
-        .tasks
-        .iter()
-        .filter(|t| t.date == app.view_date && t.status != TaskStatus::Dropped)
-        .collect();
-
-    let mut q_tasks: Vec<_> = tasks
-        .iter()
-        .filter(|t| t.quadrant() == app.selected_quadrant)
-        .cloned()
-        .collect();
-    q_tasks.sort_by_key(|t| std::cmp::Reverse(t.score()));
-
-    let widget = QuadrantWidget::new(
-        q_tasks,
-        true,
-        app.selected_quadrant,
-        Some(app.selected_task_index),
-    );
-    f.render_widget(widget, chunks[1]);
+    if app.layout != ViewLayout::Matrix {
+        render_task_list(f, chunks[1], app);
+    } else {
+        render_quadrant(f, app.selected_quadrant, chunks[1], app);
+    }
 
     // Footer
-    let footer = Paragraph::new("[↑↓]navigate  [d/Enter]done  [x]drop  [z]zen  [Esc]exit")
-        .style(Style::default().fg(Color::DarkGray))
+    let footer = Paragraph::new("[↑↓]navigate  [d/Enter]done  [x]drop  [u]ndo  [z]zen  [Esc]exit")
+        .style(Style::default().fg(app.theme.muted))
         .alignment(Alignment::Center)
         .block(Block::default().borders(Borders::TOP));
     f.render_widget(footer, chunks[2]);
@@ -415,29 +974,20 @@ fn render_zen(f: &mut Frame, app: &mut App) {
     // Initialize zen state if needed
     let area = f.area();
     if app.zen_state.is_none() {
-        app.zen_state = Some(ZenState::new(area.width, area.height, 25)); // 25 min pomodoro
+        app.zen_state = Some(ZenState::new(area.width, area.height, app.motion));
+    }
+    if app.pomodoro.is_none() {
+        app.start_pomodoro();
     }
 
     // Update and render zen state (particles and pomodoro)
     if let Some(ref mut zen_state) = app.zen_state {
-        zen_state.update(area.width, area.height);
-        zen_state.render(area, f.buffer_mut());
+        zen_state.update(area.width, area.height, app.pomodoro.as_ref());
+        zen_state.render(area, f.buffer_mut(), app.pomodoro.as_ref(), &app.theme);
     }
 
     // Get the current task
-    let tasks: Vec<_> = app
-        .store
-        .tasks
-        .iter()
-        .filter(|t| {
-            t.date == app.view_date
-                && t.status != TaskStatus::Dropped
-                && t.quadrant() == app.selected_quadrant
-        })
-        .collect();
-
-    let mut sorted_tasks = tasks.clone();
-    sorted_tasks.sort_by_key(|t| std::cmp::Reverse(t.score()));
+    let sorted_tasks = app.current_tasks();
 
     let current_task = if app.selected_task_index < sorted_tasks.len() {
         Some(sorted_tasks[app.selected_task_index])
@@ -452,12 +1002,12 @@ fn render_zen(f: &mut Frame, app: &mut App) {
         // Task title style - add strikethrough if completed
         let title_style = if task.status == TaskStatus::Completed {
             Style::default()
-                .fg(Color::Rgb(120, 120, 120))
+                .fg(app.theme.muted)
                 .add_modifier(Modifier::BOLD)
                 .add_modifier(Modifier::CROSSED_OUT)
         } else {
             Style::default()
-                .fg(Color::White)
+                .fg(app.theme.text)
                 .add_modifier(Modifier::BOLD)
         };
 
@@ -472,7 +1022,7 @@ fn render_zen(f: &mut Frame, app: &mut App) {
                     "Urgency: {}  •  Importance: {}",
                     task.urgency, task.importance
                 ),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(app.theme.muted),
             )),
             Line::from(""),
         ];
@@ -488,12 +1038,12 @@ fn render_zen(f: &mut Frame, app: &mut App) {
             Line::from(""),
             Line::from(Span::styled(
                 "No tasks in this quadrant.",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(app.theme.muted),
             )),
             Line::from(""),
             Line::from(Span::styled(
                 "Press Esc to return.",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(app.theme.accent),
             )),
         ];
 
@@ -501,3 +1051,678 @@ fn render_zen(f: &mut Frame, app: &mut App) {
         f.render_widget(empty_display, task_area);
     }
 }
+
+/// Countdown of a pomodoro or break still running outside Zen mode
+fn pomodoro_label(app: &App) -> Option<String> {
+    let pomo = app.pomodoro.as_ref().filter(|p| !p.is_complete())?;
+    let kind = if pomo.is_break { "break " } else { "" };
+    Some(format!("◷ {}{}", kind, pomo.format_remaining()))
+}
+
+/// `3/5 ████░░░░ 60% · 1h 15m left`, or just `3/5 60%` when compact
+fn progress_label(progress: &DayProgress, compact: bool) -> String {
+    let percent = progress.percent();
+    if compact {
+        return format!("{}/{} {}%", progress.done, progress.planned, percent);
+    }
+
+    const BAR_WIDTH: usize = 8;
+    let filled = (percent as usize * BAR_WIDTH + 50) / 100;
+    let mut label = format!(
+        "{}/{} {}{} {}%",
+        progress.done,
+        progress.planned,
+        "█".repeat(filled),
+        "░".repeat(BAR_WIDTH - filled),
+        percent
+    );
+    if progress.remaining_minutes > 0 {
+        label.push_str(&format!(
+            " · {} left",
+            format_duration(progress.remaining_minutes as u64 * 60)
+        ));
+    }
+    label
+}
+
+/// What a walkthrough screen shows around the task being decided on
+struct WalkthroughView<'a> {
+    /// Header text, followed by the progress
+    heading: String,
+    /// Lines between the header and the task, such as the week's outcomes
+    above: Vec<Line<'a>>,
+    /// Asked above the task's title
+    question: &'a str,
+    /// Line under the task's title, in its quadrant's color
+    detail: Option<fn(&Task) -> String>,
+    /// Shown under the task, such as the DO FIRST overload nudge
+    note: Option<String>,
+    /// The keys for the choices
+    keys: &'a str,
+    /// Shown in place of the task once the walkthrough is finished
+    summary: Vec<Line<'a>>,
+    summary_alignment: Alignment,
+    /// Footer once the walkthrough is finished
+    finished_footer: &'a str,
+}
+
+/// Shared layout of the end-of-day review, re-triage, and weekly review:
+/// a header with the progress, the task awaiting a choice with the keys
+/// for each, and a summary at the end
+fn render_walkthrough<C: Copy + PartialEq>(
+    f: &mut Frame,
+    app: &App,
+    walk: &Walkthrough<C>,
+    view: WalkthroughView,
+) {
+    let above = view.above.len() as u16;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),     // Header
+            Constraint::Length(above), // Context, if any
+            Constraint::Min(0),        // Task or summary
+            Constraint::Length(3),     // Footer
+        ])
+        .split(f.area());
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(app.theme.muted);
+
+    let (step, total) = walk.progress();
+    let progress = if walk.is_finished() {
+        String::from("done")
+    } else {
+        format!("{}/{}", step, total)
+    };
+    let header = Paragraph::new(format!(" {} · {} ", view.heading, progress))
+        .style(bold.fg(app.theme.accent))
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center);
+    f.render_widget(header, chunks[0]);
+    f.render_widget(
+        Paragraph::new(view.above).alignment(Alignment::Center),
+        chunks[1],
+    );
+
+    let (lines, alignment, footer) = if let Some(id) = walk.current() {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(Span::styled(view.question, muted)),
+            Line::from(""),
+        ];
+        match app.store.tasks.iter().find(|t| t.id == id) {
+            Some(task) => {
+                lines.push(Line::from(Span::styled(
+                    task.title.clone(),
+                    bold.fg(app.theme.text),
+                )));
+                if let Some(detail) = view.detail {
+                    lines.push(Line::from(""));
+                    lines.push(Line::from(Span::styled(
+                        detail(task),
+                        Style::default().fg(app.theme.quadrant(task.quadrant())),
+                    )));
+                }
+            }
+            None => lines.push(Line::from(Span::styled("(task no longer exists)", muted))),
+        }
+        lines.push(Line::from(""));
+        if let Some(note) = view.note {
+            lines.push(Line::from(Span::styled(note, muted)));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(view.keys));
+        (lines, Alignment::Center, "[Esc] finish early  [?] help")
+    } else {
+        (view.summary, view.summary_alignment, view.finished_footer)
+    };
+
+    let body = Paragraph::new(lines)
+        .alignment(alignment)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .block(Block::default().borders(Borders::NONE));
+    f.render_widget(body, centered_rect(70, 100, chunks[2]));
+
+    let footer = Paragraph::new(footer)
+        .style(muted)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+    f.render_widget(footer, chunks[3]);
+}
+
+fn render_review(f: &mut Frame, app: &App) {
+    let Some(review) = &app.review else {
+        return;
+    };
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(app.theme.muted);
+
+    let done: Vec<&str> = app
+        .store
+        .tasks
+        .iter()
+        .filter(|t| t.date == review.date && t.status == TaskStatus::Completed)
+        .map(|t| t.title.as_str())
+        .collect();
+    let tally = review.tally();
+    let mut summary = vec![Line::from(Span::styled(
+        format!("Completed ({})", done.len()),
+        bold.fg(app.theme.success),
+    ))];
+    if done.is_empty() {
+        summary.push(Line::from(Span::styled("  nothing finished today", muted)));
+    }
+    summary.extend(
+        done.iter()
+            .map(|title| Line::from(format!("  ✓ {}", title))),
+    );
+    summary.push(Line::from(""));
+    summary.push(Line::from(Span::styled(
+        "Reviewed",
+        bold.fg(app.theme.accent),
+    )));
+    for (label, count) in [
+        ("Moved to tomorrow", tally.tomorrow),
+        ("Rescheduled", tally.rescheduled),
+        ("Dropped", tally.dropped),
+        ("Kept", tally.kept),
+    ] {
+        summary.push(Line::from(format!("  {:<18}{}", label, count)));
+    }
+
+    let view = WalkthroughView {
+        heading: format!("END-OF-DAY REVIEW: {}", review.date.format("%a %b %d")),
+        above: Vec::new(),
+        question: "",
+        detail: Some(|task| {
+            let estimate = task
+                .estimate_minutes
+                .map(|m| format!("  ·  ~{}m", m))
+                .unwrap_or_default();
+            format!("{}  ·  score {}{}", task.quadrant(), task.score(), estimate)
+        }),
+        note: None,
+        keys: "[t] tomorrow    [r] reschedule    [x] drop    [k] keep",
+        summary,
+        summary_alignment: Alignment::Left,
+        finished_footer: "[Enter] back to the matrix",
+    };
+    render_walkthrough(f, app, review, view);
+
+    if let Some(picker) = &app.date_picker {
+        render_date_picker(f, picker, app);
+    }
+}
+
+fn render_day(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Planned and unplanned tasks
+            Constraint::Length(3), // Footer
+        ])
+        .split(f.area());
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(app.theme.muted);
+    let day = compare_day(&app.store.tasks, &app.day_events, app.view_date);
+
+    let header = Paragraph::new(format!(
+        " {}: {} ",
+        app.view_date.format("%a %b %d"),
+        day.summary()
+    ))
+    .style(bold.fg(app.theme.accent))
+    .block(Block::default().borders(Borders::ALL))
+    .alignment(Alignment::Center);
+    f.render_widget(header, chunks[0]);
+
+    let mut lines = vec![Line::from(Span::styled(
+        "Planned that morning",
+        bold.fg(app.theme.accent),
+    ))];
+    if day.planned.is_empty() {
+        lines.push(Line::from(Span::styled("  nothing was planned", muted)));
+    }
+    for (task, outcome) in &day.planned {
+        let (mark, note, color) = match outcome {
+            DayOutcome::Completed => ("✓", String::new(), app.theme.success),
+            DayOutcome::Dropped => ("✗", String::from("dropped"), app.theme.muted),
+            DayOutcome::Moved(to) => (
+                "→",
+                format!("moved to {}", to.format("%a %b %-d")),
+                app.theme.highlight,
+            ),
+            DayOutcome::Unfinished => ("·", String::from("left open"), app.theme.danger),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", mark), Style::default().fg(color)),
+            Span::styled(
+                task.title.as_str(),
+                Style::default().fg(app.theme.quadrant(task.quadrant())),
+            ),
+            Span::styled(format!("  {}", note), muted),
+        ]));
+    }
+    if !day.unplanned.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Done without being planned",
+            bold.fg(app.theme.accent),
+        )));
+        for task in &day.unplanned {
+            lines.push(Line::from(vec![
+                Span::styled("  + ", Style::default().fg(app.theme.success)),
+                Span::styled(
+                    task.title.as_str(),
+                    Style::default().fg(app.theme.quadrant(task.quadrant())),
+                ),
+            ]));
+        }
+    }
+
+    let body = Paragraph::new(lines)
+        .wrap(ratatui::widgets::Wrap { trim: false })
+        .block(Block::default().borders(Borders::NONE));
+    f.render_widget(body, centered_rect(70, 100, chunks[1]));
+
+    let footer = Paragraph::new("[←/→] previous / next day  [Esc] back to the matrix")
+        .style(muted)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+    f.render_widget(footer, chunks[2]);
+}
+
+fn render_triage(f: &mut Frame, app: &App) {
+    let Some(triage) = &app.triage else {
+        return;
+    };
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+
+    let mut summary = vec![Line::from(Span::styled(
+        "Re-triaged",
+        bold.fg(app.theme.accent),
+    ))];
+    for (label, choice) in [
+        ("Moved to SCHEDULE", TriageChoice::Schedule),
+        ("Moved to DELEGATE", TriageChoice::Delegate),
+        ("Dropped", TriageChoice::Dropped),
+        ("Kept in DO FIRST", TriageChoice::Kept),
+    ] {
+        summary.push(Line::from(format!(
+            "  {:<18}{}",
+            label,
+            triage.count(choice)
+        )));
+    }
+
+    let view = WalkthroughView {
+        heading: format!("RE-TRIAGE DO FIRST: {}", triage.date.format("%a %b %d")),
+        above: Vec::new(),
+        question: "",
+        detail: Some(|task| {
+            format!(
+                "U={}  I={}  ·  score {}",
+                task.urgency,
+                task.importance,
+                task.score()
+            )
+        }),
+        note: app.do_first_overload().map(|overload| overload.nudge()),
+        keys: "[s] not urgent    [d] not important    [x] drop    [k] keep",
+        summary,
+        summary_alignment: Alignment::Left,
+        finished_footer: "[Enter] back to the matrix",
+    };
+    render_walkthrough(f, app, triage, view);
+}
+
+fn render_weekly(f: &mut Frame, app: &App) {
+    let Some(review) = &app.weekly_review else {
+        return;
+    };
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(app.theme.muted);
+
+    let mut outcomes = vec![Line::from(Span::styled(
+        format!("{:<10}{:>6}{:>9}{:>6}", "", "done", "dropped", "open"),
+        muted,
+    ))];
+    for outcome in week_outcomes(&app.store.tasks, review.date) {
+        outcomes.push(Line::from(Span::styled(
+            format!(
+                "{:<10}{:>6}{:>9}{:>6}",
+                outcome.quadrant.to_string(),
+                outcome.completed.len(),
+                outcome.dropped.len(),
+                outcome.open.len()
+            ),
+            Style::default().fg(app.theme.quadrant(outcome.quadrant)),
+        )));
+    }
+    outcomes.push(Line::from(""));
+
+    let title = |id| {
+        app.store
+            .tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map_or("(task no longer exists)", |t| t.title.as_str())
+    };
+    let mut summary = vec![Line::from("")];
+    for (label, choice) in [
+        ("Delegate", WeeklyChoice::Delegate),
+        ("Drop", WeeklyChoice::Drop),
+    ] {
+        let ids = review.chosen(choice);
+        summary.push(Line::from(Span::styled(
+            format!("{} ({})", label, ids.len()),
+            bold.fg(app.theme.accent),
+        )));
+        summary.extend(
+            ids.into_iter()
+                .map(|id| Line::from(format!("  {}", title(id)))),
+        );
+        summary.push(Line::from(""));
+    }
+
+    let view = WalkthroughView {
+        heading: format!("WEEKLY REVIEW: {}", weekly_note_name(review.date)),
+        above: outcomes,
+        question: "Next week, this task should…",
+        detail: None,
+        note: None,
+        keys: "[k] keep    [d] delegate    [x] drop",
+        summary,
+        summary_alignment: Alignment::Center,
+        finished_footer: "[Enter] apply and write to journal  [Esc] discard",
+    };
+    render_walkthrough(f, app, review, view);
+}
+
+fn render_plan(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(2), // Capacity
+            Constraint::Min(0),    // Candidates
+            Constraint::Length(3), // Footer
+        ])
+        .split(f.area());
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let muted = Style::default().fg(app.theme.muted);
+
+    let header = Paragraph::new(format!(" PLAN: {} ", app.view_date.format("%a %b %d")))
+        .style(bold.fg(app.theme.accent))
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center);
+    f.render_widget(header, chunks[0]);
+
+    let capacity = Paragraph::new(capacity_line(&app.store.tasks, app.view_date))
+        .style(Style::default().fg(app.theme.highlight))
+        .alignment(Alignment::Center);
+    f.render_widget(capacity, chunks[1]);
+
+    let candidates = app.plan_candidates();
+    let mut lines = Vec::new();
+    let mut selected_row = 0;
+    let mut source = None;
+    for (i, c) in candidates.iter().enumerate() {
+        if source != Some(c.source) {
+            if source.is_some() {
+                lines.push(Line::from(""));
+            }
+            source = Some(c.source);
+            lines.push(Line::from(Span::styled(
+                c.source.label(),
+                bold.fg(app.theme.accent),
+            )));
+        }
+        let estimate = c
+            .task
+            .estimate_minutes
+            .map(|m| format!("  ~{}m", m))
+            .unwrap_or_default();
+        let mut style = Style::default().fg(app.theme.quadrant(c.task.quadrant()));
+        if i == app.plan_index {
+            selected_row = lines.len();
+            style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        }
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}  ", c.task.date.format("%a %b %d")), muted),
+            Span::styled(format!("{}{}", c.task.title, estimate), style),
+        ]));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "Nothing carried over, overdue, or coming up.",
+            muted,
+        )));
+    }
+
+    let area = centered_rect(80, 100, chunks[2]);
+    let offset = selected_row.saturating_sub(area.height.saturating_sub(1) as usize);
+    let body = Paragraph::new(lines).scroll((offset as u16, 0));
+    f.render_widget(body, area);
+
+    let footer =
+        Paragraph::new("[Enter] pull in  [x] drop  [t] today/tomorrow  [c] ask AI  [Esc] back")
+            .style(muted)
+            .alignment(Alignment::Center)
+            .block(Block::default().borders(Borders::TOP));
+    f.render_widget(footer, chunks[3]);
+}
+
+fn render_stats(f: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3), // Header
+                Constraint::Length(8), // Sparkline
+                Constraint::Min(0),    // Details
+                Constraint::Length(3), // Footer
+            ]
+            .as_ref(),
+        )
+        .split(f.area());
+
+    let header = Paragraph::new(" FOCUS STATISTICS ")
+        .style(
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .block(Block::default().borders(Borders::ALL))
+        .alignment(Alignment::Center);
+    f.render_widget(header, chunks[0]);
+
+    let footer = Paragraph::new("[Esc]back")
+        .style(Style::default().fg(app.theme.muted))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::TOP));
+    f.render_widget(footer, chunks[3]);
+
+    let Some(stats) = &app.focus_stats else {
+        return;
+    };
+
+    // Focus minutes per day, oldest on the left
+    let data: Vec<u64> = stats.per_day.iter().map(|(_, secs)| secs / 60).collect();
+    let title = match (stats.per_day.first(), stats.per_day.last()) {
+        (Some((first, _)), Some((last, _))) => format!(
+            " Focus minutes per day ({} - {}) ",
+            first.format("%b %d"),
+            last.format("%b %d")
+        ),
+        _ => String::from(" Focus minutes per day "),
+    };
+    let sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .data(&data)
+        .style(Style::default().fg(app.theme.success));
+    f.render_widget(sparkline, chunks[1]);
+
+    let label = Style::default()
+        .fg(app.theme.accent)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled("Sessions:         ", label),
+            Span::raw(stats.session_count.to_string()),
+        ]),
+        Line::from(vec![
+            Span::styled("Total focus:      ", label),
+            Span::raw(format_duration(stats.total_secs)),
+        ]),
+        Line::from(vec![
+            Span::styled("Longest session:  ", label),
+            Span::raw(format_duration(stats.longest_secs)),
+        ]),
+        Line::from(vec![
+            Span::styled("Sessions per done:", label),
+            Span::raw(
+                stats
+                    .avg_sessions_to_complete
+                    .map(|avg| format!(" {:.1}", avg))
+                    .unwrap_or_else(|| String::from(" -")),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled("By quadrant:", label)),
+    ];
+
+    for q in Quadrant::ALL {
+        let secs = stats.per_quadrant.get(&q).copied().unwrap_or(0);
+        lines.push(Line::from(format!(
+            "  {}{:<10} {}",
+            app.theme.quadrant_marker(q),
+            q.to_string(),
+            format_duration(secs)
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("Top tasks:", label)));
+    for (task_id, secs) in stats.per_task.iter().take(5) {
+        let title = app
+            .store
+            .tasks
+            .iter()
+            .find(|t| t.id == *task_id)
+            .map(|t| t.title.as_str())
+            .unwrap_or("(deleted task)");
+        lines.push(Line::from(format!(
+            "  {:<8} {}",
+            format_duration(*secs),
+            title
+        )));
+    }
+
+    let details = Paragraph::new(lines).block(Block::default().borders(Borders::ALL));
+    f.render_widget(details, chunks[2]);
+}
+
+fn render_week(f: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Length(3), // Header
+                Constraint::Min(0),    // Day columns
+                Constraint::Length(3), // Footer
+            ]
+            .as_ref(),
+        )
+        .split(f.area());
+
+    let start = week_start(app.view_date);
+    let header = Paragraph::new(format!(
+        " WEEK: {} - {} ",
+        start.format("%b %d"),
+        (start + chrono::Duration::days(6)).format("%b %d")
+    ))
+    .style(
+        Style::default()
+            .fg(app.theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )
+    .block(Block::default().borders(Borders::ALL))
+    .alignment(Alignment::Center);
+    f.render_widget(header, chunks[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Ratio(1, 7); 7].as_ref())
+        .split(chunks[1]);
+
+    let today = app.store.today();
+
+    for (i, area) in columns.iter().enumerate() {
+        let date = start + chrono::Duration::days(i as i64);
+        let is_selected = i == app.week_day_index;
+        let tasks = app.tasks_for_date(date);
+
+        let border_style = if is_selected {
+            Style::default()
+                .fg(app.theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else if date == today {
+            Style::default().fg(app.theme.highlight)
+        } else {
+            Style::default().fg(app.theme.muted)
+        };
+
+        let block = Block::default()
+            .title(format!(" {} ", date.format("%a %d")))
+            .borders(Borders::ALL)
+            .border_style(border_style);
+
+        let width = area.width.saturating_sub(4) as usize;
+        let height = area.height.saturating_sub(2) as usize;
+        let mut lines: Vec<Line> = Vec::new();
+
+        for (j, task) in tasks.iter().enumerate().take(height) {
+            let selected = is_selected && j == app.week_task_index;
+            let mut style = if task.status == TaskStatus::Completed {
+                Style::default()
+                    .fg(app.theme.muted)
+                    .add_modifier(Modifier::CROSSED_OUT)
+            } else {
+                Style::default().fg(app.theme.quadrant(task.quadrant()))
+            };
+            if selected {
+                style = style.add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+
+            let marker = app.theme.quadrant_marker(task.quadrant());
+            let title = truncate_to_width(&task.title, width.saturating_sub(display_width(marker)));
+            let prefix = if selected { "› " } else { "  " };
+            lines.push(Line::from(Span::styled(
+                format!("{}{}{}", prefix, marker, title),
+                style,
+            )));
+        }
+
+        if tasks.len() > height && height > 0 {
+            lines.truncate(height - 1);
+            lines.push(Line::from(Span::styled(
+                format!("  … +{} more", tasks.len() - (height - 1)),
+                Style::default().fg(app.theme.muted),
+            )));
+        }
+
+        f.render_widget(Paragraph::new(lines).block(block), *area);
+    }
+
+    let footer = Paragraph::new(
+        "[←→]day  [↑↓]task  [</>]move task  [[/]]week  [d]one  [Enter]open day  [Esc]back",
+    )
+    .style(Style::default().fg(app.theme.muted))
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::TOP));
+    f.render_widget(footer, chunks[2]);
+}
@@ -0,0 +1,164 @@
+use crate::tui::theme::Theme;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Widget},
+};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Width of one day cell: two digits, a count marker, and spacing
+const CELL_WIDTH: u16 = 5;
+
+/// Size of the popup including borders
+pub const CALENDAR_WIDTH: u16 = CELL_WIDTH * 7 + 2;
+pub const CALENDAR_HEIGHT: u16 = 12;
+
+/// What happens when a date is picked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePickerAction {
+    Goto,
+    MoveTask(Uuid),
+    MoveSelection,
+    Reschedule(Uuid), // From the end-of-day review
+}
+
+/// Cursor state for the date-picker popup
+#[derive(Debug, Clone)]
+pub struct DatePicker {
+    pub selected: NaiveDate,
+    pub action: DatePickerAction,
+}
+
+impl DatePicker {
+    pub fn new(selected: NaiveDate, action: DatePickerAction) -> Self {
+        Self { selected, action }
+    }
+
+    pub fn move_days(&mut self, days: i64) {
+        self.selected += Duration::days(days);
+    }
+
+    pub fn move_months(&mut self, months: i32) {
+        let shifted = if months >= 0 {
+            self.selected.checked_add_months(Months::new(months as u32))
+        } else {
+            self.selected
+                .checked_sub_months(Months::new(months.unsigned_abs()))
+        };
+        if let Some(date) = shifted {
+            self.selected = date;
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self.action {
+            DatePickerAction::Goto => "Go to date",
+            DatePickerAction::MoveTask(_) => "Move task to",
+            DatePickerAction::MoveSelection => "Move selected tasks to",
+            DatePickerAction::Reschedule(_) => "Reschedule to",
+        }
+    }
+}
+
+/// Month grid with per-day pending task counts
+pub struct CalendarWidget<'a> {
+    pub selected: NaiveDate,
+    pub today: NaiveDate,
+    pub counts: &'a HashMap<NaiveDate, usize>,
+    pub title: &'a str,
+    pub theme: &'a Theme,
+}
+
+impl<'a> Widget for CalendarWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.theme.highlight));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width < CELL_WIDTH * 7 || inner.height < 8 {
+            return;
+        }
+
+        let first = self.selected.with_day(1).unwrap_or(self.selected);
+
+        // Month label
+        let label = first.format("%B %Y").to_string();
+        let label_x = inner.x + inner.width.saturating_sub(label.len() as u16) / 2;
+        buf.set_string(
+            label_x,
+            inner.y,
+            &label,
+            Style::default()
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        // Weekday header
+        for (col, name) in ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"]
+            .iter()
+            .enumerate()
+        {
+            buf.set_string(
+                inner.x + col as u16 * CELL_WIDTH,
+                inner.y + 1,
+                name,
+                Style::default().fg(self.theme.muted),
+            );
+        }
+
+        let offset = first.weekday().num_days_from_monday();
+        let mut date = first;
+        while date.month() == first.month() {
+            let idx = offset + date.day0();
+            let x = inner.x + (idx % 7) as u16 * CELL_WIDTH;
+            let y = inner.y + 2 + (idx / 7) as u16;
+            let count = self.counts.get(&date).copied().unwrap_or(0);
+
+            let mut style = if count > 0 {
+                Style::default().fg(self.theme.text)
+            } else {
+                Style::default().fg(self.theme.muted)
+            };
+            if date == self.today {
+                style = style
+                    .fg(self.theme.highlight)
+                    .add_modifier(Modifier::UNDERLINED);
+            }
+            if date == self.selected {
+                style = style
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD | Modifier::REVERSED);
+            }
+            buf.set_string(x, y, format!("{:>2}", date.day()), style);
+
+            // Pending task count marker
+            if count > 0 {
+                let marker = if count > 9 {
+                    String::from("+")
+                } else {
+                    count.to_string()
+                };
+                buf.set_string(x + 2, y, marker, Style::default().fg(self.theme.success));
+            }
+
+            match date.succ_opt() {
+                Some(next) => date = next,
+                None => break,
+            }
+        }
+
+        let hint = "←→↑↓ day  PgUp/Dn month  t today";
+        buf.set_string(
+            inner.x,
+            inner.bottom().saturating_sub(1),
+            hint,
+            Style::default().fg(self.theme.muted),
+        );
+    }
+}
@@ -1 +1,3 @@
+pub mod calendar;
 pub mod quadrant;
+pub mod task_list;
@@ -1,10 +1,18 @@
+use crate::i18n::quadrant_name;
 use crate::models::task::{Quadrant, Task, TaskStatus};
+use crate::stats::WipOverflow;
+use crate::tui::text::{display_width, truncate_to_width};
+use crate::tui::theme::Theme;
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Margin, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Widget},
+    widgets::{
+        Block, Borders, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget, Widget,
+    },
 };
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
 
 /// Fix #3: Refactored QuadrantWidget that's actually used by ui.rs
 pub struct QuadrantWidget<'a> {
@@ -12,6 +20,12 @@ pub struct QuadrantWidget<'a> {
     pub active: bool,
     pub quadrant_type: Quadrant,
     pub selected_index: Option<usize>,
+    pub marked: Option<&'a HashSet<Uuid>>,
+    pub theme: Theme,
+    pub scroll_offset: Option<usize>,
+    pub numbered: bool,
+    pub over_limit: Option<WipOverflow>,
+    pub carried: HashMap<Uuid, i64>,
 }
 
 impl<'a> QuadrantWidget<'a> {
@@ -26,16 +40,62 @@ impl<'a> QuadrantWidget<'a> {
             active,
             quadrant_type,
             selected_index,
+            marked: None,
+            theme: Theme::default(),
+            scroll_offset: None,
+            numbered: false,
+            over_limit: None,
+            carried: HashMap::new(),
         }
     }
 
+    /// Highlight tasks that are part of a multi-selection
+    pub fn marked(mut self, marked: &'a HashSet<Uuid>) -> Self {
+        self.marked = Some(marked);
+        self
+    }
+
+    /// First visible row; without it the view just follows the selection
+    pub fn scroll(mut self, offset: usize) -> Self {
+        self.scroll_offset = Some(offset);
+        self
+    }
+
+    /// Label the first nine tasks with their quick-jump digit
+    pub fn numbered(mut self, numbered: bool) -> Self {
+        self.numbered = numbered;
+        self
+    }
+
+    /// Show the pending count against the WIP limit in a red header
+    pub fn over_limit(mut self, over: Option<WipOverflow>) -> Self {
+        self.over_limit = over;
+        self
+    }
+
+    /// Days each task has been carried forward, shown next to its score
+    pub fn carried(mut self, carried: HashMap<Uuid, i64>) -> Self {
+        self.carried = carried;
+        self
+    }
+
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+
     fn get_quadrant_color(&self) -> Color {
-        match self.quadrant_type {
-            Quadrant::DoFirst => Color::Red,
-            Quadrant::Schedule => Color::Blue,
-            Quadrant::Delegate => Color::Yellow,
-            Quadrant::Drop => Color::Gray,
-        }
+        self.theme.quadrant(self.quadrant_type)
+    }
+}
+
+/// Quick-jump label for row `index`: its digit for the first nine rows,
+/// padding for the rest, nothing when numbering is off
+pub fn jump_digit(numbered: bool, index: usize) -> String {
+    match (numbered, index) {
+        (true, 0..=8) => (index + 1).to_string(),
+        (true, _) => " ".to_string(),
+        (false, _) => String::new(),
     }
 }
 
@@ -43,16 +103,26 @@ impl<'a> Widget for QuadrantWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let border_style = if self.active {
             Style::default()
-                .fg(Color::Yellow)
+                .fg(self.theme.accent)
                 .add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::DarkGray)
+            Style::default().fg(self.theme.muted)
         };
 
-        let title = format!(" {} ", self.quadrant_type);
+        let marker = self.theme.quadrant_marker(self.quadrant_type);
+        let mut title = format!(" {}{} ", marker, quadrant_name(self.quadrant_type));
+        let mut title_style = Style::default();
+        if let Some(over) = self.over_limit {
+            title.push_str(&format!("{}/{} ", over.pending, over.limit));
+            title_style = title_style
+                .fg(self.theme.danger)
+                .add_modifier(Modifier::BOLD);
+        }
         let block = Block::default()
             .title(title)
+            .title_style(title_style)
             .borders(Borders::ALL)
+            .border_type(self.theme.quadrant_border(self.quadrant_type))
             .border_style(border_style);
 
         block.render(area, buf);
@@ -71,14 +141,10 @@ impl<'a> Widget for QuadrantWidget<'a> {
         let height = inner.height as usize;
 
         // Calculate scroll offset to ensure selected task is visible
-        let start_index = if let Some(sel_idx) = self.selected_index {
-            if sel_idx >= height {
-                sel_idx - height + 1
-            } else {
-                0
-            }
-        } else {
-            0
+        let start_index = match (self.scroll_offset, self.selected_index) {
+            (Some(offset), _) => offset,
+            (None, Some(sel_idx)) if sel_idx >= height => sel_idx - height + 1,
+            _ => 0,
         };
 
         for (i, task) in self.tasks.iter().enumerate().skip(start_index) {
@@ -88,9 +154,16 @@ impl<'a> Widget for QuadrantWidget<'a> {
             }
 
             let is_selected = self.selected_index == Some(i);
+            let is_marked = self.marked.is_some_and(|m| m.contains(&task.id));
 
             let mut style = Style::default();
-            let prefix = if is_selected { "› " } else { "  " };
+            let cursor = match (is_selected, is_marked) {
+                (true, true) => "›*",
+                (true, false) => "› ",
+                (false, true) => " *",
+                (false, false) => "  ",
+            };
+            let prefix = format!("{}{}", jump_digit(self.numbered, i), cursor);
 
             if is_selected {
                 style = style.add_modifier(Modifier::BOLD);
@@ -98,41 +171,83 @@ impl<'a> Widget for QuadrantWidget<'a> {
 
             if task.status == TaskStatus::Completed {
                 style = style
-                    .fg(Color::DarkGray)
+                    .fg(self.theme.muted)
                     .add_modifier(Modifier::CROSSED_OUT);
+            } else if task.status == TaskStatus::Dropped {
+                style = style
+                    .fg(self.theme.muted)
+                    .add_modifier(Modifier::CROSSED_OUT | Modifier::ITALIC);
             } else {
                 style = style.fg(self.get_quadrant_color());
             }
 
-            // Format: "› Task Title      [15]"
+            // Format: "1› Task Title  ↻3d [15]" (digit only when numbered,
+            // age only once a pending task has been put off)
             let score_str = format!("[{}]", task.score());
+            let age_str = match self.carried.get(&task.id) {
+                Some(&days) if days > 0 && task.status == TaskStatus::Pending => {
+                    format!("↻{}d ", days)
+                }
+                _ => String::new(),
+            };
             let max_title_width = (inner.width as usize)
-                .saturating_sub(prefix.len())
+                .saturating_sub(display_width(&prefix))
+                .saturating_sub(display_width(marker))
+                .saturating_sub(display_width(&age_str))
                 .saturating_sub(score_str.len())
                 .saturating_sub(1); // Space before score
 
-            let title = if task.title.len() > max_title_width {
-                format!("{}…", &task.title[..max_title_width.saturating_sub(1)])
-            } else {
-                task.title.clone()
-            };
-
-            let padding = max_title_width.saturating_sub(title.len());
-            let content = format!("{}{}{} {}", prefix, title, " ".repeat(padding), score_str);
-
-            buf.set_string(inner.x, inner.y + render_index as u16, &content, style);
+            let title = truncate_to_width(&task.title, max_title_width);
+            let padding = max_title_width.saturating_sub(display_width(&title));
+            let content = format!(
+                "{}{}{}{} {}{}",
+                prefix,
+                marker,
+                title,
+                " ".repeat(padding),
+                age_str,
+                score_str
+            );
+
+            let y = inner.y + render_index as u16;
+            buf.set_string(inner.x, y, &content, style);
+            if !age_str.is_empty() {
+                let x = inner.x
+                    + (display_width(&content) - display_width(&age_str) - score_str.len()) as u16;
+                buf.set_string(x, y, &age_str, style.fg(self.theme.muted));
+            }
         }
 
-        // Show count if there are more items than visible
-        if self.tasks.len() > height {
-            let more = self.tasks.len() - height;
-            let indicator = format!("… +{} more", more);
-            let style = Style::default().fg(Color::DarkGray);
-            let x = inner.right().saturating_sub(indicator.len() as u16 + 1);
+        // Show how many tasks sit below the view, plus a scrollbar on the border
+        let below = self.tasks.len().saturating_sub(start_index + height);
+        if below > 0 {
+            let indicator = format!("… +{} more", below);
+            let style = Style::default().fg(self.theme.muted);
+            let x = inner
+                .right()
+                .saturating_sub(display_width(&indicator) as u16 + 1);
             let y = inner.bottom().saturating_sub(1);
             if y >= inner.y && x >= inner.x {
                 buf.set_string(x, y, &indicator, style);
             }
         }
+
+        if self.tasks.len() > height {
+            let mut state = ScrollbarState::new(self.tasks.len().saturating_sub(height))
+                .position(start_index)
+                .viewport_content_length(height);
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .style(Style::default().fg(self.theme.muted));
+            scrollbar.render(
+                area.inner(Margin {
+                    vertical: 1,
+                    horizontal: 0,
+                }),
+                buf,
+                &mut state,
+            );
+        }
     }
 }
@@ -0,0 +1,155 @@
+use crate::models::task::{Task, TaskStatus};
+use crate::tui::text::{display_width, truncate_to_width};
+use crate::tui::theme::Theme;
+use crate::tui::widgets::quadrant::jump_digit;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Widget},
+};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Flat ranked list of tasks, each tagged with its quadrant badge
+pub struct TaskListWidget<'a> {
+    pub tasks: Vec<&'a Task>,
+    pub title: &'a str,
+    pub selected_index: Option<usize>,
+    pub marked: Option<&'a HashSet<Uuid>>,
+    pub theme: Theme,
+    pub active: bool,
+    pub numbered: bool,
+}
+
+impl<'a> TaskListWidget<'a> {
+    pub fn new(tasks: Vec<&'a Task>, title: &'a str, selected_index: Option<usize>) -> Self {
+        Self {
+            tasks,
+            title,
+            selected_index,
+            marked: None,
+            theme: Theme::default(),
+            active: true,
+            numbered: false,
+        }
+    }
+
+    /// Highlight tasks that are part of a multi-selection
+    pub fn marked(mut self, marked: &'a HashSet<Uuid>) -> Self {
+        self.marked = Some(marked);
+        self
+    }
+
+    /// Dim the border when another list has focus
+    pub fn active(mut self, active: bool) -> Self {
+        self.active = active;
+        self
+    }
+
+    /// Label the first nine tasks with their quick-jump digit
+    pub fn numbered(mut self, numbered: bool) -> Self {
+        self.numbered = numbered;
+        self
+    }
+
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.theme = *theme;
+        self
+    }
+}
+
+impl<'a> Widget for TaskListWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.active {
+            Style::default()
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(self.theme.muted)
+        };
+        let block = Block::default()
+            .title(format!(" {} ", self.title))
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width == 0 || inner.height == 0 {
+            return;
+        }
+
+        let height = inner.height as usize;
+        let start_index = match self.selected_index {
+            Some(sel_idx) if sel_idx >= height => sel_idx - height + 1,
+            _ => 0,
+        };
+
+        for (i, task) in self.tasks.iter().enumerate().skip(start_index) {
+            let row = (i - start_index) as u16;
+            if row as usize >= height {
+                break;
+            }
+
+            let is_selected = self.selected_index == Some(i);
+            let is_marked = self.marked.is_some_and(|m| m.contains(&task.id));
+            let cursor = match (is_selected, is_marked) {
+                (true, true) => "›*",
+                (true, false) => "› ",
+                (false, true) => " *",
+                (false, false) => "  ",
+            };
+            let prefix = format!("{}{}", jump_digit(self.numbered, i), cursor);
+
+            let quadrant = task.quadrant();
+            let mut style = Style::default();
+            if is_selected {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            let title_style = match task.status {
+                TaskStatus::Completed => style
+                    .fg(self.theme.muted)
+                    .add_modifier(Modifier::CROSSED_OUT),
+                TaskStatus::Dropped => style
+                    .fg(self.theme.muted)
+                    .add_modifier(Modifier::CROSSED_OUT | Modifier::ITALIC),
+                TaskStatus::Pending => style.fg(self.theme.text),
+            };
+            let badge_style = style
+                .fg(self.theme.quadrant(quadrant))
+                .add_modifier(Modifier::BOLD);
+
+            // Layout: "1› Q1 ▲ Task title            [15]" (digit only when numbered)
+            let badge = format!(
+                "{} {}",
+                quadrant.badge(),
+                self.theme.quadrant_marker(quadrant)
+            );
+            let score_str = format!("[{}]", task.score());
+            let prefix_width = display_width(&prefix);
+            let used = prefix_width + display_width(&badge) + score_str.len() + 1;
+            let title = truncate_to_width(&task.title, (inner.width as usize).saturating_sub(used));
+
+            let y = inner.y + row;
+            buf.set_string(inner.x, y, &prefix, style);
+            let badge_x = inner.x + prefix_width as u16;
+            buf.set_string(badge_x, y, &badge, badge_style);
+            let title_x = badge_x + display_width(&badge) as u16;
+            buf.set_string(title_x, y, &title, title_style);
+            let score_x = inner.right().saturating_sub(score_str.len() as u16);
+            buf.set_string(score_x, y, &score_str, style.fg(self.theme.muted));
+        }
+
+        // Show count if there are more items than visible
+        if self.tasks.len() > height + start_index {
+            let indicator = format!("… +{} more", self.tasks.len() - height - start_index);
+            let x = inner
+                .right()
+                .saturating_sub(display_width(&indicator) as u16 + 1);
+            let y = inner.bottom().saturating_sub(1);
+            if x >= inner.x {
+                buf.set_string(x, y, &indicator, Style::default().fg(self.theme.muted));
+            }
+        }
+    }
+}
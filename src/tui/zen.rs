@@ -1,9 +1,63 @@
-use ratatui::{
-    buffer::Buffer,
-    layout::Rect,
-    style::{Color, Style},
-};
-use std::time::Instant;
+use crate::tui::text::display_width;
+use crate::tui::theme::Theme;
+use ratatui::{buffer::Buffer, layout::Rect, style::Style};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// How much Zen mode moves: for focus, slow connections, or motion sensitivity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Motion {
+    #[default]
+    Full,
+    Reduced, // A few slow particles, breathing at a calmer pace
+    Still,   // No particles or breathing; redrawn once a second for the timer
+}
+
+impl Motion {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "full" | "" => Some(Motion::Full),
+            "reduced" | "reduce" | "low" => Some(Motion::Reduced),
+            "still" | "off" | "none" | "static" => Some(Motion::Still),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Motion::Full => "full",
+            Motion::Reduced => "reduced",
+            Motion::Still => "still",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Motion::Full => Motion::Reduced,
+            Motion::Reduced => Motion::Still,
+            Motion::Still => Motion::Full,
+        }
+    }
+
+    /// Frame interval in Zen mode
+    pub fn tick(&self) -> Duration {
+        match self {
+            Motion::Full => Duration::from_millis(100),
+            Motion::Reduced => Duration::from_millis(500),
+            Motion::Still => Duration::from_secs(1),
+        }
+    }
+
+    /// Particles for a screen of the given size
+    fn particle_count(&self, width: u16, height: u16) -> usize {
+        let cells = width as usize * height as usize;
+        match self {
+            Motion::Full => cells / 80, // Sparse particles
+            Motion::Reduced => cells / 400,
+            Motion::Still => 0,
+        }
+    }
+}
 
 /// A floating particle
 #[derive(Clone)]
@@ -13,7 +67,7 @@ pub struct Particle {
     pub vx: f32,
     pub vy: f32,
     pub char: char,
-    pub color: Color,
+    pub shade: usize, // Index into the theme's particle colors
 }
 
 impl Particle {
@@ -31,21 +85,13 @@ impl Particle {
         let rand3 = hasher.finish();
 
         let chars = ['·', '∘', '○', '◦', '•', '✦', '✧', '⋆', '˚', '✵'];
-        let colors = [
-            Color::Rgb(100, 120, 140),
-            Color::Rgb(80, 100, 120),
-            Color::Rgb(120, 140, 160),
-            Color::Rgb(90, 110, 130),
-            Color::Rgb(70, 90, 110),
-        ];
-
         Self {
             x: (rand1 % width as u64) as f32,
             y: (rand2 % height as u64) as f32,
             vx: ((rand1 % 100) as f32 - 50.0) / 200.0,
             vy: ((rand2 % 100) as f32 - 50.0) / 300.0 - 0.05, // Slight upward bias
             char: chars[(rand3 % chars.len() as u64) as usize],
-            color: colors[(rand1 % colors.len() as u64) as usize],
+            shade: (rand1 % 5) as usize,
         }
     }
 
@@ -69,19 +115,31 @@ impl Particle {
     }
 }
 
-/// Pomodoro timer state
+/// Pomodoro timer state, kept by the app so it runs across screens
 pub struct Pomodoro {
+    pub task_id: Option<Uuid>, // Task the session is logged against
     pub start: Instant,
     pub duration_secs: u64,
     pub is_break: bool,
+    pub recorded: bool, // Session already written to the focus log
 }
 
 impl Pomodoro {
-    pub fn new(duration_mins: u64) -> Self {
+    pub fn new(duration_mins: u64, task_id: Option<Uuid>) -> Self {
         Self {
+            task_id,
             start: Instant::now(),
             duration_secs: duration_mins * 60,
             is_break: false,
+            recorded: false,
+        }
+    }
+
+    /// A break after a focus session; it is never logged
+    pub fn rest(duration_mins: u64, task_id: Option<Uuid>) -> Self {
+        Self {
+            is_break: true,
+            ..Self::new(duration_mins, task_id)
         }
     }
 
@@ -109,45 +167,83 @@ impl Pomodoro {
     }
 }
 
+/// Minutes of rest after each focus session
+pub const BREAK_MINUTES: u64 = 5;
+
+/// Suggestions shown during a break, one per minute
+const BREAK_IDEAS: &[&str] = &[
+    "Stand up and stretch your back",
+    "Drink a glass of water",
+    "Look at something far away, eyes off the screen",
+    "Roll your shoulders and neck",
+    "Take five slow, deep breaths",
+    "Walk around for a minute",
+];
+
+/// The break suggestion for the given minute of the break
+pub fn break_idea(elapsed_secs: u64) -> &'static str {
+    BREAK_IDEAS[(elapsed_secs / 60) as usize % BREAK_IDEAS.len()]
+}
+
 /// Zen mode state
 pub struct ZenState {
     pub particles: Vec<Particle>,
-    pub pomodoro: Option<Pomodoro>,
+    pub motion: Motion,
     pub tick: u64,
     pub message: String,
 }
 
 impl ZenState {
-    pub fn new(width: u16, height: u16, duration_mins: u64) -> Self {
-        let particle_count = ((width * height) / 80) as usize; // Sparse particles
-        let particles = (0..particle_count)
+    pub fn new(width: u16, height: u16, motion: Motion) -> Self {
+        let particles = (0..motion.particle_count(width, height))
             .map(|_| Particle::new(width, height))
             .collect();
 
         Self {
             particles,
-            pomodoro: Some(Pomodoro::new(duration_mins)),
+            motion,
             tick: 0,
             message: String::from("Focus on what matters"),
         }
     }
 
-    pub fn update(&mut self, width: u16, height: u16) {
+    pub fn update(&mut self, width: u16, height: u16, pomodoro: Option<&Pomodoro>) {
         self.tick = self.tick.wrapping_add(1);
 
+        // Motion changes and resizes take effect on the next frame
+        let count = self.motion.particle_count(width, height);
+        if self.particles.len() != count {
+            self.particles = (0..count).map(|_| Particle::new(width, height)).collect();
+        }
         for particle in &mut self.particles {
             particle.update(width, height);
         }
 
         // Check pomodoro completion
-        if let Some(ref pomo) = self.pomodoro {
-            if pomo.is_complete() && !pomo.is_break {
-                self.message = String::from("Time for a break! 🍵");
+        self.message = match pomodoro {
+            Some(pomo) if pomo.is_break && pomo.is_complete() => {
+                String::from("Break's over · press 'n' to start the next session")
             }
-        }
+            Some(pomo) if pomo.is_break => format!("Break · {}", break_idea(pomo.elapsed_secs())),
+            Some(pomo) if pomo.is_complete() => String::from("Time for a break! 🍵"),
+            _ => String::from("Focus on what matters"),
+        };
     }
 
-    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+    pub fn render(&self, area: Rect, buf: &mut Buffer, pomodoro: Option<&Pomodoro>, theme: &Theme) {
+        // Breaks get a calmer, greener palette
+        let palette = if pomodoro.is_some_and(|p| p.is_break) {
+            [
+                theme.success,
+                theme.zen_progress,
+                theme.highlight,
+                theme.zen_text,
+                theme.zen_track,
+            ]
+        } else {
+            theme.particles
+        };
+
         // Render particles
         for particle in &self.particles {
             let x = particle.x as u16;
@@ -157,11 +253,11 @@ impl ZenState {
                     area.x + x,
                     area.y + y,
                     particle.char.to_string(),
-                    Style::default().fg(particle.color),
+                    Style::default().fg(palette[particle.shade % palette.len()]),
                 );
             }
         }
-            if let Some(ref pomo) = self.pomodoro {
+        if let Some(pomo) = pomodoro {
             let center_x = area.x + area.width / 2;
             let center_y = area.y + area.height / 2;
 
@@ -173,14 +269,13 @@ impl ZenState {
                 center_y.saturating_sub(2),
                 &time_str,
                 Style::default()
-                    .fg(Color::White)
+                    .fg(theme.text)
                     .add_modifier(ratatui::style::Modifier::BOLD),
             );
 
-            // Progress indicator - 50 dots for 25 minutes (each dot = 30 seconds)
+            // Progress indicator - 50 dots across the session or break
             let dot_count = 50;
-            let elapsed = pomo.elapsed_secs();
-            let filled_dots = (elapsed / 30).min(dot_count as u64) as u16;
+            let filled_dots = (pomo.progress() * dot_count as f64) as u16;
 
             // Calculate centered position for dot sequence
             let dots_x = center_x.saturating_sub(dot_count / 2);
@@ -195,43 +290,63 @@ impl ZenState {
                         x_pos,
                         center_y,
                         "•",
-                        Style::default().fg(Color::Rgb(100, 180, 100)),
+                        Style::default().fg(theme.zen_progress),
                     );
                 } else {
                     // Empty dots
-                    buf.set_string(
-                        x_pos,
-                        center_y,
-                        "·",
-                        Style::default().fg(Color::Rgb(60, 60, 70)),
-                    );
+                    buf.set_string(x_pos, center_y, "·", Style::default().fg(theme.zen_track));
                 }
             }
 
             // Message
-            let msg_x = center_x.saturating_sub(self.message.len() as u16 / 2);
+            let msg_x = center_x.saturating_sub(display_width(&self.message) as u16 / 2);
             buf.set_string(
                 msg_x,
                 center_y + 2,
                 &self.message,
-                Style::default().fg(Color::Rgb(150, 150, 170)),
+                Style::default().fg(theme.zen_text),
             );
 
             // Breathing indicator
             let breath_chars = ["◯", "◎", "●", "◉", "●", "◎"];
-            let breath_idx = ((self.tick / 8) % breath_chars.len() as u64) as usize;
+            let breath_idx = match self.motion {
+                Motion::Full => (self.tick / 8) as usize % breath_chars.len(),
+                Motion::Reduced => (self.tick / 2) as usize % breath_chars.len(),
+                Motion::Still => 2,
+            };
             buf.set_string(
                 center_x,
                 center_y + 4,
                 breath_chars[breath_idx],
-                Style::default().fg(Color::Rgb(120, 140, 160)),
+                Style::default().fg(palette[2]),
             );
         }
 
         // Instructions at bottom
-        let help = "Press 'z' to exit · 'r' to reset timer";
-        let help_x = area.x + area.width.saturating_sub(help.len() as u16) / 2;
+        let help = "Press 'z' to exit · 'r' to reset timer · 'n' next session · 'm' motion";
+        let help_x = area.x + area.width.saturating_sub(display_width(help) as u16) / 2;
         let help_y = area.y + area.height.saturating_sub(2);
-        buf.set_string(help_x, help_y, help, Style::default().fg(Color::DarkGray));
+        buf.set_string(help_x, help_y, help, Style::default().fg(theme.muted));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_break_idea_rotates_each_minute() {
+        assert_eq!(break_idea(0), BREAK_IDEAS[0]);
+        assert_eq!(break_idea(59), BREAK_IDEAS[0]);
+        assert_eq!(break_idea(60), BREAK_IDEAS[1]);
+        assert_eq!(break_idea(60 * BREAK_IDEAS.len() as u64), BREAK_IDEAS[0]);
+    }
+
+    #[test]
+    fn test_rest_is_a_break() {
+        let rest = Pomodoro::rest(BREAK_MINUTES, None);
+        assert!(rest.is_break);
+        assert_eq!(rest.remaining_secs(), BREAK_MINUTES * 60);
+        assert!(!Pomodoro::new(25, None).is_break);
     }
 }
@@ -0,0 +1,225 @@
+use crate::ai::http::{build_client, network_error};
+use crate::config::Config;
+use crate::models::task::Task;
+use crate::parser::input::parse_input;
+use chrono::NaiveDate;
+use reqwest::multipart::{Form, Part};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const WHISPER_API_URL: &str = "https://api.openai.com/v1/audio/transcriptions";
+const WHISPER_API_MODEL: &str = "whisper-1";
+/// whisper.cpp's command-line program, unless `ai_whisper_bin` names another
+const WHISPER_CPP_BIN: &str = "whisper-cli";
+/// Formats whisper.cpp reads itself; anything else goes through ffmpeg
+const WHISPER_CPP_FORMATS: &[&str] = &["wav", "mp3", "ogg", "flac"];
+
+/// Shown when neither transcriber can be used
+const NO_TRANSCRIBER: &str = "Transcription needs OPENAI_API_KEY for the Whisper API, or \
+     \"ai_whisper_model\" in config.json for a local whisper.cpp";
+
+/// Spoken lead-ins dropped from the start of a memo sentence
+const LEAD_INS: &[&str] = &[
+    "don't forget to ",
+    "remember to ",
+    "i need to ",
+    "i have to ",
+    "i should ",
+    "need to ",
+    "and then ",
+    "also ",
+    "then ",
+    "and ",
+];
+
+/// Where audio is turned into text
+#[derive(Debug, Clone, PartialEq)]
+pub enum Transcriber {
+    /// OpenAI's hosted Whisper API
+    Api { api_key: String },
+    /// A local whisper.cpp program and model file
+    Local { bin: String, model: String },
+}
+
+/// Local whisper.cpp when `ai_whisper_model` is set, else the Whisper API
+/// with `OPENAI_API_KEY`; `env` looks up environment variables so this can
+/// be tested
+pub fn select_transcriber(
+    config: &Config,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<Transcriber, String> {
+    if let Some(model) = &config.ai_whisper_model {
+        let bin = config.ai_whisper_bin.as_deref().unwrap_or(WHISPER_CPP_BIN);
+        return Ok(Transcriber::Local {
+            bin: bin.to_string(),
+            model: model.clone(),
+        });
+    }
+    match env("OPENAI_API_KEY") {
+        Some(api_key) if !api_key.trim().is_empty() => Ok(Transcriber::Api { api_key }),
+        _ => Err(NO_TRANSCRIBER.to_string()),
+    }
+}
+
+/// The text spoken in the audio file at `path`
+pub async fn transcribe(config: &Config, path: &Path) -> Result<String, String> {
+    let transcript = match select_transcriber(config, |var| std::env::var(var).ok())? {
+        Transcriber::Api { api_key } => transcribe_api(config, &api_key, path).await?,
+        Transcriber::Local { bin, model } => {
+            let path = path.to_path_buf();
+            tokio::task::spawn_blocking(move || transcribe_local(&bin, &model, &path))
+                .await
+                .map_err(|e| format!("Transcription failed: {}", e))??
+        }
+    };
+    let transcript = transcript.trim().to_string();
+    if transcript.is_empty() {
+        return Err("No speech found in the recording".to_string());
+    }
+    Ok(transcript)
+}
+
+async fn transcribe_api(config: &Config, api_key: &str, path: &Path) -> Result<String, String> {
+    let audio = fs::read(path).map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    let file_name = path.file_name().map_or("memo".to_string(), |name| {
+        name.to_string_lossy().to_string()
+    });
+    let form = Form::new()
+        .text("model", WHISPER_API_MODEL)
+        .part("file", Part::bytes(audio).file_name(file_name));
+
+    let response = build_client(config)?
+        .post(WHISPER_API_URL)
+        .bearer_auth(api_key)
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|e| network_error(&e))?;
+    if !response.status().is_success() {
+        return Err(format!("API Error: {}", response.status()));
+    }
+    let json: Value = response
+        .json()
+        .await
+        .map_err(|_| "Failed to parse API response".to_string())?;
+    json["text"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| "Failed to parse API response".to_string())
+}
+
+fn transcribe_local(bin: &str, model: &str, path: &Path) -> Result<String, String> {
+    let readable = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| WHISPER_CPP_FORMATS.contains(&ext.to_lowercase().as_str()));
+    let converted = (!readable).then(|| to_wav(path)).transpose()?;
+    let input = converted.as_deref().unwrap_or(path);
+
+    let output = Command::new(bin)
+        .args(["-m", model, "-nt", "-np", "-f"])
+        .arg(input)
+        .output();
+    if let Some(wav) = &converted {
+        let _ = fs::remove_file(wav);
+    }
+    let output = output.map_err(|e| format!("Cannot run {}: {}", bin, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("{} failed: {}", bin, stderr.trim()));
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    Ok(text.lines().map(str::trim).collect::<Vec<_>>().join(" "))
+}
+
+/// Convert `path` to the 16 kHz mono WAV whisper.cpp expects
+fn to_wav(path: &Path) -> Result<PathBuf, String> {
+    let wav = std::env::temp_dir().join(format!("eq-memo-{}.wav", std::process::id()));
+    let status = Command::new("ffmpeg")
+        .args(["-y", "-loglevel", "error", "-i"])
+        .arg(path)
+        .args(["-ar", "16000", "-ac", "1"])
+        .arg(&wav)
+        .status()
+        .map_err(|e| format!("Cannot run ffmpeg to convert {}: {}", path.display(), e))?;
+    if !status.success() {
+        return Err(format!("ffmpeg could not convert {}", path.display()));
+    }
+    Ok(wav)
+}
+
+/// Chat message asking the assistant to turn a memo into tasks for `date`
+pub fn memo_prompt(date: NaiveDate, transcript: &str) -> String {
+    format!(
+        "This is a transcribed voice memo. Turn it into next actions for {}, one [ADD] \
+         line each, and skip anything that is not a task: {}",
+        date.format("%A %b %-d"),
+        transcript.trim()
+    )
+}
+
+/// Tasks read from a memo without the assistant: one per sentence, minus
+/// spoken lead-ins like "remember to"
+pub fn memo_tasks(transcript: &str, date: NaiveDate) -> Vec<Task> {
+    transcript
+        .split(['.', '!', '?', ';', '\n'])
+        .filter_map(|sentence| {
+            let mut sentence = sentence.trim().trim_end_matches(',');
+            while let Some(lead) = LEAD_INS.iter().find(|lead| {
+                sentence
+                    .get(..lead.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(lead))
+            }) {
+                sentence = sentence[lead.len()..].trim_start();
+            }
+            let mut task = parse_input(sentence, date).into_task(date);
+            let mut chars = task.title.chars();
+            let first = chars.next()?;
+            task.title = first.to_uppercase().chain(chars).collect();
+            Some(task)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_transcriber() {
+        let mut config = Config::default();
+        let key = |var: &str| (var == "OPENAI_API_KEY").then(|| "sk-test".to_string());
+        assert_eq!(
+            select_transcriber(&config, key),
+            Ok(Transcriber::Api {
+                api_key: "sk-test".into()
+            })
+        );
+        assert!(select_transcriber(&config, |_| None).is_err());
+
+        config.ai_whisper_model = Some("ggml-base.en.bin".into());
+        assert_eq!(
+            select_transcriber(&config, key),
+            Ok(Transcriber::Local {
+                bin: "whisper-cli".into(),
+                model: "ggml-base.en.bin".into()
+            })
+        );
+    }
+
+    #[test]
+    fn test_memo_tasks_one_per_sentence() {
+        let date: NaiveDate = "2024-06-12".parse().unwrap();
+        let tasks = memo_tasks(
+            "Remember to call the landlord. Also book flights u3i3 ~30m!",
+            date,
+        );
+        let titles: Vec<&str> = tasks.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(titles, vec!["Call the landlord", "Book flights"]);
+        assert_eq!((tasks[1].urgency, tasks[1].importance), (3, 3));
+        assert_eq!(tasks[1].estimate_minutes, Some(30));
+        assert_eq!(tasks[0].date, date);
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::NaiveDate;
+use uuid::Uuid;
+
+/// One-at-a-time pass over a queue of tasks, recording a choice of `C` for
+/// each; the end-of-day review, DO FIRST re-triage, and weekly review are
+/// all walkthroughs
+#[derive(Debug, Clone)]
+pub struct Walkthrough<C> {
+    /// The day under review, or the first day of the week
+    pub date: NaiveDate,
+    queue: Vec<Uuid>,
+    choices: Vec<C>,
+}
+
+impl<C: Copy + PartialEq> Walkthrough<C> {
+    /// Walk through `queue` in order
+    pub fn from_queue(date: NaiveDate, queue: Vec<Uuid>) -> Self {
+        Self {
+            date,
+            queue,
+            choices: Vec::new(),
+        }
+    }
+
+    /// Task awaiting a decision, `None` once the walkthrough is finished
+    pub fn current(&self) -> Option<Uuid> {
+        self.queue.get(self.choices.len()).copied()
+    }
+
+    /// 1-based step and the number of tasks in the walkthrough
+    pub fn progress(&self) -> (usize, usize) {
+        (
+            (self.choices.len() + 1).min(self.queue.len()),
+            self.queue.len(),
+        )
+    }
+
+    pub fn decide(&mut self, choice: C) {
+        if self.current().is_some() {
+            self.choices.push(choice);
+        }
+    }
+
+    /// Stop early; the remaining tasks are left as they are
+    pub fn finish(&mut self) {
+        self.queue.truncate(self.choices.len());
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// The choices made so far
+    pub fn choices(&self) -> &[C] {
+        &self.choices
+    }
+
+    /// How many tasks got `choice`
+    pub fn count(&self, choice: C) -> usize {
+        self.choices.iter().filter(|c| **c == choice).count()
+    }
+
+    /// Tasks given `choice` so far
+    pub fn chosen(&self, choice: C) -> Vec<Uuid> {
+        self.queue
+            .iter()
+            .zip(&self.choices)
+            .filter(|(_, c)| **c == choice)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decisions_advance_until_finished() {
+        let day: NaiveDate = "2024-06-12".parse().unwrap();
+        let ids: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let mut walk = Walkthrough::from_queue(day, ids.clone());
+        assert_eq!((walk.current(), walk.progress()), (Some(ids[0]), (1, 3)));
+
+        walk.decide('a');
+        walk.decide('b');
+        assert_eq!((walk.current(), walk.progress()), (Some(ids[2]), (3, 3)));
+        walk.decide('a');
+        assert!(walk.is_finished());
+        walk.decide('b'); // ignored once finished
+
+        assert_eq!(walk.count('a'), 2);
+        assert_eq!(walk.chosen('a'), [ids[0], ids[2]]);
+        assert_eq!(walk.choices(), ['a', 'b', 'a']);
+    }
+
+    #[test]
+    fn test_finish_early() {
+        let day: NaiveDate = "2024-06-12".parse().unwrap();
+        let mut walk = Walkthrough::from_queue(day, vec![Uuid::new_v4(), Uuid::new_v4()]);
+        walk.decide(1);
+        walk.finish();
+        assert!(walk.is_finished());
+        assert_eq!(walk.progress(), (1, 1));
+        assert_eq!(walk.count(1), 1);
+    }
+}
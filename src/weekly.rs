@@ -0,0 +1,380 @@
+use crate::clock;
+use crate::models::log::{EventAction, LogEvent};
+use crate::models::store::TaskStore;
+use crate::models::task::{Quadrant, Task, TaskStatus};
+use crate::walkthrough::Walkthrough;
+use chrono::{Duration, NaiveDate};
+use uuid::Uuid;
+
+/// What to do with an open task going into next week
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeeklyChoice {
+    Keep,
+    Delegate,
+    Drop,
+}
+
+/// One quadrant's tasks over a week, by outcome
+#[derive(Debug, Clone)]
+pub struct QuadrantOutcome<'a> {
+    pub quadrant: Quadrant,
+    pub completed: Vec<&'a Task>,
+    pub dropped: Vec<&'a Task>,
+    pub open: Vec<&'a Task>,
+}
+
+/// Outcomes of the week starting `start`, one entry per quadrant
+pub fn week_outcomes(tasks: &[Task], start: NaiveDate) -> Vec<QuadrantOutcome<'_>> {
+    let end = start + Duration::days(6);
+    Quadrant::ALL
+        .iter()
+        .map(|&quadrant| {
+            let week: Vec<&Task> = tasks
+                .iter()
+                .filter(|t| t.date >= start && t.date <= end && t.quadrant() == quadrant)
+                .collect();
+            let by_status = |status: TaskStatus| -> Vec<&Task> {
+                week.iter()
+                    .copied()
+                    .filter(|t| t.status == status)
+                    .collect()
+            };
+            QuadrantOutcome {
+                quadrant,
+                completed: by_status(TaskStatus::Completed),
+                dropped: by_status(TaskStatus::Dropped),
+                open: by_status(TaskStatus::Pending),
+            }
+        })
+        .collect()
+}
+
+/// Urgency and importance that put a task in DELEGATE
+pub fn delegated_priority(urgency: u8) -> (u8, u8) {
+    (urgency.max(2), 1)
+}
+
+/// Weekly pass over open work: this week's leftovers and next week's plan.
+/// Its `date` is the first day of the week
+pub type WeeklyReview = Walkthrough<WeeklyChoice>;
+
+impl WeeklyReview {
+    /// Queue pending tasks from `start` through the end of the following
+    /// week, highest score first
+    pub fn new(tasks: &[Task], start: NaiveDate) -> Self {
+        let end = start + Duration::days(13);
+        let mut pending: Vec<&Task> = tasks
+            .iter()
+            .filter(|t| t.status == TaskStatus::Pending && t.date >= start && t.date <= end)
+            .collect();
+        pending.sort_by_key(|t| (std::cmp::Reverse(t.score()), t.date));
+        Self::from_queue(start, pending.iter().map(|t| t.id).collect())
+    }
+
+    /// Drop and delegate the chosen tasks as one undo step
+    pub fn apply(&self, store: &mut TaskStore) {
+        store.journal.begin_batch();
+        for id in self.chosen(WeeklyChoice::Drop) {
+            store.drop_task_with_reason(id, Some("Weekly review".to_string()));
+        }
+        for id in self.chosen(WeeklyChoice::Delegate) {
+            let Some(task) = store.tasks.iter().find(|t| t.id == id) else {
+                continue;
+            };
+            let title = task.title.clone();
+            let (urgency, importance) = delegated_priority(task.urgency);
+            store.update_task(id, title, urgency, importance);
+        }
+        store.journal.end_batch("weekly review");
+    }
+}
+
+/// Journal file stem for the week starting `start`, e.g. `2024-W23`
+pub fn weekly_note_name(start: NaiveDate) -> String {
+    start.format("%G-W%V").to_string()
+}
+
+/// Render the Markdown weekly review; call before `WeeklyReview::apply` so
+/// the outcomes reflect the week as it was lived
+pub fn render_weekly_review(
+    start: NaiveDate,
+    tasks: &[Task],
+    review: &WeeklyReview,
+    notes: &[String],
+) -> String {
+    let mut md = format!(
+        "# Weekly Review — {} ({} – {})\n",
+        weekly_note_name(start),
+        start.format("%b %-d"),
+        (start + Duration::days(6)).format("%b %-d")
+    );
+
+    md.push_str("\n## Outcomes\n");
+    for outcome in week_outcomes(tasks, start) {
+        md.push_str(&format!(
+            "\n### {} — {} done, {} dropped, {} open\n",
+            outcome.quadrant,
+            outcome.completed.len(),
+            outcome.dropped.len(),
+            outcome.open.len()
+        ));
+        for task in &outcome.completed {
+            md.push_str(&format!("- [x] {}\n", task.title));
+        }
+    }
+
+    let title = |id: Uuid| {
+        tasks
+            .iter()
+            .find(|t| t.id == id)
+            .map_or("(deleted task)", |t| t.title.as_str())
+    };
+    let dropped = review.chosen(WeeklyChoice::Drop);
+    let delegated = review.chosen(WeeklyChoice::Delegate);
+    if !dropped.is_empty() || !delegated.is_empty() {
+        md.push_str("\n## Next Week\n");
+        for id in delegated {
+            md.push_str(&format!("- Delegate: {}\n", title(id)));
+        }
+        for id in dropped {
+            md.push_str(&format!("- Drop: ~~{}~~\n", title(id)));
+        }
+    }
+
+    if !notes.is_empty() {
+        md.push_str("\n## Notes\n");
+        for note in notes {
+            md.push_str(&format!("- {}\n", note));
+        }
+    }
+
+    md
+}
+
+/// The week as the event log recorded it
+#[derive(Debug, Clone, Default)]
+pub struct WeekLog<'a> {
+    pub completed: Vec<&'a Task>,
+    pub dropped: Vec<&'a Task>,
+    /// Tasks pushed to a later day, with how many times
+    pub carried_over: Vec<(&'a Task, usize)>,
+}
+
+impl WeekLog<'_> {
+    pub fn is_empty(&self) -> bool {
+        self.completed.is_empty() && self.dropped.is_empty() && self.carried_over.is_empty()
+    }
+}
+
+/// Tasks completed, dropped, and carried over during the week starting
+/// `start`; completions and drops since undone are left out
+pub fn week_log<'a>(tasks: &'a [Task], events: &[LogEvent], start: NaiveDate) -> WeekLog<'a> {
+    let end = start + Duration::days(6);
+    let in_week = |date: NaiveDate| date >= start && date <= end;
+    let mut log = WeekLog::default();
+
+    for event in events {
+        let Some(task) = tasks.iter().find(|t| t.id == event.task_id) else {
+            continue;
+        };
+        let list = match event.action {
+            EventAction::Completed if task.status == TaskStatus::Completed => &mut log.completed,
+            EventAction::Dropped if task.status == TaskStatus::Dropped => &mut log.dropped,
+            EventAction::Moved => {
+                if let Some((from, to)) = event.moved_dates() {
+                    if in_week(from) && to > from {
+                        match log.carried_over.iter_mut().find(|(t, _)| t.id == task.id) {
+                            Some((_, times)) => *times += 1,
+                            None => log.carried_over.push((task, 1)),
+                        }
+                    }
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        if in_week(clock::date_of(event.timestamp)) && !list.iter().any(|t| t.id == task.id) {
+            list.push(task);
+        }
+    }
+
+    log.carried_over
+        .sort_by_key(|(_, times)| std::cmp::Reverse(*times));
+    log
+}
+
+/// Journal file stem for the retrospective, e.g. `2024-W23-retro`
+pub fn retro_note_name(start: NaiveDate) -> String {
+    format!("{}-retro", weekly_note_name(start))
+}
+
+fn push_list(md: &mut String, heading: &str, tasks: &[&Task]) {
+    md.push_str(&format!("\n### {} ({})\n", heading, tasks.len()));
+    for task in tasks {
+        md.push_str(&format!("- {} ({})\n", task.title, task.quadrant()));
+    }
+}
+
+fn week_summary(log: &WeekLog) -> String {
+    let mut md = String::new();
+    push_list(&mut md, "Completed", &log.completed);
+    push_list(&mut md, "Dropped", &log.dropped);
+    md.push_str(&format!(
+        "\n### Carried over ({})\n",
+        log.carried_over.len()
+    ));
+    for (task, times) in &log.carried_over {
+        md.push_str(&format!(
+            "- {} ({}, moved {}×)\n",
+            task.title,
+            task.quadrant(),
+            times
+        ));
+    }
+    md
+}
+
+/// Chat message asking for patterns in the week and one improvement
+pub fn retro_prompt(start: NaiveDate, log: &WeekLog) -> String {
+    format!(
+        "Run a retrospective on my week of {}.\n{}\nWhat patterns do you see in what got done, \
+         dropped, or kept slipping? Name the two or three that matter most, then suggest ONE \
+         concrete improvement for next week. Keep it short.",
+        start.format("%b %-d"),
+        week_summary(log)
+    )
+}
+
+/// Markdown retrospective: the week's log followed by the assistant's reading
+pub fn render_retro(start: NaiveDate, log: &WeekLog, reflection: &str) -> String {
+    format!(
+        "# Retrospective — {} ({} – {})\n\n## The Week\n{}\n## Patterns and Next Step\n\n{}\n",
+        weekly_note_name(start),
+        start.format("%b %-d"),
+        (start + Duration::days(6)).format("%b %-d"),
+        week_summary(log),
+        reflection.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_week_outcomes_by_quadrant() {
+        let monday = date("2024-06-03");
+        let mut done = Task::new("Ship".into(), 3, 3, monday);
+        done.complete();
+        let mut dropped = Task::new("Feeds".into(), 1, 1, date("2024-06-05"));
+        dropped.drop_task();
+        let open = Task::new("Plan Q3".into(), 1, 3, date("2024-06-09"));
+        let next_week = Task::new("Later".into(), 3, 3, date("2024-06-10"));
+
+        let tasks = [done, dropped, open, next_week];
+        let outcomes = week_outcomes(&tasks, monday);
+        assert_eq!(outcomes[0].quadrant, Quadrant::DoFirst);
+        assert_eq!(outcomes[0].completed.len(), 1);
+        assert!(outcomes[0].open.is_empty());
+        assert_eq!(outcomes[1].open.len(), 1);
+        assert_eq!(outcomes[3].dropped.len(), 1);
+    }
+
+    #[test]
+    fn test_review_queues_open_work_through_next_week() {
+        let monday = date("2024-06-03");
+        let this_week = Task::new("low".into(), 1, 1, date("2024-06-04"));
+        let next_week = Task::new("high".into(), 3, 3, date("2024-06-14"));
+        let later = Task::new("later".into(), 3, 3, date("2024-06-20"));
+        let mut done = Task::new("done".into(), 3, 3, monday);
+        done.complete();
+        let (low_id, high_id) = (this_week.id, next_week.id);
+
+        let mut review = WeeklyReview::new(&[this_week, next_week, later, done], monday);
+        assert_eq!(review.progress(), (1, 2));
+        assert_eq!(review.current(), Some(high_id));
+        review.decide(WeeklyChoice::Delegate);
+        review.decide(WeeklyChoice::Drop);
+        assert!(review.is_finished());
+        assert_eq!(review.chosen(WeeklyChoice::Delegate), vec![high_id]);
+        assert_eq!(review.chosen(WeeklyChoice::Drop), vec![low_id]);
+    }
+
+    #[test]
+    fn test_render_weekly_review() {
+        let monday = date("2024-06-03");
+        let mut done = Task::new("Ship".into(), 3, 3, monday);
+        done.complete();
+        let meeting = Task::new("Status meeting".into(), 3, 1, date("2024-06-11"));
+        let tasks = vec![done, meeting];
+
+        let mut review = WeeklyReview::new(&tasks, monday);
+        review.decide(WeeklyChoice::Drop);
+        let md = render_weekly_review(monday, &tasks, &review, &["Fewer meetings".to_string()]);
+
+        assert!(md.starts_with("# Weekly Review — 2024-W23 (Jun 3 – Jun 9)"));
+        assert!(md.contains("### DO FIRST — 1 done, 0 dropped, 0 open"));
+        assert!(md.contains("- [x] Ship"));
+        assert!(md.contains("- Drop: ~~Status meeting~~"));
+        assert!(md.contains("- Fewer meetings"));
+    }
+
+    fn event(action: EventAction, task: &Task, day: &str, details: &str) -> LogEvent {
+        let mut event = LogEvent::new(action, task.id, details.to_string());
+        event.timestamp = format!("{}T12:00:00Z", day).parse().unwrap();
+        event
+    }
+
+    #[test]
+    fn test_week_log_reads_events() {
+        let monday = date("2024-06-03");
+        let mut done = Task::new("Ship".into(), 3, 3, monday);
+        done.complete();
+        let mut undone = Task::new("Reopened".into(), 2, 2, monday);
+        undone.complete();
+        undone.undo_complete();
+        let mut old = Task::new("Last week".into(), 2, 2, date("2024-05-29"));
+        old.complete();
+        let slipping = Task::new("Taxes".into(), 1, 3, date("2024-06-07"));
+        let events = vec![
+            event(EventAction::Completed, &done, "2024-06-05", ""),
+            event(EventAction::Completed, &undone, "2024-06-05", ""),
+            event(EventAction::Completed, &old, "2024-05-29", ""),
+            event(
+                EventAction::Moved,
+                &slipping,
+                "2024-06-04",
+                "Moved: 2024-06-04 -> 2024-06-05",
+            ),
+            event(
+                EventAction::Moved,
+                &slipping,
+                "2024-06-05",
+                "Moved: 2024-06-05 -> 2024-06-07",
+            ),
+        ];
+        let tasks = vec![done, undone, old, slipping];
+
+        let log = week_log(&tasks, &events, monday);
+        let completed: Vec<&str> = log.completed.iter().map(|t| t.title.as_str()).collect();
+        assert_eq!(completed, vec!["Ship"]);
+        assert!(log.dropped.is_empty());
+        assert_eq!(log.carried_over.len(), 1);
+        assert_eq!(log.carried_over[0].1, 2);
+
+        let md = render_retro(monday, &log, "Taxes keep slipping.");
+        assert!(md.starts_with("# Retrospective — 2024-W23"));
+        assert!(md.contains("- Taxes (SCHEDULE, moved 2×)"));
+        assert!(md.ends_with("Taxes keep slipping.\n"));
+    }
+
+    #[test]
+    fn test_delegated_priority_lands_in_delegate() {
+        let (u, i) = delegated_priority(1);
+        assert_eq!(Quadrant::from_priority(u, i), Quadrant::Delegate);
+    }
+}
@@ -0,0 +1,54 @@
+//! The TUI and another eq process saving the same store. Kept in its own
+//! test binary, since it points `EQ_DATA_DIR` at a scratch directory for
+//! the whole process
+
+use eq::models::store::TaskStore;
+use eq::models::task::{Task, TaskStatus};
+use eq::storage::lock::lock_store;
+use eq::tui::saver::Saver;
+use std::time::Duration;
+use std::{env, fs, process, thread};
+
+/// What `eq add` does, a moment later so the file's modification time moves
+fn add_elsewhere(title: &str) {
+    thread::sleep(Duration::from_millis(20));
+    let _lock = lock_store().unwrap();
+    let mut store = TaskStore::load().unwrap();
+    let today = store.today();
+    store.add_task(Task::new(title.into(), 2, 2, today));
+    store.save().unwrap();
+}
+
+#[test]
+fn test_tui_saves_keep_changes_made_elsewhere() {
+    let dir = env::temp_dir().join(format!("eq-writers-{}", process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    env::set_var("EQ_DATA_DIR", &dir);
+
+    let mut store = TaskStore::default();
+    let today = store.today();
+    store.add_task(Task::new("Write report".into(), 3, 3, today));
+    store.save().unwrap();
+
+    let mut tui = TaskStore::load().unwrap();
+    let mut saver = Saver::new(&tui, eq::ai::runtime().unwrap());
+    add_elsewhere("Email Bob");
+    let id = tui.tasks[0].id;
+    tui.complete_task(id);
+    saver.changed();
+    assert!(saver.reload_if_changed(&mut tui).unwrap());
+    assert_eq!(tui.tasks.len(), 2);
+
+    // Lands after the merge, so the queued save is turned down and redone
+    add_elsewhere("Plan Q3");
+    saver.flush(&tui);
+    assert_eq!(saver.finish(&mut tui), None);
+
+    let saved = TaskStore::load().unwrap();
+    let mut titles: Vec<&str> = saved.tasks.iter().map(|t| t.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(titles, ["Email Bob", "Plan Q3", "Write report"]);
+    let report = saved.tasks.iter().find(|t| t.id == id).unwrap();
+    assert_eq!(report.status, TaskStatus::Completed);
+    let _ = fs::remove_dir_all(&dir);
+}